@@ -18,6 +18,10 @@ where
     }
 }
 
+/// Newline-delimited JSON documents.
+#[derive(Debug)]
+pub struct JsonLines<T>(pub Vec<T>);
+
 #[derive(Debug)]
 pub struct Proto<T>(pub T);
 
@@ -261,6 +265,34 @@ pub mod extract {
         }
     }
 
+    impl<S, T> FromRequest<S> for super::JsonLines<T>
+    where
+        S: Send + Sync,
+        T: DeserializeOwned,
+    {
+        type Rejection = JsonExtractionRejection;
+
+        async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let bytes = Bytes::from_request(req, state).await.map_err(|e| {
+                JsonExtractionRejection::Other {
+                    status: e.status(),
+                    message: e.body_text().into(),
+                }
+            })?;
+            parse_json_lines(&bytes).map(Self)
+        }
+    }
+
+    fn parse_json_lines<T: DeserializeOwned>(
+        bytes: &[u8],
+    ) -> Result<Vec<T>, JsonExtractionRejection> {
+        bytes
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.trim_ascii().is_empty())
+            .map(|line| sonic_rs::from_slice(line).map_err(classify_sonic_error))
+            .collect()
+    }
+
     /// Workaround for https://github.com/tokio-rs/axum/issues/3623
     #[derive(Debug)]
     pub struct JsonOpt<T>(pub Option<T>);
@@ -370,6 +402,17 @@ pub mod extract {
             }
         }
 
+        #[test]
+        fn json_lines_skip_blank_lines() {
+            let input = b"{\"records\": []}\n\n{\"records\": [], \"match_seq_num\": 1}\r\n";
+            let parsed = parse_json_lines::<AppendInput>(input).unwrap();
+            assert_eq!(parsed.len(), 2);
+            assert_eq!(parsed[1].match_seq_num, Some(1));
+
+            let err = parse_json_lines::<AppendInput>(b"{\"records\": []}\nnot json").unwrap_err();
+            assert_eq!(err.status(), http::StatusCode::BAD_REQUEST);
+        }
+
         #[test]
         fn valid_json_parses_successfully() {
             let input = br#"{"records": [], "match_seq_num": null}"#;
@@ -415,7 +458,9 @@ pub mod extract {
             // StreamReconfiguration: exercises Maybe<T> in all three states
             use s2_common::maybe::Maybe;
 
-            use crate::v1::config::{StorageClass, TimestampingMode, TimestampingReconfiguration};
+            use crate::v1::config::{
+                CompressionHint, StorageClass, TimestampingMode, TimestampingReconfiguration,
+            };
 
             // All fields unspecified (empty JSON object)
             assert_roundtrip(&StreamReconfiguration {
@@ -423,6 +468,11 @@ pub mod extract {
                 retention_policy: Maybe::Unspecified,
                 timestamping: Maybe::Unspecified,
                 delete_on_empty: Maybe::Unspecified,
+                min_retained_records: Maybe::Unspecified,
+                max_append_bytes_per_sec: Maybe::Unspecified,
+                max_append_ops_per_sec: Maybe::Unspecified,
+                max_future_skew_secs: Maybe::Unspecified,
+                compression_hint: Maybe::Unspecified,
             });
             // Mix of specified-null and specified-value
             assert_roundtrip(&StreamReconfiguration {
@@ -433,6 +483,11 @@ pub mod extract {
                     uncapped: Maybe::Specified(Some(true)),
                 })),
                 delete_on_empty: Maybe::Unspecified,
+                min_retained_records: Maybe::Specified(Some(100)),
+                max_append_bytes_per_sec: Maybe::Specified(None),
+                max_append_ops_per_sec: Maybe::Specified(Some(1000)),
+                max_future_skew_secs: Maybe::Specified(Some(60)),
+                compression_hint: Maybe::Specified(Some(CompressionHint::Zstd)),
             });
 
             // BasinReconfiguration: nested Maybe<Option<StreamReconfiguration>>
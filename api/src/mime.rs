@@ -45,6 +45,16 @@ pub fn is_protobuf(mime: &mime::Mime) -> bool {
     }
 }
 
+/// Newline-delimited JSON, where each line is a separate JSON document.
+pub fn is_ndjson(mime: &mime::Mime) -> bool {
+    mime.type_() == mime::APPLICATION && {
+        let s = mime.subtype().as_str();
+        s.eq_ignore_ascii_case("x-ndjson")
+            || s.eq_ignore_ascii_case("ndjson")
+            || s.eq_ignore_ascii_case("jsonl")
+    }
+}
+
 pub fn is_s2s_proto(mime: &Mime) -> bool {
     mime.type_().as_str().eq_ignore_ascii_case("s2s")
         && mime.subtype().as_str().eq_ignore_ascii_case("proto")
@@ -74,6 +84,16 @@ mod tests {
         assert_eq!(super::parse(&header), expected);
     }
 
+    #[rstest]
+    #[case("application/x-ndjson", true)]
+    #[case("application/ndjson", true)]
+    #[case("application/jsonl", true)]
+    #[case("application/json", false)]
+    #[case("text/plain", false)]
+    fn is_ndjson(#[case] mime: &'static str, #[case] expected: bool) {
+        assert_eq!(super::is_ndjson(&mime.parse().unwrap()), expected);
+    }
+
     #[test]
     fn parse_returns_none_for_non_utf8_header_values() {
         let header = http::HeaderValue::from_bytes(b"\xFF\xFF").unwrap();
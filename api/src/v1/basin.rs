@@ -17,6 +17,7 @@ pub struct ListBasinsRequest {
     #[cfg_attr(feature = "utoipa", param(value_type = String, default = "", required = false))]
     pub prefix: Option<BasinNamePrefix>,
     /// Filter to basins whose names lexicographically start after this string.
+    /// If non-empty, it must not sort before `prefix`.
     #[cfg_attr(feature = "utoipa", param(value_type = String, default = "", required = false))]
     pub start_after: Option<BasinNameStartAfter>,
     /// Number of results, up to a maximum of 1000.
@@ -32,6 +32,39 @@ impl From<s2_common::config::StorageClass> for StorageClass {
     }
 }
 
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum CompressionHint {
+    /// Store records uncompressed.
+    None,
+    /// Compress records with Zstandard.
+    Zstd,
+    /// Compress records with gzip.
+    Gzip,
+}
+
+impl From<CompressionHint> for s2_common::config::CompressionHint {
+    fn from(value: CompressionHint) -> Self {
+        match value {
+            CompressionHint::None => Self::None,
+            CompressionHint::Zstd => Self::Zstd,
+            CompressionHint::Gzip => Self::Gzip,
+        }
+    }
+}
+
+impl From<s2_common::config::CompressionHint> for CompressionHint {
+    fn from(value: s2_common::config::CompressionHint) -> Self {
+        match value {
+            s2_common::config::CompressionHint::None => Self::None,
+            s2_common::config::CompressionHint::Zstd => Self::Zstd,
+            s2_common::config::CompressionHint::Gzip => Self::Gzip,
+        }
+    }
+}
+
 #[rustfmt::skip]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -82,6 +115,8 @@ pub enum TimestampingMode {
     ClientRequire,
     /// Use the arrival time and ignore any client-specified timestamp.
     Arrival,
+    /// Use the arrival time unless a client-specified timestamp is present and within 5 seconds of it.
+    ServerPrefer,
 }
 
 impl From<TimestampingMode> for s2_common::config::TimestampingMode {
@@ -90,6 +125,7 @@ impl From<TimestampingMode> for s2_common::config::TimestampingMode {
             TimestampingMode::ClientPrefer => Self::ClientPrefer,
             TimestampingMode::ClientRequire => Self::ClientRequire,
             TimestampingMode::Arrival => Self::Arrival,
+            TimestampingMode::ServerPrefer => Self::ServerPrefer,
         }
     }
 }
@@ -100,6 +136,7 @@ impl From<s2_common::config::TimestampingMode> for TimestampingMode {
             s2_common::config::TimestampingMode::ClientPrefer => Self::ClientPrefer,
             s2_common::config::TimestampingMode::ClientRequire => Self::ClientRequire,
             s2_common::config::TimestampingMode::Arrival => Self::Arrival,
+            s2_common::config::TimestampingMode::ServerPrefer => Self::ServerPrefer,
         }
     }
 }
@@ -308,6 +345,30 @@ pub struct StreamConfig {
     /// Delete-on-empty configuration.
     #[serde(default)]
     pub delete_on_empty: Option<DeleteOnEmptyConfig>,
+    /// Minimum number of records that must remain in the stream after a trim.
+    /// Trims that would leave fewer records are rejected.
+    /// If unspecified or 0, trims are unrestricted.
+    #[serde(default)]
+    pub min_retained_records: Option<u64>,
+    /// Maximum append throughput in metered bytes per second.
+    /// Appends exceeding it are rejected with a rate limit error.
+    /// If unspecified or 0, throughput is unrestricted.
+    #[serde(default)]
+    pub max_append_bytes_per_sec: Option<u64>,
+    /// Maximum number of appends per second.
+    /// Appends exceeding it are rejected with a rate limit error.
+    /// If unspecified or 0, appends are unrestricted.
+    #[serde(default)]
+    pub max_append_ops_per_sec: Option<u64>,
+    /// Maximum duration in seconds that a client-specified record timestamp may be ahead of the arrival time.
+    /// Appends containing records with timestamps further in the future are rejected.
+    /// If unspecified or 0, future timestamps are not rejected.
+    #[serde(default)]
+    pub max_future_skew_secs: Option<u64>,
+    /// Advisory codec for compressing the stream's records at rest.
+    /// If unspecified, records are stored uncompressed.
+    #[serde(default)]
+    pub compression_hint: Option<CompressionHint>,
 }
 
 impl StreamConfig {
@@ -317,6 +378,11 @@ impl StreamConfig {
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         } = config;
 
         let config = StreamConfig {
@@ -324,6 +390,11 @@ impl StreamConfig {
             retention_policy: retention_policy.map(Into::into),
             timestamping: TimestampingConfig::to_opt(timestamping),
             delete_on_empty: DeleteOnEmptyConfig::to_opt(delete_on_empty),
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint: compression_hint.map(Into::into),
         };
         if config == Self::default() {
             None
@@ -340,6 +411,11 @@ impl From<s2_common::config::StreamConfig> for StreamConfig {
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         } = value;
 
         Self {
@@ -347,6 +423,11 @@ impl From<s2_common::config::StreamConfig> for StreamConfig {
             retention_policy: Some(retention_policy.into()),
             timestamping: Some(timestamping.into()),
             delete_on_empty: Some(delete_on_empty.into()),
+            min_retained_records: Some(min_retained_records),
+            max_append_bytes_per_sec: Some(max_append_bytes_per_sec),
+            max_append_ops_per_sec: Some(max_append_ops_per_sec),
+            max_future_skew_secs: Some(max_future_skew_secs),
+            compression_hint: Some(compression_hint.into()),
         }
     }
 }
@@ -360,6 +441,11 @@ impl TryFrom<StreamConfig> for s2_common::config::OptionalStreamConfig {
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         } = value;
 
         let retention_policy = match retention_policy {
@@ -372,6 +458,11 @@ impl TryFrom<StreamConfig> for s2_common::config::OptionalStreamConfig {
             retention_policy,
             timestamping: timestamping.map(Into::into).unwrap_or_default(),
             delete_on_empty: delete_on_empty.map(Into::into).unwrap_or_default(),
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint: compression_hint.map(Into::into),
         };
         config.validate()?;
         Ok(config)
@@ -399,6 +490,26 @@ pub struct StreamReconfiguration {
     #[serde(default, skip_serializing_if = "Maybe::is_unspecified")]
     #[cfg_attr(feature = "utoipa", schema(value_type = Option<DeleteOnEmptyReconfiguration>))]
     pub delete_on_empty: Maybe<Option<DeleteOnEmptyReconfiguration>>,
+    /// Minimum number of records that must remain in the stream after a trim.
+    #[serde(default, skip_serializing_if = "Maybe::is_unspecified")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<u64>))]
+    pub min_retained_records: Maybe<Option<u64>>,
+    /// Maximum append throughput in metered bytes per second.
+    #[serde(default, skip_serializing_if = "Maybe::is_unspecified")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<u64>))]
+    pub max_append_bytes_per_sec: Maybe<Option<u64>>,
+    /// Maximum number of appends per second.
+    #[serde(default, skip_serializing_if = "Maybe::is_unspecified")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<u64>))]
+    pub max_append_ops_per_sec: Maybe<Option<u64>>,
+    /// Maximum duration in seconds that a client-specified record timestamp may be ahead of the arrival time.
+    #[serde(default, skip_serializing_if = "Maybe::is_unspecified")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<u64>))]
+    pub max_future_skew_secs: Maybe<Option<u64>>,
+    /// Advisory codec for compressing the stream's records at rest.
+    #[serde(default, skip_serializing_if = "Maybe::is_unspecified")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<CompressionHint>))]
+    pub compression_hint: Maybe<Option<CompressionHint>>,
 }
 
 impl TryFrom<StreamReconfiguration> for s2_common::config::StreamReconfiguration {
@@ -410,6 +521,11 @@ impl TryFrom<StreamReconfiguration> for s2_common::config::StreamReconfiguration
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         } = value;
 
         Ok(Self {
@@ -417,6 +533,11 @@ impl TryFrom<StreamReconfiguration> for s2_common::config::StreamReconfiguration
             retention_policy: retention_policy.try_map_opt(TryInto::try_into)?,
             timestamping: timestamping.map_opt(Into::into),
             delete_on_empty: delete_on_empty.map_opt(Into::into),
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint: compression_hint.map_opt(Into::into),
         })
     }
 }
@@ -428,6 +549,11 @@ impl From<s2_common::config::StreamReconfiguration> for StreamReconfiguration {
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         } = value;
 
         Self {
@@ -435,6 +561,11 @@ impl From<s2_common::config::StreamReconfiguration> for StreamReconfiguration {
             retention_policy: retention_policy.map_opt(Into::into),
             timestamping: timestamping.map_opt(Into::into),
             delete_on_empty: delete_on_empty.map_opt(Into::into),
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint: compression_hint.map_opt(Into::into),
         }
     }
 }
@@ -570,11 +701,20 @@ mod tests {
         prop_oneof![Just(StorageClass::Standard), Just(StorageClass::Express)]
     }
 
+    fn gen_compression_hint() -> impl Strategy<Value = CompressionHint> {
+        prop_oneof![
+            Just(CompressionHint::None),
+            Just(CompressionHint::Zstd),
+            Just(CompressionHint::Gzip),
+        ]
+    }
+
     fn gen_timestamping_mode() -> impl Strategy<Value = TimestampingMode> {
         prop_oneof![
             Just(TimestampingMode::ClientPrefer),
             Just(TimestampingMode::ClientRequire),
             Just(TimestampingMode::Arrival),
+            Just(TimestampingMode::ServerPrefer),
         ]
     }
 
@@ -610,13 +750,33 @@ mod tests {
             proptest::option::of(gen_retention_policy()),
             proptest::option::of(gen_timestamping_config()),
             proptest::option::of(gen_delete_on_empty_config()),
+            proptest::option::of(any::<u64>()),
+            proptest::option::of(any::<u64>()),
+            proptest::option::of(any::<u64>()),
+            proptest::option::of(any::<u64>()),
+            proptest::option::of(gen_compression_hint()),
         )
             .prop_map(
-                |(storage_class, retention_policy, timestamping, delete_on_empty)| StreamConfig {
+                |(
                     storage_class,
                     retention_policy,
                     timestamping,
                     delete_on_empty,
+                    min_retained_records,
+                    max_append_bytes_per_sec,
+                    max_append_ops_per_sec,
+                    max_future_skew_secs,
+                    compression_hint,
+                )| StreamConfig {
+                    storage_class,
+                    retention_policy,
+                    timestamping,
+                    delete_on_empty,
+                    min_retained_records,
+                    max_append_bytes_per_sec,
+                    max_append_ops_per_sec,
+                    max_future_skew_secs,
+                    compression_hint,
                 },
             )
     }
@@ -661,15 +821,33 @@ mod tests {
             gen_maybe(gen_retention_policy()),
             gen_maybe(gen_timestamping_reconfiguration()),
             gen_maybe(gen_delete_on_empty_reconfiguration()),
+            gen_maybe(any::<u64>()),
+            gen_maybe(any::<u64>()),
+            gen_maybe(any::<u64>()),
+            gen_maybe(any::<u64>()),
+            gen_maybe(gen_compression_hint()),
         )
             .prop_map(
-                |(storage_class, retention_policy, timestamping, delete_on_empty)| {
-                    StreamReconfiguration {
-                        storage_class,
-                        retention_policy,
-                        timestamping,
-                        delete_on_empty,
-                    }
+                |(
+                    storage_class,
+                    retention_policy,
+                    timestamping,
+                    delete_on_empty,
+                    min_retained_records,
+                    max_append_bytes_per_sec,
+                    max_append_ops_per_sec,
+                    max_future_skew_secs,
+                    compression_hint,
+                )| StreamReconfiguration {
+                    storage_class,
+                    retention_policy,
+                    timestamping,
+                    delete_on_empty,
+                    min_retained_records,
+                    max_append_bytes_per_sec,
+                    max_append_ops_per_sec,
+                    max_future_skew_secs,
+                    compression_hint,
                 },
             )
     }
@@ -721,27 +899,39 @@ mod tests {
             proptest::option::of(gen_timestamping_mode()),
             proptest::option::of(any::<bool>()),
             proptest::option::of(any::<u64>()),
+            proptest::option::of(any::<u64>()),
+            proptest::option::of(any::<u64>()),
+            proptest::option::of(any::<u64>()),
+            proptest::option::of(any::<u64>()),
+            proptest::option::of(gen_compression_hint()),
         )
-            .prop_map(|(sc, rp, ts_mode, ts_uncapped, doe)| {
-                s2_common::config::OptionalStreamConfig {
-                    storage_class: sc.map(Into::into),
-                    retention_policy: rp.map(|rp| match rp {
-                        RetentionPolicy::Age(secs) => {
-                            s2_common::config::RetentionPolicy::Age(Duration::from_secs(secs))
-                        }
-                        RetentionPolicy::Infinite(_) => {
-                            s2_common::config::RetentionPolicy::Infinite()
-                        }
-                    }),
-                    timestamping: s2_common::config::OptionalTimestampingConfig {
-                        mode: ts_mode.map(Into::into),
-                        uncapped: ts_uncapped,
-                    },
-                    delete_on_empty: s2_common::config::OptionalDeleteOnEmptyConfig {
-                        min_age: doe.map(Duration::from_secs),
-                    },
-                }
-            })
+            .prop_map(
+                |(sc, rp, ts_mode, ts_uncapped, doe, mrr, mabps, maops, mfss, ch)| {
+                    s2_common::config::OptionalStreamConfig {
+                        storage_class: sc.map(Into::into),
+                        retention_policy: rp.map(|rp| match rp {
+                            RetentionPolicy::Age(secs) => {
+                                s2_common::config::RetentionPolicy::Age(Duration::from_secs(secs))
+                            }
+                            RetentionPolicy::Infinite(_) => {
+                                s2_common::config::RetentionPolicy::Infinite()
+                            }
+                        }),
+                        timestamping: s2_common::config::OptionalTimestampingConfig {
+                            mode: ts_mode.map(Into::into),
+                            uncapped: ts_uncapped,
+                        },
+                        delete_on_empty: s2_common::config::OptionalDeleteOnEmptyConfig {
+                            min_age: doe.map(Duration::from_secs),
+                        },
+                        min_retained_records: mrr,
+                        max_append_bytes_per_sec: mabps,
+                        max_append_ops_per_sec: maops,
+                        max_future_skew_secs: mfss,
+                        compression_hint: ch.map(Into::into),
+                    }
+                },
+            )
     }
 
     proptest! {
@@ -814,6 +1004,29 @@ mod tests {
                 merged.delete_on_empty.min_age,
                 stream.delete_on_empty.min_age.or(basin.delete_on_empty.min_age).unwrap_or_default()
             );
+            prop_assert_eq!(
+                merged.min_retained_records,
+                stream.min_retained_records.or(basin.min_retained_records).unwrap_or_default()
+            );
+            prop_assert_eq!(
+                merged.max_append_bytes_per_sec,
+                stream
+                    .max_append_bytes_per_sec
+                    .or(basin.max_append_bytes_per_sec)
+                    .unwrap_or_default()
+            );
+            prop_assert_eq!(
+                merged.max_append_ops_per_sec,
+                stream.max_append_ops_per_sec.or(basin.max_append_ops_per_sec).unwrap_or_default()
+            );
+            prop_assert_eq!(
+                merged.max_future_skew_secs,
+                stream.max_future_skew_secs.or(basin.max_future_skew_secs).unwrap_or_default()
+            );
+            prop_assert_eq!(
+                merged.compression_hint,
+                stream.compression_hint.or(basin.compression_hint).unwrap_or_default()
+            );
         }
 
         #[test]
@@ -826,6 +1039,11 @@ mod tests {
             prop_assert_eq!(result.timestamping.mode, base.timestamping.mode);
             prop_assert_eq!(result.timestamping.uncapped, base.timestamping.uncapped);
             prop_assert_eq!(result.delete_on_empty.min_age, base.delete_on_empty.min_age);
+            prop_assert_eq!(result.min_retained_records, base.min_retained_records);
+            prop_assert_eq!(result.max_append_bytes_per_sec, base.max_append_bytes_per_sec);
+            prop_assert_eq!(result.max_append_ops_per_sec, base.max_append_ops_per_sec);
+            prop_assert_eq!(result.max_future_skew_secs, base.max_future_skew_secs);
+            prop_assert_eq!(result.compression_hint, base.compression_hint);
         }
 
         #[test]
@@ -835,6 +1053,11 @@ mod tests {
                 retention_policy: Maybe::Specified(None),
                 timestamping: Maybe::Specified(None),
                 delete_on_empty: Maybe::Specified(None),
+                min_retained_records: Maybe::Specified(None),
+                max_append_bytes_per_sec: Maybe::Specified(None),
+                max_append_ops_per_sec: Maybe::Specified(None),
+                max_future_skew_secs: Maybe::Specified(None),
+                compression_hint: Maybe::Specified(None),
             };
             let result = base.reconfigure(reconfig);
 
@@ -843,6 +1066,11 @@ mod tests {
             prop_assert!(result.timestamping.mode.is_none());
             prop_assert!(result.timestamping.uncapped.is_none());
             prop_assert!(result.delete_on_empty.min_age.is_none());
+            prop_assert!(result.min_retained_records.is_none());
+            prop_assert!(result.max_append_bytes_per_sec.is_none());
+            prop_assert!(result.max_append_ops_per_sec.is_none());
+            prop_assert!(result.max_future_skew_secs.is_none());
+            prop_assert!(result.compression_hint.is_none());
         }
 
         #[test]
@@ -1056,5 +1284,25 @@ mod tests {
             internal.delete_on_empty.min_age.is_none(),
             "delete_on_empty.min_age should be None"
         );
+        assert!(
+            internal.min_retained_records.is_none(),
+            "min_retained_records should be None"
+        );
+        assert!(
+            internal.max_append_bytes_per_sec.is_none(),
+            "max_append_bytes_per_sec should be None"
+        );
+        assert!(
+            internal.max_append_ops_per_sec.is_none(),
+            "max_append_ops_per_sec should be None"
+        );
+        assert!(
+            internal.max_future_skew_secs.is_none(),
+            "max_future_skew_secs should be None"
+        );
+        assert!(
+            internal.compression_hint.is_none(),
+            "compression_hint should be None"
+        );
     }
 }
@@ -33,6 +33,7 @@ pub enum ErrorCode {
     Invalid,
     NotImplemented,
     Other,
+    PayloadTooLarge,
     PermissionDenied,
     QuotaExhausted,
     RateLimited,
@@ -42,6 +43,7 @@ pub enum ErrorCode {
     StreamDeletionPending,
     StreamNotFound,
     TransactionConflict,
+    Trimmed,
     Unavailable,
     UpstreamTimeout,
 }
@@ -69,12 +71,14 @@ impl ErrorCode {
                 http::StatusCode::NOT_FOUND
             }
             Self::RequestTimeout => http::StatusCode::REQUEST_TIMEOUT,
+            Self::Trimmed => http::StatusCode::GONE,
             Self::BasinDeletionPending
             | Self::ResourceAlreadyExists
             | Self::StreamDeletionPending
             | Self::TransactionConflict => http::StatusCode::CONFLICT,
             Self::Invalid => http::StatusCode::UNPROCESSABLE_ENTITY,
             Self::NotImplemented => http::StatusCode::NOT_IMPLEMENTED,
+            Self::PayloadTooLarge => http::StatusCode::PAYLOAD_TOO_LARGE,
             Self::RateLimited => http::StatusCode::TOO_MANY_REQUESTS,
             Self::ClientHangup => http::StatusCode::from_u16(499).expect("valid status code"),
             Self::Other | Self::Storage => http::StatusCode::INTERNAL_SERVER_ERROR,
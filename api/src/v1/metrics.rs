@@ -1,4 +1,5 @@
 use compact_str::CompactString;
+use s2_common::basin::BasinNamePrefix;
 use serde::{Deserialize, Serialize};
 
 #[rustfmt::skip]
@@ -110,6 +111,45 @@ impl From<BasinMetricSetRequest> for s2_common::metrics::BasinMetricsRequest {
     }
 }
 
+#[rustfmt::skip]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Query))]
+pub struct BasinsAggregateMetricSetRequest {
+    /// Aggregate over basins whose names begin with this prefix.
+    #[cfg_attr(feature = "utoipa", param(value_type = String, default = "", required = false))]
+    pub prefix: Option<BasinNamePrefix>,
+    /// Metric set to return.
+    pub set: BasinMetricSet,
+    /// Start timestamp as Unix epoch seconds, if applicable for the metric set.
+    pub start: Option<u32>,
+    /// End timestamp as Unix epoch seconds, if applicable for the metric set.
+    pub end: Option<u32>,
+    /// Interval to aggregate over for timeseries metric sets.
+    pub interval: Option<TimeseriesInterval>,
+}
+
+impl From<BasinsAggregateMetricSetRequest>
+    for (BasinNamePrefix, s2_common::metrics::BasinMetricsRequest)
+{
+    fn from(value: BasinsAggregateMetricSetRequest) -> Self {
+        let BasinsAggregateMetricSetRequest {
+            prefix,
+            set,
+            start,
+            end,
+            interval,
+        } = value;
+        let request = BasinMetricSetRequest {
+            set,
+            start,
+            end,
+            interval,
+        };
+        (prefix.unwrap_or_default(), request.into())
+    }
+}
+
 #[rustfmt::skip]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -173,6 +213,8 @@ pub enum StreamMetricSet {
 pub enum MetricUnit {
     Bytes,
     Operations,
+    Count,
+    Percent,
 }
 
 impl From<s2_common::metrics::MetricUnit> for MetricUnit {
@@ -180,6 +222,8 @@ impl From<s2_common::metrics::MetricUnit> for MetricUnit {
         match value {
             s2_common::metrics::MetricUnit::Bytes => MetricUnit::Bytes,
             s2_common::metrics::MetricUnit::Operations => MetricUnit::Operations,
+            s2_common::metrics::MetricUnit::Count => MetricUnit::Count,
+            s2_common::metrics::MetricUnit::Percent => MetricUnit::Percent,
         }
     }
 }
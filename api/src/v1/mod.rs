@@ -7,7 +7,8 @@ pub mod metrics;
 pub mod stream;
 
 use s2_common::{
-    access::AccessTokenId, basin::BasinName, resources::RequestToken, stream::StreamName,
+    access::AccessTokenId, basin::BasinName, consumer::ConsumerName, resources::RequestToken,
+    stream::StreamName,
 };
 
 #[rustfmt::skip]
@@ -47,6 +48,15 @@ pub struct StreamNamePathSegment {
     pub stream: StreamName,
 }
 
+#[rustfmt::skip]
+#[derive(Debug)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Path))]
+pub struct ConsumerNamePathSegment {
+    /// Consumer name.
+    pub consumer: ConsumerName,
+}
+
 macro_rules! impl_list_request_conversions {
     ($name:ident, $prefix:ty, $start_after:ty) => {
         impl TryFrom<$name> for s2_common::resources::ListItemsRequest<$prefix, $start_after> {
@@ -57,13 +67,15 @@ macro_rules! impl_list_request_conversions {
                     prefix,
                     start_after,
                     limit,
+                    ..
                 } = value;
 
-                Ok(Self {
-                    prefix: prefix.unwrap_or_default(),
-                    start_after: start_after.unwrap_or_default(),
-                    limit: limit.map(Into::into).unwrap_or_default(),
-                })
+                Self::new(
+                    prefix.unwrap_or_default(),
+                    start_after.unwrap_or_default(),
+                    limit.map(Into::into).unwrap_or_default(),
+                )
+                .map_err(Into::into)
             }
         }
     };
@@ -1,5 +1,6 @@
 use axum::{
-    extract::{FromRequest, FromRequestParts, Request},
+    body::Bytes,
+    extract::{FromRequest, FromRequestParts, Request, rejection::BytesRejection},
     response::{IntoResponse, Response},
 };
 use futures_util::StreamExt as _;
@@ -10,10 +11,13 @@ use s2_common::{
 };
 use tokio_util::{codec::FramedRead, io::StreamReader};
 
-use super::{AppendInput, AppendInputStreamError, AppendRequest, ReadRequest, proto, s2s};
+use super::{
+    AppendInput, AppendInputStreamError, AppendRequest, FollowRequest, RawAppendRequest,
+    ReadRequest, proto, s2s,
+};
 use crate::{
     data::{
-        Format, Json, Proto,
+        Format, Json, JsonLines, Proto,
         extract::{JsonExtractionRejection, ProtoRejection},
     },
     mime::JsonOrProto,
@@ -29,6 +33,8 @@ pub enum AppendRequestRejection {
     #[error(transparent)]
     ProtoRejection(#[from] ProtoRejection),
     #[error(transparent)]
+    BodyRejection(#[from] BytesRejection),
+    #[error(transparent)]
     Validation(#[from] s2_common::ValidationError),
 }
 
@@ -38,6 +44,7 @@ impl IntoResponse for AppendRequestRejection {
             AppendRequestRejection::HeaderRejection(e) => e.into_response(),
             AppendRequestRejection::JsonRejection(e) => e.into_response(),
             AppendRequestRejection::ProtoRejection(e) => e.into_response(),
+            AppendRequestRejection::BodyRejection(e) => e.into_response(),
             AppendRequestRejection::Validation(e) => {
                 (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response()
             }
@@ -74,8 +81,9 @@ where
                 match msg? {
                     s2s::SessionMessage::Regular(data) => {
                         let input = data.try_into_proto::<proto::AppendInput>()?;
+                        let correlation_id = input.correlation_id;
                         let input = s2_common::stream::AppendInput::try_from(input)?;
-                        Ok(Some((input, framed)))
+                        Ok(Some(((input, correlation_id), framed)))
                     }
                     s2s::SessionMessage::Terminal(_) => {
                         Err(AppendInputStreamError::FrameDecode(std::io::Error::new(
@@ -93,6 +101,24 @@ where
             });
         }
 
+        if content_type.as_ref().is_some_and(crate::mime::is_ndjson) {
+            let format = parse_header_opt::<Format>(req.headers())?.unwrap_or_default();
+            let JsonLines(inputs) = JsonLines::<AppendInput>::from_request(req, state).await?;
+            if inputs.is_empty() {
+                return Err(s2_common::ValidationError::from(
+                    "at least one append input is required",
+                ))?;
+            }
+            let inputs = inputs
+                .into_iter()
+                .map(|input| input.decode(format))
+                .collect::<Result<_, _>>()?;
+            return Ok(Self::Batches {
+                encryption_key,
+                inputs,
+            });
+        }
+
         let request_mime = content_type
             .as_ref()
             .and_then(JsonOrProto::from_mime)
@@ -123,6 +149,32 @@ where
     }
 }
 
+impl<S> FromRequest<S> for RawAppendRequest
+where
+    S: Send + Sync,
+{
+    type Rejection = AppendRequestRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let encryption_key = parse_header_opt::<EncryptionKey>(req.headers())?;
+
+        let response_mime = crate::mime::accept(req.headers())
+            .as_ref()
+            .and_then(JsonOrProto::from_mime)
+            .unwrap_or(JsonOrProto::Json);
+
+        let headers = req.headers().clone();
+        let body = Bytes::from_request(req, state).await?;
+        let input = RawAppendRequest::decode(&headers, body)?;
+
+        Ok(Self {
+            encryption_key,
+            input,
+            response_mime,
+        })
+    }
+}
+
 impl<S> FromRequestParts<S> for ReadRequest
 where
     S: Send + Sync,
@@ -168,6 +220,20 @@ where
     }
 }
 
+impl<S> FromRequestParts<S> for FollowRequest
+where
+    S: Send + Sync,
+{
+    type Rejection = HeaderRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            encryption_key: parse_header_opt::<EncryptionKey>(&parts.headers)?,
+            format: parse_header_opt::<Format>(&parts.headers)?.unwrap_or_default(),
+        })
+    }
+}
+
 fn parse_header_opt<T>(headers: &http::HeaderMap) -> Result<Option<T>, HeaderRejection>
 where
     T: ParseableHeader,
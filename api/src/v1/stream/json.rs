@@ -9,13 +9,39 @@ use crate::data::Format;
 
 pub fn serialize_read_batch(
     format: Format,
+    inline_json: bool,
     batch: &s2_common::stream::ReadBatch,
 ) -> impl Serialize + '_ {
-    ReadBatchJson { format, batch }
+    ReadBatchJson {
+        format,
+        inline_json,
+        batch,
+    }
+}
+
+/// Body of a record as a JSON value, if it has a JSON `content-type` header and the body parses.
+pub(crate) fn inline_json_body(
+    headers: &[record::Header],
+    body: &[u8],
+) -> Option<serde_json::Value> {
+    let content_type = headers
+        .iter()
+        .rev()
+        .find(|h| h.name.eq_ignore_ascii_case(b"content-type"))?;
+    let mime: mime::Mime = std::str::from_utf8(&content_type.value)
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    if !crate::mime::is_json(&mime) {
+        return None;
+    }
+    serde_json::from_slice(body).ok()
 }
 
 struct ReadBatchJson<'a> {
     format: Format,
+    inline_json: bool,
     batch: &'a s2_common::stream::ReadBatch,
 }
 
@@ -30,6 +56,7 @@ impl Serialize for ReadBatchJson<'_> {
             "records",
             &RecordsJson {
                 format: self.format,
+                inline_json: self.inline_json,
                 records: self.batch.records.as_slice(),
             },
         )?;
@@ -42,6 +69,7 @@ impl Serialize for ReadBatchJson<'_> {
 
 struct RecordsJson<'a> {
     format: Format,
+    inline_json: bool,
     records: &'a [record::SequencedRecord],
 }
 
@@ -54,6 +82,7 @@ impl Serialize for RecordsJson<'_> {
         for record in self.records {
             seq.serialize_element(&RecordJson {
                 format: self.format,
+                inline_json: self.inline_json,
                 record,
             })?;
         }
@@ -63,6 +92,7 @@ impl Serialize for RecordsJson<'_> {
 
 struct RecordJson<'a> {
     format: Format,
+    inline_json: bool,
     record: &'a record::SequencedRecord,
 }
 
@@ -121,13 +151,20 @@ impl Serialize for RecordJson<'_> {
                     )?;
                 }
                 if !envelope.body().is_empty() {
-                    state.serialize_field(
-                        "body",
-                        &FormattedBytes {
-                            format: self.format,
-                            bytes: envelope.body().as_ref(),
-                        },
-                    )?;
+                    match self
+                        .inline_json
+                        .then(|| inline_json_body(envelope.headers(), envelope.body()))
+                        .flatten()
+                    {
+                        Some(value) => state.serialize_field("body", &value)?,
+                        None => state.serialize_field(
+                            "body",
+                            &FormattedBytes {
+                                format: self.format,
+                                bytes: envelope.body().as_ref(),
+                            },
+                        )?,
+                    }
                 }
             }
         }
@@ -355,10 +392,79 @@ mod tests {
         let batch = fixture_batch();
 
         for format in [Format::Raw, Format::Base64] {
-            let expected =
-                serde_json::to_value(ReadBatch::encode(format, batch.clone())).expect("json");
-            let actual = serde_json::to_value(serialize_read_batch(format, &batch)).expect("json");
-            assert_eq!(actual, expected);
+            for inline_json in [false, true] {
+                let expected =
+                    serde_json::to_value(ReadBatch::encode(format, inline_json, batch.clone()))
+                        .expect("json");
+                let actual =
+                    serde_json::to_value(serialize_read_batch(format, inline_json, &batch))
+                        .expect("json");
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn inline_json_embeds_bodies_with_json_content_type() {
+        fn record(content_type: &'static [u8], body: &'static [u8]) -> record::Record {
+            record::Record::try_from_parts(
+                vec![record::Header {
+                    name: Bytes::from_static(b"Content-Type"),
+                    value: Bytes::from_static(content_type),
+                }],
+                Bytes::from_static(body),
+            )
+            .expect("valid envelope")
         }
+
+        let batch = s2_common::stream::ReadBatch {
+            records: [
+                record(b"application/json", br#"{"a":[1,2]}"#),
+                record(b"application/vnd.s2+json; charset=utf-8", b"\"s2\""),
+                record(b"application/json", b"{not json"),
+                record(b"text/plain", b"[1]"),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(i, record)| {
+                record.metered().sequenced(record::StreamPosition {
+                    seq_num: i as u64,
+                    timestamp: 0,
+                })
+            })
+            .collect(),
+            tail: None,
+        };
+
+        let bodies = |inline_json| {
+            let value =
+                serde_json::to_value(serialize_read_batch(Format::Raw, inline_json, &batch))
+                    .expect("json");
+            value["records"]
+                .as_array()
+                .expect("records")
+                .iter()
+                .map(|record| record["body"].clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            bodies(true),
+            vec![
+                serde_json::json!({"a": [1, 2]}),
+                serde_json::json!("s2"),
+                serde_json::json!("{not json"),
+                serde_json::json!("[1]"),
+            ]
+        );
+        assert_eq!(
+            bodies(false),
+            vec![
+                serde_json::json!(r#"{"a":[1,2]}"#),
+                serde_json::json!("\"s2\""),
+                serde_json::json!("{not json"),
+                serde_json::json!("[1]"),
+            ]
+        );
     }
 }
@@ -57,15 +57,61 @@ pub struct ListStreamsRequest {
     #[cfg_attr(feature = "utoipa", param(value_type = String, default = "", required = false))]
     pub prefix: Option<StreamNamePrefix>,
     /// Filter to streams whose names lexicographically start after this string.
+    /// If non-empty, it must not sort before `prefix`.
     #[cfg_attr(feature = "utoipa", param(value_type = String, default = "", required = false))]
     pub start_after: Option<StreamNameStartAfter>,
     /// Number of results, up to a maximum of 1000.
     #[cfg_attr(feature = "utoipa", param(value_type = usize, maximum = 1000, default = 1000, required = false))]
     pub limit: Option<usize>,
+    /// Order in which streams are listed.
+    #[cfg_attr(feature = "utoipa", param(inline, required = false))]
+    pub order: Option<ListStreamsOrder>,
+    /// With `order=created`, the creation time of the `start_after` stream in RFC 3339 format,
+    /// as it was listed. Required with a non-empty `start_after` in that order, and listing
+    /// resumes after that stream's position even if it has since been deleted.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    #[cfg_attr(feature = "utoipa", param(value_type = String, required = false))]
+    pub start_after_created_at: Option<OffsetDateTime>,
+    /// Filter to streams in this state. All streams are listed if not specified.
+    #[cfg_attr(feature = "utoipa", param(inline, required = false))]
+    pub state: Option<ListStreamsState>,
 }
 
 super::impl_list_request_conversions!(ListStreamsRequest, StreamNamePrefix, StreamNameStartAfter);
 
+#[rustfmt::skip]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ListStreamsOrder {
+    /// Lexicographic order of stream names.
+    #[default]
+    Name,
+    /// Order of stream creation, oldest first.
+    Created,
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ListStreamsState {
+    /// Streams that are not being deleted.
+    Active,
+    /// Streams whose deletion is pending.
+    Deleting,
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Query))]
+pub struct DeleteStreamRequest {
+    /// Remove the stream and all of its records before responding, bypassing any deletion grace period.
+    #[cfg_attr(feature = "utoipa", param(value_type = bool, default = false, required = false))]
+    pub purge: Option<bool>,
+}
+
 #[rustfmt::skip]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -126,6 +172,76 @@ pub struct TailResponse {
     pub tail: StreamPosition,
 }
 
+#[rustfmt::skip]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct StreamCursorResponse {
+    /// Sequence number committed by the consumer, if any.
+    pub seq_num: Option<record::SeqNum>,
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CommitStreamCursorRequest {
+    /// Sequence number to commit for the consumer.
+    pub seq_num: record::SeqNum,
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Query))]
+pub struct ListCommandRecordsRequest {
+    /// List command records starting at this sequence number.
+    #[cfg_attr(feature = "utoipa", param(value_type = u64, default = 0, required = false))]
+    pub seq_num: Option<record::SeqNum>,
+    /// Number of results, up to a maximum of 1000.
+    #[cfg_attr(feature = "utoipa", param(value_type = usize, maximum = 1000, default = 1000, required = false))]
+    pub limit: Option<usize>,
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum Command {
+    /// Fencing token set by a `fence` command record.
+    Fence(record::FencingToken),
+    /// Trim point requested by a `trim` command record.
+    Trim(record::SeqNum),
+}
+
+impl From<record::CommandRecord> for Command {
+    fn from(value: record::CommandRecord) -> Self {
+        match value {
+            record::CommandRecord::Fence(token) => Self::Fence(token),
+            record::CommandRecord::Trim(trim_point) => Self::Trim(trim_point),
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CommandRecordInfo {
+    /// Position of the command record.
+    pub position: StreamPosition,
+    /// Command and its argument.
+    pub command: Command,
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ListCommandRecordsResponse {
+    /// Command records in sequence number order.
+    #[cfg_attr(feature = "utoipa", schema(max_items = 1000))]
+    pub commands: Vec<CommandRecordInfo>,
+    /// Indicates that there are more command records at later sequence numbers.
+    pub has_more: bool,
+}
+
 #[rustfmt::skip]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
@@ -144,6 +260,10 @@ pub struct ReadStart {
     /// Otherwise, a `416 Range Not Satisfiable` response is returned.
     #[cfg_attr(feature = "utoipa", param(value_type = bool, required = false))]
     pub clamp: Option<bool>,
+    /// Return a `410 Gone` response if the requested sequence number has been trimmed.
+    /// Otherwise, reading starts from the earliest available record.
+    #[cfg_attr(feature = "utoipa", param(value_type = bool, required = false))]
+    pub error_if_trimmed: Option<bool>,
 }
 
 impl TryFrom<ReadStart> for s2_common::stream::ReadStart {
@@ -162,7 +282,12 @@ impl TryFrom<ReadStart> for s2_common::stream::ReadStart {
             }
         };
         let clamp = value.clamp.unwrap_or(false);
-        Ok(Self { from, clamp })
+        let error_if_trimmed = value.error_if_trimmed.unwrap_or(false);
+        Ok(Self {
+            from,
+            clamp,
+            error_if_trimmed,
+        })
     }
 }
 
@@ -184,12 +309,45 @@ pub struct ReadEnd {
     pub until: Option<record::Timestamp>,
     /// Duration in seconds to wait for new records.
     /// The default duration is 0 if there is a bound on `count`, `bytes`, or `until`, and otherwise infinite.
-    /// Non-streaming reads are always bounded on `count` and `bytes`, so you can achieve long poll semantics by specifying a non-zero duration up to 60 seconds.
+    /// Non-streaming reads are always bounded on `count` and `bytes`, so you can achieve long poll semantics by specifying a non-zero duration up to the server's maximum, which is 60 seconds by default.
     /// In the context of an SSE or S2S streaming read, the duration will bound how much time can elapse between records throughout the lifetime of the session.
     #[cfg_attr(feature = "utoipa", param(value_type = u32, required = false))]
     pub wait: Option<u32>,
 }
 
+#[rustfmt::skip]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Query))]
+pub struct ReadOptions {
+    /// Embed the body of records with a JSON `content-type` header as a JSON value, rather than as a string encoded as per `s2-format`.
+    /// Bodies that are not valid JSON are encoded as usual.
+    /// Only applies to JSON responses, including SSE.
+    #[cfg_attr(feature = "utoipa", param(value_type = bool, required = false))]
+    pub inline_json: Option<bool>,
+    /// How to handle a stored record that cannot be decoded or decrypted, defaulting to `fail`.
+    #[cfg_attr(feature = "utoipa", param(value_type = OnDecodeError, required = false))]
+    pub on_decode_error: Option<OnDecodeError>,
+    /// Respond with only the position of each record, as a `PositionsBatch` with `positions` in place of `records`, omitting headers and bodies.
+    /// Records are not decoded, so no encryption key is needed, and bodies do not count towards a `bytes` limit.
+    /// The read does not wait for new records. Only supported for unary reads with a JSON response.
+    #[cfg_attr(feature = "utoipa", param(value_type = bool, required = false))]
+    pub positions_only: Option<bool>,
+}
+
+#[rustfmt::skip]
+/// Handling of a stored record that cannot be decoded or decrypted during a read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum OnDecodeError {
+    /// End the read with an error.
+    #[default]
+    Fail,
+    /// Skip the record and continue reading.
+    Skip,
+}
+
 impl From<ReadEnd> for s2_common::stream::ReadEnd {
     fn from(value: ReadEnd) -> Self {
         Self {
@@ -224,6 +382,13 @@ pub enum ReadRequest {
     },
 }
 
+/// Live tailing over a WebSocket, where each record is sent as a JSON text frame.
+#[derive(Debug, Clone)]
+pub struct FollowRequest {
+    pub encryption_key: Option<EncryptionKey>,
+    pub format: Format,
+}
+
 pub enum AppendRequest {
     /// Unary
     Unary {
@@ -231,14 +396,80 @@ pub enum AppendRequest {
         input: s2_common::stream::AppendInput,
         response_mime: JsonOrProto,
     },
-    /// S2S bi-directional streaming
+    /// Multiple batches from newline-delimited JSON, appended in order
+    Batches {
+        encryption_key: Option<EncryptionKey>,
+        inputs: Vec<s2_common::stream::AppendInput>,
+    },
+    /// S2S bi-directional streaming, with inputs paired with their client-assigned correlation id
     S2s {
         encryption_key: Option<EncryptionKey>,
-        inputs: BoxStream<'static, Result<s2_common::stream::AppendInput, AppendInputStreamError>>,
+        inputs: BoxStream<
+            'static,
+            Result<(s2_common::stream::AppendInput, Option<u64>), AppendInputStreamError>,
+        >,
         response_compression: s2s::CompressionAlgorithm,
     },
 }
 
+/// Response header on a failed newline-delimited JSON append, with how many of the leading batches
+/// were appended before the failure. The batches after the failed one were not attempted.
+pub static APPENDED_BATCHES_HEADER: http::HeaderName =
+    http::HeaderName::from_static("s2-appended-batches");
+
+/// Prefix of request headers that are mapped to record headers for a raw append.
+pub const RAW_APPEND_HEADER_PREFIX: &str = "x-s2-header-";
+
+/// Append of a single record, whose body is the request body verbatim.
+///
+/// Every `x-s2-header-{name}: {value}` request header becomes a record header `{name}: {value}`.
+/// Record headers are ordered by name, as HTTP does not preserve the order of distinct header
+/// names; repeated headers keep their request order. Header names arrive lowercased as per HTTP
+/// semantics.
+#[derive(Debug)]
+pub struct RawAppendRequest {
+    pub encryption_key: Option<EncryptionKey>,
+    pub input: s2_common::stream::AppendInput,
+    pub response_mime: JsonOrProto,
+}
+
+impl RawAppendRequest {
+    pub fn decode(
+        headers: &http::HeaderMap,
+        body: bytes::Bytes,
+    ) -> Result<s2_common::stream::AppendInput, s2_common::ValidationError> {
+        let mut headers: Vec<_> = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let name = name.as_str().strip_prefix(RAW_APPEND_HEADER_PREFIX)?;
+                Some(record::Header {
+                    name: bytes::Bytes::copy_from_slice(name.as_bytes()),
+                    value: bytes::Bytes::copy_from_slice(value.as_bytes()),
+                })
+            })
+            .collect();
+        headers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let record = record::Record::try_from_parts(headers, body)
+            .map_err(|e| e.to_string())?
+            .into();
+
+        let parts = s2_common::stream::AppendRecordParts {
+            timestamp: None,
+            record,
+        };
+
+        let record = s2_common::stream::AppendRecord::try_from(parts)
+            .map_err(|e| s2_common::ValidationError(e.to_string()))?;
+
+        Ok(s2_common::stream::AppendInput {
+            records: s2_common::stream::AppendRecordBatch::try_from(vec![record])?,
+            match_seq_num: None,
+            fencing_token: None,
+        })
+    }
+}
+
 impl std::fmt::Debug for AppendRequest {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -252,6 +483,14 @@ impl std::fmt::Debug for AppendRequest {
                 .field("input", input)
                 .field("response", response)
                 .finish(),
+            AppendRequest::Batches {
+                encryption_key,
+                inputs,
+            } => f
+                .debug_struct("AppendRequest::Batches")
+                .field("encryption_key", encryption_key)
+                .field("inputs", inputs)
+                .finish(),
             AppendRequest::S2s {
                 encryption_key,
                 response_compression,
@@ -291,15 +530,40 @@ pub struct SequencedRecord {
     #[cfg_attr(feature = "utoipa", schema(required = false))]
     pub headers: Vec<Header>,
     /// Body of the record.
-    #[serde(default, skip_serializing_if = "String::is_empty")]
+    #[serde(skip_serializing_if = "SequencedRecordBody::is_empty")]
     #[cfg_attr(feature = "utoipa", schema(required = false))]
-    pub body: String,
+    pub body: SequencedRecordBody,
+}
+
+#[rustfmt::skip]
+/// Body of a sequenced record.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(untagged)]
+pub enum SequencedRecordBody {
+    /// Body encoded as per the `s2-format` header.
+    Encoded(String),
+    /// Body embedded as a JSON value, when `inline_json` is requested and the record has a JSON `content-type` header.
+    Json(serde_json::Value),
+}
+
+impl SequencedRecordBody {
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Encoded(body) if body.is_empty())
+    }
 }
 
 impl SequencedRecord {
-    pub fn encode(format: Format, record: record::SequencedRecord) -> Self {
+    pub fn encode(format: Format, inline_json: bool, record: record::SequencedRecord) -> Self {
         let (record::StreamPosition { seq_num, timestamp }, record) = record.into_parts();
         let (headers, body) = record.into_parts();
+        let body = match inline_json
+            .then(|| json::inline_json_body(&headers, &body))
+            .flatten()
+        {
+            Some(value) => SequencedRecordBody::Json(value),
+            None => SequencedRecordBody::Encoded(format.encode(&body)),
+        };
         Self {
             seq_num,
             timestamp,
@@ -307,7 +571,7 @@ impl SequencedRecord {
                 .into_iter()
                 .map(|h| Header(format.encode(&h.name), format.encode(&h.value)))
                 .collect(),
-            body: format.encode(&body),
+            body,
         }
     }
 }
@@ -363,6 +627,18 @@ impl AppendRecord {
     }
 }
 
+#[rustfmt::skip]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "utoipa", into_params(parameter_in = Query))]
+pub struct AppendOptions {
+    /// Decode and validate the records without appending them, responding with no content if they are valid.
+    /// The stream must exist, as it is not auto-created, and its timestamping config and cipher are checked against the records and encryption key.
+    /// Conditions such as `match_seq_num` and `fencing_token` are not checked. Not supported for S2S sessions.
+    #[cfg_attr(feature = "utoipa", param(value_type = bool, required = false))]
+    pub validate_only: Option<bool>,
+}
+
 #[rustfmt::skip]
 /// Payload of an `append` request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -451,14 +727,51 @@ pub struct ReadBatch {
 }
 
 impl ReadBatch {
-    pub fn encode(format: Format, batch: s2_common::stream::ReadBatch) -> Self {
+    pub fn encode(format: Format, inline_json: bool, batch: s2_common::stream::ReadBatch) -> Self {
         Self {
             records: batch
                 .records
                 .into_iter()
-                .map(|record| SequencedRecord::encode(format, record))
+                .map(|record| SequencedRecord::encode(format, inline_json, record))
                 .collect(),
             tail: batch.tail.map(Into::into),
         }
     }
 }
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+/// Response to a read with `positions_only`, in place of a `ReadBatch`.
+pub struct PositionsBatch {
+    /// Positions of the records that would have been returned by the read, in order.
+    /// Bodies do not count towards a `bytes` limit, so up to `count` (at most 1000) positions are returned.
+    pub positions: Vec<StreamPosition>,
+    /// Sequence number that will be assigned to the next record on the stream, and timestamp of the last record.
+    /// This will only be present when the positions reach the tail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tail: Option<StreamPosition>,
+}
+
+#[rustfmt::skip]
+/// JSON response to a unary read.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(untagged)]
+pub enum UnaryReadResponse {
+    /// Records that were read.
+    #[cfg_attr(feature = "utoipa", schema(title = "records"))]
+    Records(ReadBatch),
+    /// Positions of the records that were read, when `positions_only` is requested.
+    #[cfg_attr(feature = "utoipa", schema(title = "positions"))]
+    Positions(PositionsBatch),
+}
+
+impl From<s2_common::stream::PositionsBatch> for PositionsBatch {
+    fn from(batch: s2_common::stream::PositionsBatch) -> Self {
+        Self {
+            positions: batch.positions.into_iter().map(Into::into).collect(),
+            tail: batch.tail.map(Into::into),
+        }
+    }
+}
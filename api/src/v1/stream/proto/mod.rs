@@ -51,6 +51,7 @@ impl TryFrom<AppendInput> for s2_common::stream::AppendInput {
             records,
             match_seq_num,
             fencing_token,
+            correlation_id: _,
         }: AppendInput,
     ) -> Result<Self, Self::Error> {
         let records = records
@@ -77,6 +78,7 @@ impl From<s2_common::stream::AppendAck> for AppendAck {
             start: Some(start.into()),
             end: Some(end.into()),
             tail: Some(tail.into()),
+            correlation_id: None,
         }
     }
 }
@@ -48,6 +48,9 @@ pub struct AppendInput {
     /// Enforce a fencing token which must have been previously set by a `fence` command record.
     #[prost(string, optional, tag = "3")]
     pub fencing_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// Client-assigned identifier for this batch in an append session, which is echoed in the corresponding acknowledgement.
+    #[prost(uint64, optional, tag = "4")]
+    pub correlation_id: ::core::option::Option<u64>,
 }
 /// Success response message to an Append request.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
@@ -63,6 +66,9 @@ pub struct AppendAck {
     /// This can be greater than the `end` position in case of concurrent appends.
     #[prost(message, optional, tag = "3")]
     pub tail: ::core::option::Option<StreamPosition>,
+    /// Correlation identifier of the acknowledged batch, if one was assigned by the client in an append session.
+    #[prost(uint64, optional, tag = "4")]
+    pub correlation_id: ::core::option::Option<u64>,
 }
 /// Record that is durably sequenced on a stream.
 #[derive(Clone, PartialEq, ::prost::Message)]
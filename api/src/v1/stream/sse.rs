@@ -136,13 +136,18 @@ fn elapsed_since_epoch() -> std::time::Duration {
 #[cfg(feature = "axum")]
 pub fn read_batch_event(
     format: crate::data::Format,
+    inline_json: bool,
     batch: &s2_common::stream::ReadBatch,
     id: LastEventId,
 ) -> Result<axum::response::sse::Event, axum::Error> {
     axum::response::sse::Event::default()
         .event(Batch::Batch)
         .id(id.to_string())
-        .json_data(super::json::serialize_read_batch(format, batch))
+        .json_data(super::json::serialize_read_batch(
+            format,
+            inline_json,
+            batch,
+        ))
 }
 
 #[cfg(feature = "axum")]
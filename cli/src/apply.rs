@@ -6,8 +6,8 @@ use colored::Colorize;
 use s2_common::{
     basin::BasinName,
     config::{
-        BasinConfig, OptionalDeleteOnEmptyConfig, OptionalStreamConfig, OptionalTimestampingConfig,
-        RetentionPolicy, StorageClass, StreamConfig, TimestampingMode,
+        BasinConfig, CompressionHint, OptionalDeleteOnEmptyConfig, OptionalStreamConfig,
+        OptionalTimestampingConfig, RetentionPolicy, StorageClass, StreamConfig, TimestampingMode,
     },
     encryption::EncryptionAlgorithm,
     stream::StreamName,
@@ -41,6 +41,11 @@ fn optional_stream_config_from_sdk(config: s2_sdk::types::StreamConfig) -> Optio
             .delete_on_empty
             .map(delete_on_empty_from_sdk)
             .unwrap_or_default(),
+        min_retained_records: config.min_retained_records,
+        max_append_bytes_per_sec: config.max_append_bytes_per_sec,
+        max_append_ops_per_sec: config.max_append_ops_per_sec,
+        max_future_skew_secs: config.max_future_skew_secs,
+        compression_hint: config.compression_hint.map(compression_hint_from_sdk),
     }
 }
 
@@ -51,6 +56,14 @@ fn storage_class_from_sdk(storage_class: s2_sdk::types::StorageClass) -> Storage
     }
 }
 
+fn compression_hint_from_sdk(compression_hint: s2_sdk::types::CompressionHint) -> CompressionHint {
+    match compression_hint {
+        s2_sdk::types::CompressionHint::None => CompressionHint::None,
+        s2_sdk::types::CompressionHint::Zstd => CompressionHint::Zstd,
+        s2_sdk::types::CompressionHint::Gzip => CompressionHint::Gzip,
+    }
+}
+
 fn retention_policy_from_sdk(retention_policy: s2_sdk::types::RetentionPolicy) -> RetentionPolicy {
     match retention_policy {
         s2_sdk::types::RetentionPolicy::Age(secs) => {
@@ -74,6 +87,7 @@ fn timestamping_mode_from_sdk(mode: s2_sdk::types::TimestampingMode) -> Timestam
         s2_sdk::types::TimestampingMode::ClientPrefer => TimestampingMode::ClientPrefer,
         s2_sdk::types::TimestampingMode::ClientRequire => TimestampingMode::ClientRequire,
         s2_sdk::types::TimestampingMode::Arrival => TimestampingMode::Arrival,
+        s2_sdk::types::TimestampingMode::ServerPrefer => TimestampingMode::ServerPrefer,
     }
 }
 
@@ -117,9 +131,34 @@ fn stream_config_to_sdk(config: s2_resource_spec::StreamConfig) -> s2_sdk::types
     if let Some(delete_on_empty) = config.delete_on_empty.and_then(delete_on_empty_to_sdk) {
         sdk_config = sdk_config.with_delete_on_empty(delete_on_empty);
     }
+    if let Some(min_retained_records) = config.min_retained_records {
+        sdk_config = sdk_config.with_min_retained_records(min_retained_records);
+    }
+    if let Some(max_append_bytes_per_sec) = config.max_append_bytes_per_sec {
+        sdk_config = sdk_config.with_max_append_bytes_per_sec(max_append_bytes_per_sec);
+    }
+    if let Some(max_append_ops_per_sec) = config.max_append_ops_per_sec {
+        sdk_config = sdk_config.with_max_append_ops_per_sec(max_append_ops_per_sec);
+    }
+    if let Some(max_future_skew_secs) = config.max_future_skew_secs {
+        sdk_config = sdk_config.with_max_future_skew_secs(max_future_skew_secs);
+    }
+    if let Some(compression_hint) = config.compression_hint {
+        sdk_config = sdk_config.with_compression_hint(compression_hint_to_sdk(compression_hint));
+    }
     sdk_config
 }
 
+fn compression_hint_to_sdk(
+    compression_hint: s2_resource_spec::CompressionHint,
+) -> s2_sdk::types::CompressionHint {
+    match compression_hint {
+        s2_resource_spec::CompressionHint::None => s2_sdk::types::CompressionHint::None,
+        s2_resource_spec::CompressionHint::Zstd => s2_sdk::types::CompressionHint::Zstd,
+        s2_resource_spec::CompressionHint::Gzip => s2_sdk::types::CompressionHint::Gzip,
+    }
+}
+
 fn storage_class_to_sdk(
     storage_class: s2_resource_spec::StorageClass,
 ) -> s2_sdk::types::StorageClass {
@@ -162,6 +201,9 @@ fn timestamping_mode_to_sdk(
             s2_sdk::types::TimestampingMode::ClientRequire
         }
         s2_resource_spec::TimestampingMode::Arrival => s2_sdk::types::TimestampingMode::Arrival,
+        s2_resource_spec::TimestampingMode::ServerPrefer => {
+            s2_sdk::types::TimestampingMode::ServerPrefer
+        }
     }
 }
 
@@ -322,6 +364,7 @@ fn format_timestamping_mode(m: TimestampingMode) -> &'static str {
         TimestampingMode::ClientPrefer => "client-prefer",
         TimestampingMode::ClientRequire => "client-require",
         TimestampingMode::Arrival => "arrival",
+        TimestampingMode::ServerPrefer => "server-prefer",
     }
 }
 
@@ -332,6 +375,11 @@ fn default_stream_config_field(field: &'static str) -> &'static str {
         "timestamping.mode" => "default_stream_config.timestamping.mode",
         "timestamping.uncapped" => "default_stream_config.timestamping.uncapped",
         "delete_on_empty.min_age" => "default_stream_config.delete_on_empty.min_age",
+        "min_retained_records" => "default_stream_config.min_retained_records",
+        "max_append_bytes_per_sec" => "default_stream_config.max_append_bytes_per_sec",
+        "max_append_ops_per_sec" => "default_stream_config.max_append_ops_per_sec",
+        "max_future_skew_secs" => "default_stream_config.max_future_skew_secs",
+        "compression_hint" => "default_stream_config.compression_hint",
         _ => field,
     }
 }
@@ -427,6 +475,45 @@ fn diff_stream_configs(existing: &StreamConfig, desired: &StreamConfig) -> Vec<F
         });
     }
 
+    for (field, old, new) in [
+        (
+            "min_retained_records",
+            existing.min_retained_records,
+            desired.min_retained_records,
+        ),
+        (
+            "max_append_bytes_per_sec",
+            existing.max_append_bytes_per_sec,
+            desired.max_append_bytes_per_sec,
+        ),
+        (
+            "max_append_ops_per_sec",
+            existing.max_append_ops_per_sec,
+            desired.max_append_ops_per_sec,
+        ),
+        (
+            "max_future_skew_secs",
+            existing.max_future_skew_secs,
+            desired.max_future_skew_secs,
+        ),
+    ] {
+        if old != new {
+            diffs.push(FieldDiff {
+                field,
+                old: old.to_string(),
+                new: new.to_string(),
+            });
+        }
+    }
+
+    if existing.compression_hint != desired.compression_hint {
+        diffs.push(FieldDiff {
+            field: "compression_hint",
+            old: existing.compression_hint.to_string(),
+            new: desired.compression_hint.to_string(),
+        });
+    }
+
     diffs
 }
 
@@ -509,6 +596,27 @@ fn spec_stream_fields(spec: &s2_resource_spec::StreamConfig) -> Vec<FieldDiff> {
             new: humantime::format_duration(min_age.0).to_string(),
         });
     }
+    for (field, value) in [
+        ("min_retained_records", spec.min_retained_records),
+        ("max_append_bytes_per_sec", spec.max_append_bytes_per_sec),
+        ("max_append_ops_per_sec", spec.max_append_ops_per_sec),
+        ("max_future_skew_secs", spec.max_future_skew_secs),
+    ] {
+        if let Some(value) = value {
+            fields.push(FieldDiff {
+                field,
+                old: String::new(),
+                new: value.to_string(),
+            });
+        }
+    }
+    if let Some(ref compression_hint) = spec.compression_hint {
+        fields.push(FieldDiff {
+            field: "compression_hint",
+            old: String::new(),
+            new: CompressionHint::from(compression_hint.clone()).to_string(),
+        });
+    }
 
     fields
 }
@@ -659,3 +767,36 @@ pub async fn dry_run(s2: &s2_sdk::S2, spec: s2_resource_spec::Resources) -> miet
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_stream_configs_covers_limits_and_compression() {
+        let existing = StreamConfig::default();
+        let desired = StreamConfig {
+            min_retained_records: 10,
+            max_append_bytes_per_sec: 1024,
+            max_append_ops_per_sec: 100,
+            max_future_skew_secs: 60,
+            compression_hint: CompressionHint::Zstd,
+            ..existing.clone()
+        };
+        let diffs = diff_stream_configs(&existing, &desired);
+        let fields: Vec<_> = diffs.iter().map(|diff| diff.field).collect();
+        assert_eq!(
+            fields,
+            [
+                "min_retained_records",
+                "max_append_bytes_per_sec",
+                "max_append_ops_per_sec",
+                "max_future_skew_secs",
+                "compression_hint",
+            ]
+        );
+        assert_eq!(diffs[4].old, "none");
+        assert_eq!(diffs[4].new, "zstd");
+        assert!(diff_stream_configs(&desired, &desired).is_empty());
+    }
+}
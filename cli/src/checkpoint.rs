@@ -0,0 +1,71 @@
+//! Read checkpoints: the sequence number a reader should resume from, saved to a file.
+
+use std::path::{Path, PathBuf};
+
+use crate::error::CliError;
+
+/// Load the saved sequence number, or `None` if no checkpoint has been saved yet.
+pub async fn load(path: &Path) -> Result<Option<u64>, CliError> {
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(checkpoint_error(path, e)),
+    };
+    contents
+        .trim()
+        .parse::<u64>()
+        .map(Some)
+        .map_err(|e| checkpoint_error(path, e))
+}
+
+/// Save `seq_num` as the position to resume from, replacing the file atomically so that an
+/// interrupted write never leaves a truncated checkpoint behind.
+pub async fn save(path: &Path, seq_num: u64) -> Result<(), CliError> {
+    let tmp_path = tmp_path(path);
+    tokio::fs::write(&tmp_path, format!("{seq_num}\n"))
+        .await
+        .map_err(|e| checkpoint_error(&tmp_path, e))?;
+    tokio::fs::rename(&tmp_path, path)
+        .await
+        .map_err(|e| checkpoint_error(path, e))
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".tmp");
+    path.with_file_name(file_name)
+}
+
+fn checkpoint_error(path: &Path, err: impl std::fmt::Display) -> CliError {
+    CliError::Checkpoint(format!("{}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn missing_checkpoint_loads_as_none() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load(&dir.path().join("missing")).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn saved_checkpoint_loads_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint");
+        save(&path, 42).await.unwrap();
+        assert_eq!(load(&path).await.unwrap(), Some(42));
+        save(&path, 43).await.unwrap();
+        assert_eq!(load(&path).await.unwrap(), Some(43));
+        assert!(!tmp_path(&path).exists());
+    }
+
+    #[tokio::test]
+    async fn corrupt_checkpoint_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint");
+        std::fs::write(&path, "not a number").unwrap();
+        assert!(matches!(load(&path).await, Err(CliError::Checkpoint(_))));
+    }
+}
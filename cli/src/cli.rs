@@ -1,21 +1,26 @@
-use std::{num::NonZeroU64, path::PathBuf};
+use std::{
+    num::{NonZeroU32, NonZeroU64},
+    path::PathBuf,
+};
 
 use clap::{Args, Parser, Subcommand, builder::styling};
 use s2_sdk::types::{
     AccessTokenId, AccessTokenIdPrefix, AccessTokenIdStartAfter, BasinNamePrefix,
-    BasinNameStartAfter, EncryptionAlgorithm, EncryptionKey, FencingToken, StreamNamePrefix,
-    StreamNameStartAfter,
+    BasinNameStartAfter, EncryptionAlgorithm, EncryptionKey, FencingToken, S2DateTime,
+    StreamNamePrefix, StreamNameStartAfter,
 };
 
 use crate::{
     record_format::{
-        RecordFormat, RecordsIn, RecordsOut, parse_records_input_source,
-        parse_records_output_source,
+        InputSort, RecordFormat, RecordsIn, RecordsOut, Rotate, SeqNumHeader,
+        parse_records_input_source, parse_records_output_source,
     },
+    transform::Transform,
     types::{
-        AccessTokenMatcher, BasinConfig, BasinMatcher, Interval, LocationName, Operation,
-        PermittedOperationGroups, S2BasinAndMaybeStreamUri, S2BasinAndStreamUri, S2BasinUri,
-        StorageClass, StreamConfig, StreamMatcher,
+        AccessTokenMatcher, AccessTokenTemplate, AckFormat, AppendRetryPolicy, BasinConfig,
+        BasinMatcher, Interval, ListStreamsOrder, ListStreamsState, LocationName, MetricsOutput,
+        Operation, PermittedOperationGroups, S2BasinAndMaybeStreamUri, S2BasinAndStreamUri,
+        S2BasinUri, StorageClass, StreamConfig, StreamMatcher, VerifyMode,
     },
 };
 
@@ -32,8 +37,26 @@ const GENERAL_USAGE: &str = color_print::cstr!(
     "#
 );
 
+const EXIT_CODES: &str = color_print::cstr!(
+    r#"<green><bold>Exit codes:</bold></green>
+  <bold>0</bold>  Success
+  <bold>1</bold>  Other error
+  <bold>2</bold>  Invalid arguments
+  <bold>3</bold>  Network error, or the service is unavailable
+  <bold>4</bold>  Missing, malformed, or unauthorized access token
+  <bold>5</bold>  Basin or stream not found
+  <bold>6</bold>  Request rejected as invalid
+  <bold>7</bold>  Command did not complete within `--timeout`"#
+);
+
 #[derive(Parser, Debug)]
-#[command(name = "s2", version, override_usage = GENERAL_USAGE, styles = STYLES)]
+#[command(
+    name = "s2",
+    version,
+    override_usage = GENERAL_USAGE,
+    after_help = EXIT_CODES,
+    styles = STYLES
+)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Command>,
@@ -41,6 +64,59 @@ pub struct Cli {
     /// Launch interactive TUI mode.
     #[arg(short = 'i', long = "interactive")]
     pub interactive: bool,
+
+    /// Read and write CLI configuration at this path instead of the default location.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config_file: Option<PathBuf>,
+
+    /// Skip unknown keys in the config file instead of failing.
+    #[arg(long, global = true)]
+    pub ignore_unknown_config: bool,
+
+    /// Log each retry attempt, with its delay and error, to stderr.
+    #[arg(long, global = true)]
+    pub verbose_retries: bool,
+
+    /// Maximum number of idle HTTP connections kept open per host.
+    /// Does not limit how many connections are opened under load. Unlimited by default.
+    #[arg(long, global = true, value_name = "N")]
+    pub http_max_idle_connections: Option<usize>,
+
+    /// How long an HTTP connection may sit idle before being closed.
+    /// Defaults to 90s.
+    #[arg(long, global = true, value_name = "DURATION")]
+    pub http_idle_timeout: Option<humantime::Duration>,
+
+    /// Print the API request that read, append, list-basins, or list-streams resolves to as JSON
+    /// to stderr, then send it.
+    #[arg(long, global = true)]
+    pub explain: bool,
+
+    /// Like `--explain`, but exit without sending the request.
+    #[arg(long, global = true, conflicts_with = "explain")]
+    pub explain_only: bool,
+
+    /// Cancel the command if it has not completed within this duration.
+    /// Output written before the cancellation is kept. No limit by default.
+    #[arg(long, global = true, value_name = "DURATION")]
+    pub timeout: Option<humantime::Duration>,
+
+    /// Strip trailing `/` separators from basin and stream names in S2 URIs, and lowercase basin
+    /// names, instead of rejecting them.
+    // Applied through `Cli::normalize_names_requested` before parsing, as URIs are validated while
+    // they are parsed.
+    #[arg(long = "normalize-names", global = true)]
+    pub _normalize_names: bool,
+}
+
+impl Cli {
+    /// Whether `--normalize-names` was passed, which must be known before the S2 URIs among the
+    /// arguments are parsed.
+    pub fn normalize_names_requested() -> bool {
+        std::env::args_os()
+            .take_while(|arg| arg != "--")
+            .any(|arg| arg == "--normalize-names")
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -73,9 +149,24 @@ pub enum Command {
         basin: S2BasinUri,
     },
 
+    /// Show the endpoints the CLI connects to.
+    ///
+    /// Prints the effective account and basin endpoints, and whether they come from the
+    /// environment, the config file, or the defaults.
+    Endpoints {
+        /// Basin name to resolve the basin endpoint for.
+        basin: Option<S2BasinUri>,
+    },
+
     /// Reconfigure a basin.
     ReconfigureBasin(ReconfigureBasinArgs),
 
+    /// Copy a basin's default stream config to another basin.
+    ///
+    /// Fields not set on the source basin are cleared on the destination basin, so that both end
+    /// up with the same default stream config. Other basin settings are left unchanged.
+    CopyBasinConfig(CopyBasinConfigArgs),
+
     /// List access tokens.
     ListAccessTokens(ListAccessTokensArgs),
 
@@ -120,6 +211,19 @@ pub enum Command {
         /// S2 URI of the format: s2://{basin}/{stream}
         #[arg(value_name = "S2_URI")]
         uri: S2BasinAndStreamUri,
+        /// Remove the stream and all of its records before returning,
+        /// bypassing any deletion grace period.
+        #[arg(long)]
+        purge: bool,
+    },
+
+    /// Undelete a stream.
+    ///
+    /// Only streams deleted within the server's deletion grace period can be undeleted.
+    UndeleteStream {
+        /// S2 URI of the format: s2://{basin}/{stream}
+        #[arg(value_name = "S2_URI")]
+        uri: S2BasinAndStreamUri,
     },
 
     /// Get stream config.
@@ -142,6 +246,12 @@ pub enum Command {
         uri: S2BasinAndStreamUri,
     },
 
+    /// Resolve a timestamp or tail offset to a sequence number.
+    ///
+    /// Prints the sequence number that a read starting from that position would begin at,
+    /// without reading any further records. Positions past the last record resolve to the tail.
+    Seek(SeekArgs),
+
     /// Set a trim point for a stream.
     ///
     /// Trimming is eventually consistent, and trimmed records may be visible
@@ -170,9 +280,26 @@ pub enum Command {
     /// Tail a stream, showing the last N records.
     Tail(TailArgs),
 
+    /// Compute a BLAKE3 digest over a range of records in a stream.
+    ///
+    /// The digest covers each record's headers and body, so a faithful copy
+    /// of a stream hashes the same as its source.
+    Hash(HashArgs),
+
+    /// Summarize the time gaps between consecutive records in a stream.
+    ///
+    /// Reads only the positions of records in the range, and prints percentiles of the gaps
+    /// between their timestamps, e.g. to tell a bursty stream from a steady one.
+    TimestampGaps(TimestampGapsArgs),
+
     /// Benchmark a stream to measure throughput and latency.
     Bench(BenchArgs),
 
+    /// Measure round-trip latency to the S2 account endpoint.
+    ///
+    /// Each probe is a minimal authenticated request, made without retries.
+    Ping(PingArgs),
+
     /// Apply a declarative spec file, ensuring basins and streams.
     ///
     /// Reads a JSON file and ensures the declared basins and streams exist with the
@@ -199,6 +326,15 @@ pub enum Command {
     /// Starts a lightweight S2-compatible server that can be backed by
     /// S3, local filesystem, or in-memory storage.
     Lite(crate::lite::LiteArgs),
+
+    /// Generate a shell completion script.
+    ///
+    /// Prints the script to stdout, e.g. `s2 completions zsh > ~/.zfunc/_s2`.
+    Completions {
+        /// Shell to generate completions for.
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -248,6 +384,10 @@ pub struct LsArgs {
     /// Returns only a single page of items instead of auto-paginating.
     #[arg(long, default_value_t = false)]
     pub no_auto_paginate: bool,
+
+    /// Render the listing as a table with columns.
+    #[arg(long, default_value_t = false)]
+    pub table: bool,
 }
 
 #[derive(Args, Debug)]
@@ -304,6 +444,17 @@ pub struct ReconfigureBasinArgs {
     pub default_stream_config: StreamConfig,
 }
 
+#[derive(Args, Debug)]
+pub struct CopyBasinConfigArgs {
+    /// Name of the basin to copy the default stream config from.
+    #[arg(long)]
+    pub from: S2BasinUri,
+
+    /// Name of the basin to apply the default stream config to.
+    #[arg(long)]
+    pub to: S2BasinUri,
+}
+
 #[derive(Args, Debug)]
 pub struct ListAccessTokensArgs {
     /// List access tokens that begin with this prefix.
@@ -322,6 +473,17 @@ pub struct ListAccessTokensArgs {
     /// Returns only a single page of access tokens instead of auto-paginating.
     #[arg(long, default_value_t = false)]
     pub no_auto_paginate: bool,
+
+    /// Only list access tokens whose scope covers this basin, or stream if the S2 URI has one.
+    /// A token must also be permitted an operation on the resource, see --op.
+    #[arg(long, value_name = "S2_URI")]
+    pub scope_contains: Option<S2BasinAndMaybeStreamUri>,
+
+    /// Operation that listed tokens must be permitted on the --scope-contains resource, through
+    /// their operations or operation group permissions. Without it, any operation on the stream,
+    /// or on the basin and its streams, qualifies.
+    #[arg(long, requires = "scope_contains")]
+    pub op: Option<Operation>,
 }
 
 #[derive(Args, Debug)]
@@ -344,6 +506,13 @@ pub struct IssueAccessTokenArgs {
     #[arg(long, default_value_t = false)]
     pub auto_prefix_streams: bool,
 
+    /// Start from a built-in scope, written as `{name}:{argument}`:
+    /// `readonly-prefix:{prefix}` and `readwrite-prefix:{prefix}` grant stream access to a stream
+    /// name prefix in any basin, and `basin-admin:{basin}` grants full access to one basin.
+    /// `--basins`, `--streams` and `--op-group-perms` replace the template's value when set.
+    #[arg(long)]
+    pub template: Option<AccessTokenTemplate>,
+
     /// Basin names allowed.
     /// Matches exact value if it starts with `=`, otherwise treats it as a prefix.
     #[arg(long)]
@@ -369,6 +538,11 @@ pub struct IssueAccessTokenArgs {
     /// A union of allowed operations and groups is used as an effective set of allowed operations.
     #[arg(long, value_delimiter = ',')]
     pub ops: Vec<Operation>,
+
+    /// Print the request that would issue the token as JSON, including its resolved scope and
+    /// expiration time, without issuing it.
+    #[arg(long, default_value_t = false)]
+    pub validate_only: bool,
 }
 
 #[derive(Args, Debug)]
@@ -384,9 +558,15 @@ pub struct ListStreamsArgs {
     pub prefix: Option<StreamNamePrefix>,
 
     /// Filter to stream names that lexicographically start after this name.
+    /// With `--order created`, `--start-after-created-at` must also be given.
     #[arg(short = 's', long)]
     pub start_after: Option<StreamNameStartAfter>,
 
+    /// Creation time of the `--start-after` stream in RFC 3339 format, as it was listed.
+    /// With `--order created`, listing resumes after that stream's position.
+    #[arg(long, requires = "start_after", value_name = "RFC3339")]
+    pub start_after_created_at: Option<S2DateTime>,
+
     /// Limit the number of streams to return. Acts as page size (max 1000) when using
     /// --no-auto-paginate.
     #[arg(short = 'n', long)]
@@ -395,6 +575,14 @@ pub struct ListStreamsArgs {
     /// Returns only a single page of streams instead of auto-paginating.
     #[arg(long, default_value_t = false)]
     pub no_auto_paginate: bool,
+
+    /// Order in which to list streams.
+    #[arg(long, value_enum)]
+    pub order: Option<ListStreamsOrder>,
+
+    /// Filter to streams in this state.
+    #[arg(long, value_enum)]
+    pub state: Option<ListStreamsState>,
 }
 
 #[derive(Args, Debug)]
@@ -413,6 +601,11 @@ pub struct ReconfigureStreamArgs {
     #[arg(value_name = "S2_URI")]
     pub uri: S2BasinAndStreamUri,
 
+    /// Clear every config override so that the stream inherits the basin's default stream
+    /// config, except for any fields also set by this command.
+    #[arg(long)]
+    pub reset: bool,
+
     #[clap(flatten)]
     pub config: StreamConfig,
 }
@@ -479,10 +672,46 @@ pub struct AppendArgs {
     #[arg(short = 'i', long, value_parser = parse_records_input_source, default_value = "-")]
     pub input: RecordsIn,
 
+    /// Append every file in this directory, one after another.
+    /// `--match-seq-num` applies to the first file only.
+    #[arg(long, value_name = "DIR", conflicts_with = "input")]
+    pub input_dir: Option<PathBuf>,
+
+    /// Order in which files from `--input-dir` are appended.
+    #[arg(long, value_enum, default_value_t, requires = "input_dir")]
+    pub sort: InputSort,
+
+    /// Keep going with the next file from `--input-dir` when one fails.
+    #[arg(long, requires = "input_dir")]
+    pub continue_on_error: bool,
+
     /// How long to wait for more records before flushing a batch.
     #[arg(long, default_value = "5ms")]
     pub linger: humantime::Duration,
 
+    /// Maximum number of appended batches awaiting acknowledgement.
+    /// Batches are still flushed on `--linger`, but once this many are outstanding, further
+    /// records wait to be batched until an earlier batch is acknowledged, so batches grow larger
+    /// instead of more numerous. No limit if not specified.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(NonZeroU32))]
+    pub max_inflight_appends: Option<NonZeroU32>,
+
+    /// After appending, read the appended records back and check them against what was sent:
+    /// `count` compares the number of records, `hash` also compares their headers and bodies.
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "count")]
+    pub verify: Option<VerifyMode>,
+
+    /// How to retry appends that fail transiently.
+    /// `none` disables retries for the whole command, including reads for `--verify`.
+    #[arg(long, value_enum, default_value_t)]
+    pub append_retry_policy: AppendRetryPolicy,
+
+    /// How to report acknowledgements.
+    /// With `json`, each acknowledged batch is printed to stdout as a JSON object, and so is the
+    /// expected fencing token or sequence number when an append condition fails.
+    #[arg(long, value_enum, default_value_t)]
+    pub ack_format: AckFormat,
+
     #[command(flatten)]
     pub encryption_key: EncryptionKeyArgs,
 }
@@ -534,6 +763,11 @@ pub struct ReadArgs {
     #[arg(long, group = "start")]
     pub tail_offset: Option<u64>,
 
+    /// Resume from the sequence number saved in this file, or from the tail of the stream if
+    /// the file does not exist yet. The file is updated after each batch is written.
+    #[arg(long, value_name = "FILE", group = "start")]
+    pub resume_from_checkpoint_or_tail: Option<PathBuf>,
+
     /// Limit the number of records returned.
     #[arg(short = 'n', long)]
     pub count: Option<u64>,
@@ -556,11 +790,63 @@ pub struct ReadArgs {
     #[arg(long, value_enum, default_value_t)]
     pub format: RecordFormat,
 
+    /// Include each record's seq_num and timestamp as tab-separated columns in text output,
+    /// before (`prefix`) or after (`suffix`) the body.
+    /// JSON formats always include them.
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "prefix")]
+    pub seq_num_header: Option<SeqNumHeader>,
+
+    /// Output flat JSON objects, with headers as top-level `header.{name}` fields
+    /// and JSON object or array bodies embedded as-is.
+    #[arg(long, conflicts_with_all = ["format", "seq_num_header"])]
+    pub jsonl_flat: bool,
+
+    /// Apply a jq-style filter to each record's JSON representation before writing it,
+    /// e.g. `select(.body.level == "error") | {seq_num, msg: .body.msg}`.
+    /// JSON object or array bodies are parsed, except with the `json-base64` format.
+    /// Records for which the filter produces nothing or `null` are skipped.
+    #[arg(long, value_name = "EXPR")]
+    pub transform: Option<Transform>,
+
+    /// Only write these headers of each record (comma-separated names).
+    #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+    pub only_headers: Option<Vec<String>>,
+
+    /// Strip these headers from each record (comma-separated names).
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "NAMES",
+        conflicts_with = "only_headers"
+    )]
+    pub exclude_headers: Option<Vec<String>>,
+
+    /// Only output command records (fence and trim).
+    /// With the text format, each command is written as a human-readable description.
+    #[arg(long, default_value_t = false)]
+    pub commands_only: bool,
+
+    /// Only count the records read and sum their metered bytes, printing the totals
+    /// instead of the records.
+    #[arg(long, default_value_t = false)]
+    pub count_only: bool,
+
     /// Output records to a file or stdout.
     /// Use "-" to write to stdout.
     #[arg(short = 'o', long, value_parser = parse_records_output_source, default_value = "-")]
     pub output: RecordsOut,
 
+    /// Write records into sequentially numbered files in this directory,
+    /// continuing after any numbered files already there.
+    #[arg(long, value_name = "DIR", conflicts_with = "output")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Roll over to the next file in `--output-dir` once the current one reaches
+    /// a size (e.g. `100MiB`) or has been open for a duration (e.g. `1h`).
+    /// Files roll over between batches, so a batch is never split across files.
+    #[arg(long, value_name = "SIZE|DURATION", requires = "output_dir")]
+    pub rotate: Option<Rotate>,
+
     #[command(flatten)]
     pub encryption_key: EncryptionKeyArgs,
 }
@@ -579,10 +865,45 @@ pub struct TailArgs {
     #[arg(short = 'f', long, default_value_t = false)]
     pub follow: bool,
 
+    /// Print records/s and MiB/s to stderr every interval, e.g. `5s`.
+    #[arg(long, value_name = "INTERVAL")]
+    pub stats: Option<humantime::Duration>,
+
     /// Output format.
     #[arg(long, value_enum, default_value_t)]
     pub format: RecordFormat,
 
+    /// Include each record's seq_num and timestamp as tab-separated columns in text output,
+    /// before (`prefix`) or after (`suffix`) the body.
+    /// JSON formats always include them.
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "prefix")]
+    pub seq_num_header: Option<SeqNumHeader>,
+
+    /// Output flat JSON objects, with headers as top-level `header.{name}` fields
+    /// and JSON object or array bodies embedded as-is.
+    #[arg(long, conflicts_with_all = ["format", "seq_num_header"])]
+    pub jsonl_flat: bool,
+
+    /// Apply a jq-style filter to each record's JSON representation before writing it,
+    /// e.g. `select(.body.level == "error") | {seq_num, msg: .body.msg}`.
+    /// JSON object or array bodies are parsed, except with the `json-base64` format.
+    /// Records for which the filter produces nothing or `null` are skipped.
+    #[arg(long, value_name = "EXPR")]
+    pub transform: Option<Transform>,
+
+    /// Only write these headers of each record (comma-separated names).
+    #[arg(long, value_delimiter = ',', value_name = "NAMES")]
+    pub only_headers: Option<Vec<String>>,
+
+    /// Strip these headers from each record (comma-separated names).
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "NAMES",
+        conflicts_with = "only_headers"
+    )]
+    pub exclude_headers: Option<Vec<String>>,
+
     /// Output records to a file or stdout.
     /// Use "-" to write to stdout.
     #[arg(short = 'o', long, value_parser = parse_records_output_source, default_value = "-")]
@@ -592,6 +913,65 @@ pub struct TailArgs {
     pub encryption_key: EncryptionKeyArgs,
 }
 
+#[derive(Args, Debug)]
+pub struct HashArgs {
+    /// S2 URI of the format: s2://{basin}/{stream}
+    #[arg(value_name = "S2_URI")]
+    pub uri: S2BasinAndStreamUri,
+
+    /// First sequence number to hash (inclusive).
+    #[arg(long, default_value_t = 0)]
+    pub from: u64,
+
+    /// Sequence number to stop hashing at (exclusive).
+    /// Defaults to the tail of the stream when the command starts.
+    #[arg(long)]
+    pub to: Option<u64>,
+
+    #[command(flatten)]
+    pub encryption_key: EncryptionKeyArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct TimestampGapsArgs {
+    /// S2 URI of the format: s2://{basin}/{stream}
+    #[arg(value_name = "S2_URI")]
+    pub uri: S2BasinAndStreamUri,
+
+    /// First sequence number in the range (inclusive).
+    #[arg(long, default_value_t = 0)]
+    pub from: u64,
+
+    /// Sequence number to stop at (exclusive).
+    /// Defaults to the tail of the stream when the command starts.
+    #[arg(long)]
+    pub to: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+#[command(group(clap::ArgGroup::new("position").required(true)))]
+pub struct SeekArgs {
+    /// S2 URI of the format: s2://{basin}/{stream}
+    #[arg(value_name = "S2_URI")]
+    pub uri: S2BasinAndStreamUri,
+
+    /// Timestamp in milliseconds since Unix epoch.
+    #[arg(long, group = "position")]
+    pub timestamp: Option<u64>,
+
+    /// Timestamp as a human-friendly delta from current time e.g. "1h",
+    /// which will be converted to milliseconds since Unix epoch.
+    #[arg(long, group = "position")]
+    pub ago: Option<humantime::Duration>,
+
+    /// N records before the tail of the stream.
+    #[arg(long, group = "position")]
+    pub tail_offset: Option<u64>,
+
+    #[command(flatten)]
+    pub encryption_key: EncryptionKeyArgs,
+}
+
 #[derive(Args, Debug)]
 pub struct ApplyArgs {
     /// Path to a JSON spec file defining basins and streams to ensure.
@@ -615,6 +995,22 @@ pub struct ApplyArgs {
     pub schema: bool,
 }
 
+#[derive(Args, Debug)]
+pub struct PingArgs {
+    /// Number of probes to send.
+    #[arg(
+        short = 'c',
+        long,
+        value_parser = clap::value_parser!(NonZeroU64),
+        default_value_t = NonZeroU64::new(4).expect("non-zero")
+    )]
+    pub count: NonZeroU64,
+
+    /// Delay between probes.
+    #[arg(short = 'i', long, default_value = "1s")]
+    pub interval: humantime::Duration,
+}
+
 #[derive(Args, Debug)]
 pub struct BenchArgs {
     /// Name of the basin to use for the test.
@@ -720,9 +1116,27 @@ pub enum StreamMetricCommand {
     Storage(TimeRangeArgs),
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct MetricsOutputArgs {
+    /// Output format.
+    #[arg(long, value_enum, default_value_t, global = true)]
+    pub output: MetricsOutput,
+
+    /// Write JSON or CSV output to this file instead of stdout.
+    #[arg(long, value_name = "FILE", global = true)]
+    pub metric_file: Option<PathBuf>,
+
+    /// Show accumulation values as per-second rates over their interval in table output.
+    #[arg(long, global = true)]
+    pub as_rate: bool,
+}
+
 #[derive(Args, Debug)]
 #[command(subcommand_value_name = "METRIC", subcommand_help_heading = "Metrics")]
 pub struct GetAccountMetricsArgs {
+    #[command(flatten)]
+    pub output: MetricsOutputArgs,
+
     #[command(subcommand)]
     pub metric: AccountMetricCommand,
 }
@@ -733,6 +1147,9 @@ pub struct GetBasinMetricsArgs {
     /// Basin name.
     pub basin: S2BasinUri,
 
+    #[command(flatten)]
+    pub output: MetricsOutputArgs,
+
     #[command(subcommand)]
     pub metric: BasinMetricCommand,
 }
@@ -744,6 +1161,9 @@ pub struct GetStreamMetricsArgs {
     #[arg(value_name = "S2_URI")]
     pub uri: S2BasinAndStreamUri,
 
+    #[command(flatten)]
+    pub output: MetricsOutputArgs,
+
     #[command(subcommand)]
     pub metric: StreamMetricCommand,
 }
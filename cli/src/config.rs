@@ -9,6 +9,7 @@ use s2_sdk::{
     types::{AccountEndpoint, BasinEndpoint, S2Config, S2Endpoints},
 };
 use serde::{Deserialize, Serialize};
+use strum::VariantNames;
 
 use crate::error::{CliConfigError, CliError, TokenSource};
 
@@ -20,6 +21,7 @@ use crate::error::{CliConfigError, CliError, TokenSource};
 pub enum Compression {
     Gzip,
     Zstd,
+    Auto,
 }
 
 impl From<Compression> for sdk::types::Compression {
@@ -27,6 +29,7 @@ impl From<Compression> for sdk::types::Compression {
         match value {
             Compression::Gzip => sdk::types::Compression::Gzip,
             Compression::Zstd => sdk::types::Compression::Zstd,
+            Compression::Auto => sdk::types::Compression::Auto,
         }
     }
 }
@@ -58,26 +61,78 @@ fn config_path() -> Result<PathBuf, CliConfigError> {
     Ok(path)
 }
 
-pub fn load_config_file() -> Result<CliConfig, CliConfigError> {
-    let path = config_path()?;
-    if !path.exists() {
+/// CLI config file location, and whether keys it does not recognize are tolerated.
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    pub path: PathBuf,
+    pub ignore_unknown: bool,
+}
+
+impl ConfigFile {
+    /// Use `path_override` if given, or else the default per-user location.
+    pub fn resolve(
+        path_override: Option<PathBuf>,
+        ignore_unknown: bool,
+    ) -> Result<Self, CliConfigError> {
+        let path = match path_override {
+            Some(path) => path,
+            None => config_path()?,
+        };
+        Ok(Self {
+            path,
+            ignore_unknown,
+        })
+    }
+
+    fn source(&self) -> config::File<config::FileSourceFile, FileFormat> {
+        config::File::new(
+            self.path.to_str().expect("config path is valid utf8"),
+            FileFormat::Toml,
+        )
+    }
+
+    /// Check every key in the file against [`ConfigKey`], and every value against what its key
+    /// accepts.
+    fn validate(&self) -> Result<(), CliConfigError> {
+        let entries = Config::builder()
+            .add_source(self.source())
+            .build()?
+            .collect()?;
+        for (name, value) in entries {
+            let Ok(key) = name.parse::<ConfigKey>() else {
+                if self.ignore_unknown {
+                    continue;
+                }
+                return Err(CliConfigError::UnknownKey(
+                    name,
+                    self.path.display().to_string(),
+                    ConfigKey::VARIANTS.join(", "),
+                ));
+            };
+            let raw = value.to_string();
+            let value = value
+                .into_string()
+                .map_err(|_| CliConfigError::InvalidValue(key.to_string(), raw, key.expected()))?;
+            CliConfig::default().set(key, value)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn load_config_file(file: &ConfigFile) -> Result<CliConfig, CliConfigError> {
+    if !file.path.exists() {
         return Ok(CliConfig::default());
     }
-    let builder = Config::builder().add_source(config::File::new(
-        path.to_str().expect("config path is valid utf8"),
-        FileFormat::Toml,
-    ));
+    file.validate()?;
+    let builder = Config::builder().add_source(file.source());
     Ok(builder.build()?.try_deserialize::<CliConfig>()?)
 }
 
-pub fn load_cli_config() -> Result<CliConfig, CliConfigError> {
-    let path = config_path()?;
+pub fn load_cli_config(file: &ConfigFile) -> Result<CliConfig, CliConfigError> {
     let mut builder = Config::builder();
-    if path.exists() {
-        builder = builder.add_source(config::File::new(
-            path.to_str().expect("config path is valid utf8"),
-            FileFormat::Toml,
-        ));
+    if file.path.exists() {
+        file.validate()?;
+        builder = builder.add_source(file.source());
     }
     builder = builder.add_source(config::Environment::with_prefix("S2"));
     Ok(builder.build()?.try_deserialize::<CliConfig>()?)
@@ -96,6 +151,18 @@ pub enum ConfigKey {
     SslNoVerify,
 }
 
+impl ConfigKey {
+    /// Description of the values accepted for this key.
+    pub fn expected(&self) -> &'static str {
+        match self {
+            ConfigKey::AccessToken => "an access token",
+            ConfigKey::AccountEndpoint | ConfigKey::BasinEndpoint => "an endpoint",
+            ConfigKey::Compression => "one of: gzip, zstd, auto",
+            ConfigKey::SslNoVerify => "one of: true, false",
+        }
+    }
+}
+
 impl CliConfig {
     pub fn get(&self, key: ConfigKey) -> Option<String> {
         match key {
@@ -113,18 +180,14 @@ impl CliConfig {
             ConfigKey::AccountEndpoint => self.account_endpoint = Some(value),
             ConfigKey::BasinEndpoint => self.basin_endpoint = Some(value),
             ConfigKey::Compression => {
-                self.compression = Some(
-                    value
-                        .parse()
-                        .map_err(|_| CliConfigError::InvalidValue(key.to_string(), value))?,
-                );
+                self.compression = Some(value.parse().map_err(|_| {
+                    CliConfigError::InvalidValue(key.to_string(), value, key.expected())
+                })?);
             }
             ConfigKey::SslNoVerify => {
-                self.ssl_no_verify = Some(
-                    value
-                        .parse()
-                        .map_err(|_| CliConfigError::InvalidValue(key.to_string(), value))?,
-                );
+                self.ssl_no_verify = Some(value.parse().map_err(|_| {
+                    CliConfigError::InvalidValue(key.to_string(), value, key.expected())
+                })?);
             }
         }
         Ok(())
@@ -141,18 +204,17 @@ impl CliConfig {
     }
 }
 
-pub fn save_cli_config(config: &CliConfig) -> Result<PathBuf, CliConfigError> {
-    let path = config_path()?;
-
+pub fn save_cli_config(file: &ConfigFile, config: &CliConfig) -> Result<PathBuf, CliConfigError> {
+    let path = file.path.as_path();
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(CliConfigError::Write)?;
         secure_config_dir(parent).map_err(CliConfigError::Write)?;
     }
 
     let toml = toml::to_string(config).map_err(CliConfigError::Serialize)?;
-    write_config_file(&path, &toml).map_err(CliConfigError::Write)?;
+    write_config_file(path, &toml).map_err(CliConfigError::Write)?;
 
-    Ok(path)
+    Ok(path.to_path_buf())
 }
 
 #[cfg(unix)]
@@ -189,23 +251,59 @@ fn write_config_file(path: &Path, toml: &str) -> std::io::Result<()> {
     std::fs::write(path, toml)
 }
 
-pub fn set_config_value(key: ConfigKey, value: String) -> Result<PathBuf, CliConfigError> {
-    let mut config = load_config_file()?;
+pub fn set_config_value(
+    file: &ConfigFile,
+    key: ConfigKey,
+    value: String,
+) -> Result<PathBuf, CliConfigError> {
+    let mut config = load_config_file(file)?;
     config.set(key, value)?;
-    save_cli_config(&config)
+    save_cli_config(file, &config)
 }
 
-pub fn unset_config_value(key: ConfigKey) -> Result<PathBuf, CliConfigError> {
-    let mut config = load_config_file()?;
+pub fn unset_config_value(file: &ConfigFile, key: ConfigKey) -> Result<PathBuf, CliConfigError> {
+    let mut config = load_config_file(file)?;
     config.unset(key);
-    save_cli_config(&config)
+    save_cli_config(file, &config)
+}
+
+/// Replace `${VAR}` references in a config value with the value of the environment variable.
+fn interpolate_env(key: ConfigKey, value: &str) -> Result<String, CliConfigError> {
+    interpolate(key, value, |var| std::env::var(var).ok())
+}
+
+fn interpolate(
+    key: ConfigKey,
+    value: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<String, CliConfigError> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start + 2..].find('}') else {
+            return Err(CliConfigError::InvalidValue(
+                key.to_string(),
+                value.to_owned(),
+                "`${VAR}` references closed with `}`",
+            ));
+        };
+        let var = &rest[start + 2..start + 2 + len];
+        let resolved = lookup(var)
+            .ok_or_else(|| CliConfigError::UndefinedEnvVar(key.to_string(), var.to_owned()))?;
+        out.push_str(&resolved);
+        rest = &rest[start + 2 + len + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
 }
 
 pub fn sdk_config(config: &CliConfig, user_agent: &str) -> Result<S2Config, CliError> {
     let access_token = config
         .access_token
-        .as_ref()
+        .as_deref()
         .ok_or(CliConfigError::MissingAccessToken)?;
+    let access_token = interpolate_env(ConfigKey::AccessToken, access_token)?;
 
     let compression: sdk::types::Compression = config
         .compression
@@ -218,46 +316,63 @@ pub fn sdk_config(config: &CliConfig, user_agent: &str) -> Result<S2Config, CliE
         .with_request_timeout(Duration::from_secs(30))
         .with_compression(compression);
 
+    if let Some(endpoints) = custom_endpoints(config)? {
+        sdk_config = sdk_config.with_endpoints(endpoints);
+    }
+
+    if config.ssl_no_verify == Some(true) {
+        tracing::warn!("SSL certificate verification is disabled.");
+        sdk_config = sdk_config.with_insecure_skip_cert_verification(true);
+    }
+
+    Ok(sdk_config)
+}
+
+/// Custom endpoints, if both the account and basin endpoint are configured.
+pub fn custom_endpoints(config: &CliConfig) -> Result<Option<S2Endpoints>, CliError> {
     match (&config.account_endpoint, &config.basin_endpoint) {
         (Some(account_endpoint_str), Some(basin_endpoint_str)) => {
-            let account_endpoint = AccountEndpoint::new(account_endpoint_str)
+            let account_endpoint_str =
+                interpolate_env(ConfigKey::AccountEndpoint, account_endpoint_str)?;
+            let basin_endpoint_str = interpolate_env(ConfigKey::BasinEndpoint, basin_endpoint_str)?;
+            let account_endpoint = AccountEndpoint::new(&account_endpoint_str)
                 .map_err(|e| CliError::EndpointsFromEnv(e.to_string()))?;
-            let basin_endpoint = BasinEndpoint::new(basin_endpoint_str)
+            let basin_endpoint = BasinEndpoint::new(&basin_endpoint_str)
                 .map_err(|e| CliError::EndpointsFromEnv(e.to_string()))?;
             let endpoints = S2Endpoints::new(account_endpoint, basin_endpoint)
                 .map_err(|e| CliError::EndpointsFromEnv(e.to_string()))?;
-            sdk_config = sdk_config.with_endpoints(endpoints);
+            Ok(Some(endpoints))
         }
         (Some(_), None) => {
             eprintln!(
                 "Warning: account endpoint is set but basin endpoint is not. \
                  Both must be set to use custom endpoints. Using default endpoints"
             );
+            Ok(None)
         }
         (None, Some(_)) => {
             eprintln!(
                 "Warning: basin endpoint is set but account endpoint is not. \
                  Both must be set to use custom endpoints. Using default endpoints"
             );
+            Ok(None)
         }
-        (None, None) => {}
-    }
-
-    if config.ssl_no_verify == Some(true) {
-        tracing::warn!("SSL certificate verification is disabled.");
-        sdk_config = sdk_config.with_insecure_skip_cert_verification(true);
+        (None, None) => Ok(None),
     }
-
-    Ok(sdk_config)
 }
 
-pub fn access_token_source(config: &CliConfig) -> Option<TokenSource> {
-    let has_env_token = std::env::vars_os().any(|(key, _)| {
+/// Whether an environment variable is set, matching its name case-insensitively as config
+/// loading does.
+pub fn env_var_is_set(name: &str) -> bool {
+    std::env::vars_os().any(|(key, _)| {
         key.to_str()
-            .map(|k| k.eq_ignore_ascii_case("S2_ACCESS_TOKEN"))
+            .map(|k| k.eq_ignore_ascii_case(name))
             .unwrap_or(false)
-    });
-    if has_env_token {
+    })
+}
+
+pub fn access_token_source(config: &CliConfig) -> Option<TokenSource> {
+    if env_var_is_set("S2_ACCESS_TOKEN") {
         return Some(TokenSource::Environment);
     }
 
@@ -267,3 +382,81 @@ pub fn access_token_source(config: &CliConfig) -> Option<TokenSource> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(var: &str) -> Option<String> {
+        (var == "S2_TOKEN").then(|| "secret".to_owned())
+    }
+
+    #[test]
+    fn interpolates_env_references() {
+        assert_eq!(
+            interpolate(ConfigKey::AccessToken, "${S2_TOKEN}", lookup).unwrap(),
+            "secret"
+        );
+        assert_eq!(
+            interpolate(
+                ConfigKey::AccountEndpoint,
+                "https://${S2_TOKEN}.dev/${S2_TOKEN}",
+                lookup
+            )
+            .unwrap(),
+            "https://secret.dev/secret"
+        );
+        assert_eq!(
+            interpolate(ConfigKey::AccessToken, "plain", lookup).unwrap(),
+            "plain"
+        );
+    }
+
+    fn config_file(contents: &str, ignore_unknown: bool) -> (tempfile::TempDir, ConfigFile) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, contents).unwrap();
+        (
+            dir,
+            ConfigFile {
+                path,
+                ignore_unknown,
+            },
+        )
+    }
+
+    #[test]
+    fn load_rejects_unknown_keys_unless_ignored() {
+        let contents = "compression = \"zstd\"\nfuture_key = 1\n";
+        let (_dir, file) = config_file(contents, false);
+        assert!(matches!(
+            load_config_file(&file),
+            Err(CliConfigError::UnknownKey(key, ..)) if key == "future_key"
+        ));
+
+        let (_dir, file) = config_file(contents, true);
+        let config = load_config_file(&file).unwrap();
+        assert_eq!(config.compression, Some(Compression::Zstd));
+    }
+
+    #[test]
+    fn load_rejects_invalid_values() {
+        let (_dir, file) = config_file("compression = \"lz4\"\n", true);
+        assert!(matches!(
+            load_config_file(&file),
+            Err(CliConfigError::InvalidValue(key, value, _)) if key == "compression" && value == "lz4"
+        ));
+    }
+
+    #[test]
+    fn rejects_undefined_or_unterminated_references() {
+        assert!(matches!(
+            interpolate(ConfigKey::AccessToken, "${MISSING}", lookup),
+            Err(CliConfigError::UndefinedEnvVar(key, var)) if key == "access_token" && var == "MISSING"
+        ));
+        assert!(matches!(
+            interpolate(ConfigKey::AccessToken, "${S2_TOKEN", lookup),
+            Err(CliConfigError::InvalidValue(..))
+        ));
+    }
+}
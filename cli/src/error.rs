@@ -1,6 +1,6 @@
 use miette::Diagnostic;
 use s2_api::v1::error::ErrorCode;
-use s2_sdk::types::S2Error;
+use s2_sdk::types::{FencingToken, S2Error};
 use thiserror::Error;
 
 const HELP: &str = color_print::cstr!(
@@ -54,15 +54,45 @@ pub enum CliError {
     #[error("Failed to initialize a `Record Reader`! {0}")]
     RecordReaderInit(String),
 
+    #[error("Failed to append {0} of {1} files from input directory")]
+    InputDirAppend(usize, usize),
+
     #[error("Failed to write records: {0}")]
     RecordWrite(String),
 
+    #[error("Failed to write metrics: {0}")]
+    MetricsWrite(String),
+
+    #[error("Invalid read checkpoint: {0}")]
+    Checkpoint(String),
+
     #[error("Benchmark verification failed: {0}")]
     #[diagnostic(help(
         "Ensure no other writers are mutating the stream during bench and retry the test."
     ))]
     BenchVerification(String),
 
+    #[error("Append verification failed: {0}")]
+    #[diagnostic(help(
+        "Check whether another writer trimmed or appended to the stream concurrently, then verify \
+         the range with `s2 hash`."
+    ))]
+    AppendVerification(String),
+
+    #[error("Append condition failed: the stream is fenced with token `{0}`")]
+    #[diagnostic(help(
+        "Another writer may have fenced the stream. Retry with `--fencing-token {0}` only if this \
+         writer should take over from it."
+    ))]
+    AppendFencingTokenMismatch(FencingToken),
+
+    #[error("Append condition failed: the next sequence number is {0}")]
+    #[diagnostic(help(
+        "Records were appended since the expected position. Inspect them with `s2 read`, then \
+         retry with `--match-seq-num {0}` if the new records should still follow them."
+    ))]
+    AppendSeqNumMismatch(u64),
+
     #[error("{}: {}", .0, .1)]
     #[diagnostic(help("{}", HELP))]
     Operation(OpKind, #[source] S2Error),
@@ -81,6 +111,10 @@ pub enum CliError {
     #[error("Apply failed: {0}")]
     #[diagnostic(help("{}", HELP))]
     Apply(String),
+
+    #[error("Command timed out after {0}")]
+    #[diagnostic(help("Increase `--timeout` if the command needs longer to complete."))]
+    Timeout(humantime::Duration),
 }
 
 impl CliError {
@@ -101,6 +135,66 @@ impl CliError {
             (err, _) => err,
         }
     }
+
+    /// Category that determines the process exit code.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            CliError::Config(CliConfigError::MissingAccessToken)
+            | CliError::MalformedAccessToken(..)
+            | CliError::UnauthorizedAccessToken(..) => ErrorCategory::Auth,
+            CliError::InvalidArgs(_) => ErrorCategory::Usage,
+            CliError::Timeout(_) => ErrorCategory::Timeout,
+            CliError::SdkInit(source) | CliError::Operation(_, source) => s2_error_category(source),
+            _ => ErrorCategory::Other,
+        }
+    }
+}
+
+/// Failure categories, each exiting the process with a distinct code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    Other = 1,
+    Usage = 2,
+    Network = 3,
+    Auth = 4,
+    NotFound = 5,
+    Validation = 6,
+    Timeout = 7,
+}
+
+impl From<ErrorCategory> for std::process::ExitCode {
+    fn from(category: ErrorCategory) -> Self {
+        Self::from(category as u8)
+    }
+}
+
+fn s2_error_category(err: &S2Error) -> ErrorCategory {
+    match err {
+        S2Error::Client(_) => ErrorCategory::Network,
+        S2Error::MalformedAccessToken(_) => ErrorCategory::Auth,
+        S2Error::Validation(_) => ErrorCategory::Validation,
+        S2Error::Server(response) => match response.code.parse::<ErrorCode>() {
+            Ok(code) if code.is_auth_error() => ErrorCategory::Auth,
+            Ok(ErrorCode::BasinNotFound | ErrorCode::StreamNotFound) => ErrorCategory::NotFound,
+            Ok(
+                ErrorCode::Invalid
+                | ErrorCode::BadFrame
+                | ErrorCode::BadHeader
+                | ErrorCode::BadJson
+                | ErrorCode::BadPath
+                | ErrorCode::BadProto
+                | ErrorCode::BadQuery,
+            ) => ErrorCategory::Validation,
+            Ok(
+                ErrorCode::Unavailable
+                | ErrorCode::RequestTimeout
+                | ErrorCode::UpstreamTimeout
+                | ErrorCode::ClientHangup,
+            ) => ErrorCategory::Network,
+            _ => ErrorCategory::Other,
+        },
+        S2Error::AppendConditionFailed(_) | S2Error::ReadUnwritten(_) => ErrorCategory::Other,
+    }
 }
 
 impl From<S2UriParseError> for CliError {
@@ -129,16 +223,23 @@ pub enum OpKind {
     ListStreams,
     CreateStream,
     DeleteStream,
+    UndeleteStream,
     GetStreamConfig,
     ReconfigureStream,
     CheckTail,
+    Seek,
     Trim,
     #[strum(serialize = "set fencing token")]
     Fence,
     Append,
     Read,
     Tail,
+    #[strum(serialize = "hash stream")]
+    Hash,
+    #[strum(serialize = "compute timestamp gaps")]
+    TimestampGaps,
     Bench,
+    Ping,
 }
 
 impl std::fmt::Display for OpKind {
@@ -165,6 +266,13 @@ pub enum S2UriParseError {
     MissingStreamName,
 }
 
+#[derive(Debug, Error)]
+#[error("Invalid transform at offset {offset}: {message}")]
+pub struct TransformParseError {
+    pub offset: usize,
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum TokenSource {
     Environment,
@@ -255,8 +363,16 @@ pub enum CliConfigError {
     #[error("Failed to serialize config")]
     Serialize(#[source] toml::ser::Error),
 
-    #[error("Invalid value '{1}' for config key '{0}'")]
-    InvalidValue(String, String),
+    #[error("Invalid value '{1}' for config key '{0}', expected {2}")]
+    InvalidValue(String, String, &'static str),
+
+    #[error("Unknown config key '{0}' in {1}, expected one of: {2}")]
+    #[diagnostic(help("Pass `--ignore-unknown-config` to skip keys this version does not know."))]
+    UnknownKey(String, String, String),
+
+    #[error("Environment variable '{1}' referenced by config key '{0}' is not set")]
+    #[diagnostic(help("Export the variable, or replace the `${{...}}` reference in the config."))]
+    UndefinedEnvVar(String, String),
 
     #[error("Missing access token")]
     #[diagnostic(help(
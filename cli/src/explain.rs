@@ -0,0 +1,117 @@
+//! `--explain`: print the API request a command resolves to, before sending it.
+
+use clap::ValueEnum;
+use s2_sdk::types::{BasinName, ReadFrom, ReadInput, S2Endpoints};
+use serde_json::{Value, json};
+
+use crate::{
+    cli::{AppendArgs, ListBasinsArgs, ListStreamsArgs},
+    config::{CliConfig, Compression, custom_endpoints},
+    error::CliError,
+    types::S2BasinAndStreamUri,
+};
+
+pub struct Explainer {
+    endpoints: S2Endpoints,
+    compression: Option<Compression>,
+    only: bool,
+}
+
+impl Explainer {
+    /// `None` unless `--explain` or `--explain-only` was passed.
+    pub fn new(
+        explain: bool,
+        explain_only: bool,
+        config: &CliConfig,
+    ) -> Result<Option<Self>, CliError> {
+        if !explain && !explain_only {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            endpoints: custom_endpoints(config)?.unwrap_or_else(S2Endpoints::for_aws),
+            compression: config.compression,
+            only: explain_only,
+        }))
+    }
+
+    /// Print `request` as JSON to stderr, returning whether it should then be sent.
+    pub fn explain(&self, operation: &str, basin: Option<&BasinName>, request: Value) -> bool {
+        let endpoint = match basin {
+            Some(basin) => self.endpoints.basin_url(Some(basin)),
+            None => self.endpoints.account_url(),
+        };
+        let explained = json!({
+            "operation": operation,
+            "endpoint": endpoint,
+            "compression": self.compression.map_or("none".to_owned(), |c| c.to_string()),
+            "request": request,
+        });
+        eprintln!(
+            "{}",
+            serde_json::to_string_pretty(&explained).expect("JSON values serialize")
+        );
+        !self.only
+    }
+}
+
+pub fn read(uri: &S2BasinAndStreamUri, input: &ReadInput) -> Value {
+    let (from, position) = match input.start.from {
+        ReadFrom::SeqNum(seq_num) => ("seq_num", seq_num),
+        ReadFrom::Timestamp(timestamp) => ("timestamp", timestamp),
+        ReadFrom::TailOffset(offset) => ("tail_offset", offset),
+    };
+    json!({
+        "stream": uri.stream.to_string(),
+        "start": {
+            from: position,
+            "clamp_to_tail": input.start.clamp_to_tail,
+        },
+        "stop": {
+            "count": input.stop.limits.count,
+            "bytes": input.stop.limits.bytes,
+            "until": input.stop.until.map(|until| until.end),
+        },
+        "ignore_command_records": input.ignore_command_records,
+    })
+}
+
+pub fn append(args: &AppendArgs) -> Value {
+    json!({
+        "stream": args.uri.stream.to_string(),
+        "fencing_token": args.fencing_token.as_ref().map(|token| token.to_string()),
+        "match_seq_num": args.match_seq_num,
+        "linger_ms": args.linger.as_millis() as u64,
+        "max_inflight_appends": args.max_inflight_appends,
+        "verify": args.verify.map(value_name),
+        "append_retry_policy": value_name(args.append_retry_policy),
+    })
+}
+
+pub fn list_basins(args: &ListBasinsArgs) -> Value {
+    json!({
+        "prefix": args.prefix.as_ref().map(|prefix| prefix.to_string()),
+        "start_after": args.start_after.as_ref().map(|start_after| start_after.to_string()),
+        "limit": args.limit,
+        "auto_paginate": !args.no_auto_paginate,
+    })
+}
+
+pub fn list_streams(args: &ListStreamsArgs) -> Value {
+    json!({
+        "prefix": args.uri.stream.as_ref().or(args.prefix.as_ref()).map(|prefix| prefix.to_string()),
+        "start_after": args.start_after.as_ref().map(|start_after| start_after.to_string()),
+        "limit": args.limit,
+        "order": args.order.map(value_name),
+        "start_after_created_at": args.start_after_created_at.map(|dt| dt.to_string()),
+        "state": args.state.map(value_name),
+        "auto_paginate": !args.no_auto_paginate,
+    })
+}
+
+fn value_name(value: impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .expect("no skipped variants")
+        .get_name()
+        .to_owned()
+}
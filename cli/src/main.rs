@@ -2,12 +2,16 @@
 
 mod apply;
 mod bench;
+mod checkpoint;
 mod cli;
 mod config;
 mod error;
+mod explain;
 mod lite;
 mod ops;
 mod record_format;
+mod retries;
+mod transform;
 mod tui;
 mod types;
 
@@ -15,34 +19,54 @@ mod types;
 #[global_allocator]
 static ALLOC: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
-use std::{pin::Pin, time::Duration};
+use std::{
+    io::Write as _,
+    num::NonZeroU32,
+    ops::Range,
+    pin::Pin,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
 
 use clap::{CommandFactory, Parser};
-use cli::{ApplyArgs, Cli, Command, ConfigCommand, ListBasinsArgs, ListStreamsArgs};
+use cli::{
+    ApplyArgs, Cli, Command, ConfigCommand, ListBasinsArgs, ListStreamsArgs, MetricsOutputArgs,
+};
 use colored::Colorize;
 use config::{
-    ConfigKey, access_token_source, load_cli_config, load_config_file, sdk_config,
-    set_config_value, unset_config_value,
+    ConfigFile, ConfigKey, access_token_source, custom_endpoints, env_var_is_set, load_cli_config,
+    load_config_file, sdk_config, set_config_value, unset_config_value,
 };
 use error::{CliError, OpKind};
+use explain::Explainer;
 use futures::{Stream, StreamExt};
 use json_to_table::json_to_table;
 use record_format::{
-    JsonBase64Formatter, JsonFormatter, RecordFormat, RecordParser, RecordWriter, TextFormatter,
+    FlatJsonFormatter, HeaderFilter, JsonBase64Formatter, JsonFormatter, RecordFormat,
+    RecordParser, RecordWriter, RecordsIn, RecordsOut, SeqNumHeader, TextFormatter, embedded_body,
+    input_dir_files,
 };
+use s2_common::NameNormalization;
 use s2_sdk::{
     S2,
     types::{
-        AppendRetryPolicy, CreateStreamInput, DeleteOnEmptyConfig, DeleteStreamInput,
-        EncryptionKey, MeteredBytes, Metric, RetentionPolicy, RetryConfig,
-        StreamConfig as SdkStreamConfig, StreamName, TimestampingConfig, TimestampingMode,
+        AppendAck, AppendRetryPolicy, BasinName, CreateStreamInput, DeleteOnEmptyConfig,
+        DeleteStreamInput, EncryptionKey, MeteredBytes, Metric, RetentionPolicy, RetryConfig,
+        S2Endpoints, StreamConfig as SdkStreamConfig, StreamName, StreamPosition,
+        TimestampingConfig, TimestampingMode,
     },
 };
 use strum::VariantNames;
 use tabled::{Table, Tabled};
 use tokio::{io::AsyncWriteExt, select};
-use tracing_subscriber::{fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt};
-use types::{AccessTokenInfo, BasinConfig, S2BasinAndMaybeStreamUri, StreamConfig};
+use tracing_subscriber::{
+    Layer as _, fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt,
+};
+use transform::Transform;
+use types::{
+    AccessTokenInfo, AckFormat, BasinConfig, DurationPercentiles, MetricsOutput,
+    S2BasinAndMaybeStreamUri, StreamConfig,
+};
 
 fn install_rustls_crypto_provider() {
     rustls::crypto::aws_lc_rs::default_provider()
@@ -51,14 +75,23 @@ fn install_rustls_crypto_provider() {
 }
 
 #[tokio::main]
-async fn main() -> miette::Result<()> {
+async fn main() -> ExitCode {
     install_rustls_crypto_provider();
     miette::set_panic_hook();
-    run().await?;
-    Ok(())
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let category = err.category();
+            eprintln!("Error: {:?}", miette::Report::new(err));
+            category.into()
+        }
+    }
 }
 
 async fn run() -> Result<(), CliError> {
+    if Cli::normalize_names_requested() {
+        types::set_name_normalization(NameNormalization::Normalize);
+    }
     let cli = Cli::try_parse().unwrap_or_else(|e| {
         // Customize error message for metric commands to say "metric" instead of "subcommand"
         let msg = e.to_string();
@@ -74,7 +107,8 @@ async fn run() -> Result<(), CliError> {
     });
 
     if cli.interactive {
-        return tui::run().await;
+        let config_file = ConfigFile::resolve(cli.config_file, cli.ignore_unknown_config)?;
+        return tui::run(config_file).await;
     }
 
     let Some(command) = cli.command else {
@@ -82,6 +116,13 @@ async fn run() -> Result<(), CliError> {
         std::process::exit(0);
     };
 
+    if let Command::Completions { shell } = command {
+        let mut cmd = Cli::command();
+        let bin_name = cmd.get_name().to_string();
+        clap_complete::generate(shell, &mut cmd, bin_name, &mut std::io::stdout());
+        return Ok(());
+    }
+
     if let Command::Lite(args) = command {
         tracing_subscriber::registry()
             .with(
@@ -99,15 +140,22 @@ async fn run() -> Result<(), CliError> {
                 .pretty()
                 .with_span_events(FmtSpan::NEW)
                 .compact()
-                .with_writer(std::io::stderr),
+                .with_writer(std::io::stderr)
+                .with_filter(tracing_subscriber::EnvFilter::from_default_env()),
         )
-        .with(tracing_subscriber::EnvFilter::from_default_env())
+        .with(cli.verbose_retries.then(retries::RetryLogLayer::filtered))
         .init();
 
+    if let Some(op) = op_kind(&command) {
+        retries::set_op_kind(op);
+    }
+
+    let config_file = ConfigFile::resolve(cli.config_file, cli.ignore_unknown_config)?;
+
     if let Command::Config(config_cmd) = &command {
         match config_cmd {
             ConfigCommand::List => {
-                let config = load_config_file()?;
+                let config = load_config_file(&config_file)?;
                 for k in ConfigKey::VARIANTS {
                     if let Ok(key) = k.parse::<ConfigKey>()
                         && let Some(v) = config.get(key)
@@ -117,13 +165,13 @@ async fn run() -> Result<(), CliError> {
                 }
             }
             ConfigCommand::Get { key } => {
-                let config = load_config_file()?;
+                let config = load_config_file(&config_file)?;
                 if let Some(v) = config.get(*key) {
                     println!("{}", v);
                 }
             }
             ConfigCommand::Set { key, value } => {
-                let saved_path = set_config_value(*key, value.clone())?;
+                let saved_path = set_config_value(&config_file, *key, value.clone())?;
                 eprintln!("{}", format!("✓ {} set", key).green().bold());
                 eprintln!(
                     "  Configuration saved to: {}",
@@ -131,7 +179,7 @@ async fn run() -> Result<(), CliError> {
                 );
             }
             ConfigCommand::Unset { key } => {
-                let saved_path = unset_config_value(*key)?;
+                let saved_path = unset_config_value(&config_file, *key)?;
                 eprintln!("{}", format!("✓ {} unset", key).green().bold());
                 eprintln!(
                     "  Configuration saved to: {}",
@@ -142,6 +190,11 @@ async fn run() -> Result<(), CliError> {
         return Ok(());
     }
 
+    if let Command::Endpoints { basin } = &command {
+        print_endpoints(&config_file, basin.as_ref().map(|basin| &basin.0))?;
+        return Ok(());
+    }
+
     if let Command::Apply(ApplyArgs { schema: true, .. }) = &command {
         let schema = s2_resource_spec::json_schema();
         println!(
@@ -151,17 +204,27 @@ async fn run() -> Result<(), CliError> {
         return Ok(());
     }
 
-    let cli_config = load_cli_config()?;
-    let sdk_config = sdk_config(
+    let cli_config = load_cli_config(&config_file)?;
+    let mut sdk_config = sdk_config(
         &cli_config,
         &format!("s2-cli/{}", env!("CARGO_PKG_VERSION")),
     )?;
+    if let Some(max_idle) = cli.http_max_idle_connections {
+        sdk_config = sdk_config.with_pool_max_idle_per_host(max_idle);
+    }
+    if let Some(idle_timeout) = cli.http_idle_timeout {
+        sdk_config = sdk_config.with_pool_idle_timeout(*idle_timeout);
+    }
     let token_source = access_token_source(&cli_config);
     let s2 = S2::new(sdk_config.clone())
         .map_err(|e| CliError::SdkInit(e).with_token_source(token_source))?;
-    let result: Result<(), CliError> = (async {
+    let explainer = Explainer::new(cli.explain, cli.explain_only, &cli_config)?;
+    let result: Result<(), CliError> = with_timeout(cli.timeout, async {
         match command {
-        Command::Config(..) | Command::Lite(..) => unreachable!(),
+        Command::Config(..)
+        | Command::Lite(..)
+        | Command::Completions { .. }
+        | Command::Endpoints { .. } => unreachable!(),
 
         Command::Ls(args) => {
             if let Some(ref uri) = args.uri {
@@ -197,15 +260,22 @@ async fn run() -> Result<(), CliError> {
                         .map_err(|e| CliError::InvalidArgs(miette::miette!("{e}")))?,
                     limit: args.limit,
                     no_auto_paginate: args.no_auto_paginate,
+                    order: None,
+                    start_after_created_at: None,
+                    state: None,
                 };
 
                 let (streams, _) = ops::list_streams(&s2, list_streams_args).await?;
-                for stream_info in streams {
-                    print_listing_with_created_at(
-                        format!("s2://{}/{}", basin, stream_info.name),
-                        stream_info.created_at.to_string(),
-                        stream_info.deleted_at.is_some(),
-                    );
+                if args.table {
+                    print_streams_table(&basin.to_string(), streams);
+                } else {
+                    for stream_info in streams {
+                        print_listing_with_created_at(
+                            format!("s2://{}/{}", basin, stream_info.name),
+                            stream_info.created_at.to_string(),
+                            stream_info.deleted_at.is_some(),
+                        );
+                    }
                 }
             } else {
                 // List basins
@@ -227,17 +297,26 @@ async fn run() -> Result<(), CliError> {
                 };
 
                 let (basins, _) = ops::list_basins(&s2, list_basins_args).await?;
-                for basin_info in basins {
-                    print_basin_listing(
-                        basin_info.name.to_string(),
-                        basin_info.location.as_deref(),
-                        basin_info.deleted_at.is_some(),
-                    );
+                if args.table {
+                    print_basins_table(basins);
+                } else {
+                    for basin_info in basins {
+                        print_basin_listing(
+                            basin_info.name.to_string(),
+                            basin_info.location.as_deref(),
+                            basin_info.deleted_at.is_some(),
+                        );
+                    }
                 }
             }
         }
 
         Command::ListBasins(args) => {
+            if let Some(explainer) = &explainer
+                && !explainer.explain("list_basins", None, explain::list_basins(&args))
+            {
+                return Ok(());
+            }
             let (basins, _) = ops::list_basins(&s2, args).await?;
             for basin_info in basins {
                 print_basin_listing(
@@ -270,6 +349,13 @@ async fn run() -> Result<(), CliError> {
             println!("{}", json_to_table(&serde_json::to_value(&config)?));
         }
 
+        Command::CopyBasinConfig(args) => {
+            let config = ops::copy_basin_config(&s2, args).await?;
+
+            eprintln!("{}", "✓ Basin default stream config copied".green().bold());
+            println!("{}", json_to_table(&serde_json::to_value(&config)?));
+        }
+
         Command::ListAccessTokens(args) => {
             let (tokens, _) = ops::list_access_tokens(&s2, args).await?;
             for token_info in tokens {
@@ -279,8 +365,13 @@ async fn run() -> Result<(), CliError> {
         }
 
         Command::IssueAccessToken(args) => {
-            let token = ops::issue_access_token(&s2, args).await?;
-            println!("{}", token);
+            if args.validate_only {
+                let request = ops::issue_access_token_request(args)?;
+                println!("{}", serde_json::to_string_pretty(&request)?);
+            } else {
+                let token = ops::issue_access_token(&s2, args).await?;
+                println!("{}", token);
+            }
         }
 
         Command::RevokeAccessToken { id } => {
@@ -316,22 +407,34 @@ async fn run() -> Result<(), CliError> {
         }
 
         Command::GetAccountMetrics(args) => {
+            let output = args.output.clone();
             let metrics = ops::get_account_metrics(&s2, args).await?;
-            print_metrics(&metrics);
+            print_metrics(metrics, &output)?;
         }
 
         Command::GetBasinMetrics(args) => {
+            let output = args.output.clone();
             let metrics = ops::get_basin_metrics(&s2, args).await?;
-            print_metrics(&metrics);
+            print_metrics(metrics, &output)?;
         }
 
         Command::GetStreamMetrics(args) => {
+            let output = args.output.clone();
             let metrics = ops::get_stream_metrics(&s2, args).await?;
-            print_metrics(&metrics);
+            print_metrics(metrics, &output)?;
         }
 
         Command::ListStreams(args) => {
             let basin_name = args.uri.basin.clone();
+            if let Some(explainer) = &explainer
+                && !explainer.explain(
+                    "list_streams",
+                    Some(&basin_name),
+                    explain::list_streams(&args),
+                )
+            {
+                return Ok(());
+            }
             let (streams, _) = ops::list_streams(&s2, args).await?;
             for stream_info in streams {
                 print_listing_uri(
@@ -346,9 +449,18 @@ async fn run() -> Result<(), CliError> {
             eprintln!("{}", "✓ Stream created".green().bold());
         }
 
-        Command::DeleteStream { uri } => {
-            ops::delete_stream(&s2, uri).await?;
-            eprintln!("{}", "✓ Stream deletion requested".green().bold());
+        Command::DeleteStream { uri, purge } => {
+            ops::delete_stream(&s2, uri, purge).await?;
+            if purge {
+                eprintln!("{}", "✓ Stream purged".green().bold());
+            } else {
+                eprintln!("{}", "✓ Stream deletion requested".green().bold());
+            }
+        }
+
+        Command::UndeleteStream { uri } => {
+            ops::undelete_stream(&s2, uri).await?;
+            eprintln!("{}", "✓ Stream undeleted".green().bold());
         }
 
         Command::GetStreamConfig { uri } => {
@@ -369,6 +481,12 @@ async fn run() -> Result<(), CliError> {
             println!("{}", format_position(tail.seq_num, tail.timestamp));
         }
 
+        Command::Seek(args) => {
+            let encryption_key = resolve_encryption_key(&args.encryption_key)?;
+            let seq_num = ops::seek(&s2, &args, encryption_key.as_ref()).await?;
+            println!("{seq_num}");
+        }
+
         Command::Trim(args) => {
             let trim_point = args.trim_point;
             let out = ops::trim(&s2, args).await?;
@@ -400,75 +518,103 @@ async fn run() -> Result<(), CliError> {
         }
 
         Command::Append(args) => {
+            if let Some(explainer) = &explainer
+                && !explainer.explain("append", Some(&args.uri.basin), explain::append(&args))
+            {
+                return Ok(());
+            }
+            let s2 = S2::new(sdk_config.clone().with_retry(args.append_retry_policy.into()))
+                .map_err(|e| CliError::SdkInit(e).with_token_source(token_source))?;
             let encryption_key = resolve_encryption_key(&args.encryption_key)?;
-            let records_in = args
-                .input
-                .reader()
-                .await
-                .map_err(|e| CliError::RecordReaderInit(e.to_string()))?;
-
-            let record_stream: Pin<Box<dyn Stream<Item = _> + Send + Unpin>> = match args.format {
-                RecordFormat::Text => Box::pin(TextFormatter::parse_records(records_in)),
-                RecordFormat::Json => Box::pin(JsonFormatter::parse_records(records_in)),
-                RecordFormat::JsonBase64 => {
-                    Box::pin(JsonBase64Formatter::parse_records(records_in))
-                }
+            let Some(input_dir) = &args.input_dir else {
+                append_records(
+                    &s2,
+                    &args,
+                    &args.input,
+                    encryption_key.as_ref(),
+                    args.match_seq_num,
+                )
+                .await?;
+                return Ok(());
             };
 
-            let acks = ops::append(
-                &s2,
-                record_stream,
-                args.uri,
-                encryption_key.as_ref(),
-                args.fencing_token,
-                args.match_seq_num,
-                *args.linger,
-            );
-            let mut acks = std::pin::pin!(acks);
-            let mut last_printed_batch_end: Option<u64> = None;
-
-            loop {
-                select! {
-                    ack = acks.next() => {
-                        match ack {
-                            Some(Ok(ack)) => {
-                                if last_printed_batch_end.is_none_or(|end| end != ack.batch.end.seq_num) {
-                                    last_printed_batch_end = Some(ack.batch.end.seq_num);
-                                    eprintln!(
-                                        "{}",
-                                        format!(
-                                            "✓ [APPENDED] {}..{} // tail: {}",
-                                            ack.batch.start.seq_num,
-                                            ack.batch.end.seq_num,
-                                            format_position(ack.batch.tail.seq_num, ack.batch.tail.timestamp)
-                                        )
-                                        .green()
-                                        .bold()
-                                    );
-                                }
-                            }
-                            Some(Err(e)) => {
-                                return Err(e);
-                            }
-                            None => break, // Stream exhausted, all done
-                        }
+            let files = input_dir_files(input_dir, args.sort)
+                .await
+                .map_err(|e| CliError::RecordReaderInit(format!("{}: {e}", input_dir.display())))?;
+            let mut match_seq_num = args.match_seq_num;
+            let mut failed = 0;
+            for (i, path) in files.iter().enumerate() {
+                eprintln!(
+                    "{}",
+                    format!("▶ [FILE {}/{}] {}", i + 1, files.len(), path.display())
+                        .cyan()
+                        .bold()
+                );
+                let input = RecordsIn::File(path.clone());
+                let result = append_records(
+                    &s2,
+                    &args,
+                    &input,
+                    encryption_key.as_ref(),
+                    match_seq_num.take(),
+                )
+                .await;
+                match result {
+                    Ok(AppendSummary { aborted: true, .. }) => return Ok(()),
+                    Ok(AppendSummary { records, .. }) => {
+                        eprintln!(
+                            "{}",
+                            format!("✓ [FILE DONE] {} // {records} records", path.display())
+                                .green()
+                                .bold()
+                        );
                     }
-                    _ = tokio::signal::ctrl_c() => {
-                        eprintln!("{}", "■ [ABORTED]".red().bold());
-                        break;
+                    Err(e) if args.continue_on_error => {
+                        failed += 1;
+                        eprintln!(
+                            "{}",
+                            format!("✗ [FILE FAILED] {}: {e}", path.display()).red().bold()
+                        );
                     }
+                    Err(e) => return Err(e),
                 }
             }
+            if failed > 0 {
+                return Err(CliError::InputDirAppend(failed, files.len()));
+            }
         }
 
         Command::Read(args) => {
+            let input = ops::read_input(&args).await?;
+            if let Some(explainer) = &explainer
+                && !explainer.explain(
+                    "read",
+                    Some(&args.uri.basin),
+                    explain::read(&args.uri, &input),
+                )
+            {
+                return Ok(());
+            }
             let encryption_key = resolve_encryption_key(&args.encryption_key)?;
-            let mut batches = ops::read(&s2, &args, encryption_key.as_ref()).await?;
-            let mut writer = args
-                .output
+            let mut batches =
+                ops::read_session(&s2, args.uri.clone(), input, encryption_key.as_ref()).await?;
+            let records_out = match &args.output_dir {
+                Some(dir) => RecordsOut::Dir { dir: dir.clone(), rotate: args.rotate },
+                None => args.output.clone(),
+            };
+            let mut writer = records_out
                 .writer()
                 .await
                 .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+            let output = RecordOutput {
+                format: args.format,
+                seq_num_header: args.seq_num_header,
+                jsonl_flat: args.jsonl_flat,
+                transform: args.transform.clone(),
+                headers: HeaderFilter::new(args.only_headers.clone(), args.exclude_headers.clone()),
+            };
+            let mut record_count: u64 = 0;
+            let mut record_bytes: u64 = 0;
 
             loop {
                 select! {
@@ -494,21 +640,36 @@ async fn run() -> Result<(), CliError> {
                                 );
 
                                 for record in &batch.records {
-                                    write_record(record, &mut writer, args.format).await?;
-                                    let skip_newline = matches!(args.format, RecordFormat::Text)
-                                        && record.is_command_record();
-                                    if !skip_newline {
+                                    if args.commands_only && !record.is_command_record() {
+                                        continue;
+                                    }
+                                    if args.count_only {
+                                        record_count += 1;
+                                        record_bytes += record.metered_bytes() as u64;
+                                        continue;
+                                    }
+                                    if args.commands_only && output.describes_commands() {
+                                        let line = format!(
+                                            "{} // {}\n",
+                                            describe_command(record),
+                                            format_position(record.seq_num, record.timestamp)
+                                        );
                                         writer
-                                            .write_all(b"\n")
+                                            .write_all(line.as_bytes())
                                             .await
                                             .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+                                        continue;
                                     }
+                                    write_record(record, &mut writer, &output).await?;
                                 }
 
                                 writer
                                     .flush()
                                     .await
                                     .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+                                if let Some(path) = &args.resume_from_checkpoint_or_tail {
+                                    checkpoint::save(path, seq_range.end() + 1).await?;
+                                }
                             }
                             Some(Err(e)) => {
                                 return Err(CliError::op(OpKind::Read, e));
@@ -522,9 +683,27 @@ async fn run() -> Result<(), CliError> {
                     }
                 }
             }
+
+            if args.count_only {
+                let totals = format!("{record_count} records, {record_bytes} bytes\n");
+                writer
+                    .write_all(totals.as_bytes())
+                    .await
+                    .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+                writer
+                    .flush()
+                    .await
+                    .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+            }
         }
 
         Command::Tail(args) => {
+            let stats_interval = args.stats.map(|interval| *interval);
+            if stats_interval.is_some_and(|interval| interval.is_zero()) {
+                return Err(CliError::InvalidArgs(miette::miette!(
+                    "--stats interval must be greater than zero"
+                )));
+            }
             let encryption_key = resolve_encryption_key(&args.encryption_key)?;
             let mut records = ops::tail(&s2, &args, encryption_key.as_ref()).await?;
             let mut writer = args
@@ -532,21 +711,27 @@ async fn run() -> Result<(), CliError> {
                 .writer()
                 .await
                 .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+            let output = RecordOutput {
+                format: args.format,
+                seq_num_header: args.seq_num_header,
+                jsonl_flat: args.jsonl_flat,
+                transform: args.transform.clone(),
+                headers: HeaderFilter::new(args.only_headers.clone(), args.exclude_headers.clone()),
+            };
+            let mut stats_timer =
+                tokio::time::interval(stats_interval.unwrap_or(Duration::from_secs(1)));
+            stats_timer.reset();
+            let mut stats_start = Instant::now();
+            let (mut stats_records, mut stats_bytes) = (0u64, 0u64);
 
             loop {
                 select! {
                     record = records.next() => {
                         match record {
                             Some(Ok(record)) => {
-                                write_record(&record, &mut writer, args.format).await?;
-                                let skip_newline = matches!(args.format, RecordFormat::Text)
-                                    && record.is_command_record();
-                                if !skip_newline {
-                                    writer
-                                        .write_all(b"\n")
-                                        .await
-                                        .map_err(|e| CliError::RecordWrite(e.to_string()))?;
-                                }
+                                stats_records += 1;
+                                stats_bytes += record.metered_bytes() as u64;
+                                write_record(&record, &mut writer, &output).await?;
                                 writer
                                     .flush()
                                     .await
@@ -558,14 +743,111 @@ async fn run() -> Result<(), CliError> {
                             None => break,
                         }
                     }
+                    _ = stats_timer.tick(), if stats_interval.is_some() => {
+                        let (mibps, recps) = tui::calculate_throughput(
+                            stats_bytes,
+                            stats_records,
+                            stats_start.elapsed().as_secs_f64(),
+                        );
+                        eprintln!(
+                            "{}",
+                            format!("■ {recps:.1} records/s, {mibps:.2} MiB/s").dimmed()
+                        );
+                        stats_start = Instant::now();
+                        (stats_records, stats_bytes) = (0, 0);
+                    }
+                    _ = tokio::signal::ctrl_c() => {
+                        eprintln!("{}", "■ [ABORTED]".red().bold());
+                        break;
+                    }
+                }
+            }
+        }
+
+        Command::Ping(args) => {
+            let s2 = S2::new(
+                sdk_config
+                    .clone()
+                    .with_retry(RetryConfig::new().with_max_attempts(NonZeroU32::MIN)),
+            )
+            .map_err(CliError::SdkInit)?;
+            let count = args.count.get();
+            let mut rtts = Vec::new();
+            let mut sent = 0;
+            let mut last_err = None;
+            for probe in 1..=count {
+                let start = Instant::now();
+                let result = select! {
+                    result = ops::ping(&s2) => result,
                     _ = tokio::signal::ctrl_c() => {
                         eprintln!("{}", "■ [ABORTED]".red().bold());
                         break;
                     }
+                };
+                let rtt = start.elapsed();
+                sent += 1;
+                match result {
+                    Ok(()) => {
+                        println!(
+                            "{} probe={probe} time={:.1} ms",
+                            "✓".green().bold(),
+                            rtt.as_secs_f64() * 1000.0
+                        );
+                        rtts.push(rtt);
+                    }
+                    Err(e) => {
+                        println!("{} probe={probe} error: {e}", "✗".red().bold());
+                        last_err = Some(e);
+                    }
                 }
+                if probe < count {
+                    tokio::time::sleep(*args.interval).await;
+                }
+            }
+            print_ping_summary(&rtts, sent);
+            if rtts.is_empty()
+                && let Some(e) = last_err
+            {
+                return Err(e);
             }
         }
 
+        Command::Hash(args) => {
+            let encryption_key = resolve_encryption_key(&args.encryption_key)?;
+            let hash = ops::hash(&s2, &args, encryption_key.as_ref()).await?;
+            println!(
+                "{}  s2://{}/{}",
+                hash.digest.to_hex(),
+                args.uri.basin,
+                args.uri.stream
+            );
+            eprintln!(
+                "{}",
+                format!(
+                    "✓ {} records, {} bytes in range {}..{}",
+                    hash.records, hash.bytes, args.from, hash.end
+                )
+                .green()
+                .bold()
+            );
+        }
+
+        Command::TimestampGaps(args) => {
+            let gaps = ops::timestamp_gaps(&s2, &args).await?;
+            for (name, gap) in gaps.gaps.map(DurationPercentiles::into_vec).unwrap_or_default() {
+                println!("{name}\t{}", humantime::format_duration(gap));
+            }
+            eprintln!(
+                "{}",
+                format!(
+                    "✓ {} records in range {}..{}",
+                    gaps.records, args.from, gaps.end
+                )
+                .green()
+                .bold()
+            );
+        }
+
         Command::Apply(ApplyArgs {
             file,
             dry_run,
@@ -649,10 +931,25 @@ async fn run() -> Result<(), CliError> {
         Ok(())
     })
     .await;
+    // Output is flushed after each batch, so anything written before a timeout is complete.
+    std::io::stdout().flush().ok();
 
     result.map_err(|err| err.with_token_source(token_source))
 }
 
+/// Run `command`, failing with [`CliError::Timeout`] if it does not complete within `timeout`.
+async fn with_timeout(
+    timeout: Option<humantime::Duration>,
+    command: impl Future<Output = Result<(), CliError>>,
+) -> Result<(), CliError> {
+    let Some(timeout) = timeout else {
+        return command.await;
+    };
+    tokio::time::timeout(*timeout, command)
+        .await
+        .unwrap_or(Err(CliError::Timeout(timeout)))
+}
+
 fn format_position(seq_num: u64, timestamp: u64) -> String {
     format!("{seq_num} @ {timestamp}")
 }
@@ -678,6 +975,133 @@ fn print_basin_listing(name: String, location: Option<&str>, is_deleting: bool)
     }
 }
 
+fn print_basins_table(basins: Vec<s2_sdk::types::BasinInfo>) {
+    #[derive(Tabled)]
+    struct BasinRow {
+        name: String,
+        state: String,
+        created_at: String,
+    }
+
+    let rows = basins.into_iter().map(|basin_info| BasinRow {
+        name: basin_info.name.to_string(),
+        state: if basin_info.deleted_at.is_some() {
+            "deleting"
+        } else {
+            "active"
+        }
+        .to_string(),
+        created_at: basin_info.created_at.to_string(),
+    });
+    println!("{}", Table::new(rows));
+}
+
+fn print_streams_table(basin: &str, streams: Vec<s2_sdk::types::StreamInfo>) {
+    #[derive(Tabled)]
+    struct StreamRow {
+        name: String,
+        created_at: String,
+        deleted_at: String,
+    }
+
+    let rows = streams.into_iter().map(|stream_info| StreamRow {
+        name: format!("s2://{}/{}", basin, stream_info.name),
+        created_at: stream_info.created_at.to_string(),
+        deleted_at: stream_info
+            .deleted_at
+            .map(|deleted_at| deleted_at.to_string())
+            .unwrap_or_default(),
+    });
+    println!("{}", Table::new(rows));
+}
+
+fn print_ping_summary(rtts: &[Duration], sent: usize) {
+    println!();
+    println!(
+        "{} probes, {} ok, {} failed",
+        sent,
+        rtts.len(),
+        sent - rtts.len()
+    );
+    let (Some(min), Some(max)) = (rtts.iter().min(), rtts.iter().max()) else {
+        return;
+    };
+    let avg = rtts.iter().sum::<Duration>() / rtts.len() as u32;
+    let ms = |d: &Duration| d.as_secs_f64() * 1000.0;
+    println!(
+        "rtt min/avg/max = {:.1}/{:.1}/{:.1} ms",
+        ms(min),
+        ms(&avg),
+        ms(max)
+    );
+}
+
+/// The operation a command performs against S2, if it maps to a single one.
+fn op_kind(command: &Command) -> Option<OpKind> {
+    Some(match command {
+        Command::Ls(args) if args.uri.is_some() => OpKind::ListStreams,
+        Command::Ls(_) | Command::ListBasins(_) => OpKind::ListBasins,
+        Command::CreateBasin(_) => OpKind::CreateBasin,
+        Command::DeleteBasin { .. } => OpKind::DeleteBasin,
+        Command::GetBasinConfig { .. } => OpKind::GetBasinConfig,
+        Command::ReconfigureBasin(_) | Command::CopyBasinConfig(_) => OpKind::ReconfigureBasin,
+        Command::ListAccessTokens(_) => OpKind::ListAccessTokens,
+        Command::IssueAccessToken(_) => OpKind::IssueAccessToken,
+        Command::RevokeAccessToken { .. } => OpKind::RevokeAccessToken,
+        Command::ListLocations => OpKind::ListLocations,
+        Command::GetDefaultLocation => OpKind::GetDefaultLocation,
+        Command::SetDefaultLocation { .. } => OpKind::SetDefaultLocation,
+        Command::GetAccountMetrics(_) => OpKind::GetAccountMetrics,
+        Command::GetBasinMetrics(_) => OpKind::GetBasinMetrics,
+        Command::GetStreamMetrics(_) => OpKind::GetStreamMetrics,
+        Command::ListStreams(_) => OpKind::ListStreams,
+        Command::CreateStream(_) => OpKind::CreateStream,
+        Command::DeleteStream { .. } => OpKind::DeleteStream,
+        Command::UndeleteStream { .. } => OpKind::UndeleteStream,
+        Command::GetStreamConfig { .. } => OpKind::GetStreamConfig,
+        Command::ReconfigureStream(_) => OpKind::ReconfigureStream,
+        Command::CheckTail { .. } => OpKind::CheckTail,
+        Command::Seek(_) => OpKind::Seek,
+        Command::Trim(_) => OpKind::Trim,
+        Command::Fence(_) => OpKind::Fence,
+        Command::Append(_) => OpKind::Append,
+        Command::Read(_) => OpKind::Read,
+        Command::Tail(_) => OpKind::Tail,
+        Command::Hash(_) => OpKind::Hash,
+        Command::TimestampGaps(_) => OpKind::TimestampGaps,
+        Command::Bench(_) => OpKind::Bench,
+        Command::Ping(_) => OpKind::Ping,
+        Command::Config(_)
+        | Command::Endpoints { .. }
+        | Command::Apply(_)
+        | Command::Lite(_)
+        | Command::Completions { .. } => return None,
+    })
+}
+
+fn print_endpoints(config_file: &ConfigFile, basin: Option<&BasinName>) -> Result<(), CliError> {
+    let endpoints = custom_endpoints(&load_cli_config(config_file)?)?;
+    let source = |env_var: &str| match endpoints {
+        None => "default".to_string(),
+        Some(_) if env_var_is_set(env_var) => format!("environment ({env_var})"),
+        Some(_) => format!("config file ({})", config_file.path.display()),
+    };
+    let account_source = source("S2_ACCOUNT_ENDPOINT");
+    let basin_source = source("S2_BASIN_ENDPOINT");
+    let endpoints = endpoints.unwrap_or_else(S2Endpoints::for_aws);
+    println!(
+        "account endpoint: {} {}",
+        endpoints.account_url(),
+        format!("({account_source})").dimmed()
+    );
+    println!(
+        "basin endpoint:   {} {}",
+        endpoints.basin_url(basin),
+        format!("({basin_source})").dimmed()
+    );
+    Ok(())
+}
+
 fn print_location_listing(name: String, is_private: bool) {
     let visibility = format_location_visibility(is_private);
     println!("{name} {visibility}");
@@ -722,53 +1146,114 @@ fn deletion_marker() -> colored::ColoredString {
     "[deleting]".red().bold()
 }
 
+fn describe_command(record: &s2_sdk::types::SequencedRecord) -> String {
+    match record.command() {
+        Some(s2_sdk::types::Command::Fence { fencing_token }) => {
+            format!("new fencing token \"{}\"", fencing_token)
+        }
+        Some(s2_sdk::types::Command::Trim { trim_point }) => format!("trim to {}", trim_point),
+        None => "unknown command".to_string(),
+    }
+}
+
+/// How `read` and `tail` write each record.
+struct RecordOutput {
+    format: RecordFormat,
+    seq_num_header: Option<SeqNumHeader>,
+    jsonl_flat: bool,
+    transform: Option<Transform>,
+    headers: HeaderFilter,
+}
+
+impl RecordOutput {
+    /// Whether command records are described on stderr instead of being written.
+    fn describes_commands(&self) -> bool {
+        matches!(self.format, RecordFormat::Text) && !self.jsonl_flat && self.transform.is_none()
+    }
+
+    /// JSON representation of a record that a transform is applied to.
+    fn json_value(&self, record: &s2_sdk::types::SequencedRecord) -> serde_json::Value {
+        if self.jsonl_flat {
+            return FlatJsonFormatter::to_value(record);
+        }
+        match self.format {
+            RecordFormat::JsonBase64 => JsonBase64Formatter::to_value(record),
+            RecordFormat::Text | RecordFormat::Json => {
+                // Parse JSON bodies so that filters can reach into their fields.
+                let mut value = JsonFormatter::to_value(record);
+                value["body"] = embedded_body(&record.body);
+                value
+            }
+        }
+    }
+}
+
+/// Write a record followed by a newline, unless it is described on stderr or
+/// dropped by a transform.
 async fn write_record(
     record: &s2_sdk::types::SequencedRecord,
     writer: &mut (impl tokio::io::AsyncWrite + Unpin),
-    format: RecordFormat,
+    output: &RecordOutput,
 ) -> Result<(), CliError> {
-    match format {
-        RecordFormat::Text => {
-            if record.is_command_record() {
-                if let Some(header) = record.headers.first() {
-                    let cmd_type = &header.value;
-                    let cmd_desc = if cmd_type.as_ref() == b"fence" {
-                        let fencing_token = String::from_utf8_lossy(&record.body);
-                        format!("new fencing token \"{}\"", fencing_token)
-                    } else if cmd_type.as_ref() == b"trim" {
-                        let trim_point = if record.body.len() >= 8 {
-                            u64::from_be_bytes(record.body[..8].try_into().unwrap_or_default())
-                        } else {
-                            0
-                        };
-                        format!("trim to {}", trim_point)
-                    } else {
-                        "unknown command".to_string()
-                    };
+    let record = output.headers.apply(record);
+    let record = record.as_ref();
+    if let Some(transform) = &output.transform {
+        let Some(value) = transform.apply(&output.json_value(record)) else {
+            return Ok(());
+        };
+        // Like `jq -r`, strings are written raw in text format.
+        let line = match value {
+            serde_json::Value::String(s)
+                if matches!(output.format, RecordFormat::Text) && !output.jsonl_flat =>
+            {
+                s
+            }
+            value => value.to_string(),
+        };
+        writer
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+    } else if output.jsonl_flat {
+        FlatJsonFormatter::write_record(record, writer)
+            .await
+            .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+    } else {
+        match output.format {
+            RecordFormat::Text => {
+                if record.is_command_record() {
                     eprintln!(
                         "{} // {}",
-                        cmd_desc.bold(),
+                        describe_command(record).bold(),
                         format_position(record.seq_num, record.timestamp)
                     );
+                    return Ok(());
+                } else if let Some(placement) = output.seq_num_header {
+                    TextFormatter::write_record_with_position(record, writer, placement)
+                        .await
+                        .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+                } else {
+                    TextFormatter::write_record(record, writer)
+                        .await
+                        .map_err(|e| CliError::RecordWrite(e.to_string()))?;
                 }
-            } else {
-                TextFormatter::write_record(record, writer)
+            }
+            RecordFormat::Json => {
+                JsonFormatter::write_record(record, writer)
+                    .await
+                    .map_err(|e| CliError::RecordWrite(e.to_string()))?;
+            }
+            RecordFormat::JsonBase64 => {
+                JsonBase64Formatter::write_record(record, writer)
                     .await
                     .map_err(|e| CliError::RecordWrite(e.to_string()))?;
             }
-        }
-        RecordFormat::Json => {
-            JsonFormatter::write_record(record, writer)
-                .await
-                .map_err(|e| CliError::RecordWrite(e.to_string()))?;
-        }
-        RecordFormat::JsonBase64 => {
-            JsonBase64Formatter::write_record(record, writer)
-                .await
-                .map_err(|e| CliError::RecordWrite(e.to_string()))?;
         }
     }
-    Ok(())
+    writer
+        .write_all(b"\n")
+        .await
+        .map_err(|e| CliError::RecordWrite(e.to_string()))
 }
 
 fn format_timestamp(ts: u32) -> String {
@@ -777,38 +1262,95 @@ fn format_timestamp(ts: u32) -> String {
     humantime::format_rfc3339_seconds(time).to_string()
 }
 
+fn format_relative_time(ts: u32, now: u64) -> String {
+    let secs = now.saturating_sub(ts as u64);
+    match secs {
+        0 => "now".to_string(),
+        1..60 => format!("{secs}s ago"),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+fn interval_secs(interval: s2_sdk::types::TimeseriesInterval) -> u64 {
+    match interval {
+        s2_sdk::types::TimeseriesInterval::Minute => 60,
+        s2_sdk::types::TimeseriesInterval::Hour => 3600,
+        s2_sdk::types::TimeseriesInterval::Day => 86400,
+    }
+}
+
 fn format_unit(unit: s2_sdk::types::MetricUnit) -> &'static str {
     match unit {
         s2_sdk::types::MetricUnit::Bytes => "bytes",
         s2_sdk::types::MetricUnit::Operations => "operations",
+        s2_sdk::types::MetricUnit::Count => "count",
+        s2_sdk::types::MetricUnit::Percent => "percent",
+        _ => "unknown",
     }
 }
 
-fn print_metrics(metrics: &[Metric]) {
+fn print_metrics(metrics: Vec<Metric>, args: &MetricsOutputArgs) -> Result<(), CliError> {
+    if let MetricsOutput::Json | MetricsOutput::Csv = args.output {
+        let mut out: Box<dyn std::io::Write> = match &args.metric_file {
+            Some(path) => Box::new(
+                std::fs::File::create(path).map_err(|e| CliError::MetricsWrite(e.to_string()))?,
+            ),
+            None => Box::new(std::io::stdout().lock()),
+        };
+        let metrics: Vec<types::Metric> = metrics.into_iter().map(Into::into).collect();
+        if let MetricsOutput::Json = args.output {
+            write_metrics_json(&metrics, &mut out)
+        } else {
+            write_metrics_csv(&metrics, &mut out)
+        }
+        .map_err(|e| CliError::MetricsWrite(e.to_string()))?;
+        return Ok(());
+    }
+    if args.metric_file.is_some() {
+        return Err(CliError::InvalidArgs(miette::miette!(
+            "--metric-file requires --output json or --output csv"
+        )));
+    }
+
     #[derive(Tabled)]
     struct AccumulationRow {
         interval_start: String,
+        ago: String,
         count: String,
     }
 
     #[derive(Tabled)]
     struct GaugeRow {
         time: String,
+        ago: String,
         value: String,
     }
 
-    for metric in metrics {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    for metric in &metrics {
         match metric {
             Metric::Scalar(m) => {
                 println!("{}: {} {}", m.name, m.value, format_unit(m.unit));
             }
             Metric::Accumulation(m) => {
+                let secs = interval_secs(m.interval) as f64;
                 let rows: Vec<AccumulationRow> = m
                     .values
                     .iter()
                     .map(|(ts, value)| AccumulationRow {
                         interval_start: format_timestamp(*ts),
-                        count: value.to_string(),
+                        ago: format_relative_time(*ts, now),
+                        count: if args.as_rate {
+                            format!("{:.3}", value / secs)
+                        } else {
+                            value.to_string()
+                        },
                     })
                     .collect();
 
@@ -821,13 +1363,17 @@ fn print_metrics(metrics: &[Metric]) {
                 );
 
                 let interval_col = "interval start time".to_string();
-                let count_col = format_unit(m.unit).to_string();
+                let count_col = if args.as_rate {
+                    format!("{}/s", format_unit(m.unit))
+                } else {
+                    format_unit(m.unit).to_string()
+                };
                 table.with(
                     tabled::settings::Modify::new(tabled::settings::object::Cell::new(0, 0))
                         .with(tabled::settings::Format::content(|_| interval_col.clone())),
                 );
                 table.with(
-                    tabled::settings::Modify::new(tabled::settings::object::Cell::new(0, 1))
+                    tabled::settings::Modify::new(tabled::settings::object::Cell::new(0, 2))
                         .with(tabled::settings::Format::content(|_| count_col.clone())),
                 );
 
@@ -840,6 +1386,7 @@ fn print_metrics(metrics: &[Metric]) {
                     .iter()
                     .map(|(ts, value)| GaugeRow {
                         time: format_timestamp(*ts),
+                        ago: format_relative_time(*ts, now),
                         value: value.to_string(),
                     })
                     .collect();
@@ -854,7 +1401,7 @@ fn print_metrics(metrics: &[Metric]) {
                 );
 
                 table.with(
-                    tabled::settings::Modify::new(tabled::settings::object::Cell::new(0, 1))
+                    tabled::settings::Modify::new(tabled::settings::object::Cell::new(0, 2))
                         .with(tabled::settings::Format::content(|_| count_col.clone())),
                 );
 
@@ -869,6 +1416,214 @@ fn print_metrics(metrics: &[Metric]) {
             }
         }
     }
+    Ok(())
+}
+
+fn write_metrics_json(
+    metrics: &[types::Metric],
+    out: &mut impl std::io::Write,
+) -> std::io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, metrics)?;
+    writeln!(out)?;
+    out.flush()
+}
+
+fn write_metrics_csv(
+    metrics: &[types::Metric],
+    out: &mut impl std::io::Write,
+) -> std::io::Result<()> {
+    writeln!(out, "metric,timestamp,value,unit")?;
+    for metric in metrics {
+        let (name, unit, values) = match metric {
+            types::Metric::Accumulation {
+                name, unit, values, ..
+            }
+            | types::Metric::Gauge { name, unit, values } => (name, unit, values),
+            types::Metric::Scalar { .. } | types::Metric::Label { .. } => continue,
+        };
+        let name = if name.contains([',', '"', '\n']) {
+            format!("\"{}\"", name.replace('"', "\"\""))
+        } else {
+            name.clone()
+        };
+        for types::Datapoint { timestamp, value } in values {
+            writeln!(
+                out,
+                "{name},{},{value},{}",
+                format_timestamp(*timestamp),
+                unit.as_str()
+            )?;
+        }
+    }
+    out.flush()
+}
+
+/// Outcome of appending one input source.
+struct AppendSummary {
+    records: u64,
+    aborted: bool,
+}
+
+/// Append all records from `input`, printing acknowledged batches to stderr.
+async fn append_records(
+    s2: &S2,
+    args: &cli::AppendArgs,
+    input: &RecordsIn,
+    encryption_key: Option<&EncryptionKey>,
+    match_seq_num: Option<u64>,
+) -> Result<AppendSummary, CliError> {
+    let records_in = input
+        .reader()
+        .await
+        .map_err(|e| CliError::RecordReaderInit(e.to_string()))?;
+
+    let record_stream: Pin<Box<dyn Stream<Item = _> + Send + Unpin>> = match args.format {
+        RecordFormat::Text => Box::pin(TextFormatter::parse_records(records_in)),
+        RecordFormat::Json => Box::pin(JsonFormatter::parse_records(records_in)),
+        RecordFormat::JsonBase64 => Box::pin(JsonBase64Formatter::parse_records(records_in)),
+    };
+    // The first Ctrl+C ends the input, so that the records already read are still appended.
+    let (drain_tx, drain_rx) = tokio::sync::oneshot::channel::<()>();
+    let mut drain_tx = Some(drain_tx);
+    let mut sent = args.verify.map(|_| ops::SentRecords::default());
+    let mut appended: Vec<Range<u64>> = Vec::new();
+
+    let summary = {
+        let record_stream = record_stream.inspect(|record| {
+            if let (Some(sent), Ok(record)) = (sent.as_mut(), record) {
+                sent.update(record);
+            }
+        });
+        let acks = ops::append(
+            s2,
+            record_stream.take_until(drain_rx),
+            args.uri.clone(),
+            encryption_key,
+            args.fencing_token.clone(),
+            match_seq_num,
+            *args.linger,
+            args.max_inflight_appends,
+        );
+        let mut acks = std::pin::pin!(acks);
+        let mut last_printed_batch_end: Option<u64> = None;
+        let mut records = 0;
+
+        loop {
+            select! {
+                ack = acks.next() => {
+                    match ack {
+                        Some(Ok(ack)) => {
+                            records += 1;
+                            match appended.last_mut() {
+                                Some(range) if range.end == ack.seq_num => range.end += 1,
+                                _ => appended.push(ack.seq_num..ack.seq_num + 1),
+                            }
+                            let batch_end = ack.batch.end.seq_num;
+                            if last_printed_batch_end.is_none_or(|end| end != batch_end) {
+                                last_printed_batch_end = Some(batch_end);
+                                print_append_ack(&ack.batch, args.ack_format);
+                            }
+                        }
+                        Some(Err(e)) => {
+                            if args.ack_format == AckFormat::Json
+                                && let Some(condition_failed) = append_condition_failed_json(&e)
+                            {
+                                println!("{condition_failed}");
+                            }
+                            return Err(e);
+                        }
+                        None => break, // Stream exhausted, all done
+                    }
+                }
+                _ = tokio::signal::ctrl_c() => {
+                    if let Some(drain_tx) = drain_tx.take() {
+                        eprintln!(
+                            "{}",
+                            "■ [DRAINING] Waiting for in-flight appends, \
+                             press Ctrl+C again to abort"
+                                .yellow()
+                                .bold()
+                        );
+                        let _ = drain_tx.send(());
+                    } else {
+                        eprintln!("{}", "■ [ABORTED]".red().bold());
+                        return Ok(AppendSummary { records, aborted: true });
+                    }
+                }
+            }
+        }
+
+        let drained = drain_tx.is_none();
+        if drained {
+            eprintln!(
+                "{}",
+                format!("■ [DRAINED] {records} records appended")
+                    .yellow()
+                    .bold()
+            );
+        }
+        AppendSummary {
+            records,
+            aborted: drained,
+        }
+    };
+
+    if let (Some(mode), Some(sent)) = (args.verify, sent) {
+        let verified =
+            ops::verify_append(s2, args.uri.clone(), encryption_key, &appended, sent, mode).await?;
+        eprintln!(
+            "{}",
+            format!("✓ [VERIFIED] {verified} records read back")
+                .green()
+                .bold()
+        );
+    }
+    Ok(summary)
+}
+
+fn print_append_ack(ack: &AppendAck, format: AckFormat) {
+    match format {
+        AckFormat::Text => eprintln!(
+            "{}",
+            format!(
+                "✓ [APPENDED] {}..{} // tail: {}",
+                ack.start.seq_num,
+                ack.end.seq_num,
+                format_position(ack.tail.seq_num, ack.tail.timestamp)
+            )
+            .green()
+            .bold()
+        ),
+        AckFormat::Json => {
+            let position = |pos: &StreamPosition| {
+                serde_json::json!({
+                    "seq_num": pos.seq_num,
+                    "timestamp": pos.timestamp,
+                })
+            };
+            let ack = serde_json::json!({
+                "start": position(&ack.start),
+                "end": position(&ack.end),
+                "tail": position(&ack.tail),
+            });
+            println!("{ack}");
+        }
+    }
+}
+
+/// What the stream expected instead, if `err` is a failed append condition.
+fn append_condition_failed_json(err: &CliError) -> Option<serde_json::Value> {
+    match err {
+        CliError::AppendFencingTokenMismatch(token) => Some(serde_json::json!({
+            "condition_failed": "fencing_token_mismatch",
+            "expected_fencing_token": token.to_string(),
+        })),
+        CliError::AppendSeqNumMismatch(seq_num) => Some(serde_json::json!({
+            "condition_failed": "seq_num_mismatch",
+            "expected_seq_num": seq_num,
+        })),
+        _ => None,
+    }
 }
 
 fn resolve_encryption_key(
@@ -887,3 +1642,144 @@ fn resolve_encryption_key(
         _ => Ok(None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use s2_sdk::types::SequencedRecord;
+
+    use super::{RecordOutput, print_metrics, write_metrics_csv, write_metrics_json};
+    use crate::{
+        cli::MetricsOutputArgs,
+        error::CliError,
+        record_format::{HeaderFilter, RecordFormat},
+        types::{Datapoint, Interval, Metric, MetricUnit, MetricsOutput},
+    };
+
+    fn sample_metrics() -> Vec<Metric> {
+        vec![
+            Metric::Scalar {
+                name: "basins".to_owned(),
+                unit: MetricUnit::Count,
+                value: 3.0,
+            },
+            Metric::Accumulation {
+                name: "appends, total".to_owned(),
+                unit: MetricUnit::Operations,
+                interval: Interval::Minute,
+                values: vec![
+                    Datapoint {
+                        timestamp: 0,
+                        value: 1.0,
+                    },
+                    Datapoint {
+                        timestamp: 60,
+                        value: 2.5,
+                    },
+                ],
+            },
+            Metric::Gauge {
+                name: "storage".to_owned(),
+                unit: MetricUnit::Bytes,
+                values: vec![Datapoint {
+                    timestamp: 3600,
+                    value: 42.0,
+                }],
+            },
+            Metric::Label {
+                name: "regions".to_owned(),
+                values: vec!["aws:us-east-1".to_owned()],
+            },
+        ]
+    }
+
+    #[test]
+    fn metrics_json_is_tagged_with_datapoints() {
+        let mut out = Vec::new();
+        write_metrics_json(&sample_metrics(), &mut out).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"type": "scalar", "name": "basins", "unit": "count", "value": 3.0},
+                {
+                    "type": "accumulation",
+                    "name": "appends, total",
+                    "unit": "operations",
+                    "interval": "minute",
+                    "values": [
+                        {"timestamp": 0, "value": 1.0},
+                        {"timestamp": 60, "value": 2.5},
+                    ],
+                },
+                {
+                    "type": "gauge",
+                    "name": "storage",
+                    "unit": "bytes",
+                    "values": [{"timestamp": 3600, "value": 42.0}],
+                },
+                {"type": "label", "name": "regions", "values": ["aws:us-east-1"]},
+            ])
+        );
+    }
+
+    #[test]
+    fn metrics_csv_flattens_series_and_quotes_names() {
+        let mut out = Vec::new();
+        write_metrics_csv(&sample_metrics(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "metric,timestamp,value,unit\n\
+             \"appends, total\",1970-01-01T00:00:00Z,1,operations\n\
+             \"appends, total\",1970-01-01T00:01:00Z,2.5,operations\n\
+             storage,1970-01-01T01:00:00Z,42,bytes\n"
+        );
+    }
+
+    #[test]
+    fn metrics_file_failure_is_a_write_error() {
+        let args = MetricsOutputArgs {
+            output: MetricsOutput::Json,
+            metric_file: Some(PathBuf::from("/nonexistent-dir/metrics.json")),
+            as_rate: false,
+        };
+        assert!(matches!(
+            print_metrics(vec![], &args),
+            Err(CliError::MetricsWrite(_))
+        ));
+    }
+
+    #[test]
+    fn transform_sees_parsed_json_bodies() {
+        let output = |format| RecordOutput {
+            format,
+            seq_num_header: None,
+            jsonl_flat: false,
+            transform: Some(r#"select(.body.level == "error") | .body.msg"#.parse().unwrap()),
+            headers: HeaderFilter::All,
+        };
+        let record = |body: &str| SequencedRecord::from_parts(0, 0, vec![], body.to_owned());
+        let apply = |output: &RecordOutput, body| {
+            let value = output.json_value(&record(body));
+            output.transform.as_ref().unwrap().apply(&value)
+        };
+
+        for format in [RecordFormat::Text, RecordFormat::Json] {
+            let output = output(format);
+            assert_eq!(
+                apply(&output, r#"{"level":"error","msg":"boom"}"#),
+                Some("boom".into())
+            );
+            assert_eq!(apply(&output, r#"{"level":"info","msg":"ok"}"#), None);
+            assert_eq!(apply(&output, "not json"), None);
+        }
+        assert_eq!(
+            apply(
+                &output(RecordFormat::JsonBase64),
+                r#"{"level":"error","msg":"boom"}"#
+            ),
+            None
+        );
+    }
+}
@@ -1,5 +1,6 @@
-use std::{pin::Pin, time::Duration};
+use std::{collections::BTreeMap, num::NonZeroU32, ops::Range, pin::Pin, time::Duration};
 
+use clap::ValueEnum;
 use futures::{Stream, StreamExt, TryStreamExt, stream, stream::FuturesOrdered};
 use s2_sdk::{
     self as sdk, S2, S2Stream,
@@ -7,15 +8,16 @@ use s2_sdk::{
     producer::{IndexedAppendAck, ProducerConfig},
     types::{
         AccessTokenId, AccessTokenInfo, AccessTokenScopeInput, AccountMetricSet, AppendAck,
-        AppendInput, AppendRecord, AppendRecordBatch, BasinInfo, BasinMetricSet, BasinName,
-        BasinReconfiguration, CommandRecord, CreateBasinInput, CreateStreamInput, DeleteBasinInput,
-        DeleteStreamInput, EncryptionKey, FencingToken, GetAccountMetricsInput,
-        GetBasinMetricsInput, GetStreamMetricsInput, IssueAccessTokenInput, ListAccessTokensInput,
-        ListAllAccessTokensInput, ListAllBasinsInput, ListAllStreamsInput, ListBasinsInput,
-        ListStreamsInput, LocationInfo, LocationName, MeteredBytes, Metric, ReadBatch, ReadFrom,
-        ReadInput, ReadLimits, ReadStart, ReadStop, ReconfigureBasinInput, ReconfigureStreamInput,
-        S2DateTime, SequencedRecord, StreamInfo, StreamMetricSet, StreamPosition,
-        StreamReconfiguration, Streaming, TimeRange, TimeRangeAndInterval,
+        AppendConditionFailed, AppendInput, AppendRecord, AppendRecordBatch, BasinInfo,
+        BasinMetricSet, BasinName, BasinReconfiguration, CommandRecord, CreateBasinInput,
+        CreateStreamInput, DeleteBasinInput, DeleteStreamInput, EncryptionKey, FencingToken,
+        GetAccountMetricsInput, GetBasinMetricsInput, GetStreamMetricsInput, Header,
+        IssueAccessTokenInput, ListAccessTokensInput, ListAllAccessTokensInput, ListAllBasinsInput,
+        ListAllStreamsInput, ListBasinsInput, ListStreamsInput, LocationInfo, LocationName,
+        MeteredBytes, Metric, ReadBatch, ReadFrom, ReadInput, ReadLimits, ReadStart, ReadStop,
+        ReconfigureBasinInput, ReconfigureStreamInput, S2DateTime, S2Error, SequencedRecord,
+        StreamInfo, StreamMetricSet, StreamPosition, StreamReconfiguration, Streaming, TimeRange,
+        TimeRangeAndInterval,
     },
 };
 
@@ -32,16 +34,34 @@ fn stream_with_encryption(
 }
 
 use crate::{
+    checkpoint,
     cli::{
-        CreateBasinArgs, CreateStreamArgs, FenceArgs, GetAccountMetricsArgs, GetBasinMetricsArgs,
-        GetStreamMetricsArgs, IssueAccessTokenArgs, ListAccessTokensArgs, ListBasinsArgs,
-        ListStreamsArgs, ReadArgs, ReconfigureBasinArgs, ReconfigureStreamArgs, TailArgs,
-        TimeRangeArgs, TrimArgs,
+        CopyBasinConfigArgs, CreateBasinArgs, CreateStreamArgs, FenceArgs, GetAccountMetricsArgs,
+        GetBasinMetricsArgs, GetStreamMetricsArgs, HashArgs, IssueAccessTokenArgs,
+        ListAccessTokensArgs, ListBasinsArgs, ListStreamsArgs, ReadArgs, ReconfigureBasinArgs,
+        ReconfigureStreamArgs, SeekArgs, TailArgs, TimeRangeArgs, TimestampGapsArgs, TrimArgs,
     },
     error::{CliError, OpKind},
-    types::{BasinConfig, Interval, S2BasinAndStreamUri, StreamConfig},
+    types::{
+        BasinConfig, DurationPercentiles, Interval, Operation, OperationGroup,
+        PermittedOperationGroups, S2BasinAndMaybeStreamUri, S2BasinAndStreamUri, StreamConfig,
+        VerifyMode,
+    },
 };
 
+/// Reject a `start_after` that sorts before `prefix` before making the call, as it could never
+/// match anything.
+fn validate_start_after(
+    prefix: Option<&impl AsRef<str>>,
+    start_after: Option<&impl AsRef<str>>,
+) -> Result<(), CliError> {
+    s2_common::resources::validate_start_after(
+        prefix.map_or("", AsRef::as_ref),
+        start_after.map_or("", AsRef::as_ref),
+    )
+    .map_err(|e| CliError::InvalidArgs(miette::miette!("{e}")))
+}
+
 /// List basins, returning items and whether there are more.
 /// If `no_auto_paginate` is true, returns a single page.
 /// If false, fetches all pages and returns (all_items, false).
@@ -55,6 +75,7 @@ pub async fn list_basins(
         limit,
         no_auto_paginate,
     } = args;
+    validate_start_after(prefix.as_ref(), start_after.as_ref())?;
 
     if no_auto_paginate {
         let mut input = ListBasinsInput::new();
@@ -154,6 +175,20 @@ pub async fn reconfigure_basin_with(
     Ok(config.into())
 }
 
+/// Apply the default stream config of one basin to another, returning the reconfigured basin's
+/// config.
+pub async fn copy_basin_config(
+    s2: &S2,
+    args: CopyBasinConfigArgs,
+) -> Result<BasinConfig, CliError> {
+    let source = get_basin_config(s2, &args.from.into()).await?;
+    let default_stream_config =
+        StreamConfig::from(source.default_stream_config.unwrap_or_default());
+    let reconfig = BasinReconfiguration::new()
+        .with_default_stream_config(default_stream_config.into_reset_reconfiguration());
+    reconfigure_basin_with(s2, args.to.into(), reconfig).await
+}
+
 /// List access tokens, returning items and whether there are more.
 pub async fn list_access_tokens(
     s2: &S2,
@@ -164,7 +199,14 @@ pub async fn list_access_tokens(
         start_after,
         limit,
         no_auto_paginate,
+        scope_contains,
+        op,
     } = args;
+    let is_listed = |info: &AccessTokenInfo| {
+        scope_contains
+            .as_ref()
+            .is_none_or(|uri| scope_covers(info, uri, op.as_ref()))
+    };
 
     if no_auto_paginate {
         let mut input = ListAccessTokensInput::new();
@@ -183,7 +225,8 @@ pub async fn list_access_tokens(
             .await
             .map_err(|e| CliError::op(OpKind::ListAccessTokens, e))?;
 
-        Ok((page.values, page.has_more))
+        let tokens = page.values.into_iter().filter(is_listed).collect();
+        Ok((tokens, page.has_more))
     } else {
         let mut input = ListAllAccessTokensInput::new();
         if let Some(p) = prefix {
@@ -195,6 +238,7 @@ pub async fn list_access_tokens(
 
         let items: Vec<_> = s2
             .list_all_access_tokens(input)
+            .try_filter(|info| std::future::ready(is_listed(info)))
             .take(limit.unwrap_or(usize::MAX))
             .try_collect()
             .await
@@ -204,18 +248,94 @@ pub async fn list_access_tokens(
     }
 }
 
+/// Whether the scope of a token covers the basin of `uri`, and its stream if it has one, and
+/// permits `op` on it. Without `op`, any operation at the level of `uri` or below qualifies.
+fn scope_covers(
+    info: &AccessTokenInfo,
+    uri: &S2BasinAndMaybeStreamUri,
+    op: Option<&Operation>,
+) -> bool {
+    let basin: &str = &uri.basin;
+    let basin_covered = match &info.scope.basins {
+        Some(sdk::types::BasinMatcher::Exact(exact)) => **exact == *basin,
+        Some(sdk::types::BasinMatcher::Prefix(prefix)) => basin.starts_with(&**prefix),
+        Some(sdk::types::BasinMatcher::None) | None => false,
+    };
+    if !basin_covered {
+        return false;
+    }
+    if let Some(stream) = &uri.stream {
+        let stream: &str = stream;
+        let stream_covered = match &info.scope.streams {
+            Some(sdk::types::StreamMatcher::Exact(exact)) => **exact == *stream,
+            // An auto-prefixing token addresses streams by the name after its prefix, so it
+            // cannot reach a stream named exactly the prefix.
+            Some(sdk::types::StreamMatcher::Prefix(prefix)) => {
+                stream.starts_with(&**prefix)
+                    && (!info.auto_prefix_streams || stream.len() > prefix.len())
+            }
+            Some(sdk::types::StreamMatcher::None) | None => false,
+        };
+        if !stream_covered {
+            return false;
+        }
+    }
+
+    let op_groups = info
+        .scope
+        .op_group_perms
+        .clone()
+        .map(PermittedOperationGroups::from);
+    let permits = |op: &Operation| {
+        info.scope
+            .ops
+            .contains(&sdk::types::Operation::from(op.clone()))
+            || op_groups.as_ref().is_some_and(|groups| groups.permits(op))
+    };
+    match op {
+        Some(op) => permits(op),
+        None => Operation::value_variants()
+            .iter()
+            .filter(|op| {
+                if uri.stream.is_some() {
+                    op.group() == OperationGroup::Stream
+                } else {
+                    op.group() != OperationGroup::Account
+                }
+            })
+            .any(permits),
+    }
+}
+
 pub async fn issue_access_token(s2: &S2, args: IssueAccessTokenArgs) -> Result<String, CliError> {
+    s2.issue_access_token(issue_access_token_input(args)?)
+        .await
+        .map_err(|e| CliError::op(OpKind::IssueAccessToken, e))
+}
+
+/// The request that issuing a token with `args` would send, without issuing it.
+pub fn issue_access_token_request(
+    args: IssueAccessTokenArgs,
+) -> Result<s2_api::v1::access::IssueAccessTokenRequest, CliError> {
+    Ok(issue_access_token_input(args)?.into())
+}
+
+fn issue_access_token_input(args: IssueAccessTokenArgs) -> Result<IssueAccessTokenInput, CliError> {
+    let template = args.template.as_ref();
     let mut scope = AccessTokenScopeInput::from_ops(args.ops.into_iter().map(|op| op.into()));
-    if let Some(basins) = args.basins {
+    if let Some(basins) = args.basins.or_else(|| template.map(|t| t.basins())) {
         scope = scope.with_basins(basins.into());
     }
-    if let Some(streams) = args.streams {
+    if let Some(streams) = args.streams.or_else(|| template.map(|t| t.streams())) {
         scope = scope.with_streams(streams.into());
     }
     if let Some(access_tokens) = args.access_tokens {
         scope = scope.with_access_tokens(access_tokens.into());
     }
-    if let Some(op_group_perms) = args.op_group_perms {
+    if let Some(op_group_perms) = args
+        .op_group_perms
+        .or_else(|| template.map(|t| t.op_group_perms()))
+    {
         scope = scope.with_op_group_perms(op_group_perms.into());
     }
 
@@ -239,10 +359,7 @@ pub async fn issue_access_token(s2: &S2, args: IssueAccessTokenArgs) -> Result<S
     if args.auto_prefix_streams {
         input = input.with_auto_prefix_streams(true);
     }
-
-    s2.issue_access_token(input)
-        .await
-        .map_err(|e| CliError::op(OpKind::IssueAccessToken, e))
+    Ok(input)
 }
 
 pub async fn revoke_access_token(s2: &S2, id: AccessTokenId) -> Result<(), CliError> {
@@ -251,6 +368,14 @@ pub async fn revoke_access_token(s2: &S2, id: AccessTokenId) -> Result<(), CliEr
         .map_err(|e| CliError::op(OpKind::RevokeAccessToken, e))
 }
 
+/// Check connectivity and credentials with the cheapest authenticated request.
+pub async fn ping(s2: &S2) -> Result<(), CliError> {
+    s2.list_basins(ListBasinsInput::new().with_limit(1))
+        .await
+        .map(|_| ())
+        .map_err(|e| CliError::op(OpKind::Ping, e))
+}
+
 /// List locations.
 pub async fn list_locations(s2: &S2) -> Result<Vec<LocationInfo>, CliError> {
     s2.list_locations()
@@ -360,6 +485,7 @@ pub async fn list_streams(
     args: ListStreamsArgs,
 ) -> Result<(Vec<StreamInfo>, bool), CliError> {
     let prefix = args.uri.stream.or(args.prefix);
+    validate_start_after(prefix.as_ref(), args.start_after.as_ref())?;
     let basin = s2.basin(args.uri.basin);
 
     if args.no_auto_paginate {
@@ -373,6 +499,15 @@ pub async fn list_streams(
         if let Some(l) = args.limit {
             input = input.with_limit(l);
         }
+        if let Some(o) = args.order {
+            input = input.with_order(o.into());
+        }
+        if let Some(dt) = args.start_after_created_at {
+            input = input.with_start_after_created_at(dt);
+        }
+        if let Some(s) = args.state {
+            input = input.with_state(s.into());
+        }
 
         let page = basin
             .list_streams(input)
@@ -387,6 +522,15 @@ pub async fn list_streams(
         if let Some(s) = args.start_after {
             input = input.with_start_after(s);
         }
+        if let Some(o) = args.order {
+            input = input.with_order(o.into());
+        }
+        if let Some(dt) = args.start_after_created_at {
+            input = input.with_start_after_created_at(dt);
+        }
+        if let Some(s) = args.state {
+            input = input.with_state(s.into());
+        }
 
         let items: Vec<_> = basin
             .list_all_streams(input)
@@ -408,14 +552,22 @@ pub async fn create_stream(s2: &S2, args: CreateStreamArgs) -> Result<StreamInfo
         .map_err(|e| CliError::op(OpKind::CreateStream, e))
 }
 
-pub async fn delete_stream(s2: &S2, uri: S2BasinAndStreamUri) -> Result<(), CliError> {
+pub async fn delete_stream(s2: &S2, uri: S2BasinAndStreamUri, purge: bool) -> Result<(), CliError> {
     let basin = s2.basin(uri.basin);
     basin
-        .delete_stream(DeleteStreamInput::new(uri.stream))
+        .delete_stream(DeleteStreamInput::new(uri.stream).with_purge(purge))
         .await
         .map_err(|e| CliError::op(OpKind::DeleteStream, e))
 }
 
+pub async fn undelete_stream(s2: &S2, uri: S2BasinAndStreamUri) -> Result<StreamInfo, CliError> {
+    let basin = s2.basin(uri.basin);
+    basin
+        .undelete_stream(uri.stream)
+        .await
+        .map_err(|e| CliError::op(OpKind::UndeleteStream, e))
+}
+
 pub async fn get_stream_config(
     s2: &S2,
     uri: S2BasinAndStreamUri,
@@ -431,7 +583,11 @@ pub async fn reconfigure_stream(
     s2: &S2,
     args: ReconfigureStreamArgs,
 ) -> Result<StreamConfig, CliError> {
-    let reconfig: StreamReconfiguration = args.config.into();
+    let reconfig: StreamReconfiguration = if args.reset {
+        args.config.into_reset_reconfiguration()
+    } else {
+        args.config.into()
+    };
     reconfigure_stream_with(s2, args.uri, reconfig).await
 }
 
@@ -457,6 +613,50 @@ pub async fn check_tail(s2: &S2, uri: S2BasinAndStreamUri) -> Result<StreamPosit
         .map_err(|e| CliError::op(OpKind::CheckTail, e))
 }
 
+/// Resolve the position in `args` to the sequence number a read from it would start at.
+pub async fn seek(
+    s2: &S2,
+    args: &SeekArgs,
+    encryption_key: Option<&EncryptionKey>,
+) -> Result<u64, CliError> {
+    let from = match (args.timestamp, args.ago, args.tail_offset) {
+        (Some(ts), None, None) => ReadFrom::Timestamp(ts),
+        (None, Some(ago), None) => ReadFrom::Timestamp(timestamp_ago(ago)),
+        (None, None, Some(offset)) => ReadFrom::TailOffset(offset),
+        _ => unreachable!("clap ensures exactly one position option"),
+    };
+    let stream = stream_with_encryption(s2, args.uri.clone(), encryption_key);
+
+    // Reading a single record is enough for the server to resolve the position. Clamping makes
+    // positions past the last record resolve to the tail instead of failing.
+    let start = ReadStart::new().with_from(from).with_clamp_to_tail(true);
+    let stop = ReadStop::new().with_limits(ReadLimits::new().with_count(1));
+    let batch = stream
+        .read(ReadInput::new().with_start(start).with_stop(stop))
+        .await
+        .map_err(|e| CliError::op(OpKind::Seek, e))?;
+    if let Some(record) = batch.records.first() {
+        return Ok(record.seq_num);
+    }
+    let tail = match batch.tail {
+        Some(tail) => tail,
+        None => stream
+            .check_tail()
+            .await
+            .map_err(|e| CliError::op(OpKind::Seek, e))?,
+    };
+    Ok(tail.seq_num)
+}
+
+/// Milliseconds since Unix epoch, `ago` before now.
+fn timestamp_ago(ago: humantime::Duration) -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_millis()
+        .saturating_sub(ago.as_millis()) as u64
+}
+
 pub async fn trim(s2: &S2, args: TrimArgs) -> Result<AppendAck, CliError> {
     let stream = s2.basin(args.uri.basin).stream(args.uri.stream);
     append_command(
@@ -486,23 +686,51 @@ pub async fn read(
     args: &ReadArgs,
     encryption_key: Option<&EncryptionKey>,
 ) -> Result<Streaming<ReadBatch>, CliError> {
-    use std::time::SystemTime;
+    read_session(
+        s2,
+        args.uri.clone(),
+        read_input(args).await?,
+        encryption_key,
+    )
+    .await
+}
 
-    let stream = stream_with_encryption(s2, args.uri.clone(), encryption_key);
+/// Open a read session with an already resolved `input`, e.g. one that was also explained, since
+/// resolving it again could pick a different start for relative options such as `--ago`.
+pub async fn read_session(
+    s2: &S2,
+    uri: S2BasinAndStreamUri,
+    input: ReadInput,
+    encryption_key: Option<&EncryptionKey>,
+) -> Result<Streaming<ReadBatch>, CliError> {
+    let stream = stream_with_encryption(s2, uri, encryption_key);
+    stream
+        .read_session(input)
+        .await
+        .map_err(|e| CliError::op(OpKind::Read, e))
+}
 
-    let from = match (args.seq_num, args.timestamp, args.tail_offset, args.ago) {
-        (Some(seq), None, None, None) => ReadFrom::SeqNum(seq),
-        (None, Some(ts), None, None) => ReadFrom::Timestamp(ts),
-        (None, None, Some(offset), None) => ReadFrom::TailOffset(offset),
-        (None, None, None, Some(ago)) => {
-            let ts = SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_millis()
-                .saturating_sub(ago.as_millis()) as u64;
-            ReadFrom::Timestamp(ts)
+/// The read session request that `args` resolve to.
+pub async fn read_input(args: &ReadArgs) -> Result<ReadInput, CliError> {
+    let checkpoint = match &args.resume_from_checkpoint_or_tail {
+        Some(path) => checkpoint::load(path).await?,
+        None => None,
+    };
+    let from = match (
+        args.seq_num,
+        args.timestamp,
+        args.tail_offset,
+        args.ago,
+        checkpoint,
+    ) {
+        (Some(seq), None, None, None, None) | (None, None, None, None, Some(seq)) => {
+            ReadFrom::SeqNum(seq)
         }
-        (None, None, None, None) => ReadFrom::TailOffset(0),
+        (None, Some(ts), None, None, None) => ReadFrom::Timestamp(ts),
+        (None, None, Some(offset), None, None) => ReadFrom::TailOffset(offset),
+        (None, None, None, Some(ago), None) => ReadFrom::Timestamp(timestamp_ago(ago)),
+        // Also the first run with a checkpoint file that does not exist yet.
+        (None, None, None, None, None) => ReadFrom::TailOffset(0),
         _ => unreachable!("clap ensures only one start option"),
     };
 
@@ -523,10 +751,7 @@ pub async fn read(
         stop = stop.with_until(..until);
     }
 
-    stream
-        .read_session(ReadInput::new().with_start(start).with_stop(stop))
-        .await
-        .map_err(|e| CliError::op(OpKind::Read, e))
+    Ok(ReadInput::new().with_start(start).with_stop(stop))
 }
 
 pub fn append<'a, S, E>(
@@ -537,6 +762,7 @@ pub fn append<'a, S, E>(
     fencing_token: Option<FencingToken>,
     match_seq_num: Option<u64>,
     linger: Duration,
+    max_inflight_appends: Option<NonZeroU32>,
 ) -> impl Stream<Item = Result<IndexedAppendAck, CliError>> + Send + 'a
 where
     S: Stream<Item = Result<AppendRecord, E>> + Send + Unpin + 'a,
@@ -552,6 +778,9 @@ where
     if let Some(seq) = match_seq_num {
         producer_config = producer_config.with_match_seq_num(seq);
     }
+    if let Some(max) = max_inflight_appends {
+        producer_config = producer_config.with_max_unacked_batches(max);
+    }
 
     let producer = stream.producer(producer_config);
 
@@ -571,7 +800,7 @@ where
                             pending_acks.push_back(permit.submit(record));
                         }
                         Err(e) => {
-                            yield Err(CliError::op(OpKind::Append, e));
+                            yield Err(append_error(e));
                             break 'inner;
                         }
                     }
@@ -597,7 +826,7 @@ where
                     match res {
                         Ok(ack) => yield Ok(ack),
                         Err(e) => {
-                            yield Err(CliError::op(OpKind::Append, e));
+                            yield Err(append_error(e));
                             break 'inner;
                         }
                     }
@@ -612,7 +841,7 @@ where
         }
 
         if let Err(e) = producer.close().await {
-            yield Err(CliError::op(OpKind::Append, e));
+            yield Err(append_error(e));
             return;
         }
 
@@ -620,7 +849,7 @@ where
             match res {
                 Ok(ack) => yield Ok(ack),
                 Err(e) => {
-                    yield Err(CliError::op(OpKind::Append, e));
+                    yield Err(append_error(e));
                     return;
                 }
             }
@@ -628,6 +857,19 @@ where
     }
 }
 
+/// Surface a failed append condition along with what the stream expected instead.
+fn append_error(err: S2Error) -> CliError {
+    match err {
+        S2Error::AppendConditionFailed(AppendConditionFailed::FencingTokenMismatch(token)) => {
+            CliError::AppendFencingTokenMismatch(token)
+        }
+        S2Error::AppendConditionFailed(AppendConditionFailed::SeqNumMismatch(seq_num)) => {
+            CliError::AppendSeqNumMismatch(seq_num)
+        }
+        err => CliError::op(OpKind::Append, err),
+    }
+}
+
 pub async fn tail(
     s2: &S2,
     args: &TailArgs,
@@ -661,6 +903,201 @@ pub async fn tail(
     ))
 }
 
+/// Digest over a range of a stream's records, as computed by [`hash`].
+pub struct StreamHash {
+    pub end: u64,
+    pub records: u64,
+    pub bytes: u64,
+    pub digest: blake3::Hash,
+}
+
+pub async fn hash(
+    s2: &S2,
+    args: &HashArgs,
+    encryption_key: Option<&EncryptionKey>,
+) -> Result<StreamHash, CliError> {
+    let stream = stream_with_encryption(s2, args.uri.clone(), encryption_key);
+
+    // Pin the end of the range up front, so the digest is not affected by
+    // concurrent appends and the read session does not wait at the tail.
+    let tail = stream
+        .check_tail()
+        .await
+        .map_err(|e| CliError::op(OpKind::Hash, e))?;
+    let end = args.to.map_or(tail.seq_num, |to| to.min(tail.seq_num));
+
+    let mut hasher = blake3::Hasher::new();
+    let (records, bytes) = hash_range(&stream, args.from..end, &mut hasher, OpKind::Hash).await?;
+
+    Ok(StreamHash {
+        end,
+        records,
+        bytes,
+        digest: hasher.finalize(),
+    })
+}
+
+/// Distribution of the timestamp gaps between consecutive records in a range of a stream, as
+/// computed by [`timestamp_gaps`].
+pub struct TimestampGaps {
+    pub end: u64,
+    pub records: u64,
+    /// `None` if the range has fewer than two records.
+    pub gaps: Option<DurationPercentiles>,
+}
+
+pub async fn timestamp_gaps(s2: &S2, args: &TimestampGapsArgs) -> Result<TimestampGaps, CliError> {
+    // Positions are read without decrypting records, so no key is needed.
+    let stream = s2
+        .basin(args.uri.basin.clone())
+        .stream(args.uri.stream.clone());
+
+    // Pin the end of the range up front, as for `hash`.
+    let tail = stream
+        .check_tail()
+        .await
+        .map_err(|e| CliError::op(OpKind::TimestampGaps, e))?;
+    let end = args.to.map_or(tail.seq_num, |to| to.min(tail.seq_num));
+
+    let mut gaps = BTreeMap::<Duration, u64>::new();
+    let mut records = 0;
+    let mut prev_timestamp = None;
+    let mut next = args.from;
+    while next < end {
+        let start = ReadStart::new().with_from(ReadFrom::SeqNum(next));
+        let stop = ReadStop::new().with_limits(ReadLimits::new().with_count((end - next) as usize));
+        let batch = stream
+            .read_positions(ReadInput::new().with_start(start).with_stop(stop))
+            .await
+            .map_err(|e| CliError::op(OpKind::TimestampGaps, e))?;
+        let Some(last) = batch.positions.last() else {
+            break;
+        };
+        next = last.seq_num + 1;
+        for position in batch.positions.iter().filter(|p| p.seq_num < end) {
+            if let Some(prev) = prev_timestamp {
+                let gap = Duration::from_millis(position.timestamp.saturating_sub(prev));
+                *gaps.entry(gap).or_default() += 1;
+            }
+            prev_timestamp = Some(position.timestamp);
+            records += 1;
+        }
+    }
+
+    Ok(TimestampGaps {
+        end,
+        records,
+        gaps: DurationPercentiles::from_counts(&gaps),
+    })
+}
+
+/// Feed the records in `range` into `hasher`, returning how many records and
+/// metered bytes were hashed.
+async fn hash_range(
+    stream: &S2Stream,
+    range: Range<u64>,
+    hasher: &mut blake3::Hasher,
+    op: OpKind,
+) -> Result<(u64, u64), CliError> {
+    let mut records = 0;
+    let mut bytes = 0;
+
+    if range.is_empty() {
+        return Ok((records, bytes));
+    }
+    let start = ReadStart::new().with_from(ReadFrom::SeqNum(range.start));
+    let stop = ReadStop::new()
+        .with_limits(ReadLimits::new().with_count((range.end - range.start) as usize));
+    let mut batches = stream
+        .read_session(ReadInput::new().with_start(start).with_stop(stop))
+        .await
+        .map_err(|e| CliError::op(op, e))?;
+
+    'read: while let Some(batch) = batches.next().await {
+        let batch = batch.map_err(|e| CliError::op(op, e))?;
+        for record in &batch.records {
+            if record.seq_num >= range.end {
+                break 'read;
+            }
+            hash_record(hasher, &record.headers, &record.body);
+            records += 1;
+            bytes += record.metered_bytes() as u64;
+        }
+    }
+    Ok((records, bytes))
+}
+
+/// Feed a record's headers and body into `hasher`, length-prefixing each field
+/// so that different records can never produce the same input.
+fn hash_record(hasher: &mut blake3::Hasher, headers: &[Header], body: &[u8]) {
+    hasher.update(&(headers.len() as u64).to_be_bytes());
+    for header in headers {
+        for field in [&header.name, &header.value] {
+            hasher.update(&(field.len() as u64).to_be_bytes());
+            hasher.update(field);
+        }
+    }
+    hasher.update(&(body.len() as u64).to_be_bytes());
+    hasher.update(body);
+}
+
+/// Records as they were sent by `append`, for checking against what is read back.
+#[derive(Default)]
+pub struct SentRecords {
+    records: u64,
+    hasher: blake3::Hasher,
+}
+
+impl SentRecords {
+    pub fn update(&mut self, record: &AppendRecord) {
+        self.records += 1;
+        hash_record(&mut self.hasher, record.headers(), record.body());
+    }
+
+    /// Check `records` read back, whose contents were fed into `read_hasher`, against what was
+    /// sent. Returns the number of records verified.
+    fn check(
+        self,
+        records: u64,
+        read_hasher: &blake3::Hasher,
+        mode: VerifyMode,
+    ) -> Result<u64, CliError> {
+        if records != self.records {
+            return Err(CliError::AppendVerification(format!(
+                "appended {} records but read back {records}",
+                self.records
+            )));
+        }
+        if mode == VerifyMode::Hash && read_hasher.finalize() != self.hasher.finalize() {
+            return Err(CliError::AppendVerification(
+                "records read back do not match the records appended".to_owned(),
+            ));
+        }
+        Ok(records)
+    }
+}
+
+/// Read back the sequence number `ranges` an append was acknowledged for, and check that they
+/// hold the `sent` records. Returns the number of records verified.
+pub async fn verify_append(
+    s2: &S2,
+    uri: S2BasinAndStreamUri,
+    encryption_key: Option<&EncryptionKey>,
+    ranges: &[Range<u64>],
+    sent: SentRecords,
+    mode: VerifyMode,
+) -> Result<u64, CliError> {
+    let stream = stream_with_encryption(s2, uri, encryption_key);
+    let mut hasher = blake3::Hasher::new();
+    let mut records = 0;
+    for range in ranges {
+        let (range_records, _) =
+            hash_range(&stream, range.clone(), &mut hasher, OpKind::Read).await?;
+        records += range_records;
+    }
+    sent.check(records, &hasher, mode)
+}
+
 async fn append_command(
     stream: &S2Stream,
     command: CommandRecord,
@@ -716,3 +1153,82 @@ fn time_range_and_interval(
     }
     range
 }
+
+#[cfg(test)]
+mod tests {
+    use s2_sdk::types::{AppendRecord, Header};
+
+    use super::{SentRecords, hash_record};
+    use crate::{error::CliError, types::VerifyMode};
+
+    fn sent(records: &[AppendRecord]) -> SentRecords {
+        let mut sent = SentRecords::default();
+        for record in records {
+            sent.update(record);
+        }
+        sent
+    }
+
+    fn read_back(records: Vec<(Vec<Header>, &str)>) -> blake3::Hasher {
+        let mut hasher = blake3::Hasher::new();
+        for (headers, body) in records {
+            hash_record(&mut hasher, &headers, body.as_bytes());
+        }
+        hasher
+    }
+
+    fn records() -> Vec<AppendRecord> {
+        vec![
+            AppendRecord::new("first")
+                .unwrap()
+                .with_headers([Header::new("k", "v")])
+                .unwrap(),
+            AppendRecord::new("second").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn verify_passes_when_records_read_back_match() {
+        let hasher = read_back(vec![
+            (vec![Header::new("k", "v")], "first"),
+            (vec![], "second"),
+        ]);
+        for mode in [VerifyMode::Count, VerifyMode::Hash] {
+            assert_eq!(sent(&records()).check(2, &hasher, mode).unwrap(), 2);
+        }
+    }
+
+    #[test]
+    fn verify_fails_when_count_differs() {
+        let hasher = read_back(vec![(vec![Header::new("k", "v")], "first")]);
+        let err = sent(&records())
+            .check(1, &hasher, VerifyMode::Count)
+            .unwrap_err();
+        let CliError::AppendVerification(msg) = err else {
+            panic!("expected verification error, got {err:?}");
+        };
+        assert_eq!(msg, "appended 2 records but read back 1");
+    }
+
+    #[test]
+    fn verify_hash_fails_when_contents_differ() {
+        // Moving the header onto the other record must not produce the same hash.
+        let hasher = read_back(vec![
+            (vec![], "first"),
+            (vec![Header::new("k", "v")], "second"),
+        ]);
+        assert_eq!(
+            sent(&records())
+                .check(2, &hasher, VerifyMode::Count)
+                .unwrap(),
+            2
+        );
+        let err = sent(&records())
+            .check(2, &hasher, VerifyMode::Hash)
+            .unwrap_err();
+        assert!(
+            matches!(err, CliError::AppendVerification(_)),
+            "expected verification error, got {err:?}"
+        );
+    }
+}
@@ -1,5 +1,15 @@
-use std::{io, io::BufRead, path::PathBuf, pin::Pin};
+use std::{
+    borrow::Cow,
+    io,
+    io::BufRead,
+    path::{Path, PathBuf},
+    pin::Pin,
+    str::FromStr,
+    task::{Context, Poll, ready},
+    time::{Duration, Instant},
+};
 
+use bytesize::ByteSize;
 use clap::ValueEnum;
 use futures::Stream;
 use s2_sdk::types::{AppendRecord, SequencedRecord};
@@ -23,6 +33,7 @@ pub enum RecordFormat {
     Text,
     /// JSON format with UTF-8 headers and body.
     /// If the data is not valid UTF-8, this will be a lossy decoding.
+    /// Like all JSON formats, each record is written as a minified object on a single line.
     #[clap(alias = "raw")]
     Json,
     /// JSON format with headers and body encoded as Base64.
@@ -36,11 +47,122 @@ pub enum RecordsIn {
     Stdin,
 }
 
+/// Where `--seq-num-header` places a record's position in text output.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum SeqNumHeader {
+    /// `{seq_num}\t{timestamp}\t{body}`
+    #[default]
+    Prefix,
+    /// `{body}\t{seq_num}\t{timestamp}`
+    Suffix,
+}
+
+/// Which headers of a read record are written out.
+#[derive(Debug, Clone, Default)]
+pub enum HeaderFilter {
+    #[default]
+    All,
+    Only(Vec<String>),
+    Exclude(Vec<String>),
+}
+
+impl HeaderFilter {
+    pub fn new(only: Option<Vec<String>>, exclude: Option<Vec<String>>) -> Self {
+        match (only, exclude) {
+            (Some(names), _) => Self::Only(names),
+            (None, Some(names)) => Self::Exclude(names),
+            (None, None) => Self::All,
+        }
+    }
+
+    /// The record with its headers filtered. Command records are left intact.
+    pub fn apply<'a>(&self, record: &'a SequencedRecord) -> Cow<'a, SequencedRecord> {
+        let names = match self {
+            Self::All => return Cow::Borrowed(record),
+            _ if record.is_command_record() => return Cow::Borrowed(record),
+            Self::Only(names) | Self::Exclude(names) => names,
+        };
+        let keep = matches!(self, Self::Only(_));
+        let mut record = record.clone();
+        record
+            .headers
+            .retain(|header| names.iter().any(|name| header.name == name.as_bytes()) == keep);
+        Cow::Owned(record)
+    }
+}
+
+/// Order in which files from an input directory are appended.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum InputSort {
+    /// Lexicographic order of file names.
+    #[default]
+    Name,
+    /// Oldest modification time first.
+    Modified,
+}
+
+/// Regular files directly within `dir`, in the requested order.
+pub async fn input_dir_files(dir: &Path, sort: InputSort) -> io::Result<Vec<PathBuf>> {
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    let mut files = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        if metadata.is_file() {
+            files.push((entry.path(), metadata.modified()?));
+        }
+    }
+    match sort {
+        InputSort::Name => files.sort_by(|(a, _), (b, _)| a.file_name().cmp(&b.file_name())),
+        InputSort::Modified => files.sort_by(|(a, a_mtime), (b, b_mtime)| {
+            a_mtime
+                .cmp(b_mtime)
+                .then_with(|| a.file_name().cmp(&b.file_name()))
+        }),
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
 /// Sink for records in a read session.
 #[derive(Debug, Clone)]
 pub enum RecordsOut {
     File(PathBuf),
     Stdout,
+    /// Sequentially numbered files in a directory, optionally rotated.
+    Dir {
+        dir: PathBuf,
+        rotate: Option<Rotate>,
+    },
+}
+
+/// When `--output-dir` rolls over to a new file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotate {
+    /// Once the current file holds at least this many bytes.
+    Size(ByteSize),
+    /// Once the current file has been open for this long.
+    Age(Duration),
+}
+
+impl FromStr for Rotate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Units like `m` could be read as either, so sizes must spell out bytes (`B`).
+        let is_size = s.ends_with(['B', 'b']) || s.bytes().all(|b| b.is_ascii_digit());
+        let rotate = if is_size {
+            s.parse::<ByteSize>().map(Self::Size)
+        } else {
+            s.parse::<humantime::Duration>()
+                .map(|age| Self::Age(*age))
+                .map_err(|e| e.to_string())
+        }
+        .map_err(|_| format!("expected a size like `100MiB` or a duration like `1h`, got {s:?}"))?;
+        match rotate {
+            Self::Size(size) if size.as_u64() == 0 => Err("rotation size must be non-zero".into()),
+            Self::Age(age) if age.is_zero() => Err("rotation interval must be non-zero".into()),
+            rotate => Ok(rotate),
+        }
+    }
 }
 
 impl RecordsIn {
@@ -77,6 +199,113 @@ impl RecordsOut {
                 trace!("stdout writer");
                 Ok(Box::new(BufWriter::new(tokio::io::stdout())))
             }
+            RecordsOut::Dir { dir, rotate } => {
+                trace!(?dir, ?rotate, "opening rotating writer");
+                Ok(Box::new(
+                    RotatingWriter::create(dir.clone(), *rotate).await?,
+                ))
+            }
+        }
+    }
+}
+
+/// Writes into sequentially numbered files in a directory, continuing after any files already
+/// there.
+///
+/// Rotation is only checked on flush, so that a record written between flushes is never split
+/// across files. Files are opened on the first write after a rotation, so that no empty file is
+/// left behind.
+struct RotatingWriter {
+    dir: PathBuf,
+    rotate: Option<Rotate>,
+    next_index: u64,
+    file: Option<BufWriter<File>>,
+    file_bytes: u64,
+    file_opened_at: Instant,
+}
+
+impl RotatingWriter {
+    async fn create(dir: PathBuf, rotate: Option<Rotate>) -> io::Result<Self> {
+        tokio::fs::create_dir_all(&dir).await?;
+        let mut next_index = 0;
+        let mut entries = tokio::fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if let Some(index) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<u64>().ok())
+            {
+                next_index = next_index.max(index + 1);
+            }
+        }
+        Ok(Self {
+            dir,
+            rotate,
+            next_index,
+            file: None,
+            file_bytes: 0,
+            file_opened_at: Instant::now(),
+        })
+    }
+
+    /// The current file, opening the next one if there is none.
+    fn file(&mut self) -> io::Result<&mut BufWriter<File>> {
+        if self.file.is_none() {
+            let path = self.dir.join(format!("{:08}", self.next_index));
+            trace!(?path, "opening output file");
+            // Opening a file is quick enough not to bother with a blocking task.
+            let file = std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)?;
+            self.file = Some(BufWriter::new(File::from_std(file)));
+            self.next_index += 1;
+            self.file_bytes = 0;
+            self.file_opened_at = Instant::now();
+        }
+        Ok(self.file.as_mut().expect("file is open"))
+    }
+
+    fn rotation_due(&self) -> bool {
+        if self.file.is_none() {
+            return false;
+        }
+        match self.rotate {
+            Some(Rotate::Size(size)) => self.file_bytes >= size.as_u64(),
+            Some(Rotate::Age(age)) => self.file_bytes > 0 && self.file_opened_at.elapsed() >= age,
+            None => false,
+        }
+    }
+}
+
+impl AsyncWrite for RotatingWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let written = ready!(Pin::new(self.file()?).poll_write(cx, buf))?;
+        self.file_bytes += written as u64;
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if let Some(file) = self.file.as_mut() {
+            ready!(Pin::new(file).poll_flush(cx))?;
+        }
+        if self.rotation_due() {
+            self.file = None;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.file.as_mut() {
+            Some(file) => Pin::new(file).poll_shutdown(cx),
+            None => Poll::Ready(Ok(())),
         }
     }
 }
@@ -128,8 +357,10 @@ pub trait RecordWriter {
 }
 
 pub use body::TextFormatter;
+pub use json::embedded_body;
 pub type JsonFormatter = json::Formatter<false>;
 pub type JsonBase64Formatter = json::Formatter<true>;
+pub type FlatJsonFormatter = json::FlatFormatter;
 
 mod body {
     use std::{
@@ -142,10 +373,28 @@ mod body {
     use s2_sdk::types::{AppendRecord, SequencedRecord};
     use tokio::io::{AsyncWrite, AsyncWriteExt};
 
-    use super::{RecordParseError, RecordParser, RecordWriter};
+    use super::{RecordParseError, RecordParser, RecordWriter, SeqNumHeader};
 
     pub struct TextFormatter;
 
+    impl TextFormatter {
+        /// Write the record body along with its `seq_num` and `timestamp`,
+        /// as tab-separated columns.
+        pub async fn write_record_with_position(
+            record: &SequencedRecord,
+            writer: &mut (impl AsyncWrite + Unpin),
+            placement: SeqNumHeader,
+        ) -> io::Result<()> {
+            let position = format!("{}\t{}", record.seq_num, record.timestamp);
+            let body = String::from_utf8_lossy(&record.body);
+            let line = match placement {
+                SeqNumHeader::Prefix => format!("{position}\t{body}"),
+                SeqNumHeader::Suffix => format!("{body}\t{position}"),
+            };
+            writer.write_all(line.as_bytes()).await
+        }
+    }
+
     impl RecordWriter for TextFormatter {
         async fn write_record(
             record: &SequencedRecord,
@@ -299,6 +548,14 @@ mod json {
         }
     }
 
+    impl<const BIN_SAFE: bool> Formatter<BIN_SAFE> {
+        /// The JSON value written for a record.
+        pub fn to_value(record: &SequencedRecord) -> serde_json::Value {
+            let record: SerializableSequencedRecord<BIN_SAFE> = record.into();
+            serde_json::to_value(record).expect("serializable record")
+        }
+    }
+
     impl<const BIN_SAFE: bool> RecordWriter for Formatter<BIN_SAFE> {
         async fn write_record(
             record: &SequencedRecord,
@@ -310,6 +567,57 @@ mod json {
         }
     }
 
+    /// Prefix for header fields in flat JSON output, so they cannot collide with
+    /// `seq_num`, `timestamp` or `body`.
+    const FLAT_HEADER_PREFIX: &str = "header.";
+
+    /// Writes each record as a flat JSON object, with headers as top-level
+    /// `header.{name}` fields. Repeated header names are collected into an array.
+    pub struct FlatFormatter;
+
+    impl FlatFormatter {
+        /// The JSON value written for a record.
+        pub fn to_value(record: &SequencedRecord) -> serde_json::Value {
+            let mut object = serde_json::Map::new();
+            object.insert("seq_num".to_owned(), record.seq_num.into());
+            object.insert("timestamp".to_owned(), record.timestamp.into());
+            for header in &record.headers {
+                let name = format!(
+                    "{FLAT_HEADER_PREFIX}{}",
+                    String::from_utf8_lossy(&header.name)
+                );
+                let value = serde_json::Value::from(String::from_utf8_lossy(&header.value));
+                match object.get_mut(&name) {
+                    Some(serde_json::Value::Array(values)) => values.push(value),
+                    Some(existing) => *existing = vec![existing.take(), value].into(),
+                    None => {
+                        object.insert(name, value);
+                    }
+                }
+            }
+            object.insert("body".to_owned(), embedded_body(&record.body));
+            object.into()
+        }
+    }
+
+    /// JSON object and array bodies are embedded as-is, anything else as a string.
+    pub fn embedded_body(body: &[u8]) -> serde_json::Value {
+        match serde_json::from_slice(body) {
+            Ok(value @ (serde_json::Value::Object(_) | serde_json::Value::Array(_))) => value,
+            _ => String::from_utf8_lossy(body).into(),
+        }
+    }
+
+    impl RecordWriter for FlatFormatter {
+        async fn write_record(
+            record: &SequencedRecord,
+            writer: &mut (impl AsyncWrite + Unpin),
+        ) -> io::Result<()> {
+            let s = serde_json::to_string(&Self::to_value(record)).map_err(io::Error::other)?;
+            writer.write_all(s.as_bytes()).await
+        }
+    }
+
     impl<const BIN_SAFE: bool, I> RecordParser<I> for Formatter<BIN_SAFE>
     where
         I: Stream<Item = io::Result<String>> + Send + Unpin,
@@ -468,6 +776,188 @@ mod tests {
         assert!(stream.next().await.is_none());
     }
 
+    // -- input_dir_files --
+
+    #[tokio::test]
+    async fn input_dir_files_sorted_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.jsonl", "a.jsonl", "c.jsonl"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+
+        let files = input_dir_files(dir.path(), InputSort::Name).await.unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, ["a.jsonl", "b.jsonl", "c.jsonl"]);
+    }
+
+    // -- RecordsOut::Dir --
+
+    #[test]
+    fn rotate_parses_sizes_and_durations() {
+        assert_eq!(
+            "100MiB".parse::<Rotate>().unwrap(),
+            Rotate::Size(ByteSize::mib(100))
+        );
+        assert_eq!(
+            "1h".parse::<Rotate>().unwrap(),
+            Rotate::Age(Duration::from_secs(3600))
+        );
+        assert!("0B".parse::<Rotate>().is_err());
+        assert!("soon".parse::<Rotate>().is_err());
+    }
+
+    #[tokio::test]
+    async fn output_dir_rotates_by_size_on_flush() {
+        use tokio::io::AsyncWriteExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("00000004"), "earlier run").unwrap();
+        let out = RecordsOut::Dir {
+            dir: dir.path().to_owned(),
+            rotate: Some(Rotate::Size(ByteSize::b(4))),
+        };
+        let mut writer = out.writer().await.unwrap();
+        writer.write_all(b"ab").await.unwrap();
+        writer.flush().await.unwrap();
+        writer.write_all(b"cdef").await.unwrap();
+        writer.flush().await.unwrap();
+        writer.write_all(b"ghij").await.unwrap();
+        writer.flush().await.unwrap();
+        writer.shutdown().await.unwrap();
+
+        let read = |name: &str| std::fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read("00000004"), "earlier run");
+        assert_eq!(read("00000005"), "abcdef");
+        assert_eq!(read("00000006"), "ghij");
+        assert!(!dir.path().join("00000007").exists());
+    }
+
+    #[tokio::test]
+    async fn text_write_record_with_position() {
+        let record = sequenced_record(42, 1000, vec![], Bytes::from_static(b"hello"));
+
+        let mut out = Vec::new();
+        TextFormatter::write_record_with_position(&record, &mut out, SeqNumHeader::Prefix)
+            .await
+            .unwrap();
+        assert_eq!(out, b"42\t1000\thello");
+
+        let mut out = Vec::new();
+        TextFormatter::write_record_with_position(&record, &mut out, SeqNumHeader::Suffix)
+            .await
+            .unwrap();
+        assert_eq!(out, b"hello\t42\t1000");
+    }
+
+    // -- HeaderFilter --
+
+    #[test]
+    fn header_filter() {
+        let record = sequenced_record(
+            0,
+            0,
+            vec![
+                Header::new("a", "1"),
+                Header::new("b", "2"),
+                Header::new("c", "3"),
+            ],
+            Bytes::new(),
+        );
+        let names = |filter: HeaderFilter| -> Vec<Bytes> {
+            filter
+                .apply(&record)
+                .headers
+                .iter()
+                .map(|h| h.name.clone())
+                .collect()
+        };
+
+        assert_eq!(names(HeaderFilter::All), ["a", "b", "c"]);
+        assert_eq!(
+            names(HeaderFilter::new(Some(vec!["a".into(), "c".into()]), None)),
+            ["a", "c"]
+        );
+        assert_eq!(
+            names(HeaderFilter::new(None, Some(vec!["a".into()]))),
+            ["b", "c"]
+        );
+
+        let command = sequenced_record(0, 0, vec![Header::new("", "fence")], Bytes::new());
+        let filtered = HeaderFilter::Only(vec!["a".into()]).apply(&command);
+        assert!(filtered.is_command_record());
+    }
+
+    // -- FlatJsonFormatter: write_record --
+
+    #[tokio::test]
+    async fn flat_json_write_record() {
+        let record = sequenced_record(
+            7,
+            1000,
+            vec![
+                Header::new("level", "info"),
+                Header::new("tag", "a"),
+                Header::new("tag", "b"),
+                Header::new("seq_num", "shadowed"),
+            ],
+            Bytes::from_static(br#"{"msg":"hi"}"#),
+        );
+
+        let mut out = Vec::new();
+        FlatJsonFormatter::write_record(&record, &mut out)
+            .await
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "seq_num": 7,
+                "timestamp": 1000,
+                "header.level": "info",
+                "header.tag": ["a", "b"],
+                "header.seq_num": "shadowed",
+                "body": {"msg": "hi"},
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn flat_json_write_record_raw_body() {
+        let record = sequenced_record(0, 0, vec![], Bytes::from_static(b"42"));
+
+        let mut out = Vec::new();
+        FlatJsonFormatter::write_record(&record, &mut out)
+            .await
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value["body"], "42");
+    }
+
+    // -- JsonFormatter: write_record --
+
+    #[tokio::test]
+    async fn json_write_record_is_single_line() {
+        let record = sequenced_record(
+            3,
+            5,
+            vec![Header::new("k", "v")],
+            Bytes::from_static(b"a\nb"),
+        );
+
+        let mut out = Vec::new();
+        JsonFormatter::write_record(&record, &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"{"seq_num":3,"timestamp":5,"headers":[["k","v"]],"body":"a\nb"}"#
+        );
+    }
+
     // -- JsonFormatter: parse_records --
 
     #[tokio::test]
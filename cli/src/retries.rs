@@ -0,0 +1,76 @@
+//! Reporting of SDK retry attempts for `--verbose-retries`.
+
+use std::{fmt, sync::OnceLock};
+
+use colored::Colorize;
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{
+    Layer,
+    filter::{Filtered, Targets},
+    layer::Context,
+};
+
+use crate::error::OpKind;
+
+static OP_KIND: OnceLock<OpKind> = OnceLock::new();
+
+/// Record the operation the CLI is running, so that retries can be attributed to it.
+///
+/// Retries may happen on tasks spawned by the SDK, outside of any span the CLI
+/// controls, hence the process-wide slot.
+pub fn set_op_kind(op: OpKind) {
+    let _ = OP_KIND.set(op);
+}
+
+/// Tracing layer that prints each retry attempt made by the SDK to stderr.
+pub struct RetryLogLayer;
+
+impl RetryLogLayer {
+    /// Layer that only sees the SDK's debug events, regardless of `RUST_LOG`.
+    pub fn filtered<S: Subscriber>() -> Filtered<Self, Targets, S> {
+        Self.with_filter(Targets::new().with_target("s2_sdk", Level::DEBUG))
+    }
+}
+
+impl<S: Subscriber> Layer<S> for RetryLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut retry = RetryEvent::default();
+        event.record(&mut retry);
+        if !retry.message.starts_with("retrying") {
+            return;
+        }
+        let op = OP_KIND
+            .get()
+            .map(|op| op.as_ref().to_lowercase())
+            .unwrap_or_else(|| "request".to_owned());
+        eprintln!(
+            "{} {}: {} in {} after error: {}",
+            "↻".yellow().bold(),
+            op.bold(),
+            retry.message,
+            retry.backoff.as_deref().unwrap_or("?").cyan(),
+            retry.err.as_deref().unwrap_or("unknown").red(),
+        );
+    }
+}
+
+#[derive(Default)]
+struct RetryEvent {
+    message: String,
+    backoff: Option<String>,
+    err: Option<String>,
+}
+
+impl Visit for RetryEvent {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{value:?}"),
+            "backoff" => self.backoff = Some(format!("{value:?}")),
+            "err" => self.err = Some(format!("{value:?}")),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,539 @@
+//! Minimal jq-style expressions for `s2 read --transform`.
+
+use std::{
+    cmp::Ordering,
+    iter::Peekable,
+    str::{CharIndices, FromStr},
+};
+
+use serde_json::{Map, Number, Value};
+
+use crate::error::TransformParseError;
+
+/// A filter applied to the JSON representation of each record.
+///
+/// Supports a subset of jq: paths (`.`, `.a.b`, `."a b"`, `.[0]`, `.["a"]`),
+/// literals, object and array construction (`{id: .seq_num, body}`, `[.a, .b]`),
+/// comparisons, `and`, `or`, `not`, `select(...)` and pipes. Each filter produces
+/// at most one output; a filter that produces none or `null` drops the record.
+#[derive(Debug, Clone)]
+pub struct Transform(Expr);
+
+impl Transform {
+    /// Apply the filter, returning `None` if the record should be skipped.
+    pub fn apply(&self, input: &Value) -> Option<Value> {
+        self.0.eval(input).filter(|value| !value.is_null())
+    }
+}
+
+impl FromStr for Transform {
+    type Err = TransformParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            pos: 0,
+            end: s.len(),
+        };
+        let expr = parser.pipe()?;
+        match parser.peek() {
+            None => Ok(Self(expr)),
+            Some(_) => Err(parser.error("unexpected trailing input")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Identity,
+    Literal(Value),
+    Field(Box<Expr>, String),
+    Index(Box<Expr>, i64),
+    Object(Vec<(String, Expr)>),
+    Array(Vec<Expr>),
+    Compare(Box<Expr>, CmpOp, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not,
+    Select(Box<Expr>),
+    Pipe(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, input: &Value) -> Option<Value> {
+        match self {
+            Expr::Identity => Some(input.clone()),
+            Expr::Literal(value) => Some(value.clone()),
+            Expr::Field(base, name) => {
+                let base = base.eval(input)?;
+                Some(base.get(name).cloned().unwrap_or(Value::Null))
+            }
+            Expr::Index(base, index) => {
+                let base = base.eval(input)?;
+                let element = base.as_array().and_then(|values| {
+                    let index = if *index < 0 {
+                        values.len().checked_sub(index.unsigned_abs() as usize)?
+                    } else {
+                        *index as usize
+                    };
+                    values.get(index).cloned()
+                });
+                Some(element.unwrap_or(Value::Null))
+            }
+            Expr::Object(fields) => fields
+                .iter()
+                .map(|(key, expr)| Some((key.clone(), expr.eval(input)?)))
+                .collect::<Option<Map<_, _>>>()
+                .map(Value::Object),
+            Expr::Array(elements) => Some(Value::Array(
+                elements
+                    .iter()
+                    .filter_map(|expr| expr.eval(input))
+                    .collect(),
+            )),
+            Expr::Compare(lhs, op, rhs) => {
+                let (lhs, rhs) = (lhs.eval(input)?, rhs.eval(input)?);
+                let ordering = compare(&lhs, &rhs);
+                Some(Value::Bool(match op {
+                    CmpOp::Eq => ordering == Some(Ordering::Equal),
+                    CmpOp::Ne => ordering != Some(Ordering::Equal),
+                    CmpOp::Lt => ordering == Some(Ordering::Less),
+                    CmpOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                    CmpOp::Gt => ordering == Some(Ordering::Greater),
+                    CmpOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                }))
+            }
+            Expr::And(lhs, rhs) => Some(Value::Bool(
+                is_truthy(&lhs.eval(input)?) && is_truthy(&rhs.eval(input)?),
+            )),
+            Expr::Or(lhs, rhs) => Some(Value::Bool(
+                is_truthy(&lhs.eval(input)?) || is_truthy(&rhs.eval(input)?),
+            )),
+            Expr::Not => Some(Value::Bool(!is_truthy(input))),
+            Expr::Select(cond) => is_truthy(&cond.eval(input)?).then(|| input.clone()),
+            Expr::Pipe(lhs, rhs) => rhs.eval(&lhs.eval(input)?),
+        }
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+/// Numbers compare numerically and strings lexicographically. Other values are
+/// only ever equal to themselves.
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Number(lhs), Value::Number(rhs)) => lhs.as_f64()?.partial_cmp(&rhs.as_f64()?),
+        (Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+        _ => (lhs == rhs).then_some(Ordering::Equal),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Dot,
+    Ident(String),
+    Str(String),
+    Num(Number),
+    Cmp(CmpOp),
+    Pipe,
+    Comma,
+    Colon,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+}
+
+fn tokenize(src: &str) -> Result<Vec<(usize, Token)>, TransformParseError> {
+    let error = |offset, message: &str| TransformParseError {
+        offset,
+        message: message.to_owned(),
+    };
+    let mut tokens = Vec::new();
+    let mut chars = src.char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '.' => Token::Dot,
+            '|' => Token::Pipe,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '=' | '!' | '<' | '>' => {
+                let followed_by_eq = chars.next_if(|&(_, c)| c == '=').is_some();
+                Token::Cmp(match (c, followed_by_eq) {
+                    ('=', true) => CmpOp::Eq,
+                    ('!', true) => CmpOp::Ne,
+                    ('<', false) => CmpOp::Lt,
+                    ('<', true) => CmpOp::Le,
+                    ('>', false) => CmpOp::Gt,
+                    ('>', true) => CmpOp::Ge,
+                    _ => return Err(error(offset, "expected a comparison operator")),
+                })
+            }
+            '"' => {
+                let end =
+                    string_end(&mut chars).ok_or_else(|| error(offset, "unterminated string"))?;
+                let s = serde_json::from_str(&src[offset..end])
+                    .map_err(|e| error(offset, &format!("invalid string: {e}")))?;
+                Token::Str(s)
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut end = offset + c.len_utf8();
+                while let Some((i, c)) = chars.next_if(|&(_, c)| {
+                    c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-')
+                }) {
+                    end = i + c.len_utf8();
+                }
+                let number = serde_json::from_str(&src[offset..end])
+                    .map_err(|_| error(offset, "invalid number"))?;
+                Token::Num(number)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end = offset + c.len_utf8();
+                while let Some((i, c)) =
+                    chars.next_if(|&(_, c)| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    end = i + c.len_utf8();
+                }
+                Token::Ident(src[offset..end].to_owned())
+            }
+            _ => return Err(error(offset, &format!("unexpected character `{c}`"))),
+        };
+        tokens.push((offset, token));
+    }
+    Ok(tokens)
+}
+
+/// Consume a JSON string body after its opening quote, returning the offset
+/// just past the closing quote.
+fn string_end(chars: &mut Peekable<CharIndices<'_>>) -> Option<usize> {
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next()?;
+            }
+            '"' => return Some(offset + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.end, |(offset, _)| *offset)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let matched = self.peek() == Some(token);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let matched = matches!(self.peek(), Some(Token::Ident(ident)) if ident == keyword);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect(&mut self, token: Token, what: &str) -> Result<(), TransformParseError> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {what}")))
+        }
+    }
+
+    fn error(&self, message: &str) -> TransformParseError {
+        TransformParseError {
+            offset: self.offset(),
+            message: message.to_owned(),
+        }
+    }
+
+    /// Whether the current token starts right after the previous one, as in `.foo`.
+    fn is_adjacent(&self) -> bool {
+        match (
+            self.tokens.get(self.pos.wrapping_sub(1)),
+            self.tokens.get(self.pos),
+        ) {
+            (Some((prev, Token::Dot)), Some((offset, _))) => *offset == prev + 1,
+            _ => false,
+        }
+    }
+
+    fn pipe(&mut self) -> Result<Expr, TransformParseError> {
+        let mut expr = self.or()?;
+        while self.eat(&Token::Pipe) {
+            expr = Expr::Pipe(Box::new(expr), Box::new(self.or()?));
+        }
+        Ok(expr)
+    }
+
+    fn or(&mut self) -> Result<Expr, TransformParseError> {
+        let mut expr = self.and()?;
+        while self.eat_keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, TransformParseError> {
+        let mut expr = self.comparison()?;
+        while self.eat_keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.comparison()?));
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, TransformParseError> {
+        let lhs = self.postfix()?;
+        if let Some(Token::Cmp(op)) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = self.postfix()?;
+            return Ok(Expr::Compare(Box::new(lhs), op, Box::new(rhs)));
+        }
+        Ok(lhs)
+    }
+
+    fn postfix(&mut self) -> Result<Expr, TransformParseError> {
+        let mut expr = self.primary()?;
+        loop {
+            if self.peek() == Some(&Token::Dot) {
+                self.pos += 1;
+                if self.peek() == Some(&Token::LBracket) {
+                    continue;
+                }
+                expr = self.field(expr)?;
+            } else if self.eat(&Token::LBracket) {
+                expr = match self.next() {
+                    Some(Token::Num(n)) => match n.as_i64() {
+                        Some(index) => Expr::Index(Box::new(expr), index),
+                        None => return Err(self.error("expected an integer index")),
+                    },
+                    Some(Token::Str(name)) => Expr::Field(Box::new(expr), name),
+                    _ => return Err(self.error("expected an index or a string")),
+                };
+                self.expect(Token::RBracket, "`]`")?;
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    /// Parse the name following a `.`.
+    fn field(&mut self, base: Expr) -> Result<Expr, TransformParseError> {
+        if !self.is_adjacent() {
+            return Err(self.error("expected a field name after `.`"));
+        }
+        match self.next() {
+            Some(Token::Ident(name) | Token::Str(name)) => Ok(Expr::Field(Box::new(base), name)),
+            _ => {
+                self.pos -= 1;
+                Err(self.error("expected a field name after `.`"))
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, TransformParseError> {
+        let Some(token) = self.next() else {
+            return Err(self.error("unexpected end of transform"));
+        };
+        match token {
+            Token::Dot => {
+                if self.is_adjacent()
+                    && matches!(self.peek(), Some(Token::Ident(_) | Token::Str(_)))
+                {
+                    self.field(Expr::Identity)
+                } else {
+                    Ok(Expr::Identity)
+                }
+            }
+            Token::Str(s) => Ok(Expr::Literal(Value::String(s))),
+            Token::Num(n) => Ok(Expr::Literal(Value::Number(n))),
+            Token::Ident(ident) => match ident.as_str() {
+                "null" => Ok(Expr::Literal(Value::Null)),
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "not" => Ok(Expr::Not),
+                "select" => {
+                    self.expect(Token::LParen, "`(` after `select`")?;
+                    let cond = self.pipe()?;
+                    self.expect(Token::RParen, "`)`")?;
+                    Ok(Expr::Select(Box::new(cond)))
+                }
+                _ => {
+                    self.pos -= 1;
+                    Err(self.error(&format!("unknown function `{ident}`")))
+                }
+            },
+            Token::LParen => {
+                let expr = self.pipe()?;
+                self.expect(Token::RParen, "`)`")?;
+                Ok(expr)
+            }
+            Token::LBracket => {
+                let mut elements = Vec::new();
+                if !self.eat(&Token::RBracket) {
+                    loop {
+                        elements.push(self.or()?);
+                        if self.eat(&Token::RBracket) {
+                            break;
+                        }
+                        self.expect(Token::Comma, "`,` or `]`")?;
+                    }
+                }
+                Ok(Expr::Array(elements))
+            }
+            Token::LBrace => {
+                let mut fields = Vec::new();
+                if !self.eat(&Token::RBrace) {
+                    loop {
+                        let key = match self.next() {
+                            Some(Token::Ident(key) | Token::Str(key)) => key,
+                            _ => {
+                                self.pos -= 1;
+                                return Err(self.error("expected an object key"));
+                            }
+                        };
+                        let value = if self.eat(&Token::Colon) {
+                            self.or()?
+                        } else {
+                            Expr::Field(Box::new(Expr::Identity), key.clone())
+                        };
+                        fields.push((key, value));
+                        if self.eat(&Token::RBrace) {
+                            break;
+                        }
+                        self.expect(Token::Comma, "`,` or `}`")?;
+                    }
+                }
+                Ok(Expr::Object(fields))
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.error("unexpected token"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn apply(transform: &str, input: Value) -> Option<Value> {
+        transform.parse::<Transform>().unwrap().apply(&input)
+    }
+
+    #[test]
+    fn paths() {
+        let input = json!({"seq_num": 3, "body": {"a": {"b": [1, 2, 3]}}, "x y": true});
+        assert_eq!(apply(".", input.clone()), Some(input.clone()));
+        assert_eq!(apply(".seq_num", input.clone()), Some(json!(3)));
+        assert_eq!(apply(".body.a.b[1]", input.clone()), Some(json!(2)));
+        assert_eq!(apply(".body.a.b.[-1]", input.clone()), Some(json!(3)));
+        assert_eq!(apply(r#"."x y""#, input.clone()), Some(json!(true)));
+        assert_eq!(apply(r#".["x y"]"#, input.clone()), Some(json!(true)));
+        assert_eq!(
+            apply(".body | .a", input.clone()),
+            Some(json!({"b": [1, 2, 3]}))
+        );
+        assert_eq!(apply(".missing", input), None);
+    }
+
+    #[test]
+    fn construction() {
+        let input = json!({"seq_num": 3, "timestamp": 10, "body": "hi"});
+        assert_eq!(
+            apply("{id: .seq_num, body, \"ts\": .timestamp}", input.clone()),
+            Some(json!({"id": 3, "body": "hi", "ts": 10}))
+        );
+        assert_eq!(apply("[.seq_num, .body]", input), Some(json!([3, "hi"])));
+    }
+
+    #[test]
+    fn select_drops_records() {
+        let transform: Transform = r#"select(.level == "error" and .code >= 500) | .msg"#
+            .parse()
+            .unwrap();
+        assert_eq!(
+            transform.apply(&json!({"level": "error", "code": 503, "msg": "down"})),
+            Some(json!("down"))
+        );
+        assert_eq!(
+            transform.apply(&json!({"level": "error", "code": 404, "msg": "missing"})),
+            None
+        );
+        assert_eq!(
+            transform.apply(&json!({"level": "info", "code": 503.0, "msg": "ok"})),
+            None
+        );
+        assert_eq!(
+            apply("select(.deleted | not)", json!({"deleted": false})),
+            Some(json!({"deleted": false}))
+        );
+        assert_eq!(apply("select(.a or .b)", json!({"a": null})), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (transform, offset) in [
+            (".a ==", 5),
+            (".a |", 4),
+            ("select(.a", 9),
+            ("{a: }", 4),
+            (". foo", 2),
+            ("map(.a)", 0),
+            ("\"unterminated", 0),
+            (".a = 1", 3),
+        ] {
+            let err = transform.parse::<Transform>().unwrap_err();
+            assert_eq!(err.offset, offset, "{transform}: {err}");
+        }
+    }
+}
@@ -19,6 +19,7 @@ use s2_sdk::types::{
 use tokio::sync::mpsc;
 
 use super::{
+    calculate_throughput,
     event::{
         BasinConfigInfo, BenchFinalStats, BenchPhase, BenchRunId, BenchSample, Event,
         StreamConfigInfo,
@@ -34,7 +35,7 @@ use crate::{
         CreateStreamArgs, IssueAccessTokenArgs, ListAccessTokensArgs, ListBasinsArgs,
         ListStreamsArgs, ReadArgs,
     },
-    config::{self, Compression, ConfigKey},
+    config::{self, Compression, ConfigFile, ConfigKey},
     error::CliError,
     ops,
     record_format::{RecordFormat, RecordsOut},
@@ -56,15 +57,6 @@ const SPLASH_DURATION_MS: u64 = 1200;
 /// Target frame interval in milliseconds (~60fps)
 const FRAME_INTERVAL_MS: u64 = 16;
 
-/// Calculate throughput rates from accumulated bytes/records over elapsed time.
-/// Returns (MiB/s, records/s).
-#[inline]
-fn calculate_throughput(bytes: u64, records: u64, elapsed_secs: f64) -> (f64, f64) {
-    let mibps = (bytes as f64) / (1024.0 * 1024.0) / elapsed_secs;
-    let recps = (records as f64) / elapsed_secs;
-    (mibps, recps)
-}
-
 /// Top-level navigation tabs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Tab {
@@ -244,6 +236,7 @@ pub enum CompressionOption {
     None,
     Gzip,
     Zstd,
+    Auto,
 }
 
 impl CompressionOption {
@@ -252,6 +245,7 @@ impl CompressionOption {
             CompressionOption::None => "None",
             CompressionOption::Gzip => "Gzip",
             CompressionOption::Zstd => "Zstd",
+            CompressionOption::Auto => "Auto",
         }
     }
 
@@ -259,15 +253,17 @@ impl CompressionOption {
         match self {
             CompressionOption::None => CompressionOption::Gzip,
             CompressionOption::Gzip => CompressionOption::Zstd,
-            CompressionOption::Zstd => CompressionOption::None,
+            CompressionOption::Zstd => CompressionOption::Auto,
+            CompressionOption::Auto => CompressionOption::None,
         }
     }
 
     pub fn prev(&self) -> Self {
         match self {
-            CompressionOption::None => CompressionOption::Zstd,
+            CompressionOption::None => CompressionOption::Auto,
             CompressionOption::Gzip => CompressionOption::None,
             CompressionOption::Zstd => CompressionOption::Gzip,
+            CompressionOption::Auto => CompressionOption::Zstd,
         }
     }
 }
@@ -810,23 +806,27 @@ fn storage_class_prev(sc: &Option<StorageClass>) -> Option<StorageClass> {
     }
 }
 
-/// Cycle timestamping mode forward: None -> ClientPrefer -> ClientRequire -> Arrival -> None
+/// Cycle timestamping mode forward:
+/// None -> ClientPrefer -> ClientRequire -> Arrival -> ServerPrefer -> None
 fn timestamping_mode_next(tm: &Option<TimestampingMode>) -> Option<TimestampingMode> {
     match tm {
         None => Some(TimestampingMode::ClientPrefer),
         Some(TimestampingMode::ClientPrefer) => Some(TimestampingMode::ClientRequire),
         Some(TimestampingMode::ClientRequire) => Some(TimestampingMode::Arrival),
-        Some(TimestampingMode::Arrival) => None,
+        Some(TimestampingMode::Arrival) => Some(TimestampingMode::ServerPrefer),
+        Some(TimestampingMode::ServerPrefer) => None,
     }
 }
 
-/// Cycle timestamping mode backward: None -> Arrival -> ClientRequire -> ClientPrefer -> None
+/// Cycle timestamping mode backward:
+/// None -> ServerPrefer -> Arrival -> ClientRequire -> ClientPrefer -> None
 fn timestamping_mode_prev(tm: &Option<TimestampingMode>) -> Option<TimestampingMode> {
     match tm {
-        None => Some(TimestampingMode::Arrival),
+        None => Some(TimestampingMode::ServerPrefer),
         Some(TimestampingMode::ClientPrefer) => None,
         Some(TimestampingMode::ClientRequire) => Some(TimestampingMode::ClientPrefer),
         Some(TimestampingMode::Arrival) => Some(TimestampingMode::ClientRequire),
+        Some(TimestampingMode::ServerPrefer) => Some(TimestampingMode::Arrival),
     }
 }
 
@@ -1031,6 +1031,8 @@ pub struct App {
     pub screen: Screen,
     pub tab: Tab,
     pub s2: Option<s2_sdk::S2>,
+    /// CLI config file read and written by the settings screens.
+    config_file: ConfigFile,
     pub message: Option<StatusMessage>,
     pub show_help: bool,
     pub input_mode: InputMode,
@@ -1128,6 +1130,11 @@ fn build_basin_config(
             retention_policy: retention,
             timestamping,
             delete_on_empty,
+            min_retained_records: None,
+            max_append_bytes_per_sec: None,
+            max_append_ops_per_sec: None,
+            max_future_skew_secs: None,
+            compression_hint: None,
         },
         stream_cipher: None,
         create_stream_on_append,
@@ -1177,6 +1184,11 @@ fn build_stream_config(
         retention_policy: retention,
         timestamping,
         delete_on_empty,
+        min_retained_records: None,
+        max_append_bytes_per_sec: None,
+        max_append_ops_per_sec: None,
+        max_future_skew_secs: None,
+        compression_hint: None,
     }
 }
 
@@ -1208,7 +1220,7 @@ fn build_stream_reconfiguration(
 }
 
 impl App {
-    pub fn new(s2: Option<s2_sdk::S2>) -> Self {
+    pub fn new(s2: Option<s2_sdk::S2>, config_file: ConfigFile) -> Self {
         let screen = if s2.is_some() {
             Screen::Splash
         } else {
@@ -1218,6 +1230,7 @@ impl App {
             screen,
             tab: Tab::Basins,
             s2,
+            config_file,
             message: None,
             show_help: false,
             input_mode: InputMode::Normal,
@@ -1258,10 +1271,10 @@ impl App {
     }
 
     /// Load settings from config file
-    fn load_settings_state() -> SettingsState {
-        let file_config = config::load_config_file().unwrap_or_default();
+    fn load_settings_state(config_file: &ConfigFile) -> SettingsState {
+        let file_config = config::load_config_file(config_file).unwrap_or_default();
 
-        let env_config = config::load_cli_config().unwrap_or_default();
+        let env_config = config::load_cli_config(config_file).unwrap_or_default();
         let access_token = file_config
             .access_token
             .clone()
@@ -1280,6 +1293,7 @@ impl App {
             compression: match file_config.compression {
                 Some(Compression::Gzip) => CompressionOption::Gzip,
                 Some(Compression::Zstd) => CompressionOption::Zstd,
+                Some(Compression::Auto) => CompressionOption::Auto,
                 None => CompressionOption::None,
             },
             selected: 0,
@@ -1294,8 +1308,11 @@ impl App {
     }
 
     /// Save settings to config file
-    fn save_settings_static(state: &SettingsState) -> Result<(), CliError> {
-        let mut cli_config = config::load_config_file().unwrap_or_default();
+    fn save_settings_static(
+        config_file: &ConfigFile,
+        state: &SettingsState,
+    ) -> Result<(), CliError> {
+        let mut cli_config = config::load_config_file(config_file).unwrap_or_default();
         if state.access_token.is_empty() {
             cli_config.unset(ConfigKey::AccessToken);
         } else {
@@ -1338,9 +1355,14 @@ impl App {
                     .set(ConfigKey::Compression, "zstd".to_string())
                     .map_err(CliError::Config)?;
             }
+            CompressionOption::Auto => {
+                cli_config
+                    .set(ConfigKey::Compression, "auto".to_string())
+                    .map_err(CliError::Config)?;
+            }
         }
 
-        config::save_cli_config(&cli_config).map_err(CliError::Config)?;
+        config::save_cli_config(config_file, &cli_config).map_err(CliError::Config)?;
         Ok(())
     }
 
@@ -4377,6 +4399,9 @@ impl App {
                 start_after: start_after.map(|n| n.to_string().parse().unwrap()),
                 limit: Some(100),
                 no_auto_paginate: true,
+                order: None,
+                start_after_created_at: None,
+                state: None,
             };
             let event = match ops::list_streams(&s2, args).await {
                 Ok((streams, has_more)) => {
@@ -4565,6 +4590,9 @@ impl App {
                         start_after: None,
                         limit: Some(100),
                         no_auto_paginate: true,
+                        order: None,
+                        start_after_created_at: None,
+                        state: None,
                     };
                     if let Ok((streams, has_more)) = ops::list_streams(&s2, args).await {
                         let _ = tx_refresh.send(Event::StreamsLoaded(Ok((streams, has_more))));
@@ -4605,6 +4633,9 @@ impl App {
                         start_after: None,
                         limit: Some(100),
                         no_auto_paginate: true,
+                        order: None,
+                        start_after_created_at: None,
+                        state: None,
                     };
                     if let Ok((streams, has_more)) = ops::list_streams(&s2, args).await {
                         let _ = tx_refresh.send(Event::StreamsLoaded(Ok((streams, has_more))));
@@ -4659,12 +4690,22 @@ impl App {
                 timestamp: None,
                 ago: None,
                 tail_offset: None, // Defaults to TailOffset(0) in ops::read
+                resume_from_checkpoint_or_tail: None,
                 count: None,
                 bytes: None,
                 clamp: true,
                 until: None,
                 format: RecordFormat::default(),
+                commands_only: false,
+                count_only: false,
+                seq_num_header: None,
+                jsonl_flat: false,
+                transform: None,
+                only_headers: None,
+                exclude_headers: None,
                 output: RecordsOut::Stdout,
+                output_dir: None,
+                rotate: None,
                 encryption_key: Default::default(),
             };
 
@@ -4732,12 +4773,22 @@ impl App {
                 timestamp: None,
                 ago: None,
                 tail_offset: None,
+                resume_from_checkpoint_or_tail: None,
                 count: None,
                 bytes: None,
                 clamp: true,
                 until: None,
                 format: RecordFormat::default(),
+                commands_only: false,
+                count_only: false,
+                seq_num_header: None,
+                jsonl_flat: false,
+                transform: None,
+                only_headers: None,
+                exclude_headers: None,
                 output: RecordsOut::Stdout,
+                output_dir: None,
+                rotate: None,
                 encryption_key: Default::default(),
             };
 
@@ -4896,12 +4947,22 @@ impl App {
                 timestamp,
                 ago,
                 tail_offset,
+                resume_from_checkpoint_or_tail: None,
                 count,
                 bytes,
                 clamp,
                 until,
                 format: record_format,
+                commands_only: false,
+                count_only: false,
+                seq_num_header: None,
+                jsonl_flat: false,
+                transform: None,
+                only_headers: None,
+                exclude_headers: None,
                 output: output.clone(),
+                output_dir: None,
+                rotate: None,
                 encryption_key: Default::default(),
             };
 
@@ -5197,6 +5258,9 @@ impl App {
                         start_after: None,
                         limit: Some(100),
                         no_auto_paginate: true,
+                        order: None,
+                        start_after_created_at: None,
+                        state: None,
                     };
                     if let Ok((streams, has_more)) = ops::list_streams(&s2, args).await {
                         let _ = tx_refresh.send(Event::StreamsLoaded(Ok((streams, has_more))));
@@ -5904,7 +5968,7 @@ impl App {
             }
             Tab::AccessTokens => {
                 self.tab = Tab::Settings;
-                self.screen = Screen::Settings(Self::load_settings_state());
+                self.screen = Screen::Settings(Self::load_settings_state(&self.config_file));
             }
             Tab::Settings => {
                 self.tab = Tab::Basins;
@@ -6052,6 +6116,7 @@ impl App {
                 match Self::create_s2_client(state.access_token.value()) {
                     Ok(s2) => {
                         if let Err(e) = config::set_config_value(
+                            &self.config_file,
                             ConfigKey::AccessToken,
                             state.access_token.value().to_owned(),
                         ) {
@@ -6177,7 +6242,7 @@ impl App {
             KeyCode::Enter if state.selected == 4 => {
                 // Save settings - clone state to avoid borrow issues
                 let state_clone = state.clone();
-                match Self::save_settings_static(&state_clone) {
+                match Self::save_settings_static(&self.config_file, &state_clone) {
                     Err(e) => {
                         state.message = Some(format!("Failed to save: {}", e));
                     }
@@ -6202,7 +6267,7 @@ impl App {
             }
             KeyCode::Char('r') => {
                 // Reload settings from file
-                *state = Self::load_settings_state();
+                *state = Self::load_settings_state(&self.config_file);
                 state.message = Some("Settings reloaded".to_string());
             }
             _ => {}
@@ -6218,6 +6283,8 @@ impl App {
                 start_after: None,
                 limit: Some(100),
                 no_auto_paginate: false,
+                scope_contains: None,
+                op: None,
             };
             let event = match ops::list_access_tokens(&s2, args).await {
                 Ok((tokens, _)) => Event::AccessTokensLoaded(Ok(tokens)),
@@ -6346,6 +6413,7 @@ impl App {
             };
             let args = IssueAccessTokenArgs {
                 id: token_id,
+                template: None,
                 expires_in: expires_in_str.and_then(|s| s.parse().ok()),
                 expires_at: None,
                 auto_prefix_streams,
@@ -6366,6 +6434,7 @@ impl App {
                     .and_then(|s| if s.is_empty() { None } else { s.parse().ok() }),
                 op_group_perms: None,
                 ops: operations,
+                validate_only: false,
             };
 
             match ops::issue_access_token(&s2, args).await {
@@ -6377,6 +6446,8 @@ impl App {
                         start_after: None,
                         limit: Some(100),
                         no_auto_paginate: false,
+                        scope_contains: None,
+                        op: None,
                     };
                     if let Ok((tokens, _)) = ops::list_access_tokens(&s2, list_args).await {
                         let _ = tx_refresh.send(Event::AccessTokensLoaded(Ok(tokens)));
@@ -6414,6 +6485,8 @@ impl App {
                         start_after: None,
                         limit: Some(100),
                         no_auto_paginate: false,
+                        scope_contains: None,
+                        op: None,
                     };
                     if let Ok((tokens, _)) = ops::list_access_tokens(&s2, list_args).await {
                         let _ = tx_refresh.send(Event::AccessTokensLoaded(Ok(tokens)));
@@ -7756,6 +7829,16 @@ mod tests {
 
     use super::*;
 
+    fn new_app() -> App {
+        App::new(
+            None,
+            ConfigFile {
+                path: std::path::PathBuf::from("config.toml"),
+                ignore_unknown: false,
+            },
+        )
+    }
+
     fn key(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::NONE)
     }
@@ -7772,7 +7855,7 @@ mod tests {
 
     #[test]
     fn stale_benchmark_events_do_not_mutate_current_view() {
-        let mut app = App::new(None);
+        let mut app = new_app();
         app.bench_run_id = 2;
         app.screen = Screen::BenchView(BenchViewState::new("benchbasin".parse().unwrap()));
         let Screen::BenchView(state) = &mut app.screen else {
@@ -7808,7 +7891,7 @@ mod tests {
 
     #[test]
     fn benchmark_events_do_not_mutate_config_phase() {
-        let mut app = App::new(None);
+        let mut app = new_app();
         app.bench_run_id = 1;
         app.screen = Screen::BenchView(BenchViewState::new("benchbasin".parse().unwrap()));
 
@@ -7831,7 +7914,7 @@ mod tests {
 
     #[tokio::test]
     async fn stop_benchmark_detaches_task_for_cleanup() {
-        let mut app = App::new(None);
+        let mut app = new_app();
         let stop = Arc::new(AtomicBool::new(false));
         let task_stop = stop.clone();
         let (done_tx, done_rx) = oneshot::channel();
@@ -7856,7 +7939,7 @@ mod tests {
 
     #[test]
     fn setup_screen_multibyte_input() {
-        let mut app = App::new(None);
+        let mut app = new_app();
         app.screen = Screen::Setup(SetupState::default());
         let (tx, _rx) = mpsc::unbounded_channel();
 
@@ -7891,7 +7974,7 @@ mod tests {
 
     #[test]
     fn settings_screen_multibyte_input() {
-        let mut app = App::new(None);
+        let mut app = new_app();
         app.screen = Screen::Settings(SettingsState {
             access_token: TextInput::with_value("tok"),
             account_endpoint: TextInput::with_value(""),
@@ -7919,7 +8002,7 @@ mod tests {
 
     #[test]
     fn input_mode_unfiltered_field_multibyte() {
-        let mut app = App::new(None);
+        let mut app = new_app();
         app.screen = Screen::AccessTokens(AccessTokensState::default());
         app.input_mode = InputMode::IssueAccessToken {
             id: String::new(),
@@ -8014,7 +8097,7 @@ mod tests {
 
     #[test]
     fn reconfigure_stream_uncapped_can_clear_to_inherit() {
-        let mut app = App::new(None);
+        let mut app = new_app();
         app.input_mode = InputMode::ReconfigureStream {
             basin: "test-basin".parse().unwrap(),
             stream: "test-stream".parse().unwrap(),
@@ -8059,7 +8142,7 @@ mod tests {
     fn reconfigure_stream_submit_blocked_until_loaded() {
         // No S2 client: submitting an unloaded form would panic if not gated,
         // and would otherwise clobber the stream's config with form defaults.
-        let mut app = App::new(None);
+        let mut app = new_app();
         app.input_mode = InputMode::ReconfigureStream {
             basin: "test-basin".parse().unwrap(),
             stream: "test-stream".parse().unwrap(),
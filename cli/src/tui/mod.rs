@@ -16,7 +16,7 @@ use crossterm::{
 use ratatui::{Terminal, prelude::CrosstermBackend};
 
 use crate::{
-    config::{load_cli_config, sdk_config},
+    config::{ConfigFile, load_cli_config, sdk_config},
     error::CliError,
 };
 
@@ -27,6 +27,15 @@ pub fn user_agent() -> String {
     format!("s2-tui/{}", env!("CARGO_PKG_VERSION"))
 }
 
+/// Calculate throughput rates from accumulated bytes/records over elapsed time.
+/// Returns (MiB/s, records/s).
+#[inline]
+pub fn calculate_throughput(bytes: u64, records: u64, elapsed_secs: f64) -> (f64, f64) {
+    let mibps = (bytes as f64) / (1024.0 * 1024.0) / elapsed_secs;
+    let recps = (records as f64) / elapsed_secs;
+    (mibps, recps)
+}
+
 fn restore_terminal() {
     let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
     let _ = disable_raw_mode();
@@ -77,10 +86,10 @@ impl Drop for TerminalGuard {
     }
 }
 
-pub async fn run() -> Result<(), CliError> {
+pub async fn run(config_file: ConfigFile) -> Result<(), CliError> {
     // Load config and try to create SDK client
     // If access token is missing, we'll start with Setup screen instead of failing
-    let cli_config = load_cli_config()?;
+    let cli_config = load_cli_config(&config_file)?;
     let s2 = match sdk_config(&cli_config, &user_agent()) {
         Ok(sdk_cfg) => Some(s2_sdk::S2::new(sdk_cfg).map_err(CliError::SdkInit)?),
         Err(_) => None, // No access token - will show setup screen
@@ -92,6 +101,6 @@ pub async fn run() -> Result<(), CliError> {
         .map_err(|e| CliError::RecordReaderInit(format!("terminal setup: {e}")))?;
 
     // Create and run app
-    let app = App::new(s2);
+    let app = App::new(s2, config_file);
     app.run(&mut terminal).await
 }
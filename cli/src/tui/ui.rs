@@ -112,6 +112,7 @@ mod help_text {
     pub const TS_CLIENT_PREFER: &str = "Use client timestamp if provided, else arrival time.";
     pub const TS_CLIENT_REQUIRE: &str = "Require client timestamp, reject if missing.";
     pub const TS_ARRIVAL: &str = "Always use server arrival time.";
+    pub const TS_SERVER_PREFER: &str = "Use arrival time unless client timestamp is close to it.";
 
     // Uncapped - exact from spec
     pub const TS_UNCAPPED: &str = "Allow client timestamps to exceed arrival time.";
@@ -678,6 +679,7 @@ fn draw_settings(f: &mut Frame, area: Rect, state: &SettingsState) {
         CompressionOption::None,
         CompressionOption::Gzip,
         CompressionOption::Zstd,
+        CompressionOption::Auto,
     ];
     let pills: Vec<Span> = options
         .iter()
@@ -1889,7 +1891,11 @@ fn render_area_chart(
         return;
     }
     let chart_min = if min_val > 0.0 { 0.0 } else { min_val };
-    let chart_max = max_val * 1.1; // 10% headroom
+    let chart_max = match unit {
+        // Percentages never exceed full scale, so cap the headroom there
+        s2_sdk::types::MetricUnit::Percent => (max_val * 1.1).clamp(1.0, 100.0),
+        _ => max_val * 1.1, // 10% headroom
+    };
     let chart_range = chart_max - chart_min;
     let values_only: Vec<f64> = values.iter().map(|(_, v)| *v).collect();
     let step = values_only.len() as f64 / width as f64;
@@ -2007,7 +2013,9 @@ fn format_metric_value_f64(value: f64, unit: s2_sdk::types::MetricUnit) -> Strin
     use s2_sdk::types::MetricUnit;
     match unit {
         MetricUnit::Bytes => format_bytes(value as u64),
-        MetricUnit::Operations => format_count(value as u64),
+        MetricUnit::Operations | MetricUnit::Count => format_count(value as u64),
+        MetricUnit::Percent => format!("{value:.1}%"),
+        _ => format!("{value}"),
     }
 }
 
@@ -4521,6 +4529,10 @@ fn draw_input_dialog(
                     "Arrival",
                     matches!(timestamping_mode, Some(TimestampingMode::Arrival)),
                 ),
+                (
+                    "ServerPrefer",
+                    matches!(timestamping_mode, Some(TimestampingMode::ServerPrefer)),
+                ),
             ];
             let ret_opts = [
                 (
@@ -4710,6 +4722,7 @@ fn draw_input_dialog(
                     Some(TimestampingMode::ClientPrefer) => help_text::TS_CLIENT_PREFER,
                     Some(TimestampingMode::ClientRequire) => help_text::TS_CLIENT_REQUIRE,
                     Some(TimestampingMode::Arrival) => help_text::TS_ARRIVAL,
+                    Some(TimestampingMode::ServerPrefer) => help_text::TS_SERVER_PREFER,
                 };
                 lines.push(Line::from(vec![
                     Span::raw("                  "),
@@ -4894,6 +4907,10 @@ fn draw_input_dialog(
                     "Arrival",
                     matches!(timestamping_mode, Some(TimestampingMode::Arrival)),
                 ),
+                (
+                    "ServerPrefer",
+                    matches!(timestamping_mode, Some(TimestampingMode::ServerPrefer)),
+                ),
             ];
             let ret_opts = [
                 (
@@ -5012,6 +5029,7 @@ fn draw_input_dialog(
                     Some(TimestampingMode::ClientPrefer) => help_text::TS_CLIENT_PREFER,
                     Some(TimestampingMode::ClientRequire) => help_text::TS_CLIENT_REQUIRE,
                     Some(TimestampingMode::Arrival) => help_text::TS_ARRIVAL,
+                    Some(TimestampingMode::ServerPrefer) => help_text::TS_SERVER_PREFER,
                 };
                 lines.push(Line::from(vec![
                     Span::raw("                  "),
@@ -5216,6 +5234,10 @@ fn draw_input_dialog(
                     "Arrival",
                     matches!(timestamping_mode, Some(TimestampingMode::Arrival)),
                 ),
+                (
+                    "ServerPrefer",
+                    matches!(timestamping_mode, Some(TimestampingMode::ServerPrefer)),
+                ),
             ];
 
             let mut lines = vec![
@@ -5311,6 +5333,7 @@ fn draw_input_dialog(
                     Some(TimestampingMode::ClientPrefer) => help_text::TS_CLIENT_PREFER,
                     Some(TimestampingMode::ClientRequire) => help_text::TS_CLIENT_REQUIRE,
                     Some(TimestampingMode::Arrival) => help_text::TS_ARRIVAL,
+                    Some(TimestampingMode::ServerPrefer) => help_text::TS_SERVER_PREFER,
                 };
                 lines.push(Line::from(vec![
                     Span::raw("                  "),
@@ -5449,6 +5472,10 @@ fn draw_input_dialog(
                     "Arrival",
                     matches!(timestamping_mode, Some(TimestampingMode::Arrival)),
                 ),
+                (
+                    "ServerPrefer",
+                    matches!(timestamping_mode, Some(TimestampingMode::ServerPrefer)),
+                ),
             ];
 
             let mut lines = vec![];
@@ -5550,6 +5577,7 @@ fn draw_input_dialog(
                     Some(TimestampingMode::ClientPrefer) => help_text::TS_CLIENT_PREFER,
                     Some(TimestampingMode::ClientRequire) => help_text::TS_CLIENT_REQUIRE,
                     Some(TimestampingMode::Arrival) => help_text::TS_ARRIVAL,
+                    Some(TimestampingMode::ServerPrefer) => help_text::TS_SERVER_PREFER,
                 };
                 lines.push(Line::from(vec![
                     Span::raw("                  "),
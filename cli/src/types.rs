@@ -1,6 +1,7 @@
-use std::{str::FromStr, time::Duration};
+use std::{collections::BTreeMap, num::NonZeroU32, str::FromStr, sync::OnceLock, time::Duration};
 
 use clap::{Args, Parser, ValueEnum};
+use s2_common::{NameNormalization, maybe::Maybe};
 use s2_sdk::{
     self as sdk,
     types::{
@@ -12,6 +13,36 @@ use serde::Serialize;
 
 use crate::error::{OpGroupsParseError, S2UriParseError};
 
+/// How names in S2 URIs are treated. Set from `--normalize-names` before arguments are parsed, as
+/// URIs are validated while parsing.
+static NAME_NORMALIZATION: OnceLock<NameNormalization> = OnceLock::new();
+
+pub fn set_name_normalization(normalization: NameNormalization) {
+    let _ = NAME_NORMALIZATION.set(normalization);
+}
+
+fn name_normalization() -> NameNormalization {
+    NAME_NORMALIZATION.get().copied().unwrap_or_default()
+}
+
+/// Basin names can never contain `/` or uppercase letters, so rejecting them up front only makes
+/// the error more specific.
+fn parse_basin_name(s: &str) -> Result<BasinName, S2UriParseError> {
+    s2_common::basin::validate_name(s, name_normalization())
+        .map_err(|e| S2UriParseError::InvalidBasinName(format!("{e}")))
+}
+
+/// Stream names may end with `/`, so they are only changed when normalizing.
+fn parse_stream_name(s: &str) -> Result<StreamName, S2UriParseError> {
+    match name_normalization() {
+        NameNormalization::Reject => s.parse(),
+        NameNormalization::Normalize => {
+            s2_common::stream::validate_name(s, NameNormalization::Normalize)
+        }
+    }
+    .map_err(|e| S2UriParseError::InvalidStreamName(format!("{e}")))
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct S2Uri {
     basin: BasinName,
@@ -35,9 +66,7 @@ impl FromStr for S2Uri {
         };
 
         Ok(S2Uri {
-            basin: basin
-                .parse()
-                .map_err(|e| S2UriParseError::InvalidBasinName(format!("{e}")))?,
+            basin: parse_basin_name(basin)?,
             stream,
         })
     }
@@ -65,11 +94,7 @@ impl FromStr for S2BasinUri {
                 basin: _,
                 stream: Some(_),
             }) => Err(S2UriParseError::UnexpectedStreamName),
-            Err(S2UriParseError::MissingUriScheme) => {
-                Ok(Self(s.parse().map_err(|e| {
-                    S2UriParseError::InvalidBasinName(format!("{e}"))
-                })?))
-            }
+            Err(S2UriParseError::MissingUriScheme) => Ok(Self(parse_basin_name(s)?)),
             Err(other) => Err(other),
         }
     }
@@ -96,9 +121,7 @@ impl FromStr for S2BasinAndMaybeStreamUri {
                 Ok(Self { basin, stream })
             }
             Err(S2UriParseError::MissingUriScheme) => Ok(Self {
-                basin: s
-                    .parse()
-                    .map_err(|e| S2UriParseError::InvalidBasinName(format!("{e}")))?,
+                basin: parse_basin_name(s)?,
                 stream: None,
             }),
             Err(other) => Err(other),
@@ -119,9 +142,7 @@ impl FromStr for S2BasinAndStreamUri {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let S2Uri { basin, stream } = s.parse()?;
         let stream = stream.ok_or(S2UriParseError::MissingStreamName)?;
-        let stream: StreamName = stream
-            .parse()
-            .map_err(|e| S2UriParseError::InvalidStreamName(format!("{e}")))?;
+        let stream = parse_stream_name(&stream)?;
         Ok(Self { basin, stream })
     }
 }
@@ -155,6 +176,22 @@ pub struct StreamConfig {
     #[clap(flatten)]
     /// Delete-on-empty configuration.
     pub delete_on_empty: Option<DeleteOnEmptyConfig>,
+    #[arg(long)]
+    /// Minimum number of records that a trim must leave in a stream.
+    pub min_retained_records: Option<u64>,
+    #[arg(long)]
+    /// Maximum append throughput in metered bytes per second.
+    pub max_append_bytes_per_sec: Option<u64>,
+    #[arg(long)]
+    /// Maximum number of appends per second.
+    pub max_append_ops_per_sec: Option<u64>,
+    #[arg(long)]
+    /// Maximum number of seconds a client-specified record timestamp may be ahead of the arrival
+    /// time.
+    pub max_future_skew_secs: Option<u64>,
+    #[arg(long)]
+    /// Codec the server should use to compress records at rest. Advisory.
+    pub compression_hint: Option<CompressionHint>,
 }
 
 impl StreamConfig {
@@ -164,11 +201,21 @@ impl StreamConfig {
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         } = self;
         storage_class.is_none()
             && retention_policy.is_none()
             && timestamping.is_none()
             && delete_on_empty.is_none()
+            && min_retained_records.is_none()
+            && max_append_bytes_per_sec.is_none()
+            && max_append_ops_per_sec.is_none()
+            && max_future_skew_secs.is_none()
+            && compression_hint.is_none()
     }
 }
 
@@ -181,12 +228,55 @@ pub enum StorageClass {
     Express,
 }
 
+#[derive(ValueEnum, Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompressionHint {
+    None,
+    Zstd,
+    Gzip,
+}
+
 #[derive(ValueEnum, Debug, Clone, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum TimestampingMode {
     ClientPrefer,
     ClientRequire,
     Arrival,
+    ServerPrefer,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum ListStreamsOrder {
+    /// Lexicographic order of stream names.
+    Name,
+    /// Order of stream creation, oldest first.
+    Created,
+}
+
+impl From<ListStreamsOrder> for sdk::types::ListStreamsOrder {
+    fn from(order: ListStreamsOrder) -> Self {
+        match order {
+            ListStreamsOrder::Name => sdk::types::ListStreamsOrder::Name,
+            ListStreamsOrder::Created => sdk::types::ListStreamsOrder::Created,
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum ListStreamsState {
+    /// Streams that are not being deleted.
+    Active,
+    /// Streams whose deletion is pending.
+    Deleting,
+}
+
+impl From<ListStreamsState> for sdk::types::ListStreamsState {
+    fn from(state: ListStreamsState) -> Self {
+        match state {
+            ListStreamsState::Active => sdk::types::ListStreamsState::Active,
+            ListStreamsState::Deleting => sdk::types::ListStreamsState::Deleting,
+        }
+    }
 }
 
 #[derive(Parser, Debug, Clone, Serialize)]
@@ -284,6 +374,21 @@ impl From<StreamConfig> for sdk::types::StreamConfig {
         if let Some(delete_on_empty) = config.delete_on_empty {
             stream_config = stream_config.with_delete_on_empty(delete_on_empty.into());
         }
+        if let Some(min_retained_records) = config.min_retained_records {
+            stream_config = stream_config.with_min_retained_records(min_retained_records);
+        }
+        if let Some(max_append_bytes_per_sec) = config.max_append_bytes_per_sec {
+            stream_config = stream_config.with_max_append_bytes_per_sec(max_append_bytes_per_sec);
+        }
+        if let Some(max_append_ops_per_sec) = config.max_append_ops_per_sec {
+            stream_config = stream_config.with_max_append_ops_per_sec(max_append_ops_per_sec);
+        }
+        if let Some(max_future_skew_secs) = config.max_future_skew_secs {
+            stream_config = stream_config.with_max_future_skew_secs(max_future_skew_secs);
+        }
+        if let Some(compression_hint) = config.compression_hint {
+            stream_config = stream_config.with_compression_hint(compression_hint.into());
+        }
         stream_config
     }
 }
@@ -306,12 +411,33 @@ impl From<sdk::types::StorageClass> for StorageClass {
     }
 }
 
+impl From<CompressionHint> for sdk::types::CompressionHint {
+    fn from(hint: CompressionHint) -> Self {
+        match hint {
+            CompressionHint::None => sdk::types::CompressionHint::None,
+            CompressionHint::Zstd => sdk::types::CompressionHint::Zstd,
+            CompressionHint::Gzip => sdk::types::CompressionHint::Gzip,
+        }
+    }
+}
+
+impl From<sdk::types::CompressionHint> for CompressionHint {
+    fn from(hint: sdk::types::CompressionHint) -> Self {
+        match hint {
+            sdk::types::CompressionHint::None => CompressionHint::None,
+            sdk::types::CompressionHint::Zstd => CompressionHint::Zstd,
+            sdk::types::CompressionHint::Gzip => CompressionHint::Gzip,
+        }
+    }
+}
+
 impl From<TimestampingMode> for sdk::types::TimestampingMode {
     fn from(mode: TimestampingMode) -> Self {
         match mode {
             TimestampingMode::ClientPrefer => sdk::types::TimestampingMode::ClientPrefer,
             TimestampingMode::ClientRequire => sdk::types::TimestampingMode::ClientRequire,
             TimestampingMode::Arrival => sdk::types::TimestampingMode::Arrival,
+            TimestampingMode::ServerPrefer => sdk::types::TimestampingMode::ServerPrefer,
         }
     }
 }
@@ -322,6 +448,7 @@ impl From<sdk::types::TimestampingMode> for TimestampingMode {
             sdk::types::TimestampingMode::ClientPrefer => TimestampingMode::ClientPrefer,
             sdk::types::TimestampingMode::ClientRequire => TimestampingMode::ClientRequire,
             sdk::types::TimestampingMode::Arrival => TimestampingMode::Arrival,
+            sdk::types::TimestampingMode::ServerPrefer => TimestampingMode::ServerPrefer,
         }
     }
 }
@@ -389,30 +516,72 @@ impl From<sdk::types::StreamConfig> for StreamConfig {
             retention_policy: config.retention_policy.map(Into::into),
             timestamping: config.timestamping.map(Into::into),
             delete_on_empty: config.delete_on_empty.map(Into::into),
+            min_retained_records: config.min_retained_records,
+            max_append_bytes_per_sec: config.max_append_bytes_per_sec,
+            max_append_ops_per_sec: config.max_append_ops_per_sec,
+            max_future_skew_secs: config.max_future_skew_secs,
+            compression_hint: config.compression_hint.map(Into::into),
         }
     }
 }
 
-impl From<StreamConfig> for sdk::types::StreamReconfiguration {
-    fn from(config: StreamConfig) -> Self {
-        let mut reconfig = sdk::types::StreamReconfiguration::new();
-        if let Some(storage_class) = config.storage_class {
+impl StreamConfig {
+    /// Reconfiguration that overrides the fields set here and clears every other override, so that
+    /// those fields inherit the basin's default stream config.
+    pub fn into_reset_reconfiguration(self) -> sdk::types::StreamReconfiguration {
+        let timestamping = self.timestamping.clone();
+        let mut reconfig = self.overlay(sdk::types::StreamReconfiguration::reset());
+        if let Some(timestamping) = timestamping {
+            let mut ts_reconfig = sdk::types::TimestampingReconfiguration::new();
+            ts_reconfig.mode = Maybe::Specified(timestamping.timestamping_mode.map(Into::into));
+            ts_reconfig.uncapped = Maybe::Specified(timestamping.timestamping_uncapped);
+            reconfig = reconfig.with_timestamping(ts_reconfig);
+        }
+        reconfig
+    }
+
+    fn overlay(
+        self,
+        mut reconfig: sdk::types::StreamReconfiguration,
+    ) -> sdk::types::StreamReconfiguration {
+        if let Some(storage_class) = self.storage_class {
             reconfig = reconfig.with_storage_class(storage_class.into());
         }
-        if let Some(retention_policy) = config.retention_policy {
+        if let Some(retention_policy) = self.retention_policy {
             reconfig = reconfig.with_retention_policy(retention_policy.into());
         }
-        if let Some(timestamping) = config.timestamping {
+        if let Some(timestamping) = self.timestamping {
             let ts_reconfig = sdk::types::TimestampingReconfiguration::from(timestamping);
             reconfig = reconfig.with_timestamping(ts_reconfig);
         }
-        if let Some(delete_on_empty) = config.delete_on_empty {
+        if let Some(delete_on_empty) = self.delete_on_empty {
             reconfig = reconfig.with_delete_on_empty(delete_on_empty.into());
         }
+        if let Some(min_retained_records) = self.min_retained_records {
+            reconfig = reconfig.with_min_retained_records(min_retained_records);
+        }
+        if let Some(max_append_bytes_per_sec) = self.max_append_bytes_per_sec {
+            reconfig = reconfig.with_max_append_bytes_per_sec(max_append_bytes_per_sec);
+        }
+        if let Some(max_append_ops_per_sec) = self.max_append_ops_per_sec {
+            reconfig = reconfig.with_max_append_ops_per_sec(max_append_ops_per_sec);
+        }
+        if let Some(max_future_skew_secs) = self.max_future_skew_secs {
+            reconfig = reconfig.with_max_future_skew_secs(max_future_skew_secs);
+        }
+        if let Some(compression_hint) = self.compression_hint {
+            reconfig = reconfig.with_compression_hint(compression_hint.into());
+        }
         reconfig
     }
 }
 
+impl From<StreamConfig> for sdk::types::StreamReconfiguration {
+    fn from(config: StreamConfig) -> Self {
+        config.overlay(sdk::types::StreamReconfiguration::new())
+    }
+}
+
 impl From<TimestampingConfig> for sdk::types::TimestampingReconfiguration {
     fn from(config: TimestampingConfig) -> Self {
         let mut result = sdk::types::TimestampingReconfiguration::new();
@@ -561,6 +730,96 @@ impl From<sdk::types::AccessTokenMatcher> for AccessTokenMatcher {
     }
 }
 
+/// Built-in access token scope, written as `{name}:{argument}`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessTokenTemplate {
+    /// `readonly-prefix:{prefix}`: read streams whose names begin with the prefix, in any basin.
+    ReadonlyPrefix(StreamNamePrefix),
+    /// `readwrite-prefix:{prefix}`: read and append to streams whose names begin with the prefix,
+    /// in any basin.
+    ReadwritePrefix(StreamNamePrefix),
+    /// `basin-admin:{basin}`: manage the basin, and read and write all of its streams.
+    BasinAdmin(BasinName),
+}
+
+impl AccessTokenTemplate {
+    const NAMES: &str = "readonly-prefix, readwrite-prefix, basin-admin";
+
+    pub fn basins(&self) -> BasinMatcher {
+        match self {
+            Self::ReadonlyPrefix(_) | Self::ReadwritePrefix(_) => {
+                BasinMatcher::Prefix(Default::default())
+            }
+            Self::BasinAdmin(basin) => BasinMatcher::Exact(basin.clone()),
+        }
+    }
+
+    pub fn streams(&self) -> StreamMatcher {
+        match self {
+            Self::ReadonlyPrefix(prefix) | Self::ReadwritePrefix(prefix) => {
+                StreamMatcher::Prefix(prefix.clone())
+            }
+            Self::BasinAdmin(_) => StreamMatcher::Prefix(Default::default()),
+        }
+    }
+
+    pub fn op_group_perms(&self) -> PermittedOperationGroups {
+        let read_only = ReadWritePermissions {
+            read: true,
+            write: false,
+        };
+        let read_write = ReadWritePermissions {
+            read: true,
+            write: true,
+        };
+        match self {
+            Self::ReadonlyPrefix(_) => PermittedOperationGroups {
+                account: None,
+                basin: None,
+                stream: Some(read_only),
+            },
+            Self::ReadwritePrefix(_) => PermittedOperationGroups {
+                account: None,
+                basin: None,
+                stream: Some(read_write),
+            },
+            Self::BasinAdmin(_) => PermittedOperationGroups {
+                account: None,
+                basin: Some(read_write.clone()),
+                stream: Some(read_write),
+            },
+        }
+    }
+}
+
+impl FromStr for AccessTokenTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, argument) = s.split_once(':').ok_or_else(|| {
+            format!(
+                "expected `{{name}}:{{argument}}`, where name is one of: {}",
+                Self::NAMES
+            )
+        })?;
+        match name {
+            "readonly-prefix" => Ok(Self::ReadonlyPrefix(
+                argument.parse().map_err(|e| format!("{e}"))?,
+            )),
+            "readwrite-prefix" => Ok(Self::ReadwritePrefix(
+                argument.parse().map_err(|e| format!("{e}"))?,
+            )),
+            "basin-admin" => Ok(Self::BasinAdmin(
+                argument.parse().map_err(|e| format!("{e}"))?,
+            )),
+            _ => Err(format!(
+                "unknown template `{name}`, expected one of: {}",
+                Self::NAMES
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct PermittedOperationGroups {
     pub account: Option<ReadWritePermissions>,
@@ -641,6 +900,24 @@ impl FromStr for PermittedOperationGroups {
     }
 }
 
+impl PermittedOperationGroups {
+    /// Whether the permissions for the group of `op` allow it.
+    pub fn permits(&self, op: &Operation) -> bool {
+        let perms = match op.group() {
+            OperationGroup::Account => &self.account,
+            OperationGroup::Basin => &self.basin,
+            OperationGroup::Stream => &self.stream,
+        };
+        perms.as_ref().is_some_and(|perms| {
+            if op.is_write() {
+                perms.write
+            } else {
+                perms.read
+            }
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct ReadWritePermissions {
     pub read: bool,
@@ -822,7 +1099,67 @@ impl From<sdk::types::Operation> for Operation {
     }
 }
 
-#[derive(ValueEnum, Debug, Clone, Copy)]
+/// Level of the resource an [`Operation`] acts on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationGroup {
+    Account,
+    Basin,
+    Stream,
+}
+
+impl Operation {
+    /// Group whose permissions govern this operation.
+    pub fn group(&self) -> OperationGroup {
+        match self {
+            Operation::ListBasins
+            | Operation::CreateBasin
+            | Operation::DeleteBasin
+            | Operation::ListAccessTokens
+            | Operation::IssueAccessToken
+            | Operation::RevokeAccessToken
+            | Operation::GetAccountMetrics
+            | Operation::ListLocations
+            | Operation::GetDefaultLocation
+            | Operation::SetDefaultLocation => OperationGroup::Account,
+            Operation::GetBasinConfig
+            | Operation::ReconfigureBasin
+            | Operation::GetBasinMetrics
+            | Operation::ListStreams
+            | Operation::CreateStream
+            | Operation::DeleteStream => OperationGroup::Basin,
+            Operation::GetStreamMetrics
+            | Operation::GetStreamConfig
+            | Operation::ReconfigureStream
+            | Operation::CheckTail
+            | Operation::Trim
+            | Operation::Fence
+            | Operation::Append
+            | Operation::Read => OperationGroup::Stream,
+        }
+    }
+
+    /// Whether this operation needs write rather than read permission for its group.
+    pub fn is_write(&self) -> bool {
+        matches!(
+            self,
+            Operation::CreateBasin
+                | Operation::DeleteBasin
+                | Operation::ReconfigureBasin
+                | Operation::IssueAccessToken
+                | Operation::RevokeAccessToken
+                | Operation::SetDefaultLocation
+                | Operation::CreateStream
+                | Operation::DeleteStream
+                | Operation::ReconfigureStream
+                | Operation::Trim
+                | Operation::Fence
+                | Operation::Append
+        )
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Interval {
     /// Per-minute intervals.
     Minute,
@@ -842,6 +1179,176 @@ impl From<Interval> for TimeseriesInterval {
     }
 }
 
+impl From<TimeseriesInterval> for Interval {
+    fn from(value: TimeseriesInterval) -> Self {
+        match value {
+            TimeseriesInterval::Minute => Interval::Minute,
+            TimeseriesInterval::Hour => Interval::Hour,
+            TimeseriesInterval::Day => Interval::Day,
+        }
+    }
+}
+
+/// How `append` retries appends after a transient failure.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AppendRetryPolicy {
+    /// Do not retry.
+    None,
+    /// Retry only when the failed append cannot have reached the stream, so that records are
+    /// never duplicated.
+    NoSideEffects,
+    /// Retry all appends, which may duplicate records on the stream.
+    #[default]
+    All,
+}
+
+impl From<AppendRetryPolicy> for sdk::types::RetryConfig {
+    fn from(policy: AppendRetryPolicy) -> Self {
+        let retry_config = sdk::types::RetryConfig::new();
+        match policy {
+            AppendRetryPolicy::None => retry_config.with_max_attempts(NonZeroU32::MIN),
+            AppendRetryPolicy::NoSideEffects => {
+                retry_config.with_append_retry_policy(sdk::types::AppendRetryPolicy::NoSideEffects)
+            }
+            AppendRetryPolicy::All => {
+                retry_config.with_append_retry_policy(sdk::types::AppendRetryPolicy::All)
+            }
+        }
+    }
+}
+
+/// How `append` reports acknowledgements.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AckFormat {
+    /// A line per acknowledged batch on stderr.
+    #[default]
+    Text,
+    /// A JSON object per acknowledged batch on stdout, and one describing a failed append
+    /// condition.
+    Json,
+}
+
+/// What `append --verify` checks when reading back appended records.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VerifyMode {
+    /// The number of records read back matches the number appended.
+    #[default]
+    Count,
+    /// Additionally, the headers and bodies read back hash to the same digest as those appended.
+    Hash,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum MetricsOutput {
+    /// Human-readable tables.
+    #[default]
+    Table,
+    /// JSON array of metrics, with timestamps in Unix epoch seconds.
+    Json,
+    /// CSV rows of `metric,timestamp,value,unit` for accumulation and gauge series.
+    Csv,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetricUnit {
+    Bytes,
+    Operations,
+    Count,
+    Percent,
+    /// Unit not known to this version of the CLI.
+    Unknown,
+}
+
+impl MetricUnit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricUnit::Bytes => "bytes",
+            MetricUnit::Operations => "operations",
+            MetricUnit::Count => "count",
+            MetricUnit::Percent => "percent",
+            MetricUnit::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<sdk::types::MetricUnit> for MetricUnit {
+    fn from(value: sdk::types::MetricUnit) -> Self {
+        match value {
+            sdk::types::MetricUnit::Bytes => MetricUnit::Bytes,
+            sdk::types::MetricUnit::Operations => MetricUnit::Operations,
+            sdk::types::MetricUnit::Count => MetricUnit::Count,
+            sdk::types::MetricUnit::Percent => MetricUnit::Percent,
+            _ => MetricUnit::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Datapoint {
+    pub timestamp: u32,
+    pub value: f64,
+}
+
+fn datapoints(values: Vec<(u32, f64)>) -> Vec<Datapoint> {
+    values
+        .into_iter()
+        .map(|(timestamp, value)| Datapoint { timestamp, value })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Metric {
+    Scalar {
+        name: String,
+        unit: MetricUnit,
+        value: f64,
+    },
+    Accumulation {
+        name: String,
+        unit: MetricUnit,
+        interval: Interval,
+        values: Vec<Datapoint>,
+    },
+    Gauge {
+        name: String,
+        unit: MetricUnit,
+        values: Vec<Datapoint>,
+    },
+    Label {
+        name: String,
+        values: Vec<String>,
+    },
+}
+
+impl From<sdk::types::Metric> for Metric {
+    fn from(value: sdk::types::Metric) -> Self {
+        match value {
+            sdk::types::Metric::Scalar(m) => Metric::Scalar {
+                name: m.name,
+                unit: m.unit.into(),
+                value: m.value,
+            },
+            sdk::types::Metric::Accumulation(m) => Metric::Accumulation {
+                name: m.name,
+                unit: m.unit.into(),
+                interval: m.interval.into(),
+                values: datapoints(m.values),
+            },
+            sdk::types::Metric::Gauge(m) => Metric::Gauge {
+                name: m.name,
+                unit: m.unit.into(),
+                values: datapoints(m.values),
+            },
+            sdk::types::Metric::Label(m) => Metric::Label {
+                name: m.name,
+                values: m.values,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LatencyStats {
     pub min: std::time::Duration,
@@ -863,13 +1370,65 @@ impl LatencyStats {
     }
 }
 
+/// Minimum, maximum and selected percentiles of a distribution of durations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DurationPercentiles {
+    pub min: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl DurationPercentiles {
+    /// Summarize `counts`, which maps each distinct duration to how often it occurred, or `None`
+    /// if it is empty. Percentiles use the nearest-rank method.
+    pub fn from_counts(counts: &BTreeMap<Duration, u64>) -> Option<Self> {
+        let (&min, _) = counts.first_key_value()?;
+        let (&max, _) = counts.last_key_value()?;
+        let total: u64 = counts.values().sum();
+        let percentile = |p: f64| {
+            let rank = ((total as f64) * p).ceil().max(1.0) as u64;
+            let mut seen = 0;
+            counts
+                .iter()
+                .find_map(|(&duration, &count)| {
+                    seen += count;
+                    (seen >= rank).then_some(duration)
+                })
+                .unwrap_or(max)
+        };
+        Some(Self {
+            min,
+            p50: percentile(0.50),
+            p90: percentile(0.90),
+            p99: percentile(0.99),
+            max,
+        })
+    }
+
+    pub fn into_vec(self) -> Vec<(&'static str, Duration)> {
+        vec![
+            ("min", self.min),
+            ("p50", self.p50),
+            ("p90", self.p90),
+            ("p99", self.p99),
+            ("max", self.max),
+        ]
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::{collections::BTreeMap, time::Duration};
+
     use rstest::rstest;
 
     use super::{
-        OpGroupsParseError, PermittedOperationGroups, ReadWritePermissions,
-        S2BasinAndMaybeStreamUri, S2BasinAndStreamUri, S2BasinUri, S2Uri,
+        AccessTokenTemplate, BasinMatcher, DurationPercentiles, Maybe, OpGroupsParseError,
+        Operation, PermittedOperationGroups, ReadWritePermissions, S2BasinAndMaybeStreamUri,
+        S2BasinAndStreamUri, S2BasinUri, S2Uri, StorageClass, StreamConfig, TimestampingConfig,
+        TimestampingMode, sdk,
     };
     use crate::error::S2UriParseError;
 
@@ -943,6 +1502,23 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("stream=r", Operation::Read, true)]
+    #[case("stream=r", Operation::Append, false)]
+    #[case("stream=w", Operation::Append, true)]
+    #[case("basin=r", Operation::Read, false)]
+    #[case("basin=r", Operation::ListStreams, true)]
+    #[case("basin=r", Operation::CreateStream, false)]
+    #[case("account=rw", Operation::IssueAccessToken, true)]
+    fn op_groups_permit_operations(
+        #[case] groups: &str,
+        #[case] op: Operation,
+        #[case] expected: bool,
+    ) {
+        let groups: PermittedOperationGroups = groups.parse().unwrap();
+        assert_eq!(groups.permits(&op), expected, "{op} with {groups:?}");
+    }
+
     #[test]
     fn test_s2_uri_parse() {
         let test_cases = vec![
@@ -1057,4 +1633,94 @@ mod tests {
             );
         }
     }
+
+    #[rstest]
+    #[case::uppercase("My-Basin", "must be lowercase, try \"my-basin\"")]
+    #[case::trailing_separator("my-basin/", "must not end with a '/' separator")]
+    fn basin_name_errors_suggest_fix(#[case] s: &str, #[case] expected: &str) {
+        let Err(S2UriParseError::InvalidBasinName(msg)) = s.parse::<S2BasinUri>() else {
+            panic!("expected invalid basin name error for {s}");
+        };
+        assert!(msg.contains(expected), "{msg}");
+    }
+
+    #[test]
+    fn access_token_template_expands_scope() {
+        let template: AccessTokenTemplate = "basin-admin:my-basin".parse().unwrap();
+        assert!(matches!(
+            template.basins(),
+            BasinMatcher::Exact(basin) if basin.as_ref() == "my-basin"
+        ));
+        let perms = template.op_group_perms();
+        assert_eq!(perms.account, None);
+        assert!(perms.basin.is_some_and(|p| p.read && p.write));
+
+        let template: AccessTokenTemplate = "readonly-prefix:logs/".parse().unwrap();
+        assert!(matches!(
+            template.basins(),
+            BasinMatcher::Prefix(prefix) if prefix.is_empty()
+        ));
+        assert!(
+            template
+                .op_group_perms()
+                .stream
+                .is_some_and(|p| p.read && !p.write)
+        );
+    }
+
+    #[rstest]
+    #[case::missing_argument("readonly-prefix", "expected `{name}:{argument}`")]
+    #[case::unknown_name("superuser:x", "unknown template `superuser`")]
+    #[case::invalid_basin("basin-admin:My-Basin", "must be lowercase")]
+    fn access_token_template_errors(#[case] s: &str, #[case] expected: &str) {
+        let err = s.parse::<AccessTokenTemplate>().unwrap_err();
+        assert!(err.contains(expected), "{err}");
+    }
+
+    #[test]
+    fn reset_reconfiguration_clears_fields_not_set() {
+        let config = StreamConfig {
+            storage_class: Some(StorageClass::Express),
+            timestamping: Some(TimestampingConfig {
+                timestamping_mode: Some(TimestampingMode::Arrival),
+                timestamping_uncapped: None,
+            }),
+            ..Default::default()
+        };
+        let reconfig = config.into_reset_reconfiguration();
+
+        assert!(matches!(
+            reconfig.storage_class,
+            Maybe::Specified(Some(sdk::types::StorageClass::Express))
+        ));
+        let Maybe::Specified(Some(timestamping)) = reconfig.timestamping else {
+            panic!("timestamping should be overridden");
+        };
+        assert!(matches!(
+            timestamping.mode,
+            Maybe::Specified(Some(sdk::types::TimestampingMode::Arrival))
+        ));
+        assert!(matches!(timestamping.uncapped, Maybe::Specified(None)));
+        assert!(matches!(reconfig.retention_policy, Maybe::Specified(None)));
+        assert!(matches!(reconfig.delete_on_empty, Maybe::Specified(None)));
+        assert!(matches!(reconfig.compression_hint, Maybe::Specified(None)));
+    }
+
+    #[test]
+    fn duration_percentiles_use_nearest_rank() {
+        assert_eq!(DurationPercentiles::from_counts(&BTreeMap::new()), None);
+
+        let ms = Duration::from_millis;
+        let counts = BTreeMap::from([(ms(1), 50), (ms(2), 40), (ms(3), 9), (ms(10), 1)]);
+        assert_eq!(
+            DurationPercentiles::from_counts(&counts),
+            Some(DurationPercentiles {
+                min: ms(1),
+                p50: ms(1),
+                p90: ms(2),
+                p99: ms(3),
+                max: ms(10),
+            })
+        );
+    }
 }
@@ -75,10 +75,149 @@ fn missing_access_token() {
     let mut cmd = env.s2();
     cmd.args(["list-basins"])
         .assert()
-        .failure()
+        .code(4)
         .stderr(predicate::str::contains("access token"));
 }
 
+#[test]
+fn timeout_cancels_stalled_command() {
+    // Accepts connections but never responds.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let endpoint = format!("http://{}", listener.local_addr().expect("local addr"));
+    TestEnv::new()
+        .s2()
+        .env("S2_ACCESS_TOKEN", "test-token")
+        .env("S2_ACCOUNT_ENDPOINT", &endpoint)
+        .env("S2_BASIN_ENDPOINT", &endpoint)
+        .args(["--timeout", "200ms", "list-basins"])
+        .timeout(std::time::Duration::from_secs(30))
+        .assert()
+        .code(7)
+        .stderr(predicate::str::contains("timed out after 200ms"));
+}
+
+#[test]
+fn explain_only_prints_resolved_read_without_sending() {
+    TestEnv::new()
+        .s2()
+        .env("S2_ACCESS_TOKEN", "test-token")
+        .args([
+            "read",
+            "s2://explain-basin/stream",
+            "--seq-num",
+            "5",
+            "--count",
+            "10",
+            "--explain-only",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(r#""seq_num": 5"#))
+        .stderr(predicate::str::contains(r#""count": 10"#))
+        .stderr(predicate::str::contains("explain-basin"));
+}
+
+#[test]
+fn append_verify_defaults_to_count() {
+    TestEnv::new()
+        .s2()
+        .env("S2_ACCESS_TOKEN", "test-token")
+        .args([
+            "append",
+            "s2://explain-basin/stream",
+            "--verify",
+            "--explain-only",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(r#""verify": "count""#));
+}
+
+#[test]
+fn append_retry_policy_is_explained() {
+    TestEnv::new()
+        .s2()
+        .env("S2_ACCESS_TOKEN", "test-token")
+        .args([
+            "append",
+            "s2://explain-basin/stream",
+            "--append-retry-policy",
+            "no-side-effects",
+            "--explain-only",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            r#""append_retry_policy": "no-side-effects""#,
+        ));
+}
+
+#[test]
+fn tail_stats_rejects_zero_interval() {
+    TestEnv::new()
+        .s2()
+        .env("S2_ACCESS_TOKEN", "test-token")
+        .args(["tail", "s2://stats-basin/stream", "--stats", "0s"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "--stats interval must be greater than zero",
+        ));
+}
+
+#[test]
+fn seek_requires_a_position() {
+    TestEnv::new()
+        .s2()
+        .env("S2_ACCESS_TOKEN", "test-token")
+        .args(["seek", "s2://seek-basin/stream"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--timestamp"));
+}
+
+#[test]
+fn issue_access_token_validate_only_prints_request() {
+    TestEnv::new()
+        .s2()
+        .env("S2_ACCESS_TOKEN", "test-token")
+        .args([
+            "issue-access-token",
+            "my-token",
+            "--basins",
+            "=my-basin",
+            "--streams",
+            "logs/",
+            "--ops",
+            "read,append",
+            "--validate-only",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""id": "my-token""#))
+        .stdout(predicate::str::contains(r#""exact": "my-basin""#))
+        .stdout(predicate::str::contains(r#""prefix": "logs/""#));
+}
+
+#[test]
+fn issue_access_token_template_expands_scope() {
+    TestEnv::new()
+        .s2()
+        .env("S2_ACCESS_TOKEN", "test-token")
+        .args([
+            "issue-access-token",
+            "my-token",
+            "--template",
+            "readonly-prefix:logs/",
+            "--validate-only",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""prefix": "logs/""#))
+        .stdout(predicate::str::contains(r#""op_groups""#));
+}
+
 #[test]
 fn unknown_subcommand() {
     TestEnv::new()
@@ -78,6 +78,26 @@ fn create_get_delete_basin() {
     s2().args(["delete-basin", &basin]).assert().success();
 }
 
+#[test]
+#[serial]
+fn normalize_names() {
+    let basin = ensure_test_basin(&unique_name("test-cli-normalize"));
+    let unnormalized = format!("{}/", basin.to_uppercase());
+
+    s2().args(["get-basin-config", &unnormalized])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "must not end with a '/' separator",
+        ));
+
+    s2().args(["get-basin-config", &unnormalized, "--normalize-names"])
+        .assert()
+        .success();
+
+    cleanup_basin(&basin);
+}
+
 #[test]
 #[serial]
 fn create_basin_with_config() {
@@ -128,6 +148,63 @@ fn reconfigure_basin() {
     cleanup_basin(&basin);
 }
 
+#[test]
+#[serial]
+fn list_access_tokens_scope_contains() {
+    let covered = unique_name("test-cli-token-covered");
+    let other = unique_name("test-cli-token-other");
+
+    s2().args([
+        "issue-access-token",
+        &covered,
+        "--basins",
+        "=test-cli-scope",
+        "--streams",
+        "logs/",
+        "--ops",
+        "read",
+    ])
+    .assert()
+    .success();
+    s2().args([
+        "issue-access-token",
+        &other,
+        "--basins",
+        "=test-cli-elsewhere",
+        "--ops",
+        "read",
+    ])
+    .assert()
+    .success();
+
+    s2().args([
+        "list-access-tokens",
+        "--prefix",
+        "test-cli-token-",
+        "--scope-contains",
+        "s2://test-cli-scope/logs/app",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(&covered).and(predicate::str::contains(&other).not()));
+
+    s2().args([
+        "list-access-tokens",
+        "--prefix",
+        "test-cli-token-",
+        "--scope-contains",
+        "s2://test-cli-scope/logs/app",
+        "--op",
+        "append",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(&covered).not());
+
+    let _ = s2().args(["revoke-access-token", &covered]).output();
+    let _ = s2().args(["revoke-access-token", &other]).output();
+}
+
 #[test]
 #[serial]
 fn ls_basins() {
@@ -297,6 +374,40 @@ fn append_and_read_text() {
     cleanup_stream(&basin, &stream);
 }
 
+#[test]
+#[serial]
+fn append_with_verify_hash() {
+    let basin = ensure_test_basin("test-cli-data");
+    let stream = unique_name("test-data-verify");
+    let uri = format!("s2://{basin}/{stream}");
+
+    s2().args(["create-stream", &uri]).assert().success();
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let input = temp.path().join("input.json");
+    {
+        let mut f = std::fs::File::create(&input).unwrap();
+        writeln!(f, r#"{{"headers": [["k", "v"]], "body": "record one"}}"#).unwrap();
+        writeln!(f, r#"{{"body": "record two"}}"#).unwrap();
+    }
+
+    s2().args([
+        "append",
+        &uri,
+        "--format",
+        "json",
+        "--verify",
+        "hash",
+        "--input",
+        input.to_str().unwrap(),
+    ])
+    .assert()
+    .success()
+    .stderr(predicate::str::contains("[VERIFIED] 2 records read back"));
+
+    cleanup_stream(&basin, &stream);
+}
+
 #[test]
 #[serial]
 fn append_and_read_json() {
@@ -716,7 +827,25 @@ fn append_with_match_seq_num() {
     ])
     .write_stdin("should fail\n")
     .assert()
-    .failure();
+    .failure()
+    .stderr(predicate::str::contains("the next sequence number is 1"));
+
+    s2().args([
+        "append",
+        &uri,
+        "--format",
+        "text",
+        "--input",
+        "-",
+        "--match-seq-num",
+        "0",
+        "--ack-format",
+        "json",
+    ])
+    .write_stdin("should fail\n")
+    .assert()
+    .failure()
+    .stdout(predicate::str::contains(r#""expected_seq_num":1"#));
 
     cleanup_stream(&basin, &stream);
 }
@@ -883,7 +1012,10 @@ fn append_wrong_fencing_token_fails() {
     ])
     .write_stdin("should fail\n")
     .assert()
-    .failure();
+    .failure()
+    .stderr(predicate::str::contains(
+        "fenced with token `correct-token`",
+    ));
 
     cleanup_stream(&basin, &stream);
 }
@@ -919,6 +1051,34 @@ fn reconfigure_basin_storage_class() {
     cleanup_basin(&basin);
 }
 
+#[test]
+#[serial]
+fn copy_basin_config() {
+    let from = unique_name("test-cli-basin-copy-from");
+    let to = unique_name("test-cli-basin-copy-to");
+
+    s2().args(["create-basin", &from]).assert().success();
+    s2().args(["create-basin", &to]).assert().success();
+    wait_for_basin(&from);
+    wait_for_basin(&to);
+
+    s2().args(["reconfigure-basin", &from, "--timestamping-mode", "arrival"])
+        .assert()
+        .success();
+
+    s2().args(["copy-basin-config", "--from", &from, "--to", &to])
+        .assert()
+        .success();
+
+    s2().args(["get-basin-config", &to])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("arrival").or(predicate::str::contains("Arrival")));
+
+    cleanup_basin(&from);
+    cleanup_basin(&to);
+}
+
 #[test]
 #[serial]
 fn reconfigure_stream_timestamping() {
@@ -970,6 +1130,59 @@ fn read_with_timestamp() {
     cleanup_stream(&basin, &stream);
 }
 
+#[test]
+#[serial]
+fn seek_resolves_positions() {
+    let basin = ensure_test_basin("test-cli-data");
+    let stream = unique_name("test-data-seek");
+    let uri = format!("s2://{basin}/{stream}");
+
+    s2().args(["create-stream", &uri]).assert().success();
+
+    s2().args(["append", &uri, "--format", "text", "--input", "-"])
+        .write_stdin("one\ntwo\nthree\n")
+        .assert()
+        .success();
+
+    s2().args(["seek", &uri, "--timestamp", "0"])
+        .assert()
+        .success()
+        .stdout("0\n");
+    s2().args(["seek", &uri, "--tail-offset", "1"])
+        .assert()
+        .success()
+        .stdout("2\n");
+    s2().args(["seek", &uri, "--timestamp", &u64::MAX.to_string()])
+        .assert()
+        .success()
+        .stdout("3\n");
+
+    cleanup_stream(&basin, &stream);
+}
+
+#[test]
+#[serial]
+fn timestamp_gaps_summarizes_range() {
+    let basin = ensure_test_basin("test-cli-data");
+    let stream = unique_name("test-data-gaps");
+    let uri = format!("s2://{basin}/{stream}");
+
+    s2().args(["create-stream", &uri]).assert().success();
+
+    s2().args(["append", &uri, "--format", "text", "--input", "-"])
+        .write_stdin("one\ntwo\nthree\n")
+        .assert()
+        .success();
+
+    s2().args(["timestamp-gaps", &uri])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("p50\t"))
+        .stderr(predicate::str::contains("3 records in range 0..3"));
+
+    cleanup_stream(&basin, &stream);
+}
+
 #[test]
 #[serial]
 fn trim_with_fencing_token() {
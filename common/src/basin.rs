@@ -6,7 +6,9 @@ use time::OffsetDateTime;
 use super::{
     ValidationError,
     location::LocationName,
-    strings::{NameProps, PrefixProps, StartAfterProps, StrProps},
+    strings::{
+        NAME_SEPARATOR, NameNormalization, NameProps, PrefixProps, StartAfterProps, StrProps,
+    },
 };
 use crate::{caps, resources::ListItemsRequest};
 
@@ -165,6 +167,34 @@ impl<T: StrProps> From<BasinNameStr<T>> for CompactString {
 
 pub type BasinName = BasinNameStr<NameProps>;
 
+/// Validate a user-supplied basin name, applying `normalization` first, so that callers such as
+/// the CLI can catch mistakes before making a request.
+pub fn validate_name(
+    name: &str,
+    normalization: NameNormalization,
+) -> Result<BasinName, ValidationError> {
+    let trimmed = name.trim_end_matches(NAME_SEPARATOR);
+    match normalization {
+        NameNormalization::Reject => {
+            if trimmed.len() != name.len() {
+                return Err(format!(
+                    "basin name must not end with a {NAME_SEPARATOR:?} separator, try {trimmed:?}"
+                )
+                .into());
+            }
+            if name.chars().any(|c| c.is_ascii_uppercase()) {
+                return Err(format!(
+                    "basin name must be lowercase, try {:?}",
+                    name.to_ascii_lowercase()
+                )
+                .into());
+            }
+            name.parse()
+        }
+        NameNormalization::Normalize => trimmed.to_ascii_lowercase().parse(),
+    }
+}
+
 pub type BasinNamePrefix = BasinNameStr<PrefixProps>;
 
 impl Default for BasinNamePrefix {
@@ -213,7 +243,7 @@ pub struct BasinInfo {
 mod test {
     use rstest::rstest;
 
-    use super::{BasinNameStr, NameProps, PrefixProps, StartAfterProps};
+    use super::{BasinNameStr, NameNormalization, NameProps, PrefixProps, StartAfterProps};
 
     #[rstest]
     #[case::min_len("abcdefgh".to_owned())]
@@ -271,4 +301,29 @@ mod test {
         BasinNameStr::<StartAfterProps>::validate_str(&start_after)
             .expect_err("expected validation error");
     }
+
+    #[rstest]
+    #[case::unchanged("my-basin", "my-basin")]
+    #[case::trailing_separator("my-basin/", "my-basin")]
+    #[case::trailing_separators("my-basin//", "my-basin")]
+    #[case::uppercase("My-Basin", "my-basin")]
+    fn validate_name_normalize(#[case] name: &str, #[case] expected: &str) {
+        let normalized = super::validate_name(name, NameNormalization::Normalize).unwrap();
+        assert_eq!(normalized.as_ref(), expected);
+    }
+
+    #[rstest]
+    #[case::trailing_separator("my-basin/", "must not end with a '/' separator")]
+    #[case::uppercase("My-Basin", "must be lowercase, try \"my-basin\"")]
+    #[case::invalid_characters("my_basin", "must comprise lowercase letters")]
+    fn validate_name_reject(#[case] name: &str, #[case] expected_err: &str) {
+        let err = super::validate_name(name, NameNormalization::Reject).unwrap_err();
+        assert!(err.to_string().contains(expected_err), "{err}");
+    }
+
+    #[test]
+    fn validate_name_normalize_still_validates() {
+        super::validate_name("ab/", NameNormalization::Normalize)
+            .expect_err("expected validation error");
+    }
 }
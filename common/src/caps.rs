@@ -7,6 +7,8 @@ pub const MAX_STREAM_NAME_LEN: usize = 512;
 
 pub const MAX_ACCESS_TOKEN_ID_LEN: usize = 96;
 
+pub const MAX_CONSUMER_NAME_LEN: usize = 128;
+
 /// All record batches in the system are limited to 1000 records.
 /// Batches are limited to a collective size of 1 MiB, which is also the maximum size of a single
 /// record.
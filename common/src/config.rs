@@ -51,6 +51,33 @@ pub enum StorageClass {
     Express = 2,
 }
 
+/// Advisory codec for compressing a stream's records at rest. Storage may ignore it.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    strum::Display,
+    strum::IntoStaticStr,
+    strum::EnumIter,
+    strum::FromRepr,
+    strum::EnumString,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[repr(u8)]
+pub enum CompressionHint {
+    #[default]
+    #[strum(serialize = "none")]
+    None = 0,
+    #[strum(serialize = "zstd")]
+    Zstd = 1,
+    #[strum(serialize = "gzip")]
+    Gzip = 2,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RetentionPolicy {
     Age(Duration),
@@ -83,12 +110,17 @@ impl Default for RetentionPolicy {
     }
 }
 
+/// How far a client-specified timestamp may be from the arrival time and still be honored in
+/// [`TimestampingMode::ServerPrefer`] mode.
+pub const SERVER_PREFER_TIMESTAMP_TOLERANCE: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum TimestampingMode {
     #[default]
     ClientPrefer,
     ClientRequire,
     Arrival,
+    ServerPrefer,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -114,6 +146,11 @@ pub struct StreamConfig {
     pub retention_policy: RetentionPolicy,
     pub timestamping: TimestampingConfig,
     pub delete_on_empty: DeleteOnEmptyConfig,
+    pub min_retained_records: u64,
+    pub max_append_bytes_per_sec: u64,
+    pub max_append_ops_per_sec: u64,
+    pub max_future_skew_secs: u64,
+    pub compression_hint: CompressionHint,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -133,6 +170,11 @@ pub struct StreamReconfiguration {
     pub retention_policy: Maybe<Option<RetentionPolicy>>,
     pub timestamping: Maybe<Option<TimestampingReconfiguration>>,
     pub delete_on_empty: Maybe<Option<DeleteOnEmptyReconfiguration>>,
+    pub min_retained_records: Maybe<Option<u64>>,
+    pub max_append_bytes_per_sec: Maybe<Option<u64>>,
+    pub max_append_ops_per_sec: Maybe<Option<u64>>,
+    pub max_future_skew_secs: Maybe<Option<u64>>,
+    pub compression_hint: Maybe<Option<CompressionHint>>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -221,6 +263,11 @@ pub struct OptionalStreamConfig {
     pub retention_policy: Option<RetentionPolicy>,
     pub timestamping: OptionalTimestampingConfig,
     pub delete_on_empty: OptionalDeleteOnEmptyConfig,
+    pub min_retained_records: Option<u64>,
+    pub max_append_bytes_per_sec: Option<u64>,
+    pub max_append_ops_per_sec: Option<u64>,
+    pub max_future_skew_secs: Option<u64>,
+    pub compression_hint: Option<CompressionHint>,
 }
 
 impl OptionalStreamConfig {
@@ -231,12 +278,19 @@ impl OptionalStreamConfig {
         Ok(())
     }
 
+    /// Apply `reconfiguration` field by field: unspecified fields keep their current value, and a
+    /// field specified as `None` is cleared.
     pub fn reconfigure(mut self, reconfiguration: StreamReconfiguration) -> Self {
         let StreamReconfiguration {
             storage_class,
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         } = reconfiguration;
         if let Maybe::Specified(storage_class) = storage_class {
             self.storage_class = storage_class;
@@ -254,6 +308,21 @@ impl OptionalStreamConfig {
                 .map(|reconfig| self.delete_on_empty.reconfigure(reconfig))
                 .unwrap_or_default();
         }
+        if let Maybe::Specified(min_retained_records) = min_retained_records {
+            self.min_retained_records = min_retained_records;
+        }
+        if let Maybe::Specified(max_append_bytes_per_sec) = max_append_bytes_per_sec {
+            self.max_append_bytes_per_sec = max_append_bytes_per_sec;
+        }
+        if let Maybe::Specified(max_append_ops_per_sec) = max_append_ops_per_sec {
+            self.max_append_ops_per_sec = max_append_ops_per_sec;
+        }
+        if let Maybe::Specified(max_future_skew_secs) = max_future_skew_secs {
+            self.max_future_skew_secs = max_future_skew_secs;
+        }
+        if let Maybe::Specified(compression_hint) = compression_hint {
+            self.compression_hint = compression_hint;
+        }
         self
     }
 
@@ -272,11 +341,41 @@ impl OptionalStreamConfig {
 
         let delete_on_empty = self.delete_on_empty.merge(basin_defaults.delete_on_empty);
 
+        let min_retained_records = self
+            .min_retained_records
+            .or(basin_defaults.min_retained_records)
+            .unwrap_or_default();
+
+        let max_append_bytes_per_sec = self
+            .max_append_bytes_per_sec
+            .or(basin_defaults.max_append_bytes_per_sec)
+            .unwrap_or_default();
+
+        let max_append_ops_per_sec = self
+            .max_append_ops_per_sec
+            .or(basin_defaults.max_append_ops_per_sec)
+            .unwrap_or_default();
+
+        let max_future_skew_secs = self
+            .max_future_skew_secs
+            .or(basin_defaults.max_future_skew_secs)
+            .unwrap_or_default();
+
+        let compression_hint = self
+            .compression_hint
+            .or(basin_defaults.compression_hint)
+            .unwrap_or_default();
+
         StreamConfig {
             storage_class,
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         }
     }
 }
@@ -288,6 +387,11 @@ impl From<OptionalStreamConfig> for StreamConfig {
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         } = value;
 
         Self {
@@ -295,6 +399,11 @@ impl From<OptionalStreamConfig> for StreamConfig {
             retention_policy: retention_policy.unwrap_or_default(),
             timestamping: timestamping.into(),
             delete_on_empty: delete_on_empty.into(),
+            min_retained_records: min_retained_records.unwrap_or_default(),
+            max_append_bytes_per_sec: max_append_bytes_per_sec.unwrap_or_default(),
+            max_append_ops_per_sec: max_append_ops_per_sec.unwrap_or_default(),
+            max_future_skew_secs: max_future_skew_secs.unwrap_or_default(),
+            compression_hint: compression_hint.unwrap_or_default(),
         }
     }
 }
@@ -306,6 +415,11 @@ impl From<StreamConfig> for OptionalStreamConfig {
             retention_policy,
             timestamping,
             delete_on_empty,
+            min_retained_records,
+            max_append_bytes_per_sec,
+            max_append_ops_per_sec,
+            max_future_skew_secs,
+            compression_hint,
         } = value;
 
         Self {
@@ -313,6 +427,11 @@ impl From<StreamConfig> for OptionalStreamConfig {
             retention_policy: Some(retention_policy),
             timestamping: timestamping.into(),
             delete_on_empty: delete_on_empty.into(),
+            min_retained_records: Some(min_retained_records),
+            max_append_bytes_per_sec: Some(max_append_bytes_per_sec),
+            max_append_ops_per_sec: Some(max_append_ops_per_sec),
+            max_future_skew_secs: Some(max_future_skew_secs),
+            compression_hint: Some(compression_hint),
         }
     }
 }
@@ -330,6 +449,7 @@ impl BasinConfig {
         self.default_stream_config.validate()
     }
 
+    /// Apply `reconfiguration` field by field, including within the default stream config.
     pub fn reconfigure(mut self, reconfiguration: BasinReconfiguration) -> Self {
         let BasinReconfiguration {
             default_stream_config,
@@ -0,0 +1,159 @@
+use std::{ops::Deref, str::FromStr};
+
+use compact_str::{CompactString, ToCompactString};
+
+use super::ValidationError;
+use crate::caps;
+
+/// Name of a consumer whose read cursor is tracked on a stream.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConsumerName(CompactString);
+
+impl ConsumerName {
+    fn validate_str(name: &str) -> Result<(), ValidationError> {
+        if name.is_empty() {
+            return Err("consumer name must not be empty".into());
+        }
+
+        if name == "." || name == ".." {
+            return Err("consumer name must not be \".\" or \"..\"".into());
+        }
+
+        if name.len() > caps::MAX_CONSUMER_NAME_LEN {
+            return Err(format!(
+                "consumer name must not exceed {} bytes in length",
+                caps::MAX_CONSUMER_NAME_LEN
+            )
+            .into());
+        }
+
+        if name
+            .chars()
+            .any(|c| !c.is_ascii_alphanumeric() && c != '_' && c != '-' && c != '.')
+        {
+            return Err(
+                "consumer name must comprise ASCII letters, numbers, underscores, hyphens, and periods"
+                    .into(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl utoipa::PartialSchema for ConsumerName {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        utoipa::openapi::Object::builder()
+            .schema_type(utoipa::openapi::Type::String)
+            .min_length(Some(1))
+            .max_length(Some(caps::MAX_CONSUMER_NAME_LEN))
+            .into()
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl utoipa::ToSchema for ConsumerName {}
+
+impl serde::Serialize for ConsumerName {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ConsumerName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = CompactString::deserialize(deserializer)?;
+        s.try_into().map_err(serde::de::Error::custom)
+    }
+}
+
+impl AsRef<str> for ConsumerName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for ConsumerName {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl TryFrom<CompactString> for ConsumerName {
+    type Error = ValidationError;
+
+    fn try_from(name: CompactString) -> Result<Self, Self::Error> {
+        Self::validate_str(&name)?;
+        Ok(Self(name))
+    }
+}
+
+impl TryFrom<&str> for ConsumerName {
+    type Error = ValidationError;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        name.to_compact_string().try_into()
+    }
+}
+
+impl FromStr for ConsumerName {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
+    }
+}
+
+impl std::fmt::Debug for ConsumerName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::fmt::Display for ConsumerName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<ConsumerName> for CompactString {
+    fn from(value: ConsumerName) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::ConsumerName;
+
+    #[rstest]
+    #[case::single_char("a".to_owned())]
+    #[case::mixed("group-1.worker_A".to_owned())]
+    #[case::max_len("a".repeat(crate::caps::MAX_CONSUMER_NAME_LEN))]
+    fn validate_name_ok(#[case] name: String) {
+        assert_eq!(name.parse::<ConsumerName>().as_deref(), Ok(name.as_str()));
+    }
+
+    #[rstest]
+    #[case::empty("".to_owned())]
+    #[case::dot(".".to_owned())]
+    #[case::dot_dot("..".to_owned())]
+    #[case::too_long("a".repeat(crate::caps::MAX_CONSUMER_NAME_LEN + 1))]
+    #[case::slash("group/worker".to_owned())]
+    #[case::space("group worker".to_owned())]
+    fn validate_name_err(#[case] name: String) {
+        name.parse::<ConsumerName>()
+            .expect_err("expected validation error");
+    }
+}
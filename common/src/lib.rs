@@ -4,6 +4,7 @@ pub mod access;
 pub mod basin;
 pub mod caps;
 pub mod config;
+pub mod consumer;
 pub mod deep_size;
 pub mod encryption;
 pub mod http;
@@ -16,6 +17,8 @@ pub mod resources;
 pub mod stream;
 mod strings;
 
+pub use strings::NameNormalization;
+
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[error("{0}")]
 pub struct ValidationError(pub String);
@@ -32,6 +35,12 @@ impl From<&str> for ValidationError {
     }
 }
 
+impl From<resources::StartAfterLessThanPrefixError> for ValidationError {
+    fn from(e: resources::StartAfterLessThanPrefixError) -> Self {
+        ValidationError(e.to_string())
+    }
+}
+
 impl From<record::FencingTokenTooLongError> for ValidationError {
     fn from(e: record::FencingTokenTooLongError) -> Self {
         ValidationError(e.to_string())
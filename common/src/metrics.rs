@@ -1,9 +1,13 @@
+use std::collections::BTreeMap;
+
 use compact_str::CompactString;
 
 #[derive(Clone, Copy, Debug)]
 pub enum MetricUnit {
     Bytes,
     Operations,
+    Count,
+    Percent,
 }
 
 #[derive(Clone, Copy, Debug, strum::IntoStaticStr)]
@@ -64,6 +68,7 @@ pub struct AccountMetricsRequest {
     pub interval: Option<TimeseriesInterval>,
 }
 
+#[derive(Clone, Copy)]
 pub enum BasinMetricSet {
     Storage,
     AppendOps,
@@ -73,6 +78,7 @@ pub enum BasinMetricSet {
     AppendThroughput,
 }
 
+#[derive(Clone)]
 pub struct BasinMetricsRequest {
     pub set: BasinMetricSet,
     pub start: Option<u32>,
@@ -95,3 +101,91 @@ pub struct StreamMetricsRequest {
 pub struct MetricsResponse {
     pub values: Vec<Metric>,
 }
+
+/// Sum metrics gathered from several sources, e.g. one set per basin.
+///
+/// Scalars are summed by name, and accumulations by name and timestamp. Gauges and labels do not
+/// add up meaningfully across sources, so they are dropped.
+pub fn sum_metrics(sets: impl IntoIterator<Item = Vec<Metric>>) -> Vec<Metric> {
+    let mut summed: Vec<Metric> = Vec::new();
+    for metric in sets.into_iter().flatten() {
+        match metric {
+            Metric::Scalar(m) => {
+                match summed.iter_mut().find_map(|s| match s {
+                    Metric::Scalar(s) if s.name == m.name => Some(s),
+                    _ => None,
+                }) {
+                    Some(s) => s.value += m.value,
+                    None => summed.push(Metric::Scalar(m)),
+                }
+            }
+            Metric::Accumulation(m) => {
+                match summed.iter_mut().find_map(|s| match s {
+                    Metric::Accumulation(s) if s.name == m.name => Some(s),
+                    _ => None,
+                }) {
+                    Some(s) => {
+                        let mut values: BTreeMap<u32, f64> = s.values.drain(..).collect();
+                        for (ts, value) in m.values {
+                            *values.entry(ts).or_default() += value;
+                        }
+                        s.values = values.into_iter().collect();
+                    }
+                    None => summed.push(Metric::Accumulation(m)),
+                }
+            }
+            Metric::Gauge(_) | Metric::Label(_) => {}
+        }
+    }
+    summed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(name: &str, value: f64) -> Metric {
+        Metric::Scalar(ScalarMetric {
+            name: name.into(),
+            unit: MetricUnit::Operations,
+            value,
+        })
+    }
+
+    fn accumulation(name: &str, values: Vec<(u32, f64)>) -> Metric {
+        Metric::Accumulation(AccumulationMetric {
+            name: name.into(),
+            unit: MetricUnit::Bytes,
+            interval: TimeseriesInterval::Minute,
+            values,
+        })
+    }
+
+    #[test]
+    fn sum_metrics_adds_scalars_and_accumulations() {
+        let summed = sum_metrics([
+            vec![
+                scalar("ops", 2.0),
+                accumulation("bytes", vec![(60, 1.0), (120, 2.0)]),
+                Metric::Label(LabelMetric {
+                    name: "streams".into(),
+                    values: vec!["a".to_owned()],
+                }),
+            ],
+            vec![
+                accumulation("bytes", vec![(0, 5.0), (120, 3.0)]),
+                scalar("ops", 3.0),
+            ],
+        ]);
+
+        assert_eq!(summed.len(), 2);
+        let Metric::Scalar(ops) = &summed[0] else {
+            panic!("expected scalar, got {:?}", summed[0]);
+        };
+        assert_eq!(ops.value, 5.0);
+        let Metric::Accumulation(bytes) = &summed[1] else {
+            panic!("expected accumulation, got {:?}", summed[1]);
+        };
+        assert_eq!(bytes.values, vec![(0, 5.0), (60, 1.0), (120, 5.0)]);
+    }
+}
@@ -67,6 +67,24 @@ impl ReadLimit {
         }
     }
 
+    /// Tighten the count bound to at most `max_count`, preserving any byte bound.
+    pub fn with_max_count(self, max_count: usize) -> Self {
+        match self {
+            ReadLimit::Unbounded => ReadLimit::Count(max_count),
+            ReadLimit::Count(count) => ReadLimit::Count(count.min(max_count)),
+            ReadLimit::Bytes(bytes) => ReadLimit::CountOrBytes(CountOrBytes {
+                count: max_count,
+                bytes,
+            }),
+            ReadLimit::CountOrBytes(CountOrBytes { count, bytes }) => {
+                ReadLimit::CountOrBytes(CountOrBytes {
+                    count: count.min(max_count),
+                    bytes,
+                })
+            }
+        }
+    }
+
     pub fn into_allowance(self, max: CountOrBytes) -> CountOrBytes {
         match self {
             ReadLimit::Unbounded => max,
@@ -217,4 +235,18 @@ mod test {
             remaining_limit
         )
     }
+
+    #[rstest]
+    #[case(ReadLimit::Unbounded, 10, ReadLimit::Count(10))]
+    #[case(ReadLimit::Count(5), 10, ReadLimit::Count(5))]
+    #[case(ReadLimit::Count(50), 10, ReadLimit::Count(10))]
+    #[case(ReadLimit::Bytes(100), 10, ReadLimit::CountOrBytes(CountOrBytes{count: 10, bytes: 100}))]
+    #[case(ReadLimit::CountOrBytes(CountOrBytes{count: 50, bytes: 100}), 10, ReadLimit::CountOrBytes(CountOrBytes{count: 10, bytes: 100}))]
+    fn with_max_count(
+        #[case] limit: ReadLimit,
+        #[case] max_count: usize,
+        #[case] expected: ReadLimit,
+    ) {
+        assert_eq!(limit.with_max_count(max_count), expected)
+    }
 }
@@ -66,6 +66,37 @@ pub struct ListItemsRequest<P, S> {
     pub limit: ListLimit,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("`start_after` must be greater than or equal to `prefix`")]
+pub struct StartAfterLessThanPrefixError;
+
+impl<P: AsRef<str>, S: AsRef<str>> ListItemsRequest<P, S> {
+    /// A non-empty `start_after` that sorts before `prefix` can never match, since every name
+    /// with the prefix sorts at or after it.
+    pub fn new(
+        prefix: P,
+        start_after: S,
+        limit: ListLimit,
+    ) -> Result<Self, StartAfterLessThanPrefixError> {
+        validate_start_after(prefix.as_ref(), start_after.as_ref())?;
+        Ok(Self {
+            prefix,
+            start_after,
+            limit,
+        })
+    }
+}
+
+pub fn validate_start_after(
+    prefix: &str,
+    start_after: &str,
+) -> Result<(), StartAfterLessThanPrefixError> {
+    if !start_after.is_empty() && start_after < prefix {
+        return Err(StartAfterLessThanPrefixError);
+    }
+    Ok(())
+}
+
 /// Mode for provisioning a resource.
 ///
 /// Provisioning either creates a new resource with create-only semantics, or ensures that
@@ -141,6 +172,10 @@ pub static REQUEST_TOKEN_HEADER: http::HeaderName =
 pub static PROVISION_RESULT_HEADER: http::HeaderName =
     http::HeaderName::from_static("s2-provision-result");
 
+/// Response header carrying the page size a list request was served with, after clamping the
+/// requested limit to [`ListLimit::MAX`].
+pub static LIST_LIMIT_HEADER: http::HeaderName = http::HeaderName::from_static("s2-list-limit");
+
 pub const MAX_REQUEST_TOKEN_LENGTH: usize = 36;
 
 #[derive(Debug, PartialEq, Eq, thiserror::Error)]
@@ -232,3 +267,33 @@ impl crate::http::ParseableHeader for RequestToken {
         &REQUEST_TOKEN_HEADER
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ListItemsRequest, ListLimit, StartAfterLessThanPrefixError};
+
+    fn list_request(
+        prefix: &'static str,
+        start_after: &'static str,
+    ) -> Result<ListItemsRequest<&'static str, &'static str>, StartAfterLessThanPrefixError> {
+        ListItemsRequest::new(prefix, start_after, ListLimit::default())
+    }
+
+    #[test]
+    fn start_after_less_than_prefix_is_rejected() {
+        assert_eq!(
+            list_request("foo", "bar").unwrap_err(),
+            StartAfterLessThanPrefixError
+        );
+        assert!(list_request("foo", "fo").is_err());
+    }
+
+    #[test]
+    fn start_after_at_or_after_prefix_is_accepted() {
+        assert!(list_request("foo", "foo").is_ok());
+        assert!(list_request("foo", "foo-1").is_ok());
+        assert!(list_request("foo", "zzz").is_ok());
+        assert!(list_request("", "anything").is_ok());
+        assert!(list_request("foo", "").is_ok());
+    }
+}
@@ -5,7 +5,9 @@ use time::OffsetDateTime;
 
 use super::{
     ValidationError,
-    strings::{NameProps, PrefixProps, StartAfterProps, StrProps},
+    strings::{
+        NAME_SEPARATOR, NameNormalization, NameProps, PrefixProps, StartAfterProps, StrProps,
+    },
 };
 use crate::{
     caps,
@@ -135,6 +137,30 @@ impl<T: StrProps> From<StreamNameStr<T>> for CompactString {
 
 pub type StreamName = StreamNameStr<NameProps>;
 
+/// Validate a user-supplied stream name, applying `normalization` first, so that callers such as
+/// the CLI can catch mistakes before making a request.
+///
+/// Stream names are case-sensitive and may contain `/`, so only trailing separators are
+/// affected; [`NameNormalization::Reject`] therefore refuses some names the service accepts.
+pub fn validate_name(
+    name: &str,
+    normalization: NameNormalization,
+) -> Result<StreamName, ValidationError> {
+    let trimmed = name.trim_end_matches(NAME_SEPARATOR);
+    match normalization {
+        NameNormalization::Reject => {
+            if !trimmed.is_empty() && trimmed.len() != name.len() {
+                return Err(format!(
+                    "stream name must not end with a {NAME_SEPARATOR:?} separator, try {trimmed:?}"
+                )
+                .into());
+            }
+            name.parse()
+        }
+        NameNormalization::Normalize => trimmed.parse(),
+    }
+}
+
 pub type StreamNamePrefix = StreamNameStr<PrefixProps>;
 
 impl Default for StreamNamePrefix {
@@ -306,11 +332,34 @@ pub enum ReadPosition {
     Timestamp(Timestamp),
 }
 
+/// Range of sequence numbers, inclusive of `start` and exclusive of `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeqNumRange {
+    pub start: SeqNum,
+    pub end: SeqNum,
+}
+
+impl SeqNumRange {
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, seq_num: SeqNum) -> bool {
+        self.start <= seq_num && seq_num < self.end
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ReadFrom {
     SeqNum(SeqNum),
     Timestamp(Timestamp),
     TailOffset(u64),
+    /// Read exactly the records in the range, subject to any tighter [`ReadEnd`] bounds.
+    SeqNumRange(SeqNumRange),
 }
 
 impl Default for ReadFrom {
@@ -323,6 +372,33 @@ impl Default for ReadFrom {
 pub struct ReadStart {
     pub from: ReadFrom,
     pub clamp: bool,
+    /// Fail a read from a sequence number that has been trimmed, rather than starting from the
+    /// earliest available record.
+    pub error_if_trimmed: bool,
+}
+
+impl ReadStart {
+    /// Rewrite a [`ReadFrom::SeqNumRange`] as a [`ReadFrom::SeqNum`] start, returning the exclusive
+    /// end of the range as a sequence number bound the read must stop at.
+    ///
+    /// The length of the range is also applied as a count limit on `end`, so that a range read
+    /// never follows the tail. Other starting points are returned as-is, without a bound.
+    pub fn resolve_seq_num_range(self, mut end: ReadEnd) -> (ReadStart, ReadEnd, Option<SeqNum>) {
+        let ReadFrom::SeqNumRange(range) = self.from else {
+            return (self, end, None);
+        };
+        let max_count = usize::try_from(range.len()).unwrap_or(usize::MAX);
+        end.limit = end.limit.with_max_count(max_count);
+        (
+            ReadStart {
+                from: ReadFrom::SeqNum(range.start),
+                clamp: self.clamp,
+                error_if_trimmed: self.error_if_trimmed,
+            },
+            end,
+            Some(range.end),
+        )
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -367,6 +443,13 @@ impl<T> std::fmt::Debug for ReadBatch<T> {
     }
 }
 
+/// Positions of the records a read covers, without their contents.
+#[derive(Debug, Clone, Default)]
+pub struct PositionsBatch {
+    pub positions: Vec<StreamPosition>,
+    pub tail: Option<StreamPosition>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ReadSessionOutput<T = Record> {
     Heartbeat(StreamPosition),
@@ -434,10 +517,146 @@ mod test {
             .expect_err("expected validation error");
     }
 
+    /// Simulates a read over a stream with records `trim_point..tail`, applying the resolved start,
+    /// limit and bound the way a read session does.
+    fn simulate_read(
+        start: ReadStart,
+        end: ReadEnd,
+        trim_point: SeqNum,
+        tail: SeqNum,
+    ) -> Vec<SeqNum> {
+        let (start, end, end_seq_num) = start.resolve_seq_num_range(end);
+        let ReadFrom::SeqNum(start_seq_num) = start.from else {
+            panic!("expected seq_num start");
+        };
+        let mut limit = end.limit;
+        let mut seq_nums = Vec::new();
+        for seq_num in start_seq_num.max(trim_point)..tail {
+            if end_seq_num.is_some_and(|end| seq_num >= end) || limit.deny(1, 0) {
+                break;
+            }
+            seq_nums.push(seq_num);
+            match limit.remaining(1, 0) {
+                crate::read_extent::EvaluatedReadLimit::Remaining(remaining) => limit = remaining,
+                crate::read_extent::EvaluatedReadLimit::Exhausted => break,
+            }
+        }
+        seq_nums
+    }
+
+    #[rstest]
+    #[case::single(5, 6)]
+    #[case::middle(10, 20)]
+    #[case::from_zero(0, 50)]
+    #[case::through_tail(90, 100)]
+    fn seq_num_range_reads_exactly_start_to_end(#[case] start: SeqNum, #[case] end: SeqNum) {
+        let read_start = ReadStart {
+            from: ReadFrom::SeqNumRange(SeqNumRange { start, end }),
+            clamp: false,
+            error_if_trimmed: false,
+        };
+        assert_eq!(
+            simulate_read(read_start, ReadEnd::default(), 0, 100),
+            (start..end).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn seq_num_range_end_beyond_tail_stops_at_tail() {
+        let read_start = ReadStart {
+            from: ReadFrom::SeqNumRange(SeqNumRange {
+                start: 95,
+                end: 200,
+            }),
+            clamp: false,
+            error_if_trimmed: false,
+        };
+        assert_eq!(
+            simulate_read(read_start, ReadEnd::default(), 0, 100),
+            (95..100).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn seq_num_range_stops_at_end_after_trim() {
+        let read_start = ReadStart {
+            from: ReadFrom::SeqNumRange(SeqNumRange { start: 5, end: 10 }),
+            clamp: false,
+            error_if_trimmed: false,
+        };
+        assert_eq!(
+            simulate_read(read_start, ReadEnd::default(), 8, 100),
+            vec![8, 9]
+        );
+    }
+
+    #[test]
+    fn seq_num_range_empty_reads_nothing() {
+        let read_start = ReadStart {
+            from: ReadFrom::SeqNumRange(SeqNumRange { start: 10, end: 10 }),
+            clamp: false,
+            error_if_trimmed: false,
+        };
+        assert!(simulate_read(read_start, ReadEnd::default(), 0, 100).is_empty());
+    }
+
+    #[test]
+    fn seq_num_range_respects_tighter_count_limit() {
+        let read_start = ReadStart {
+            from: ReadFrom::SeqNumRange(SeqNumRange { start: 10, end: 20 }),
+            clamp: false,
+            error_if_trimmed: false,
+        };
+        let read_end = ReadEnd {
+            limit: ReadLimit::Count(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            simulate_read(read_start, read_end, 0, 100),
+            vec![10, 11, 12]
+        );
+    }
+
+    #[test]
+    fn seq_num_range_leaves_other_starts_untouched() {
+        let read_start = ReadStart {
+            from: ReadFrom::TailOffset(5),
+            clamp: true,
+            error_if_trimmed: false,
+        };
+        let (start, end, end_seq_num) = read_start.resolve_seq_num_range(ReadEnd::default());
+        assert!(matches!(start.from, ReadFrom::TailOffset(5)));
+        assert!(start.clamp);
+        assert!(end.limit.is_unbounded());
+        assert_eq!(end_seq_num, None);
+    }
+
     #[test]
     fn append_record_batch_rejects_empty_batches() {
         let empty_batch: Result<AppendRecordBatch, _> = Vec::<AppendRecord>::new().try_into();
 
         assert_eq!(empty_batch.unwrap_err(), "record batch must not be empty");
     }
+
+    #[rstest]
+    #[case::unchanged("logs/app", "logs/app")]
+    #[case::trailing_separator("logs/app/", "logs/app")]
+    #[case::case_preserved("Logs/App//", "Logs/App")]
+    fn validate_name_normalize(#[case] name: &str, #[case] expected: &str) {
+        let normalized = validate_name(name, NameNormalization::Normalize).unwrap();
+        assert_eq!(normalized.as_ref(), expected);
+    }
+
+    #[rstest]
+    #[case::trailing_separator("logs/app/", "must not end with a '/' separator, try \"logs/app\"")]
+    #[case::empty("", "must not be empty")]
+    fn validate_name_reject(#[case] name: &str, #[case] expected_err: &str) {
+        let err = validate_name(name, NameNormalization::Reject).unwrap_err();
+        assert!(err.to_string().contains(expected_err), "{err}");
+    }
+
+    #[test]
+    fn validate_name_normalize_still_validates() {
+        validate_name("//", NameNormalization::Normalize).expect_err("expected validation error");
+    }
 }
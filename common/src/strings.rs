@@ -34,3 +34,15 @@ impl StrProps for StartAfterProps {
     const IS_PREFIX: bool = true;
     const FIELD_NAME: &'static str = "start-after";
 }
+
+/// How a user-supplied name is treated before it is validated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameNormalization {
+    /// Reject names that would only be valid after normalization, with an error that says why.
+    #[default]
+    Reject,
+    /// Strip trailing `/` separators, and lowercase basin names, before validating.
+    Normalize,
+}
+
+pub(crate) const NAME_SEPARATOR: char = '/';
@@ -0,0 +1,203 @@
+//! Throughput of cold sequential scans for a range of read-ahead depths. The stream is flushed to
+//! SSTs once, then every scan runs against a freshly opened database over an object store that
+//! adds latency to each fetch, with the read cache disabled, so no record is served from memory.
+//! Each scan hashes every record body it receives, standing in for delivery to a client.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use bytesize::ByteSize;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use futures::StreamExt as _;
+use s2_common::{
+    basin::BasinName,
+    config::{BasinConfig, OptionalStreamConfig},
+    read_extent::{ReadLimit, ReadUntil},
+    record::{Metered, Record},
+    resources::ProvisionMode,
+    stream::{
+        AppendInput, AppendRecord, AppendRecordBatch, AppendRecordParts, ReadEnd, ReadFrom,
+        ReadSessionOutput, ReadStart, StreamName,
+    },
+};
+use s2_lite::backend::Backend;
+use slatedb::{
+    Db,
+    config::{FlushOptions, FlushType},
+    object_store::{
+        ObjectStore,
+        memory::InMemory,
+        throttle::{ThrottleConfig, ThrottledStore},
+    },
+};
+
+const DB_PATH: &str = "/bench";
+const RECORD_BYTES: usize = 4 * 1024;
+const RECORDS_PER_APPEND: usize = 100;
+const APPENDS: usize = 40;
+const RECORDS: usize = RECORDS_PER_APPEND * APPENDS;
+/// Latency added to every object store fetch, in the range of a request to a remote bucket.
+const FETCH_LATENCY: Duration = Duration::from_millis(10);
+
+fn basin() -> BasinName {
+    "bench-basin".parse().expect("valid basin name")
+}
+
+fn stream() -> StreamName {
+    "bench-stream".parse().expect("valid stream name")
+}
+
+/// Write the stream to `object_store` and flush it out of the memtable, so that reopening the
+/// database serves it from SSTs.
+async fn populate(object_store: Arc<dyn ObjectStore>) {
+    let db = Db::builder(DB_PATH, object_store)
+        .build()
+        .await
+        .expect("create in-memory db");
+    let backend = Backend::new(db.clone(), ByteSize::mib(64), ByteSize::b(0));
+
+    backend
+        .provision_basin(
+            basin(),
+            BasinConfig::default(),
+            ProvisionMode::CreateOnly {
+                request_token: None,
+            },
+        )
+        .await
+        .expect("create basin");
+    backend
+        .provision_stream(
+            basin(),
+            stream(),
+            OptionalStreamConfig::default(),
+            ProvisionMode::CreateOnly {
+                request_token: None,
+            },
+        )
+        .await
+        .expect("create stream");
+
+    let body = bytes::Bytes::from(vec![0xAB; RECORD_BYTES]);
+    for _ in 0..APPENDS {
+        let records: Vec<AppendRecord> = (0..RECORDS_PER_APPEND)
+            .map(|_| {
+                AppendRecordParts {
+                    timestamp: None,
+                    record: Metered::from(
+                        Record::try_from_parts(vec![], body.clone()).expect("valid record"),
+                    ),
+                }
+                .try_into()
+                .expect("valid append record")
+            })
+            .collect();
+        let records: AppendRecordBatch = records.try_into().expect("valid append batch");
+        backend
+            .open_for_append(&basin(), &stream(), None)
+            .await
+            .expect("open for append")
+            .append(AppendInput {
+                records,
+                match_seq_num: None,
+                fencing_token: None,
+            })
+            .await
+            .expect("append");
+    }
+
+    db.flush_with_options(FlushOptions {
+        flush_type: FlushType::MemTable,
+    })
+    .await
+    .expect("flush memtable");
+    backend.shutdown().await.expect("shutdown");
+}
+
+/// Open a backend over `object_store` with empty caches, adding [`FETCH_LATENCY`] to each fetch.
+async fn cold_backend(object_store: Arc<dyn ObjectStore>, read_ahead_batches: usize) -> Backend {
+    let object_store = Arc::new(ThrottledStore::new(
+        object_store,
+        ThrottleConfig {
+            wait_get_per_call: FETCH_LATENCY,
+            ..Default::default()
+        },
+    ));
+    let db = Db::builder(DB_PATH, object_store)
+        .build()
+        .await
+        .expect("open db");
+    Backend::new(db, ByteSize::mib(64), ByteSize::b(0)).with_read_ahead_batches(read_ahead_batches)
+}
+
+async fn scan(backend: &Backend) -> blake3::Hash {
+    let start = ReadStart {
+        from: ReadFrom::SeqNum(0),
+        clamp: false,
+        error_if_trimmed: false,
+    };
+    let end = ReadEnd {
+        limit: ReadLimit::Count(RECORDS),
+        until: ReadUntil::Unbounded,
+        wait: None,
+    };
+    let session = backend
+        .open_for_read(&basin(), &stream(), None)
+        .await
+        .expect("open for read")
+        .read(start, end)
+        .await
+        .expect("read");
+    let mut session = std::pin::pin!(session);
+    let mut hasher = blake3::Hasher::new();
+    while let Some(output) = session.next().await {
+        if let ReadSessionOutput::Batch(batch) = output.expect("read output") {
+            for record in batch.records.iter() {
+                if let Record::Envelope(envelope) = record.inner() {
+                    hasher.update(envelope.body());
+                }
+            }
+        }
+    }
+    hasher.finalize()
+}
+
+fn cold_sequential_scan(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+    let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    runtime.block_on(populate(object_store.clone()));
+
+    let mut group = c.benchmark_group("cold_sequential_scan");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes((RECORDS * RECORD_BYTES) as u64));
+    for read_ahead_batches in [0, 1, 4, 16] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(read_ahead_batches),
+            &read_ahead_batches,
+            |b, &read_ahead_batches| {
+                b.to_async(&runtime).iter_custom(|iters| {
+                    let object_store = object_store.clone();
+                    async move {
+                        let mut elapsed = Duration::ZERO;
+                        for _ in 0..iters {
+                            // Reopening is not timed, only the scan over the cold database.
+                            let backend =
+                                cold_backend(object_store.clone(), read_ahead_batches).await;
+                            let start = Instant::now();
+                            std::hint::black_box(scan(&backend).await);
+                            elapsed += start.elapsed();
+                            backend.shutdown().await.expect("shutdown");
+                        }
+                        elapsed
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, cold_sequential_scan);
+criterion_main!(benches);
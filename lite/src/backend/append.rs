@@ -14,8 +14,14 @@ use s2_common::{
 use s2_storage::record::encrypt_append_input;
 use tokio::sync::oneshot;
 
-use super::{Backend, StreamHandle};
-use crate::backend::error::{AppendError, AppendErrorInternal, StorageError};
+use super::{Backend, StreamHandle, streamer::resolve_client_timestamp};
+use crate::backend::{
+    error::{
+        AppendError, AppendErrorInternal, StorageError, StreamDeletionPendingError,
+        StreamNotFoundError,
+    },
+    kv,
+};
 
 impl Backend {
     pub async fn open_for_append(
@@ -32,6 +38,44 @@ impl Backend {
         )
         .await
     }
+
+    /// Check `inputs` against the stream the way an append would, without persisting anything.
+    ///
+    /// The stream must exist, as it is never auto-created. Client timestamps are checked against
+    /// the stream's timestamping config and future skew limit, and `encryption_key` against its
+    /// cipher.
+    pub async fn validate_append(
+        &self,
+        basin: &BasinName,
+        stream: &StreamName,
+        encryption_key: Option<EncryptionKey>,
+        inputs: &[AppendInput],
+    ) -> Result<(), AppendError> {
+        let meta = self
+            .db_get(
+                kv::stream_meta::ser_key(basin, stream),
+                kv::stream_meta::deser_value,
+            )
+            .await?
+            .ok_or_else(|| StreamNotFoundError {
+                basin: basin.clone(),
+                stream: stream.clone(),
+            })?;
+        if meta.deleted_at.is_some() {
+            return Err(StreamDeletionPendingError.into());
+        }
+        EncryptionSpec::resolve(meta.cipher, encryption_key)?;
+        let now = self.clock.timestamp_millis();
+        for record in inputs.iter().flat_map(|input| input.records.iter()) {
+            resolve_client_timestamp::<AppendError>(
+                record.parts().timestamp,
+                now,
+                &meta.config.timestamping,
+                meta.config.max_future_skew_secs,
+            )?;
+        }
+        Ok(())
+    }
 }
 
 impl StreamHandle {
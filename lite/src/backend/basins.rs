@@ -1,3 +1,9 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
 use s2_common::{
     basin::{BasinInfo, BasinName, ListBasinsRequest},
     config::{BasinConfig, BasinReconfiguration},
@@ -9,17 +15,22 @@ use slatedb::{
     IsolationLevel,
     config::{DurabilityLevel, ScanOptions},
 };
-use time::OffsetDateTime;
 
-use super::{Backend, bgtasks::BgtaskTrigger, store::db_txn_get};
-use crate::backend::{
-    error::{
-        BasinAlreadyExistsError, BasinDeletionPendingError, BasinNotFoundError, DeleteBasinError,
-        GetBasinConfigError, ListBasinsError, ProvisionBasinError, ReconfigureBasinError,
+use super::{Backend, StreamConfigChange, bgtasks::BgtaskTrigger, store::db_txn_get};
+use crate::{
+    backend::{
+        error::{
+            BasinAlreadyExistsError, BasinDeletionPendingError, BasinNotFoundError,
+            DeleteBasinError, GetBasinConfigError, ListBasinsError, ProvisionBasinError,
+            ReconfigureBasinError,
+        },
+        kv,
     },
-    kv,
+    metrics::{self, BasinConfigCacheLookup},
 };
 
+const BASIN_CONFIG_CACHE_CAPACITY: usize = 10_000;
+
 impl Backend {
     pub async fn list_basins(
         &self,
@@ -117,14 +128,14 @@ impl Backend {
                     .map(|req_token| creation_idempotency_key(req_token, &config));
                 ProvisionResult::Created(kv::basin_meta::BasinMeta {
                     config,
-                    created_at: OffsetDateTime::now_utc(),
+                    created_at: self.clock.now_utc(),
                     deleted_at: None,
                     creation_idempotency_key: new_creation_idempotency_key,
                 })
             }
             (None, ProvisionMode::Ensure) => ProvisionResult::Created(kv::basin_meta::BasinMeta {
                 config,
-                created_at: OffsetDateTime::now_utc(),
+                created_at: self.clock.now_utc(),
                 deleted_at: None,
                 creation_idempotency_key: None,
             }),
@@ -135,6 +146,7 @@ impl Backend {
             txn.put(&meta_key, kv::basin_meta::ser_value(meta))?;
 
             txn.commit().await?;
+            self.basin_config_cache.invalidate(&basin);
         }
 
         Ok(outcome.map(|meta| BasinInfo {
@@ -149,12 +161,18 @@ impl Backend {
         &self,
         basin: BasinName,
     ) -> Result<BasinConfig, GetBasinConfigError> {
+        if let Some(config) = self.basin_config_cache.get(&basin) {
+            return Ok(config);
+        }
+        let generation = self.basin_config_cache.generation();
         let Some(meta) = self
             .db_get(kv::basin_meta::ser_key(&basin), kv::basin_meta::deser_value)
             .await?
         else {
             return Err(BasinNotFoundError { basin }.into());
         };
+        self.basin_config_cache
+            .insert(basin, generation, meta.config.clone());
         Ok(meta.config)
     }
 
@@ -180,6 +198,8 @@ impl Backend {
         txn.put(&meta_key, kv::basin_meta::ser_value(&meta))?;
 
         txn.commit().await?;
+        self.basin_config_cache.invalidate(&basin);
+        self.publish_stream_config_change(StreamConfigChange::BasinReconfigured { basin });
 
         Ok(meta.config)
     }
@@ -191,7 +211,7 @@ impl Backend {
             return Err(BasinNotFoundError { basin }.into());
         };
         if meta.deleted_at.is_none() {
-            meta.deleted_at = Some(OffsetDateTime::now_utc());
+            meta.deleted_at = Some(self.clock.now_utc());
             txn.put(&meta_key, kv::basin_meta::ser_value(&meta))?;
             txn.put(
                 kv::basin_deletion_pending::ser_key(&basin),
@@ -204,6 +224,106 @@ impl Backend {
     }
 }
 
+/// Cache of basin configs served by [`Backend::get_basin_config`], bounded by entry count with
+/// least recently used entries evicted first.
+///
+/// Entries are invalidated after any committed change to a basin's config, and when the basin is
+/// removed. A config read from the DB is only cached if no invalidation happened since the read
+/// started, so a racing reconfiguration cannot leave a stale entry behind.
+#[derive(Clone)]
+pub(super) struct BasinConfigCache {
+    inner: Arc<Mutex<BasinConfigCacheInner>>,
+}
+
+struct BasinConfigCacheInner {
+    capacity: usize,
+    generation: u64,
+    tick: u64,
+    configs: HashMap<BasinName, (BasinConfig, u64)>,
+    recency: BTreeMap<u64, BasinName>,
+}
+
+impl Default for BasinConfigCache {
+    fn default() -> Self {
+        Self::new(BASIN_CONFIG_CACHE_CAPACITY)
+    }
+}
+
+impl BasinConfigCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(BasinConfigCacheInner {
+                capacity,
+                generation: 0,
+                tick: 0,
+                configs: HashMap::new(),
+                recency: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Current invalidation generation, to be passed to [`Self::insert`] for a config read after
+    /// this call.
+    pub fn generation(&self) -> u64 {
+        self.inner.lock().generation
+    }
+
+    pub fn get(&self, basin: &BasinName) -> Option<BasinConfig> {
+        let config = self.inner.lock().touch(basin);
+        metrics::observe_basin_config_cache_lookup(if config.is_some() {
+            BasinConfigCacheLookup::Hit
+        } else {
+            BasinConfigCacheLookup::Miss
+        });
+        config
+    }
+
+    /// Cache a config read from the DB, unless an invalidation happened since `generation` was
+    /// obtained. The least recently used entry is evicted if the cache is full.
+    pub fn insert(&self, basin: BasinName, generation: u64, config: BasinConfig) {
+        let mut inner = self.inner.lock();
+        if inner.generation != generation {
+            return;
+        }
+        inner.remove(&basin);
+        while inner.configs.len() >= inner.capacity {
+            let Some((_, evicted)) = inner.recency.pop_first() else {
+                break;
+            };
+            inner.configs.remove(&evicted);
+        }
+        inner.tick += 1;
+        let tick = inner.tick;
+        inner.recency.insert(tick, basin.clone());
+        inner.configs.insert(basin, (config, tick));
+    }
+
+    pub fn invalidate(&self, basin: &BasinName) {
+        let mut inner = self.inner.lock();
+        inner.generation += 1;
+        inner.remove(basin);
+    }
+}
+
+impl BasinConfigCacheInner {
+    /// Look up a config, marking it as the most recently used.
+    fn touch(&mut self, basin: &BasinName) -> Option<BasinConfig> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (config, last_used) = self.configs.get_mut(basin)?;
+        self.recency.remove(last_used);
+        *last_used = tick;
+        self.recency.insert(tick, basin.clone());
+        Some(config.clone())
+    }
+
+    fn remove(&mut self, basin: &BasinName) {
+        if let Some((_, last_used)) = self.configs.remove(basin) {
+            self.recency.remove(&last_used);
+        }
+    }
+}
+
 fn creation_idempotency_key(req_token: &RequestToken, config: &BasinConfig) -> Bash {
     Bash::length_prefixed(&[
         req_token.as_bytes(),
@@ -211,3 +331,32 @@ fn creation_idempotency_key(req_token: &RequestToken, config: &BasinConfig) -> B
             .expect("serializable"),
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use s2_common::{basin::BasinName, config::BasinConfig};
+
+    use super::BasinConfigCache;
+
+    #[test]
+    fn basin_config_cache_evicts_least_recently_used_at_capacity() {
+        let cache = BasinConfigCache::new(2);
+        let [a, b, c] = ["basin-a", "basin-b", "basin-c"].map(|b| BasinName::from_str(b).unwrap());
+
+        cache.insert(a.clone(), cache.generation(), BasinConfig::default());
+        cache.insert(b.clone(), cache.generation(), BasinConfig::default());
+        assert!(cache.get(&a).is_some());
+
+        cache.insert(c.clone(), cache.generation(), BasinConfig::default());
+        assert!(cache.get(&a).is_some());
+        assert!(cache.get(&b).is_none());
+        assert!(cache.get(&c).is_some());
+
+        cache.insert(b.clone(), cache.generation(), BasinConfig::default());
+        assert!(cache.get(&a).is_none());
+        assert!(cache.get(&b).is_some());
+        assert!(cache.get(&c).is_some());
+    }
+}
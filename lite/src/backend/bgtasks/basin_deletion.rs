@@ -14,6 +14,7 @@ use crate::backend::{
     Backend,
     error::{BasinDeletionError, ListStreamsError, StorageError},
     kv,
+    streamer::TerminalTrimCondition,
 };
 
 const PENDING_LIST_LIMIT: usize = 32;
@@ -75,7 +76,7 @@ impl Backend {
             limit: ListLimit::MAX,
         };
         let page = self
-            .list_streams(basin.clone(), request)
+            .list_streams(basin.clone(), request, None)
             .await
             .map_err(|err| match err {
                 ListStreamsError::Storage(error) => error,
@@ -85,10 +86,18 @@ impl Backend {
         for info in page.values {
             let stream = info.name;
             last_stream = Some(StreamNameStartAfter::from(stream.clone()));
+            // Streams deleted within the grace period are purged once their deadline expires.
             if info.deleted_at.is_some() {
                 continue;
             }
-            self.delete_stream(basin.clone(), stream.clone()).await?;
+            // Deleting the basin bypasses the grace period, as streams in a basin being deleted
+            // cannot be undeleted.
+            self.delete_stream_with_condition(
+                basin.clone(),
+                stream.clone(),
+                TerminalTrimCondition::Always,
+            )
+            .await?;
         }
 
         if page.has_more {
@@ -132,6 +141,7 @@ impl Backend {
         batch.delete(kv::basin_meta::ser_key(basin));
         batch.delete(kv::basin_deletion_pending::ser_key(basin));
         self.db.write(batch).await?;
+        self.basin_config_cache.invalidate(basin);
         Ok(())
     }
 }
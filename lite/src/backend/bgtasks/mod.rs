@@ -1,12 +1,14 @@
 use std::{error::Error, future::Future, pin::Pin, time::Duration};
 
-use tokio::{sync::broadcast, time::Instant};
+use tokio::{sync::broadcast, task::JoinHandle, time::Instant};
 use tracing::{info, warn};
 
 use crate::backend::Backend;
 
 mod basin_deletion;
 mod stream_doe;
+mod stream_purge;
+mod stream_record_expiry;
 mod stream_trim;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,31 +18,48 @@ pub(super) enum BgtaskTrigger {
     StreamTrim,
 }
 
+/// Spawn background tasks, which run until [`Backend::shutdown`] is initiated.
 pub fn spawn(backend: &Backend) {
-    spawn_bgtask(
+    backend.track_bgtask(spawn_bgtask(
         "stream-trim",
         Duration::from_secs(60),
         &[BgtaskTrigger::StreamTrim],
         backend.bgtask_trigger_subscribe(),
         move |backend| backend.clone().tick_stream_trim(),
         backend.clone(),
-    );
-    spawn_bgtask(
+    ));
+    backend.track_bgtask(spawn_bgtask(
+        "stream-record-expiry",
+        Duration::from_secs(60),
+        &[],
+        backend.bgtask_trigger_subscribe(),
+        move |backend| backend.clone().tick_stream_record_expiry(),
+        backend.clone(),
+    ));
+    backend.track_bgtask(spawn_bgtask(
         "stream-delete-on-empty",
         Duration::from_secs(60),
         &[BgtaskTrigger::StreamDeleteOnEmpty],
         backend.bgtask_trigger_subscribe(),
         move |backend| backend.clone().tick_stream_doe(),
         backend.clone(),
-    );
-    spawn_bgtask(
+    ));
+    backend.track_bgtask(spawn_bgtask(
+        "stream-purge",
+        Duration::from_secs(60),
+        &[],
+        backend.bgtask_trigger_subscribe(),
+        move |backend| backend.clone().tick_stream_purge(),
+        backend.clone(),
+    ));
+    backend.track_bgtask(spawn_bgtask(
         "basin-deletion",
         Duration::from_secs(60),
         &[BgtaskTrigger::BasinDeletion],
         backend.bgtask_trigger_subscribe(),
         move |backend| backend.clone().tick_basin_deletion(),
         backend.clone(),
-    );
+    ));
 }
 
 fn spawn_bgtask<Tick, Fut, E>(
@@ -50,11 +69,13 @@ fn spawn_bgtask<Tick, Fut, E>(
     mut trigger_rx: broadcast::Receiver<BgtaskTrigger>,
     tick: Tick,
     backend: Backend,
-) where
+) -> JoinHandle<()>
+where
     Tick: Fn(&Backend) -> Fut + Send + Sync + 'static,
     Fut: Future<Output = Result<bool, E>> + Send,
     E: Error + Send + Sync + 'static,
 {
+    let shutdown = backend.shutdown_signal();
     tokio::spawn(async move {
         let sleep = tokio::time::sleep(jittered_delay(interval));
         tokio::pin!(sleep);
@@ -65,6 +86,11 @@ fn spawn_bgtask<Tick, Fut, E>(
         };
         loop {
             tokio::select! {
+                biased;
+                _ = shutdown.cancelled() => {
+                    info!(task = name, "bgtask shutting down");
+                    break;
+                }
                 _ = &mut sleep => {
                     run_tick(name, &tick, &backend).await;
                     reset_sleep(&mut sleep);
@@ -94,7 +120,7 @@ fn spawn_bgtask<Tick, Fut, E>(
                 }
             }
         }
-    });
+    })
 }
 
 fn jittered_delay(interval: Duration) -> Duration {
@@ -120,8 +146,11 @@ where
     Fut: Future<Output = Result<bool, E>> + Send,
     E: Error + Send + Sync,
 {
+    let shutdown = backend.shutdown_signal();
     loop {
         match tick(backend).await {
+            // Each tick leaves the backend consistent, so stop between ticks on shutdown.
+            Ok(true) if shutdown.is_cancelled() => break,
             Ok(true) => continue,
             Ok(false) => break,
             Err(error) => {
@@ -150,7 +179,7 @@ mod tests {
             .build()
             .await
             .unwrap();
-        Backend::new(db, ByteSize::mib(10))
+        Backend::new(db, ByteSize::mib(10), ByteSize::mib(10))
     }
 
     #[tokio::test]
@@ -172,4 +201,46 @@ mod tests {
 
         assert_eq!(calls.load(Ordering::SeqCst), 3);
     }
+
+    #[tokio::test]
+    async fn run_tick_stops_on_shutdown() {
+        let backend = test_backend().await;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let tick = {
+            let calls = Arc::clone(&calls);
+            move |_backend: &Backend| {
+                let calls = Arc::clone(&calls);
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Ok::<bool, std::io::Error>(true)
+                }
+            }
+        };
+
+        backend.initiate_shutdown();
+        run_tick("test", &tick, &backend).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn shutdown_waits_for_bgtasks_to_exit() {
+        let backend = test_backend().await;
+        let handle = spawn_bgtask(
+            "test",
+            Duration::from_secs(60),
+            &[BgtaskTrigger::StreamTrim],
+            backend.bgtask_trigger_subscribe(),
+            |_backend: &Backend| async { Ok::<bool, std::io::Error>(false) },
+            backend.clone(),
+        );
+        let abort_handle = handle.abort_handle();
+        backend.track_bgtask(handle);
+
+        tokio::time::timeout(Duration::from_secs(5), backend.shutdown())
+            .await
+            .expect("shutdown should not hang")
+            .expect("shutdown");
+        assert!(abort_handle.is_finished());
+    }
 }
@@ -48,7 +48,7 @@ impl PendingDoeBatch {
 
 impl Backend {
     pub(super) async fn tick_stream_doe(self) -> Result<bool, StreamDeleteOnEmptyError> {
-        let now = TimestampSecs::now();
+        let now = TimestampSecs::now(&self.clock);
         let page = self.list_pending_stream_doe(now).await?;
         if page.values.is_empty() {
             return Ok(page.has_more);
@@ -164,7 +164,7 @@ impl Backend {
         let Some(min_age) = meta.config.delete_on_empty.min_age() else {
             return Ok(());
         };
-        let deadline = TimestampSecs::after(doe_arm_delay(Duration::ZERO, min_age));
+        let deadline = TimestampSecs::after(&self.clock, doe_arm_delay(Duration::ZERO, min_age));
         self.db
             .put(
                 kv::stream_doe_deadline::ser_key(deadline, stream_id),
@@ -479,7 +479,7 @@ mod tests {
             stream_meta_with_doe_min_age(MIN_AGE),
         )
         .await;
-        let deadline = TimestampSecs::now();
+        let deadline = TimestampSecs::now(&backend.clock);
 
         let pos = StreamPosition {
             seq_num: 1,
@@ -541,7 +541,7 @@ mod tests {
             stream_meta_with_doe_min_age(MIN_AGE),
         )
         .await;
-        let deadline = TimestampSecs::after(Duration::from_secs(3600));
+        let deadline = TimestampSecs::after(&backend.clock, Duration::from_secs(3600));
 
         backend
             .db
@@ -584,9 +584,9 @@ mod tests {
             stream_meta_with_doe_min_age(MIN_AGE),
         )
         .await;
-        let far_future = TimestampSecs::after(Duration::from_secs(3600));
-        let deadline_a = TimestampSecs::after(Duration::ZERO);
-        let deadline_b = TimestampSecs::after(Duration::from_secs(1));
+        let far_future = TimestampSecs::after(&backend.clock, Duration::from_secs(3600));
+        let deadline_a = TimestampSecs::after(&backend.clock, Duration::ZERO);
+        let deadline_b = TimestampSecs::after(&backend.clock, Duration::from_secs(1));
 
         backend
             .db
@@ -725,7 +725,7 @@ mod tests {
         let min_age = Duration::from_secs(30);
         let expected_delay =
             crate::backend::streamer::doe_arm_delay(Duration::from_secs(120), min_age);
-        let lower_bound = TimestampSecs::now();
+        let lower_bound = TimestampSecs::now(&backend.clock);
         backend
             .reconfigure_stream(
                 basin,
@@ -739,7 +739,7 @@ mod tests {
             )
             .await
             .unwrap();
-        let upper_bound = TimestampSecs::now();
+        let upper_bound = TimestampSecs::now(&backend.clock);
 
         let entries = list_doe_entries(&backend).await;
         assert_eq!(entries.len(), 1);
@@ -0,0 +1,313 @@
+use futures::{StreamExt, stream};
+use s2_common::resources::Page;
+use slatedb::config::{DurabilityLevel, ScanOptions};
+use time::OffsetDateTime;
+use tracing::instrument;
+
+use crate::{
+    backend::{
+        Backend,
+        error::{DeleteStreamError, StorageError, StreamPurgeError},
+        kv::{self, timestamp::TimestampSecs},
+        streamer::TerminalTrimCondition,
+    },
+    stream_id::StreamId,
+};
+
+const PENDING_LIST_LIMIT: usize = 1_000;
+const CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, Copy)]
+struct PendingPurge {
+    deadline: TimestampSecs,
+    stream_id: StreamId,
+    deleted_at: OffsetDateTime,
+}
+
+impl Backend {
+    pub(super) async fn tick_stream_purge(self) -> Result<bool, StreamPurgeError> {
+        let page = self
+            .list_pending_stream_purges(TimestampSecs::now(&self.clock))
+            .await?;
+        if page.values.is_empty() {
+            return Ok(page.has_more);
+        }
+        let mut processed = stream::iter(page.values)
+            .map(|pending| {
+                let backend = self.clone();
+                async move { backend.process_stream_purge(pending).await }
+            })
+            .buffer_unordered(CONCURRENCY);
+        while let Some(result) = processed.next().await {
+            result?;
+        }
+        Ok(page.has_more)
+    }
+
+    async fn list_pending_stream_purges(
+        &self,
+        now: TimestampSecs,
+    ) -> Result<Page<PendingPurge>, StorageError> {
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let mut it = self
+            .db
+            .scan_with_options(
+                kv::stream_purge_deadline::expired_key_range(now),
+                &scan_opts,
+            )
+            .await?;
+        let mut pending = Vec::new();
+        while let Some(kv) = it.next().await? {
+            let (deadline, stream_id) = kv::stream_purge_deadline::deser_key(kv.key)?;
+            let deleted_at = kv::stream_purge_deadline::deser_value(kv.value)?;
+            assert!(deadline <= now);
+            if pending.len() == PENDING_LIST_LIMIT {
+                return Ok(Page::new(pending, true));
+            }
+            pending.push(PendingPurge {
+                deadline,
+                stream_id,
+                deleted_at,
+            });
+        }
+        Ok(Page::new(pending, false))
+    }
+
+    #[instrument(ret, err, skip(self))]
+    async fn process_stream_purge(&self, pending: PendingPurge) -> Result<(), StreamPurgeError> {
+        if let Some((basin, stream)) = self.stream_id_mapping(pending.stream_id).await?
+            && let Some(meta) = self
+                .db_get(
+                    kv::stream_meta::ser_key(&basin, &stream),
+                    kv::stream_meta::deser_value,
+                )
+                .await?
+            // A stream that was undeleted, and possibly deleted again since, is not purged by a
+            // stale deadline.
+            && meta.deleted_at == Some(pending.deleted_at)
+        {
+            match self
+                .delete_stream_with_condition(basin, stream, TerminalTrimCondition::Always)
+                .await
+            {
+                Ok(()) | Err(DeleteStreamError::StreamNotFound(_)) => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        self.db
+            .delete(kv::stream_purge_deadline::ser_key(
+                pending.deadline,
+                pending.stream_id,
+            ))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        time::{Duration, SystemTime},
+    };
+
+    use s2_common::{
+        basin::BasinName,
+        config::BasinConfig,
+        record::{NonZeroSeqNum, StreamPosition},
+        stream::StreamName,
+    };
+    use time::OffsetDateTime;
+
+    use super::{super::tests::test_backend, TimestampSecs};
+    use crate::{
+        backend::{Backend, clock::Clock, error::UndeleteStreamError, kv},
+        stream_id::StreamId,
+    };
+
+    async fn seed_stream(backend: &Backend, basin: &BasinName, stream: &StreamName) -> StreamId {
+        let stream_id = StreamId::new(basin, stream);
+        backend
+            .db
+            .put(
+                kv::basin_meta::ser_key(basin),
+                kv::basin_meta::ser_value(&kv::basin_meta::BasinMeta {
+                    config: BasinConfig::default(),
+                    created_at: OffsetDateTime::now_utc(),
+                    deleted_at: None,
+                    creation_idempotency_key: None,
+                }),
+            )
+            .await
+            .unwrap();
+        backend
+            .db
+            .put(
+                kv::stream_meta::ser_key(basin, stream),
+                kv::stream_meta::ser_value(&kv::stream_meta::StreamMeta {
+                    config: Default::default(),
+                    cipher: None,
+                    created_at: OffsetDateTime::now_utc(),
+                    deleted_at: None,
+                    creation_idempotency_key: None,
+                }),
+            )
+            .await
+            .unwrap();
+        backend
+            .db
+            .put(
+                kv::stream_id_mapping::ser_key(stream_id),
+                kv::stream_id_mapping::ser_value(basin, stream),
+            )
+            .await
+            .unwrap();
+        backend
+            .db
+            .put(
+                kv::stream_tail_position::ser_key(stream_id),
+                kv::stream_tail_position::ser_value(StreamPosition::MIN),
+            )
+            .await
+            .unwrap();
+        stream_id
+    }
+
+    async fn deleted_at(
+        backend: &Backend,
+        basin: &BasinName,
+        stream: &StreamName,
+    ) -> Option<OffsetDateTime> {
+        let meta = backend
+            .db
+            .get(kv::stream_meta::ser_key(basin, stream))
+            .await
+            .unwrap()
+            .expect("stream meta should exist");
+        kv::stream_meta::deser_value(meta).unwrap().deleted_at
+    }
+
+    async fn purge_expired(backend: &Backend) {
+        let page = backend
+            .list_pending_stream_purges(TimestampSecs::MAX)
+            .await
+            .unwrap();
+        assert!(!page.has_more);
+        for pending in page.values {
+            backend.process_stream_purge(pending).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn deleted_stream_is_retained_until_purged() {
+        let backend = test_backend()
+            .await
+            .with_stream_deletion_grace_period(Duration::from_secs(3600));
+        let basin = BasinName::from_str("purge-basin").unwrap();
+        let stream = StreamName::from_str("purge-stream").unwrap();
+        let stream_id = seed_stream(&backend, &basin, &stream).await;
+
+        backend
+            .delete_stream(basin.clone(), stream.clone())
+            .await
+            .unwrap();
+        assert!(deleted_at(&backend, &basin, &stream).await.is_some());
+        assert!(
+            backend
+                .list_pending_stream_purges(TimestampSecs::now(&backend.clock))
+                .await
+                .unwrap()
+                .values
+                .is_empty()
+        );
+
+        purge_expired(&backend).await;
+
+        let trim_point = backend
+            .db
+            .get(kv::stream_trim_point::ser_key(stream_id))
+            .await
+            .unwrap()
+            .map(|bytes| kv::stream_trim_point::deser_value(bytes).unwrap());
+        assert_eq!(trim_point, Some(..NonZeroSeqNum::MAX));
+        assert!(matches!(
+            backend.undelete_stream(basin, stream).await,
+            Err(UndeleteStreamError::StreamDeletionPending(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn stream_is_purged_once_manual_clock_passes_grace_period() {
+        let clock = Clock::manual(SystemTime::now());
+        let backend = test_backend()
+            .await
+            .with_clock(clock.clone())
+            .with_stream_deletion_grace_period(Duration::from_secs(3600));
+        let basin = BasinName::from_str("purge-basin").unwrap();
+        let stream = StreamName::from_str("clock-stream").unwrap();
+        let stream_id = seed_stream(&backend, &basin, &stream).await;
+        let trim_point = || async {
+            backend
+                .db
+                .get(kv::stream_trim_point::ser_key(stream_id))
+                .await
+                .unwrap()
+                .map(|bytes| kv::stream_trim_point::deser_value(bytes).unwrap())
+        };
+
+        backend
+            .delete_stream(basin.clone(), stream.clone())
+            .await
+            .unwrap();
+        clock.advance(Duration::from_secs(3599));
+        backend.clone().tick_stream_purge().await.unwrap();
+        assert_eq!(trim_point().await, None);
+
+        clock.advance(Duration::from_secs(1));
+        backend.clone().tick_stream_purge().await.unwrap();
+        assert_eq!(trim_point().await, Some(..NonZeroSeqNum::MAX));
+    }
+
+    #[tokio::test]
+    async fn undeleted_stream_is_not_purged() {
+        let backend = test_backend()
+            .await
+            .with_stream_deletion_grace_period(Duration::from_secs(3600));
+        let basin = BasinName::from_str("purge-basin").unwrap();
+        let stream = StreamName::from_str("undelete-stream").unwrap();
+        let stream_id = seed_stream(&backend, &basin, &stream).await;
+
+        backend
+            .delete_stream(basin.clone(), stream.clone())
+            .await
+            .unwrap();
+        let info = backend
+            .undelete_stream(basin.clone(), stream.clone())
+            .await
+            .unwrap();
+        assert!(info.deleted_at.is_none());
+        assert!(deleted_at(&backend, &basin, &stream).await.is_none());
+
+        purge_expired(&backend).await;
+
+        assert!(deleted_at(&backend, &basin, &stream).await.is_none());
+        let trim_point = backend
+            .db
+            .get(kv::stream_trim_point::ser_key(stream_id))
+            .await
+            .unwrap()
+            .map(|bytes| kv::stream_trim_point::deser_value(bytes).unwrap());
+        assert!(trim_point.is_none());
+        assert!(
+            backend
+                .list_pending_stream_purges(TimestampSecs::MAX)
+                .await
+                .unwrap()
+                .values
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,382 @@
+use futures::{StreamExt, stream};
+use s2_common::{record::StreamPosition, resources::Page};
+use slatedb::{
+    WriteBatch,
+    config::{DurabilityLevel, ReadOptions, ScanOptions},
+};
+use tracing::instrument;
+
+use crate::{
+    backend::{
+        Backend,
+        error::StorageError,
+        kv::{self, timestamp::TimestampSecs},
+    },
+    stream_id::StreamId,
+};
+
+const PENDING_LIST_LIMIT: usize = 128;
+const CONCURRENCY: usize = 4;
+const DELETE_BATCH_SIZE: usize = 10_000;
+
+impl Backend {
+    pub(super) async fn tick_stream_record_expiry(self) -> Result<bool, StorageError> {
+        let now = TimestampSecs::now(&self.clock);
+        let page = self.list_streams_with_expired_records(now).await?;
+        if page.values.is_empty() {
+            return Ok(page.has_more);
+        }
+        let mut processed = stream::iter(page.values)
+            .map(|stream_id| {
+                let backend = self.clone();
+                async move { backend.delete_expired_records(stream_id, now).await }
+            })
+            .buffer_unordered(CONCURRENCY);
+        while let Some(result) = processed.next().await {
+            result?;
+        }
+        Ok(page.has_more)
+    }
+
+    /// Streams whose first record has a TTL that has expired, skipping from each stream's first
+    /// expiry entry to the next stream's.
+    ///
+    /// Streams held back by an older record without a TTL are left out, as deleting their
+    /// expired records could make no progress.
+    async fn list_streams_with_expired_records(
+        &self,
+        now: TimestampSecs,
+    ) -> Result<Page<StreamId>, StorageError> {
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let mut pending = Vec::new();
+        let mut after = None;
+        loop {
+            let mut it = self
+                .db
+                .scan_with_options(
+                    kv::stream_record_expiry::ser_streams_after_range(after),
+                    &scan_opts,
+                )
+                .await?;
+            let Some(kv) = it.next().await? else {
+                return Ok(Page::new(pending, false));
+            };
+            let (stream_id, pos) = kv::stream_record_expiry::deser_key(kv.key)?;
+            let expires_at = kv::stream_record_expiry::deser_value(kv.value)?;
+            if expires_at <= now && self.first_record_position(stream_id).await? == Some(pos) {
+                if pending.len() == PENDING_LIST_LIMIT {
+                    return Ok(Page::new(pending, true));
+                }
+                pending.push(stream_id);
+            }
+            after = Some(stream_id);
+        }
+    }
+
+    async fn first_record_position(
+        &self,
+        stream_id: StreamId,
+    ) -> Result<Option<StreamPosition>, StorageError> {
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let mut it = self
+            .db
+            .scan_prefix_with_options(
+                kv::stream_record_data::ser_key_prefix(stream_id),
+                &scan_opts,
+            )
+            .await?;
+        let Some(kv) = it.next().await? else {
+            return Ok(None);
+        };
+        let (_, pos) = kv::stream_record_data::deser_key(kv.key)?;
+        Ok(Some(pos))
+    }
+
+    /// Delete the stream's records from its start for as long as each one has expired, stopping
+    /// at the first record without a TTL or with a later expiry so that no gap is left
+    /// mid-stream.
+    #[instrument(ret, err, skip(self))]
+    async fn delete_expired_records(
+        &self,
+        stream_id: StreamId,
+        now: TimestampSecs,
+    ) -> Result<usize, StorageError> {
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let read_opts = ReadOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        // Timestamps are non-decreasing in sequence number order, so this visits records in
+        // stream order.
+        let mut it = self
+            .db
+            .scan_prefix_with_options(
+                kv::stream_record_timestamp::ser_key_prefix(stream_id),
+                &scan_opts,
+            )
+            .await?;
+        let mut batch = WriteBatch::new();
+        let mut batch_size = 0usize;
+        let mut deleted = 0usize;
+        let mut trimmed_end = None;
+        while let Some(kv) = it.next().await? {
+            let (deser_stream_id, pos) = kv::stream_record_timestamp::deser_key(kv.key.clone())?;
+            debug_assert_eq!(deser_stream_id, stream_id);
+            let expiry_key = kv::stream_record_expiry::ser_key(stream_id, pos);
+            let Some(expires_at) = self
+                .db
+                .get_with_options(expiry_key.clone(), &read_opts)
+                .await?
+                .map(kv::stream_record_expiry::deser_value)
+                .transpose()?
+            else {
+                break;
+            };
+            if expires_at > now {
+                break;
+            }
+            batch.delete(kv.key);
+            batch.delete(kv::stream_record_data::ser_key(stream_id, pos));
+            batch.delete(expiry_key);
+            batch_size += 1;
+            deleted += 1;
+            trimmed_end = Some(pos.seq_num + 1);
+            if batch_size >= DELETE_BATCH_SIZE {
+                self.db.write(batch).await?;
+                batch = WriteBatch::new();
+                batch_size = 0;
+            }
+        }
+        if batch_size > 0 {
+            self.db.write(batch).await?;
+        }
+        if let Some(trimmed_end) = trimmed_end {
+            self.read_cache.invalidate(stream_id, trimmed_end);
+        }
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        time::{Duration, SystemTime},
+    };
+
+    use bytes::Bytes;
+    use s2_common::{
+        basin::BasinName,
+        config::{BasinConfig, OptionalStreamConfig},
+        record::{EnvelopeRecord, Header, Metered, MeteredExt as _, Record, StreamPosition},
+        resources::ProvisionMode,
+        stream::{AppendInput, AppendRecord, AppendRecordBatch, AppendRecordParts, StreamName},
+    };
+    use s2_storage::record::StoredRecord;
+
+    use super::{super::tests::test_backend, TimestampSecs};
+    use crate::{
+        backend::{Backend, clock::Clock, kv},
+        stream_id::StreamId,
+    };
+
+    fn test_record() -> Metered<StoredRecord> {
+        let record = Record::try_from_parts(vec![], Bytes::from_static(b"ttl-test")).unwrap();
+        StoredRecord::from(record).metered()
+    }
+
+    fn pos(seq_num: u64) -> StreamPosition {
+        StreamPosition {
+            seq_num,
+            timestamp: 1000 + seq_num,
+        }
+    }
+
+    async fn seed_record(
+        backend: &Backend,
+        stream_id: StreamId,
+        seq_num: u64,
+        expires_at: Option<TimestampSecs>,
+    ) {
+        let metered = test_record();
+        backend
+            .db
+            .put(
+                kv::stream_record_data::ser_key(stream_id, pos(seq_num)),
+                kv::stream_record_data::ser_value(metered.as_ref()),
+            )
+            .await
+            .unwrap();
+        backend
+            .db
+            .put(
+                kv::stream_record_timestamp::ser_key(stream_id, pos(seq_num)),
+                kv::stream_record_timestamp::ser_value(),
+            )
+            .await
+            .unwrap();
+        if let Some(expires_at) = expires_at {
+            backend
+                .db
+                .put(
+                    kv::stream_record_expiry::ser_key(stream_id, pos(seq_num)),
+                    kv::stream_record_expiry::ser_value(expires_at),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn has_record(backend: &Backend, stream_id: StreamId, seq_num: u64) -> bool {
+        backend
+            .db
+            .get(kv::stream_record_data::ser_key(stream_id, pos(seq_num)))
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    #[tokio::test]
+    async fn expired_records_are_deleted_from_stream_start() {
+        let backend = test_backend().await;
+        let stream_id: StreamId = [1u8; StreamId::LEN].into();
+        let expired = Some(TimestampSecs::from_secs(100));
+        let live = Some(TimestampSecs::MAX);
+
+        for (seq_num, expires_at) in [(0, expired), (1, expired), (2, live), (3, expired)] {
+            seed_record(&backend, stream_id, seq_num, expires_at).await;
+        }
+
+        backend.clone().tick_stream_record_expiry().await.unwrap();
+
+        assert!(!has_record(&backend, stream_id, 0).await);
+        assert!(!has_record(&backend, stream_id, 1).await);
+        // Record 3 has expired, but trimming it would leave a gap after the unexpired record 2.
+        assert!(has_record(&backend, stream_id, 2).await);
+        assert!(has_record(&backend, stream_id, 3).await);
+        let expiry = backend
+            .db
+            .get(kv::stream_record_expiry::ser_key(stream_id, pos(0)))
+            .await
+            .unwrap();
+        assert!(expiry.is_none());
+    }
+
+    #[tokio::test]
+    async fn record_without_ttl_holds_back_expiry() {
+        let backend = test_backend().await;
+        let stream_id: StreamId = [2u8; StreamId::LEN].into();
+        let expired = Some(TimestampSecs::from_secs(100));
+
+        seed_record(&backend, stream_id, 0, None).await;
+        seed_record(&backend, stream_id, 1, expired).await;
+
+        // The stream is not listed, so it cannot keep the expiry task busy.
+        let page = backend
+            .list_streams_with_expired_records(TimestampSecs::now(&backend.clock))
+            .await
+            .unwrap();
+        assert!(page.values.is_empty());
+        assert!(!page.has_more);
+        let deleted = backend
+            .delete_expired_records(stream_id, TimestampSecs::now(&backend.clock))
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, 0);
+        assert!(has_record(&backend, stream_id, 0).await);
+        assert!(has_record(&backend, stream_id, 1).await);
+    }
+
+    fn append_record(ttl_secs: Option<&'static str>) -> AppendRecord {
+        let headers = ttl_secs
+            .map(|ttl_secs| Header {
+                name: Bytes::from_static(b"s2-ttl-seconds"),
+                value: Bytes::from_static(ttl_secs.as_bytes()),
+            })
+            .into_iter()
+            .collect();
+        let record = Record::Envelope(
+            EnvelopeRecord::try_from_parts(headers, Bytes::from_static(b"ttl-test")).unwrap(),
+        );
+        AppendRecord::try_from(AppendRecordParts {
+            timestamp: None,
+            record: Metered::from(record),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn ttl_header_on_append_expires_records() {
+        let clock = Clock::manual(SystemTime::now());
+        let backend = test_backend().await.with_clock(clock.clone());
+        let basin = BasinName::from_str("ttl-basin").unwrap();
+        let stream = StreamName::from_str("ttl-stream").unwrap();
+        backend
+            .provision_basin(
+                basin.clone(),
+                BasinConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+        backend
+            .provision_stream(
+                basin.clone(),
+                stream.clone(),
+                OptionalStreamConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+        let records = [Some("60"), Some("60"), None, Some("1")]
+            .into_iter()
+            .map(append_record)
+            .collect::<Vec<_>>();
+        backend
+            .open_for_append(&basin, &stream, None)
+            .await
+            .unwrap()
+            .append(AppendInput {
+                records: AppendRecordBatch::try_from(records).unwrap(),
+                match_seq_num: None,
+                fencing_token: None,
+            })
+            .await
+            .unwrap();
+        let stream_id = StreamId::new(&basin, &stream);
+        let first_seq_num = || async {
+            backend
+                .first_record_position(stream_id)
+                .await
+                .unwrap()
+                .map(|pos| pos.seq_num)
+        };
+
+        backend.clone().tick_stream_record_expiry().await.unwrap();
+        assert_eq!(first_seq_num().await, Some(0));
+
+        clock.advance(Duration::from_secs(61));
+        assert!(!backend.clone().tick_stream_record_expiry().await.unwrap());
+        // The record without a TTL holds back the expired record after it.
+        assert_eq!(first_seq_num().await, Some(2));
+        let page = backend
+            .list_streams_with_expired_records(TimestampSecs::now(&backend.clock))
+            .await
+            .unwrap();
+        assert!(page.values.is_empty());
+    }
+}
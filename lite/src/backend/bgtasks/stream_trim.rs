@@ -1,7 +1,10 @@
 use std::ops::RangeTo;
 
 use futures::{StreamExt, stream};
-use s2_common::{record::NonZeroSeqNum, resources::Page};
+use s2_common::{
+    record::{NonZeroSeqNum, SeqNum},
+    resources::Page,
+};
 use slatedb::{
     WriteBatch,
     config::{DurabilityLevel, ScanOptions},
@@ -58,7 +61,7 @@ impl Backend {
         Ok(Page::new(pending, false))
     }
 
-    async fn process_trim(
+    pub(in crate::backend) async fn process_trim(
         &self,
         stream_id: StreamId,
         trim_point: RangeTo<NonZeroSeqNum>,
@@ -102,6 +105,46 @@ impl Backend {
                 batch_size = 0;
             }
         }
+        let mut it = self
+            .db
+            .scan_with_options(
+                kv::stream_command_index::ser_range(stream_id, SeqNum::MIN),
+                &scan_opts,
+            )
+            .await?;
+        while let Some(kv) = it.next().await? {
+            let (_, pos) = kv::stream_command_index::deser_key(kv.key.clone())?;
+            if pos.seq_num >= trim_point.end.get() {
+                break;
+            }
+            batch.delete(kv.key);
+            batch_size += 1;
+            if batch_size >= DELETE_BATCH_SIZE {
+                self.db.write(batch).await?;
+                batch = WriteBatch::new();
+                batch_size = 0;
+            }
+        }
+        let mut it = self
+            .db
+            .scan_prefix_with_options(
+                kv::stream_record_expiry::ser_key_prefix(stream_id),
+                &scan_opts,
+            )
+            .await?;
+        while let Some(kv) = it.next().await? {
+            let (_, pos) = kv::stream_record_expiry::deser_key(kv.key.clone())?;
+            if pos.seq_num >= trim_point.end.get() {
+                break;
+            }
+            batch.delete(kv.key);
+            batch_size += 1;
+            if batch_size >= DELETE_BATCH_SIZE {
+                self.db.write(batch).await?;
+                batch = WriteBatch::new();
+                batch_size = 0;
+            }
+        }
         if batch_size > 0 {
             self.db.write(batch).await?;
         }
@@ -138,11 +181,31 @@ impl Backend {
             if let Some((basin, stream)) =
                 db_txn_get(&txn, &id_mapping_key, kv::stream_id_mapping::deser_value).await?
             {
-                txn.delete(kv::stream_meta::ser_key(&basin, &stream))?;
+                let meta_key = kv::stream_meta::ser_key(&basin, &stream);
+                if let Some(meta) =
+                    db_txn_get(&txn, &meta_key, kv::stream_meta::deser_value).await?
+                {
+                    txn.delete(kv::stream_created_at::ser_key(
+                        &basin,
+                        meta.created_at,
+                        &stream,
+                    ))?;
+                }
+                txn.delete(meta_key)?;
                 txn.delete(id_mapping_key)?;
             }
             txn.delete(kv::stream_tail_position::ser_key(stream_id))?;
             txn.delete(kv::stream_fencing_token::ser_key(stream_id))?;
+            let mut it = self
+                .db
+                .scan_prefix_with_options(
+                    kv::stream_cursor::ser_key_prefix(stream_id),
+                    &ScanOptions::default(),
+                )
+                .await?;
+            while let Some(kv) = it.next().await? {
+                txn.delete(kv.key)?;
+            }
         }
         txn.commit().await?;
         Ok(())
@@ -205,6 +268,16 @@ mod tests {
                 )
                 .await
                 .unwrap();
+            if seq % 3 == 1 {
+                backend
+                    .db
+                    .put(
+                        kv::stream_command_index::ser_key(stream_id, pos),
+                        kv::stream_command_index::ser_value(),
+                    )
+                    .await
+                    .unwrap();
+            }
         }
 
         backend
@@ -241,6 +314,18 @@ mod tests {
                 assert!(timestamp.is_some());
             }
         }
+        for (seq, indexed) in [(1, false), (4, true)] {
+            let pos = StreamPosition {
+                seq_num: seq,
+                timestamp: 1000 + seq,
+            };
+            let command = backend
+                .db
+                .get(kv::stream_command_index::ser_key(stream_id, pos))
+                .await
+                .unwrap();
+            assert_eq!(command.is_some(), indexed);
+        }
 
         let trim_point = backend
             .db
@@ -274,6 +359,14 @@ mod tests {
             )
             .await
             .unwrap();
+        backend
+            .db
+            .put(
+                kv::stream_created_at::ser_key(&basin, meta.created_at, &stream),
+                kv::stream_created_at::ser_value(),
+            )
+            .await
+            .unwrap();
         backend
             .db
             .put(
@@ -343,6 +436,16 @@ mod tests {
             .await
             .unwrap();
         assert!(meta_bytes.is_none());
+        let created_at_bytes = backend
+            .db
+            .get(kv::stream_created_at::ser_key(
+                &basin,
+                meta.created_at,
+                &stream,
+            ))
+            .await
+            .unwrap();
+        assert!(created_at_bytes.is_none());
         let mapping_bytes = backend
             .db
             .get(kv::stream_id_mapping::ser_key(stream_id))
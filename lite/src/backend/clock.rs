@@ -0,0 +1,84 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use parking_lot::Mutex;
+use s2_common::record::Timestamp;
+use time::OffsetDateTime;
+
+/// Source of wall-clock time for record timestamps and time-based stream lifecycle, i.e.
+/// retention, delete-on-empty and deletion grace periods.
+///
+/// Defaults to the system clock. A [`Clock::manual`] clock only moves when advanced, so tests can
+/// step past deadlines without waiting on them. Record expiry by storage TTL follows SlateDB's own
+/// clock, not this one.
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    manual: Option<Arc<Mutex<SystemTime>>>,
+}
+
+impl Clock {
+    pub fn system() -> Self {
+        Self::default()
+    }
+
+    /// A clock that starts at `now` and only moves on [`Clock::advance`].
+    pub fn manual(now: SystemTime) -> Self {
+        Self {
+            manual: Some(Arc::new(Mutex::new(now))),
+        }
+    }
+
+    pub fn now(&self) -> SystemTime {
+        match &self.manual {
+            Some(now) => *now.lock(),
+            None => SystemTime::now(),
+        }
+    }
+
+    /// Move a manual clock forward by `by`.
+    ///
+    /// # Panics
+    ///
+    /// If this is the system clock.
+    pub fn advance(&self, by: Duration) {
+        let now = self
+            .manual
+            .as_ref()
+            .expect("only a manual clock can be advanced");
+        let mut now = now.lock();
+        *now += by;
+    }
+
+    pub fn now_utc(&self) -> OffsetDateTime {
+        self.now().into()
+    }
+
+    /// Milliseconds since the Unix epoch, as used for record timestamps.
+    pub fn timestamp_millis(&self) -> Timestamp {
+        self.now()
+            .duration_since(UNIX_EPOCH)
+            .expect("21st century")
+            .as_millis()
+            .try_into()
+            .expect("Milliseconds since Unix epoch fits into a u64")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::Clock;
+
+    #[test]
+    fn manual_clock_only_moves_when_advanced() {
+        let clock = Clock::manual(UNIX_EPOCH + Duration::from_secs(1_000));
+        assert_eq!(clock.timestamp_millis(), 1_000_000);
+        assert_eq!(clock.clone().timestamp_millis(), 1_000_000);
+
+        clock.clone().advance(Duration::from_secs(60));
+        assert_eq!(clock.timestamp_millis(), 1_060_000);
+    }
+}
@@ -0,0 +1,77 @@
+use s2_common::{
+    basin::BasinName,
+    record::{CommandRecord, Record, SeqNum, StreamPosition},
+    resources::{ListLimit, Page},
+    stream::StreamName,
+};
+use s2_storage::record::StoredRecord;
+use slatedb::config::{DurabilityLevel, ScanOptions};
+
+use super::Backend;
+use crate::{
+    backend::{
+        error::{ListStreamCommandsError, StreamDeletionPendingError, StreamNotFoundError},
+        kv,
+    },
+    stream_id::StreamId,
+};
+
+impl Backend {
+    /// List the stream's `fence` and `trim` command records starting at `start_seq_num`, using
+    /// the command index rather than scanning all records.
+    pub async fn list_stream_commands(
+        &self,
+        basin: BasinName,
+        stream: StreamName,
+        start_seq_num: SeqNum,
+        limit: ListLimit,
+    ) -> Result<Page<(StreamPosition, CommandRecord)>, ListStreamCommandsError> {
+        let meta = self
+            .db_get(
+                kv::stream_meta::ser_key(&basin, &stream),
+                kv::stream_meta::deser_value,
+            )
+            .await?
+            .ok_or_else(|| StreamNotFoundError {
+                basin: basin.clone(),
+                stream: stream.clone(),
+            })?;
+        if meta.deleted_at.is_some() {
+            return Err(StreamDeletionPendingError.into());
+        }
+        let stream_id = StreamId::new(&basin, &stream);
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let mut it = self
+            .db
+            .scan_with_options(
+                kv::stream_command_index::ser_range(stream_id, start_seq_num),
+                &scan_opts,
+            )
+            .await?;
+        let mut commands = Vec::new();
+        while let Some(kv) = it.next().await? {
+            if commands.len() >= limit.as_usize() {
+                return Ok(Page::new(commands, true));
+            }
+            let (_, pos) = kv::stream_command_index::deser_key(kv.key)?;
+            kv::stream_command_index::deser_value(kv.value)?;
+            let Some(record) = self
+                .db_get(
+                    kv::stream_record_data::ser_key(stream_id, pos),
+                    kv::stream_record_data::deser_value,
+                )
+                .await?
+            else {
+                // Trimmed or expired since the index entry was read.
+                continue;
+            };
+            if let StoredRecord::Plaintext(Record::Command(command)) = record.into_inner() {
+                commands.push((pos, command));
+            }
+        }
+        Ok(Page::new(commands, false))
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use bytesize::ByteSize;
 use dashmap::DashMap;
@@ -6,29 +6,39 @@ use futures::{
     FutureExt as _,
     future::{BoxFuture, Shared},
 };
+use parking_lot::Mutex;
 use s2_common::{
     basin::BasinName,
-    config::{BasinConfig, OptionalStreamConfig},
+    config::{BasinConfig, OptionalStreamConfig, StreamConfig},
     encryption::{EncryptionAlgorithm, EncryptionSpec},
     record::{NonZeroSeqNum, SeqNum, StreamPosition},
     resources::ProvisionMode,
     stream::StreamName,
 };
 use slatedb::config::{DurabilityLevel, ReadOptions, ScanOptions};
-use tokio::sync::{Semaphore, broadcast};
+use tokio::{
+    sync::{Semaphore, broadcast},
+    task::JoinHandle,
+};
+use tokio_util::sync::CancellationToken;
 
 use super::{
     StreamHandle,
+    basins::BasinConfigCache,
+    clock::Clock,
     durability_notifier::DurabilityNotifier,
     error::{
         BasinDeletionPendingError, BasinNotFoundError, GetBasinConfigError, ProvisionStreamError,
-        StorageError, StreamDeletionPendingError, StreamNotFoundError, StreamerError,
-        StreamerMissingInActionError, TransactionConflictError,
+        StorageError, StreamDeletionPendingError, StreamNotFoundError, StreamQuotaExceededError,
+        StreamerError, StreamerMissingInActionError, TransactionConflictError,
     },
     kv,
+    memory_budget::MemoryBudget,
+    rate_limit::AppendRateLimiters,
+    read_cache::ReadCache,
     streamer::{GuardedStreamerClient, StreamerClient, StreamerGenerationId},
 };
-use crate::{backend::bgtasks::BgtaskTrigger, stream_id::StreamId};
+use crate::{backend::bgtasks::BgtaskTrigger, metrics::MemoryComponent, stream_id::StreamId};
 
 type StreamerInitFuture = Shared<BoxFuture<'static, Result<StreamerClient, StreamerError>>>;
 
@@ -43,34 +53,206 @@ enum StreamerClientSlot {
     },
 }
 
+/// A committed change that may affect a stream's configuration.
+#[derive(Debug, Clone)]
+pub enum StreamConfigChange {
+    /// The stream was reconfigured, or updated by ensuring it.
+    Updated {
+        basin: BasinName,
+        stream: StreamName,
+        config: StreamConfig,
+    },
+    /// The stream was deleted.
+    Deleted {
+        basin: BasinName,
+        stream: StreamName,
+    },
+    /// The basin was reconfigured, which may change the defaults its streams resolve against.
+    BasinReconfigured { basin: BasinName },
+}
+
 #[derive(Clone)]
 pub struct Backend {
     pub(super) db: slatedb::Db,
     streamer_slots: Arc<DashMap<StreamId, StreamerClientSlot>>,
+    append_inflight_bytes: usize,
     append_inflight_bytes_sema: Arc<Semaphore>,
+    pub(super) append_rate_limiters: AppendRateLimiters,
     durability_notifier: DurabilityNotifier,
     bgtask_trigger_tx: broadcast::Sender<BgtaskTrigger>,
+    stream_config_tx: broadcast::Sender<StreamConfigChange>,
+    bgtasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    shutdown: CancellationToken,
+    pub(super) read_cache: ReadCache,
+    append_memory_budget: MemoryBudget,
+    read_ahead_memory_budget: MemoryBudget,
+    pub(super) basin_config_cache: BasinConfigCache,
+    read_ahead_batches: usize,
+    follower_max_lag: usize,
+    max_unary_read_wait: Duration,
+    pub(super) stream_deletion_grace_period: Duration,
+    pub(super) max_streams_per_basin: Option<usize>,
+    pub(super) clock: Clock,
 }
 
 impl Backend {
-    pub fn new(db: slatedb::Db, append_inflight_bytes: ByteSize) -> Self {
+    pub fn new(
+        db: slatedb::Db,
+        append_inflight_bytes: ByteSize,
+        read_cache_bytes: ByteSize,
+    ) -> Self {
         let (bgtask_trigger_tx, _) = broadcast::channel(16);
-        let append_inflight_bytes = Arc::new(Semaphore::new(
-            (append_inflight_bytes.as_u64() as usize).clamp(
-                s2_common::caps::RECORD_BATCH_MAX.bytes,
-                Semaphore::MAX_PERMITS,
-            ),
-        ));
+        let (stream_config_tx, _) = broadcast::channel(64);
+        let append_inflight_bytes = (append_inflight_bytes.as_u64() as usize).clamp(
+            s2_common::caps::RECORD_BATCH_MAX.bytes,
+            Semaphore::MAX_PERMITS,
+        );
         let durability_notifier = DurabilityNotifier::spawn(&db);
         Self {
             db,
             streamer_slots: Arc::new(DashMap::new()),
-            append_inflight_bytes_sema: append_inflight_bytes,
+            append_inflight_bytes,
+            append_inflight_bytes_sema: Arc::new(Semaphore::new(append_inflight_bytes)),
+            append_rate_limiters: AppendRateLimiters::default(),
             durability_notifier,
             bgtask_trigger_tx,
+            stream_config_tx,
+            bgtasks: Arc::new(Mutex::new(Vec::new())),
+            shutdown: CancellationToken::new(),
+            read_cache: ReadCache::new(read_cache_bytes),
+            append_memory_budget: MemoryBudget::default(),
+            read_ahead_memory_budget: MemoryBudget::default(),
+            basin_config_cache: BasinConfigCache::default(),
+            read_ahead_batches: 0,
+            follower_max_lag: super::FOLLOWER_MAX_LAG,
+            max_unary_read_wait: super::MAX_UNARY_READ_WAIT,
+            stream_deletion_grace_period: Duration::ZERO,
+            max_streams_per_basin: None,
+            clock: Clock::system(),
+        }
+    }
+
+    /// Bound the combined size of in-flight appends to `memory_budget_bytes`, blocking appends
+    /// until earlier ones are acknowledged once the budget is used up. Zero disables the budget.
+    pub fn with_append_memory_budget(self, memory_budget_bytes: ByteSize) -> Self {
+        Self {
+            append_memory_budget: MemoryBudget::new(
+                memory_budget_bytes,
+                MemoryComponent::AppendBudget,
+            ),
+            ..self
+        }
+    }
+
+    /// Bound the combined size of prefetched read batches to `memory_budget_bytes`, stalling
+    /// read-ahead until buffered batches are delivered once the budget is used up. Zero disables
+    /// the budget.
+    pub fn with_read_ahead_memory_budget(self, memory_budget_bytes: ByteSize) -> Self {
+        Self {
+            read_ahead_memory_budget: MemoryBudget::new(
+                memory_budget_bytes,
+                MemoryComponent::ReadAheadBudget,
+            ),
+            ..self
+        }
+    }
+
+    /// Prefetch up to `read_ahead_batches` read session outputs while earlier ones are being
+    /// delivered. Zero disables read-ahead.
+    pub fn with_read_ahead_batches(self, read_ahead_batches: usize) -> Self {
+        Self {
+            read_ahead_batches,
+            ..self
+        }
+    }
+
+    /// Allow followers to fall up to `follower_max_lag` durable record batches behind a stream's
+    /// tail before they have to catch up from storage. Clamped to at least 1.
+    pub fn with_follower_max_lag(self, follower_max_lag: usize) -> Self {
+        Self {
+            follower_max_lag: follower_max_lag.max(1),
+            ..self
+        }
+    }
+
+    /// Cap how long a unary read may wait for new records.
+    pub fn with_max_unary_read_wait(self, max_unary_read_wait: Duration) -> Self {
+        Self {
+            max_unary_read_wait,
+            ..self
+        }
+    }
+
+    /// Retain deleted streams for `stream_deletion_grace_period` before purging them, during
+    /// which they can be undeleted. Zero purges streams as soon as they are deleted.
+    pub fn with_stream_deletion_grace_period(self, stream_deletion_grace_period: Duration) -> Self {
+        Self {
+            stream_deletion_grace_period,
+            ..self
+        }
+    }
+
+    /// Reject creating a stream in a basin that already has `max_streams_per_basin` streams,
+    /// including streams whose deletion is pending. `None` leaves the number unbounded.
+    pub fn with_max_streams_per_basin(self, max_streams_per_basin: Option<usize>) -> Self {
+        Self {
+            max_streams_per_basin,
+            ..self
+        }
+    }
+
+    /// Read wall-clock time from `clock` instead of the system clock.
+    pub fn with_clock(self, clock: Clock) -> Self {
+        Self { clock, ..self }
+    }
+
+    pub fn max_unary_read_wait(&self) -> Duration {
+        self.max_unary_read_wait
+    }
+
+    /// Signal shutdown. New appends are rejected and background tasks exit after their current
+    /// tick. Safe to call more than once.
+    pub fn initiate_shutdown(&self) {
+        self.shutdown.cancel();
+    }
+
+    /// Initiate shutdown, wait for background tasks to exit and in-flight appends to be
+    /// acknowledged, then close the database.
+    pub async fn shutdown(&self) -> Result<(), StorageError> {
+        self.initiate_shutdown();
+        let bgtasks = std::mem::take(&mut *self.bgtasks.lock());
+        tokio::join!(
+            futures::future::join_all(bgtasks),
+            self.drain_inflight_appends(),
+        );
+        self.db.close().await?;
+        Ok(())
+    }
+
+    async fn drain_inflight_appends(&self) {
+        // Once every permit is held, no append can be in flight.
+        let mut permits = Vec::new();
+        let mut remaining = self.append_inflight_bytes;
+        while remaining > 0 {
+            let n = u32::try_from(remaining).unwrap_or(u32::MAX);
+            permits.push(
+                self.append_inflight_bytes_sema
+                    .acquire_many(n)
+                    .await
+                    .expect("semaphore is never closed"),
+            );
+            remaining -= n as usize;
         }
     }
 
+    pub(super) fn shutdown_signal(&self) -> CancellationToken {
+        self.shutdown.clone()
+    }
+
+    pub(super) fn track_bgtask(&self, handle: JoinHandle<()>) {
+        self.bgtasks.lock().push(handle);
+    }
+
     pub(super) fn bgtask_trigger(&self, trigger: BgtaskTrigger) {
         let _ = self.bgtask_trigger_tx.send(trigger);
     }
@@ -79,6 +261,15 @@ impl Backend {
         self.bgtask_trigger_tx.subscribe()
     }
 
+    pub(super) fn publish_stream_config_change(&self, change: StreamConfigChange) {
+        let _ = self.stream_config_tx.send(change);
+    }
+
+    /// Subscribe to configuration changes across all streams.
+    pub fn subscribe_stream_configs(&self) -> broadcast::Receiver<StreamConfigChange> {
+        self.stream_config_tx.subscribe()
+    }
+
     async fn start_streamer(
         &self,
         generation_id: StreamerGenerationId,
@@ -123,6 +314,8 @@ impl Backend {
         Ok(super::streamer::Spawner {
             generation_id,
             db: self.db.clone(),
+            basin,
+            stream,
             stream_id,
             config: meta.config,
             cipher: meta.cipher,
@@ -130,9 +323,16 @@ impl Backend {
             last_tail_write_timestamp,
             fencing_token,
             trim_point: ..trim_point.map_or(SeqNum::MIN, |tp| tp.end.get()),
+            deleted: meta.deleted_at.is_some(),
             append_inflight_bytes_sema: self.append_inflight_bytes_sema.clone(),
+            memory_budget: self.append_memory_budget.clone(),
+            append_rate_limiters: self.append_rate_limiters.clone(),
             durability_notifier: self.durability_notifier.clone(),
             bgtask_trigger_tx: self.bgtask_trigger_tx.clone(),
+            read_cache: self.read_cache.clone(),
+            follower_max_lag: self.follower_max_lag,
+            clock: self.clock.clone(),
+            shutdown: self.shutdown.clone(),
         }
         .spawn(move |client_id| {
             streamer_slots.remove_if(&stream_id, |_, slot| {
@@ -321,15 +521,32 @@ impl Backend {
             + From<TransactionConflictError>
             + From<BasinDeletionPendingError>
             + From<StreamDeletionPendingError>
-            + From<StreamNotFoundError>,
+            + From<StreamNotFoundError>
+            + From<StreamQuotaExceededError>,
     {
+        // A stream pending deletion keeps its streamer until it is purged, but is closed to
+        // reads and appends unless it is undeleted.
+        if let Some(meta) = self
+            .db_get(
+                kv::stream_meta::ser_key(basin, stream),
+                kv::stream_meta::deser_value,
+            )
+            .await?
+            && meta.deleted_at.is_some()
+        {
+            return Err(StreamDeletionPendingError.into());
+        }
         match self.streamer_client_guarded(basin, stream).await {
             Ok(client) => Ok(StreamHandle {
                 db: self.db.clone(),
                 encryption: resolve_encryption(client.cipher())?,
                 client,
+                read_cache: self.read_cache.clone(),
+                read_ahead_memory_budget: self.read_ahead_memory_budget.clone(),
+                read_ahead_batches: self.read_ahead_batches,
+                skip_undecodable_records: false,
             }),
-            Err(StreamerError::StreamNotFound(e)) => {
+            Err(StreamerError::StreamNotFound(not_found)) => {
                 let config = match self.get_basin_config(basin.clone()).await {
                     Ok(config) => config,
                     Err(GetBasinConfigError::Storage(e)) => Err(e)?,
@@ -354,6 +571,8 @@ impl Backend {
                             ProvisionStreamError::StreamDeletionPending(e) => Err(e)?,
                             ProvisionStreamError::BasinNotFound(e) => Err(e)?,
                             ProvisionStreamError::StreamAlreadyExists(_) => {}
+                            ProvisionStreamError::StreamIdCollision(_) => Err(not_found)?,
+                            ProvisionStreamError::StreamQuotaExceeded(e) => Err(e)?,
                             ProvisionStreamError::Validation(_) => {
                                 unreachable!("auto-create uses default config")
                             }
@@ -365,9 +584,13 @@ impl Backend {
                         db: self.db.clone(),
                         encryption,
                         client,
+                        read_cache: self.read_cache.clone(),
+                        read_ahead_memory_budget: self.read_ahead_memory_budget.clone(),
+                        read_ahead_batches: self.read_ahead_batches,
+                        skip_undecodable_records: false,
                     })
                 } else {
-                    Err(e.into())
+                    Err(not_found.into())
                 }
             }
             Err(e) => Err(e.into()),
@@ -398,7 +621,7 @@ mod tests {
             .build()
             .await
             .unwrap();
-        Backend::new(db, ByteSize::b(1))
+        Backend::new(db, ByteSize::b(1), ByteSize::mib(10))
     }
 
     #[tokio::test]
@@ -0,0 +1,77 @@
+use s2_common::{basin::BasinName, consumer::ConsumerName, record::SeqNum, stream::StreamName};
+use slatedb::IsolationLevel;
+
+use super::{Backend, store::db_txn_get};
+use crate::{
+    backend::{
+        error::{
+            CommitStreamCursorError, GetStreamCursorError, StreamDeletionPendingError,
+            StreamNotFoundError,
+        },
+        kv,
+    },
+    stream_id::StreamId,
+};
+
+impl Backend {
+    pub async fn get_stream_cursor(
+        &self,
+        basin: BasinName,
+        stream: StreamName,
+        consumer: ConsumerName,
+    ) -> Result<Option<SeqNum>, GetStreamCursorError> {
+        let meta = self
+            .db_get(
+                kv::stream_meta::ser_key(&basin, &stream),
+                kv::stream_meta::deser_value,
+            )
+            .await?
+            .ok_or_else(|| StreamNotFoundError {
+                basin: basin.clone(),
+                stream: stream.clone(),
+            })?;
+        if meta.deleted_at.is_some() {
+            return Err(StreamDeletionPendingError.into());
+        }
+        let stream_id = StreamId::new(&basin, &stream);
+        let seq_num = self
+            .db_get(
+                kv::stream_cursor::ser_key(stream_id, &consumer),
+                kv::stream_cursor::deser_value,
+            )
+            .await?;
+        Ok(seq_num)
+    }
+
+    pub async fn commit_stream_cursor(
+        &self,
+        basin: BasinName,
+        stream: StreamName,
+        consumer: ConsumerName,
+        seq_num: SeqNum,
+    ) -> Result<(), CommitStreamCursorError> {
+        let txn = self.db.begin(IsolationLevel::SerializableSnapshot).await?;
+
+        let meta = db_txn_get(
+            &txn,
+            kv::stream_meta::ser_key(&basin, &stream),
+            kv::stream_meta::deser_value,
+        )
+        .await?
+        .ok_or_else(|| StreamNotFoundError {
+            basin: basin.clone(),
+            stream: stream.clone(),
+        })?;
+        if meta.deleted_at.is_some() {
+            return Err(StreamDeletionPendingError.into());
+        }
+
+        let stream_id = StreamId::new(&basin, &stream);
+        txn.put(
+            kv::stream_cursor::ser_key(stream_id, &consumer),
+            kv::stream_cursor::ser_value(seq_num),
+        )?;
+        txn.commit().await?;
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
-use std::{ops::RangeTo, sync::Arc};
+use std::{ops::RangeTo, sync::Arc, time::Duration};
 
 use s2_common::{
     basin::BasinName,
     encryption::EncryptionSpecResolutionError,
-    record::{FencingToken, SeqNum, StreamPosition},
+    record::{FencingToken, SeqNum, StreamPosition, Timestamp},
     stream::StreamName,
 };
 use s2_storage::record::RecordDecryptionError;
@@ -50,6 +50,25 @@ pub struct StreamAlreadyExistsError {
     pub stream: StreamName,
 }
 
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "stream id for `{stream}` in basin `{basin}` collides with `{mapped_stream}` in basin \
+     `{mapped_basin}`"
+)]
+pub struct StreamIdCollisionError {
+    pub basin: BasinName,
+    pub stream: StreamName,
+    pub mapped_basin: BasinName,
+    pub mapped_stream: StreamName,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("basin `{basin}` has reached its limit of {limit} streams")]
+pub struct StreamQuotaExceededError {
+    pub basin: BasinName,
+    pub limit: usize,
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("basin `{basin}` is being deleted")]
 pub struct BasinDeletionPendingError {
@@ -64,6 +83,12 @@ pub struct StreamDeletionPendingError;
 #[error("unwritten position: {0}")]
 pub struct UnwrittenError(pub StreamPosition);
 
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("trimmed position, earliest available sequence number: {earliest_available}")]
+pub struct TrimmedError {
+    pub earliest_available: SeqNum,
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("streamer missing in action")]
 pub struct StreamerMissingInActionError;
@@ -72,10 +97,24 @@ pub struct StreamerMissingInActionError;
 #[error("request dropped")]
 pub struct RequestDroppedError;
 
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("shutting down")]
+pub struct ShuttingDownError;
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("record timestamp was required but was missing")]
 pub struct AppendTimestampRequiredError;
 
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "record timestamp {timestamp} is more than {max_future_skew_secs}s ahead of arrival time {arrival_timestamp}"
+)]
+pub struct AppendTimestampTooFarInFutureError {
+    pub timestamp: Timestamp,
+    pub arrival_timestamp: Timestamp,
+    pub max_future_skew_secs: u64,
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("max assignable sequence number is {max_assignable_seq_num}; attempted {assigned_seq_num}")]
 pub struct MaxSeqNumError {
@@ -84,6 +123,32 @@ pub struct MaxSeqNumError {
     pub max_assignable_seq_num: SeqNum,
 }
 
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("trim would retain {retained_records} records; at least {min_retained_records} required")]
+pub struct MinRetainedRecordsError {
+    pub first_seq_num: SeqNum,
+    pub trim_point: SeqNum,
+    pub retained_records: u64,
+    pub min_retained_records: u64,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AppendRateLimitedError {
+    #[error("append throughput limit of {0} bytes per second exceeded")]
+    Bytes(u64, Duration),
+    #[error("append rate limit of {0} appends per second exceeded")]
+    Ops(u64, Duration),
+}
+
+impl AppendRateLimitedError {
+    /// How long until the limiter would admit the rejected append.
+    pub fn retry_after(&self) -> Duration {
+        match self {
+            Self::Bytes(_, retry_after) | Self::Ops(_, retry_after) => *retry_after,
+        }
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("transaction conflict occurred – this is usually retriable")]
 pub struct TransactionConflictError;
@@ -107,13 +172,21 @@ pub(super) enum AppendErrorInternal {
     #[error(transparent)]
     RequestDroppedError(#[from] RequestDroppedError),
     #[error(transparent)]
+    ShuttingDown(#[from] ShuttingDownError),
+    #[error(transparent)]
     StreamDeletionPending(#[from] StreamDeletionPendingError),
     #[error(transparent)]
     ConditionFailed(#[from] AppendConditionFailedError),
     #[error(transparent)]
     TimestampMissing(#[from] AppendTimestampRequiredError),
     #[error(transparent)]
+    TimestampTooFarInFuture(#[from] AppendTimestampTooFarInFutureError),
+    #[error(transparent)]
     MaxSeqNum(#[from] MaxSeqNumError),
+    #[error(transparent)]
+    MinRetainedRecords(#[from] MinRetainedRecordsError),
+    #[error(transparent)]
+    RateLimited(#[from] AppendRateLimitedError),
 }
 
 impl AppendErrorInternal {
@@ -121,6 +194,7 @@ impl AppendErrorInternal {
         match self {
             Self::ConditionFailed(e) => e.durability_dependency(),
             Self::MaxSeqNum(e) => e.durability_dependency(),
+            Self::MinRetainedRecords(e) => e.durability_dependency(),
             _ => ..0,
         }
     }
@@ -142,6 +216,8 @@ pub enum CheckTailError {
     BasinDeletionPending(#[from] BasinDeletionPendingError),
     #[error(transparent)]
     StreamDeletionPending(#[from] StreamDeletionPendingError),
+    #[error(transparent)]
+    StreamQuotaExceeded(#[from] StreamQuotaExceededError),
 }
 
 impl From<StreamerError> for CheckTailError {
@@ -167,6 +243,8 @@ pub enum AppendError {
     #[error(transparent)]
     RequestDroppedError(#[from] RequestDroppedError),
     #[error(transparent)]
+    ShuttingDown(#[from] ShuttingDownError),
+    #[error(transparent)]
     BasinNotFound(#[from] BasinNotFoundError),
     #[error(transparent)]
     StreamNotFound(#[from] StreamNotFoundError),
@@ -175,11 +253,19 @@ pub enum AppendError {
     #[error(transparent)]
     StreamDeletionPending(#[from] StreamDeletionPendingError),
     #[error(transparent)]
+    StreamQuotaExceeded(#[from] StreamQuotaExceededError),
+    #[error(transparent)]
     ConditionFailed(#[from] AppendConditionFailedError),
     #[error(transparent)]
     TimestampMissing(#[from] AppendTimestampRequiredError),
     #[error(transparent)]
+    TimestampTooFarInFuture(#[from] AppendTimestampTooFarInFutureError),
+    #[error(transparent)]
     MaxSeqNum(#[from] MaxSeqNumError),
+    #[error(transparent)]
+    MinRetainedRecords(#[from] MinRetainedRecordsError),
+    #[error(transparent)]
+    RateLimited(#[from] AppendRateLimitedError),
 }
 
 impl From<AppendErrorInternal> for AppendError {
@@ -190,10 +276,16 @@ impl From<AppendErrorInternal> for AppendError {
                 AppendError::StreamerMissingInActionError(e)
             }
             AppendErrorInternal::RequestDroppedError(e) => AppendError::RequestDroppedError(e),
+            AppendErrorInternal::ShuttingDown(e) => AppendError::ShuttingDown(e),
             AppendErrorInternal::StreamDeletionPending(e) => AppendError::StreamDeletionPending(e),
             AppendErrorInternal::ConditionFailed(e) => AppendError::ConditionFailed(e),
             AppendErrorInternal::TimestampMissing(e) => AppendError::TimestampMissing(e),
+            AppendErrorInternal::TimestampTooFarInFuture(e) => {
+                AppendError::TimestampTooFarInFuture(e)
+            }
             AppendErrorInternal::MaxSeqNum(e) => AppendError::MaxSeqNum(e),
+            AppendErrorInternal::MinRetainedRecords(e) => AppendError::MinRetainedRecords(e),
+            AppendErrorInternal::RateLimited(e) => AppendError::RateLimited(e),
         }
     }
 }
@@ -231,6 +323,12 @@ impl MaxSeqNumError {
     }
 }
 
+impl MinRetainedRecordsError {
+    pub fn durability_dependency(&self) -> RangeTo<SeqNum> {
+        ..self.first_seq_num
+    }
+}
+
 impl From<StreamerError> for AppendError {
     fn from(e: StreamerError) -> Self {
         match e {
@@ -262,7 +360,11 @@ pub enum ReadError {
     #[error(transparent)]
     StreamDeletionPending(#[from] StreamDeletionPendingError),
     #[error(transparent)]
+    StreamQuotaExceeded(#[from] StreamQuotaExceededError),
+    #[error(transparent)]
     Unwritten(#[from] UnwrittenError),
+    #[error(transparent)]
+    Trimmed(#[from] TrimmedError),
 }
 
 impl From<StreamerError> for ReadError {
@@ -320,6 +422,10 @@ pub enum ProvisionStreamError {
     #[error(transparent)]
     StreamDeletionPending(#[from] StreamDeletionPendingError),
     #[error(transparent)]
+    StreamIdCollision(#[from] StreamIdCollisionError),
+    #[error(transparent)]
+    StreamQuotaExceeded(#[from] StreamQuotaExceededError),
+    #[error(transparent)]
     Validation(#[from] s2_common::ValidationError),
 }
 
@@ -352,6 +458,60 @@ pub enum GetStreamConfigError {
     StreamDeletionPending(#[from] StreamDeletionPendingError),
 }
 
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum GetStreamCursorError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    StreamNotFound(#[from] StreamNotFoundError),
+    #[error(transparent)]
+    StreamDeletionPending(#[from] StreamDeletionPendingError),
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ListStreamCommandsError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    StreamNotFound(#[from] StreamNotFoundError),
+    #[error(transparent)]
+    StreamDeletionPending(#[from] StreamDeletionPendingError),
+}
+
+impl From<slatedb::Error> for ListStreamCommandsError {
+    fn from(e: slatedb::Error) -> Self {
+        Self::Storage(e.into())
+    }
+}
+
+impl From<kv::DeserializationError> for ListStreamCommandsError {
+    fn from(e: kv::DeserializationError) -> Self {
+        Self::Storage(e.into())
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CommitStreamCursorError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    TransactionConflict(#[from] TransactionConflictError),
+    #[error(transparent)]
+    StreamNotFound(#[from] StreamNotFoundError),
+    #[error(transparent)]
+    StreamDeletionPending(#[from] StreamDeletionPendingError),
+}
+
+impl From<slatedb::Error> for CommitStreamCursorError {
+    fn from(err: slatedb::Error) -> Self {
+        if err.kind() == slatedb::ErrorKind::Transaction {
+            Self::TransactionConflict(TransactionConflictError)
+        } else {
+            Self::Storage(err.into())
+        }
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum DeleteStreamError {
     #[error(transparent)]
@@ -392,6 +552,14 @@ pub enum StreamDeleteOnEmptyError {
     DeleteStream(#[from] DeleteStreamError),
 }
 
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum StreamPurgeError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    DeleteStream(#[from] DeleteStreamError),
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ListBasinsError {
     #[error(transparent)]
@@ -490,6 +658,32 @@ impl From<slatedb::Error> for ReconfigureStreamError {
     }
 }
 
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum UndeleteStreamError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    TransactionConflict(#[from] TransactionConflictError),
+    #[error(transparent)]
+    BasinNotFound(#[from] BasinNotFoundError),
+    #[error(transparent)]
+    BasinDeletionPending(#[from] BasinDeletionPendingError),
+    #[error(transparent)]
+    StreamNotFound(#[from] StreamNotFoundError),
+    #[error(transparent)]
+    StreamDeletionPending(#[from] StreamDeletionPendingError),
+}
+
+impl From<slatedb::Error> for UndeleteStreamError {
+    fn from(err: slatedb::Error) -> Self {
+        if err.kind() == slatedb::ErrorKind::Transaction {
+            Self::TransactionConflict(TransactionConflictError)
+        } else {
+            Self::Storage(err.into())
+        }
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum DeleteBasinError {
     #[error(transparent)]
@@ -0,0 +1,394 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use s2_common::{
+    basin::BasinName,
+    record::{SeqNum, StreamPosition},
+    stream::StreamName,
+};
+use slatedb::{
+    WriteBatch,
+    config::{DurabilityLevel, PutOptions, ScanOptions, Ttl},
+};
+
+use super::Backend;
+use crate::{
+    backend::{error::StorageError, kv},
+    stream_id::StreamId,
+};
+
+/// An inconsistency between a stream's records, timestamp index, tail and trim point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsckIssue {
+    /// Record data without a matching timestamp index entry. Repairable.
+    MissingTimestampIndex(StreamPosition),
+    /// Timestamp index entry without matching record data. Repairable.
+    OrphanedTimestampIndex(StreamPosition),
+    /// Record at or beyond the persisted tail.
+    RecordBeyondTail {
+        pos: StreamPosition,
+        tail: StreamPosition,
+    },
+    /// Record timestamp lower than that of the preceding record.
+    NonMonotonicTimestamp {
+        prev: StreamPosition,
+        pos: StreamPosition,
+    },
+    /// Pending trim point beyond the persisted tail.
+    TrimPointBeyondTail {
+        trim_point: SeqNum,
+        tail: StreamPosition,
+    },
+}
+
+impl FsckIssue {
+    pub fn is_repairable(&self) -> bool {
+        matches!(
+            self,
+            Self::MissingTimestampIndex(_) | Self::OrphanedTimestampIndex(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FsckReport {
+    pub records_checked: u64,
+    pub issues: Vec<FsckIssue>,
+    /// Number of issues repaired, if repair was requested.
+    pub repaired: usize,
+}
+
+impl Backend {
+    /// Check every stream, see [`Self::fsck_stream`].
+    pub async fn fsck(
+        &self,
+        repair: bool,
+    ) -> Result<Vec<(BasinName, StreamName, FsckReport)>, StorageError> {
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let mut it = self
+            .db
+            .scan_with_options(kv::key_type_range(kv::KeyType::StreamIdMapping), &scan_opts)
+            .await?;
+        let mut reports = Vec::new();
+        while let Some(kv) = it.next().await? {
+            let (basin, stream) = kv::stream_id_mapping::deser_value(kv.value)?;
+            let report = self.fsck_stream(&basin, &stream, repair).await?;
+            reports.push((basin, stream, report));
+        }
+        Ok(reports)
+    }
+
+    /// Scan a stream's records and timestamp index for mismatches, and verify tail and trim point
+    /// invariants. If `repair` is set, index mismatches are fixed; other issues are only reported.
+    ///
+    /// Each scanned entry is checked with a point lookup of its counterpart, so memory use does
+    /// not grow with the size of the stream. Repairs are written in batches as they accumulate.
+    ///
+    /// Intended to be run while the stream is not being written to, e.g. before serving requests.
+    pub async fn fsck_stream(
+        &self,
+        basin: &BasinName,
+        stream: &StreamName,
+        repair: bool,
+    ) -> Result<FsckReport, StorageError> {
+        let stream_id = StreamId::new(basin, stream);
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let mut report = FsckReport::default();
+        let mut repairs = Repairs::new(repair);
+        let now_millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| i64::try_from(duration.as_millis()).unwrap_or(i64::MAX))
+            .unwrap_or(0);
+
+        let tail = self
+            .db_get(
+                kv::stream_tail_position::ser_key(stream_id),
+                kv::stream_tail_position::deser_value,
+            )
+            .await?
+            .unwrap_or(StreamPosition::MIN);
+        let trim_point = self
+            .db_get(
+                kv::stream_trim_point::ser_key(stream_id),
+                kv::stream_trim_point::deser_value,
+            )
+            .await?;
+        if let Some(trim_point) = trim_point
+            && trim_point.end.get() != SeqNum::MAX
+            && trim_point.end.get() > tail.seq_num
+        {
+            report.issues.push(FsckIssue::TrimPointBeyondTail {
+                trim_point: trim_point.end.get(),
+                tail,
+            });
+        }
+
+        let mut prev: Option<StreamPosition> = None;
+        let mut it = self
+            .db
+            .scan_prefix_with_options(
+                kv::stream_record_data::ser_key_prefix(stream_id),
+                &scan_opts,
+            )
+            .await?;
+        while let Some(kv) = it.next().await? {
+            let (_, pos) = kv::stream_record_data::deser_key(kv.key)?;
+            report.records_checked += 1;
+            let indexed = self
+                .db_get(kv::stream_record_timestamp::ser_key(stream_id, pos), |_| {
+                    Ok(())
+                })
+                .await?
+                .is_some();
+            if !indexed {
+                report.issues.push(FsckIssue::MissingTimestampIndex(pos));
+                let ttl = match kv.expire_ts {
+                    Some(expire_ts) => {
+                        Ttl::ExpireAfter(expire_ts.saturating_sub(now_millis).max(1) as u64)
+                    }
+                    None => Ttl::NoExpiry,
+                };
+                repairs.batch.put_with_options(
+                    kv::stream_record_timestamp::ser_key(stream_id, pos),
+                    kv::stream_record_timestamp::ser_value(),
+                    &PutOptions { ttl },
+                );
+                repairs.added(self).await?;
+            }
+            if pos.seq_num >= tail.seq_num {
+                report
+                    .issues
+                    .push(FsckIssue::RecordBeyondTail { pos, tail });
+            }
+            if let Some(prev) = prev
+                && pos.timestamp < prev.timestamp
+            {
+                report
+                    .issues
+                    .push(FsckIssue::NonMonotonicTimestamp { prev, pos });
+            }
+            prev = Some(pos);
+        }
+
+        let mut it = self
+            .db
+            .scan_prefix_with_options(
+                kv::stream_record_timestamp::ser_key_prefix(stream_id),
+                &scan_opts,
+            )
+            .await?;
+        while let Some(kv) = it.next().await? {
+            let (_, pos) = kv::stream_record_timestamp::deser_key(kv.key)?;
+            let has_data = self
+                .db_get(kv::stream_record_data::ser_key(stream_id, pos), |_| Ok(()))
+                .await?
+                .is_some();
+            if !has_data {
+                report.issues.push(FsckIssue::OrphanedTimestampIndex(pos));
+                repairs
+                    .batch
+                    .delete(kv::stream_record_timestamp::ser_key(stream_id, pos));
+                repairs.added(self).await?;
+            }
+        }
+
+        report.repaired = repairs.flush(self).await?;
+        Ok(report)
+    }
+}
+
+/// Number of repairs to accumulate before writing them.
+const REPAIR_BATCH_SIZE: usize = 1024;
+
+/// Index repairs pending write, or discarded if repair was not requested.
+struct Repairs {
+    enabled: bool,
+    batch: WriteBatch,
+    pending: usize,
+    written: usize,
+}
+
+impl Repairs {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            batch: WriteBatch::new(),
+            pending: 0,
+            written: 0,
+        }
+    }
+
+    async fn added(&mut self, backend: &Backend) -> Result<(), StorageError> {
+        self.pending += 1;
+        if self.pending >= REPAIR_BATCH_SIZE {
+            self.flush(backend).await?;
+        }
+        Ok(())
+    }
+
+    /// Write pending repairs, returning the total number written so far.
+    async fn flush(&mut self, backend: &Backend) -> Result<usize, StorageError> {
+        let batch = std::mem::replace(&mut self.batch, WriteBatch::new());
+        if self.enabled && self.pending > 0 {
+            backend.db.write(batch).await?;
+            self.written += self.pending;
+        }
+        self.pending = 0;
+        Ok(self.written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ops::RangeTo, str::FromStr};
+
+    use bytes::Bytes;
+    use bytesize::ByteSize;
+    use s2_common::record::{MeteredExt as _, NonZeroSeqNum, Record};
+    use s2_storage::record::StoredRecord;
+    use slatedb::object_store::memory::InMemory;
+
+    use super::*;
+
+    async fn test_backend() -> Backend {
+        let object_store = std::sync::Arc::new(InMemory::new());
+        let db = slatedb::Db::builder("/test", object_store)
+            .build()
+            .await
+            .unwrap();
+        Backend::new(db, ByteSize::mib(10), ByteSize::mib(10))
+    }
+
+    async fn put_record(backend: &Backend, stream_id: StreamId, pos: StreamPosition, index: bool) {
+        let record = Record::try_from_parts(vec![], Bytes::from_static(b"fsck")).unwrap();
+        let record = StoredRecord::from(record).metered();
+        backend
+            .db
+            .put(
+                kv::stream_record_data::ser_key(stream_id, pos),
+                kv::stream_record_data::ser_value(record.as_ref()),
+            )
+            .await
+            .unwrap();
+        if index {
+            backend
+                .db
+                .put(
+                    kv::stream_record_timestamp::ser_key(stream_id, pos),
+                    kv::stream_record_timestamp::ser_value(),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    fn pos(seq_num: SeqNum, timestamp: u64) -> StreamPosition {
+        StreamPosition { seq_num, timestamp }
+    }
+
+    #[tokio::test]
+    async fn fsck_reports_and_repairs_index_mismatches() {
+        let backend = test_backend().await;
+        let basin = BasinName::from_str("fsck-basin").unwrap();
+        let stream = StreamName::from_str("fsck-stream").unwrap();
+        let stream_id = StreamId::new(&basin, &stream);
+
+        put_record(&backend, stream_id, pos(0, 10), true).await;
+        put_record(&backend, stream_id, pos(1, 20), false).await;
+        put_record(&backend, stream_id, pos(2, 30), true).await;
+        backend
+            .db
+            .put(
+                kv::stream_record_timestamp::ser_key(stream_id, pos(3, 40)),
+                kv::stream_record_timestamp::ser_value(),
+            )
+            .await
+            .unwrap();
+        backend
+            .db
+            .put(
+                kv::stream_tail_position::ser_key(stream_id),
+                kv::stream_tail_position::ser_value(pos(3, 30)),
+            )
+            .await
+            .unwrap();
+
+        let report = backend.fsck_stream(&basin, &stream, false).await.unwrap();
+        assert_eq!(report.records_checked, 3);
+        assert_eq!(
+            report.issues,
+            vec![
+                FsckIssue::MissingTimestampIndex(pos(1, 20)),
+                FsckIssue::OrphanedTimestampIndex(pos(3, 40)),
+            ]
+        );
+        assert_eq!(report.repaired, 0);
+
+        let report = backend.fsck_stream(&basin, &stream, true).await.unwrap();
+        assert_eq!(report.issues.len(), 2);
+        assert_eq!(report.repaired, 2);
+
+        let report = backend.fsck_stream(&basin, &stream, false).await.unwrap();
+        assert_eq!(
+            report,
+            FsckReport {
+                records_checked: 3,
+                issues: vec![],
+                repaired: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fsck_verifies_tail_and_trim_point() {
+        let backend = test_backend().await;
+        let basin = BasinName::from_str("fsck-basin").unwrap();
+        let stream = StreamName::from_str("fsck-invariants").unwrap();
+        let stream_id = StreamId::new(&basin, &stream);
+
+        put_record(&backend, stream_id, pos(0, 20), true).await;
+        put_record(&backend, stream_id, pos(1, 10), true).await;
+        let trim_point: RangeTo<NonZeroSeqNum> = ..NonZeroSeqNum::new(5).unwrap();
+        backend
+            .db
+            .put(
+                kv::stream_trim_point::ser_key(stream_id),
+                kv::stream_trim_point::ser_value(trim_point),
+            )
+            .await
+            .unwrap();
+        backend
+            .db
+            .put(
+                kv::stream_tail_position::ser_key(stream_id),
+                kv::stream_tail_position::ser_value(pos(1, 20)),
+            )
+            .await
+            .unwrap();
+
+        let report = backend.fsck_stream(&basin, &stream, true).await.unwrap();
+        assert_eq!(
+            report.issues,
+            vec![
+                FsckIssue::TrimPointBeyondTail {
+                    trim_point: 5,
+                    tail: pos(1, 20),
+                },
+                FsckIssue::RecordBeyondTail {
+                    pos: pos(1, 10),
+                    tail: pos(1, 20),
+                },
+                FsckIssue::NonMonotonicTimestamp {
+                    prev: pos(0, 20),
+                    pos: pos(1, 10),
+                },
+            ]
+        );
+        assert_eq!(report.repaired, 0);
+    }
+}
@@ -1,5 +1,3 @@
-use std::ops::Range;
-
 use bytes::{BufMut, Bytes, BytesMut};
 use s2_common::{
     basin::{BasinName, BasinNamePrefix, BasinNameStartAfter},
@@ -9,7 +7,9 @@ use s2_storage::bash::Bash;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-use super::{DeserializationError, KeyType, deser_json_value, increment_bytes, ser_json_value};
+use super::{
+    DeserializationError, KeyRange, KeyType, deser_json_value, increment_bytes, ser_json_value,
+};
 
 #[derive(Debug, Clone)]
 pub struct BasinMeta {
@@ -71,8 +71,8 @@ pub fn ser_key_prefix(prefix: &BasinNamePrefix) -> Bytes {
     ser_key_internal(prefix.as_bytes()).freeze()
 }
 
-pub fn ser_key_prefix_end(prefix: &BasinNamePrefix) -> Bytes {
-    increment_bytes(ser_key_internal(prefix.as_bytes())).expect("non-empty")
+pub fn ser_key_prefix_end(prefix: &BasinNamePrefix) -> Option<Bytes> {
+    increment_bytes(ser_key_internal(prefix.as_bytes()))
 }
 
 pub fn ser_key_start_after(start_after: &BasinNameStartAfter) -> Bytes {
@@ -83,7 +83,7 @@ pub fn ser_key_start_after(start_after: &BasinNameStartAfter) -> Bytes {
     ser_key_internal(&bytes).freeze()
 }
 
-pub fn ser_key_range(prefix: &BasinNamePrefix, start_after: &BasinNameStartAfter) -> Range<Bytes> {
+pub fn ser_key_range(prefix: &BasinNamePrefix, start_after: &BasinNameStartAfter) -> KeyRange {
     let prefix_start = ser_key_prefix(prefix);
     let start = if !start_after.is_empty() {
         let start_after_key = ser_key_start_after(start_after);
@@ -92,7 +92,7 @@ pub fn ser_key_range(prefix: &BasinNamePrefix, start_after: &BasinNameStartAfter
         prefix_start
     };
     let end = ser_key_prefix_end(prefix);
-    start..end
+    KeyRange { start, end }
 }
 
 pub fn ser_key(basin: &BasinName) -> Bytes {
@@ -113,7 +113,7 @@ pub fn deser_value(bytes: Bytes) -> Result<BasinMeta, DeserializationError> {
 
 #[cfg(test)]
 mod tests {
-    use std::{str::FromStr, time::Duration};
+    use std::{ops::RangeBounds as _, str::FromStr, time::Duration};
 
     use bytes::Bytes;
     use proptest::prelude::*;
@@ -161,7 +161,7 @@ mod tests {
     #[test]
     fn basin_meta_ser_key_prefix_end_empty() {
         let prefix = BasinNamePrefix::default();
-        let end_key = super::ser_key_prefix_end(&prefix);
+        let end_key = super::ser_key_prefix_end(&prefix).unwrap();
 
         assert_eq!(end_key.len(), 1);
         assert_eq!(end_key[0], (KeyType::BasinMeta as u8) + 1);
@@ -171,7 +171,7 @@ mod tests {
     fn basin_meta_ser_key_prefix_end_advances() {
         for (input, expected_suffix) in [("test-a", &b"test-b"[..]), ("test-abc", &b"test-abd"[..])]
         {
-            let end_key = super::ser_key_prefix_end(&basin_prefix(input));
+            let end_key = super::ser_key_prefix_end(&basin_prefix(input)).unwrap();
             assert_eq!(end_key[0], (KeyType::BasinMeta as u8));
             assert_eq!(&end_key[1..], expected_suffix);
         }
@@ -207,10 +207,10 @@ mod tests {
         let key3 = super::ser_key(&basin3);
         let key_outside = super::ser_key(&outside);
 
-        assert!(key1 >= page1.start && key1 < page1.end);
-        assert!(key2 >= page1.start && key2 < page1.end);
-        assert!(key3 >= page1.start && key3 < page1.end);
-        assert!(key_outside < page1.start || key_outside >= page1.end);
+        assert!(page1.contains(&key1));
+        assert!(page1.contains(&key2));
+        assert!(page1.contains(&key3));
+        assert!(!page1.contains(&key_outside));
 
         let start_after = BasinNameStartAfter::from(basin1.clone());
         let cursor_key = super::ser_key_start_after(&start_after);
@@ -222,8 +222,8 @@ mod tests {
         assert_eq!(page2.end, super::ser_key_prefix_end(&prefix));
 
         assert!(key1 < page2.start);
-        assert!(key2 >= page2.start && key2 < page2.end);
-        assert!(key3 >= page2.start && key3 < page2.end);
+        assert!(page2.contains(&key2));
+        assert!(page2.contains(&key3));
     }
 
     #[test]
@@ -331,6 +331,49 @@ mod tests {
         assert!(matches!(err, DeserializationError::JsonDeserialization(_)));
     }
 
+    #[test]
+    fn basin_meta_deser_legacy_unversioned_value() {
+        let bytes = Bytes::from_static(
+            br#"{"config":null,"created_at":"2009-02-13T23:31:30Z","deleted_at":null,"creation_idempotency_key":null}"#,
+        );
+        let decoded = super::deser_value(bytes).unwrap();
+
+        assert_eq!(
+            decoded.created_at,
+            OffsetDateTime::from_unix_timestamp(1234567890).unwrap()
+        );
+        assert_eq!(decoded.deleted_at, None);
+    }
+
+    fn test_basin_meta() -> super::BasinMeta {
+        super::BasinMeta {
+            config: BasinConfig::default(),
+            created_at: OffsetDateTime::from_unix_timestamp(1234567890).unwrap(),
+            deleted_at: None,
+            creation_idempotency_key: None,
+        }
+    }
+
+    #[test]
+    fn basin_meta_ser_value_is_versioned() {
+        let basin_meta = test_basin_meta();
+        let bytes = super::ser_value(&basin_meta);
+        assert_eq!(bytes[0], super::super::JSON_VALUE_VERSION);
+        assert_eq!(bytes[1], b'{');
+
+        let decoded = super::deser_value(bytes).unwrap();
+        assert_eq!(decoded.created_at, basin_meta.created_at);
+    }
+
+    #[test]
+    fn basin_meta_deser_unsupported_version() {
+        let mut bytes = super::ser_value(&test_basin_meta()).to_vec();
+        bytes[0] = super::super::JSON_VALUE_VERSION + 1;
+
+        let err = super::deser_value(Bytes::from(bytes)).unwrap_err();
+        assert!(matches!(err, DeserializationError::UnsupportedVersion(2)));
+    }
+
     fn basin_name_prefix_strategy() -> impl Strategy<Value = BasinNamePrefix> {
         prop_oneof![
             Just(BasinNamePrefix::default()),
@@ -346,14 +389,14 @@ mod tests {
         let range = super::ser_key_range(&prefix, &start_after);
 
         assert!(
-            range.start < range.end,
+            !range.is_empty(),
             "range should be valid when start_after is before prefix range"
         );
 
         let staging_basin = BasinName::from_str("staging-api").unwrap();
         let staging_key = super::ser_key(&staging_basin);
         assert!(
-            staging_key >= range.start && staging_key < range.end,
+            range.contains(&staging_key),
             "basins matching prefix should be in range"
         );
 
@@ -386,10 +429,9 @@ mod tests {
             let key = super::ser_key(&basin);
 
             if matches_prefix {
-                prop_assert!(key >= range.start, "key {:?} should be >= range.start {:?}", key, range.start);
-                prop_assert!(key < range.end, "key {:?} should be < range.end {:?}", key, range.end);
+                prop_assert!(range.contains(&key), "key {:?} should be in range {:?}", key, range);
             } else {
-                prop_assert!(key < range.start || key >= range.end);
+                prop_assert!(!range.contains(&key));
             }
         }
 
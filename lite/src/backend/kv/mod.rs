@@ -1,23 +1,34 @@
 pub mod basin_deletion_pending;
 pub mod basin_meta;
+pub mod stream_command_index;
+pub mod stream_created_at;
+pub mod stream_created_at_backfill;
+pub mod stream_cursor;
 pub mod stream_doe_deadline;
 pub mod stream_fencing_token;
 pub mod stream_id_mapping;
 pub mod stream_meta;
+pub mod stream_purge_deadline;
 pub mod stream_record_data;
+pub mod stream_record_expiry;
 pub mod stream_record_timestamp;
 pub mod stream_tail_position;
 pub mod stream_trim_point;
 pub mod timestamp;
 
-use std::{ops::Range, str::FromStr};
+use std::{
+    ops::{Bound, RangeBounds},
+    str::FromStr,
+};
 
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use s2_common::{
-    basin::BasinName, caps::MIN_BASIN_NAME_LEN, record::StreamPosition, stream::StreamName,
+    basin::BasinName, caps::MIN_BASIN_NAME_LEN, consumer::ConsumerName, record::StreamPosition,
+    stream::StreamName,
 };
 use strum::FromRepr;
 use thiserror::Error;
+use time::OffsetDateTime;
 
 use crate::stream_id::StreamId;
 
@@ -33,6 +44,8 @@ pub enum DeserializationError {
     MissingFieldSeparator,
     #[error("json deserialization error: {0}")]
     JsonDeserialization(String),
+    #[error("unsupported value version: {0}")]
+    UnsupportedVersion(u8),
 }
 
 // IDs persisted so must be kept stable.
@@ -49,9 +62,15 @@ pub enum KeyType {
     StreamRecordData = 6,
     StreamRecordTimestamp = 7,
     StreamDeleteOnEmptyDeadline = 10,
+    StreamCursor = 11,
+    StreamCommandIndex = 12,
+    StreamCreatedAt = 13,
+    StreamPurgeDeadline = 14,
+    StreamRecordExpiry = 15,
+    StreamCreatedAtBackfill = 16,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Key {
     /// (BM) per-basin, updatable
     /// Key: BasinName
@@ -93,6 +112,31 @@ pub enum Key {
     /// Key: TimestampSecs StreamID
     /// Value: MinAge seconds (u64)
     StreamDeleteOnEmptyDeadline(timestamp::TimestampSecs, StreamId),
+    /// (SC) per-consumer-per-stream, updatable, optional
+    /// Key: StreamID ConsumerName
+    /// Value: SeqNum
+    StreamCursor(StreamId, ConsumerName),
+    /// (SCI) per-command-record, immutable
+    /// Key: StreamID StreamPosition
+    /// Value: empty
+    StreamCommandIndex(StreamId, StreamPosition),
+    /// (SCA) per-stream, deletable, removed along with the stream's metadata
+    /// Key: BasinName \0 CreatedAtMillis StreamName
+    /// Value: empty
+    StreamCreatedAt(BasinName, OffsetDateTime, StreamName),
+    /// (SPD) per-deadline-per-stream, deletable, present while a soft-deleted stream awaits purge
+    /// Key: TimestampSecs StreamID
+    /// Value: DeletedAt nanos (i128)
+    StreamPurgeDeadline(timestamp::TimestampSecs, StreamId),
+    /// (SRE) per-record, immutable, only present for records with a TTL
+    /// Key: StreamID StreamPosition
+    /// Value: ExpiresAt seconds (u32)
+    StreamRecordExpiry(StreamId, StreamPosition),
+    /// (SCAB) singleton, only present once the creation-time index covers streams created
+    /// before it existed
+    /// Key: (none)
+    /// Value: empty
+    StreamCreatedAtBackfill,
 }
 
 impl From<Key> for Bytes {
@@ -112,6 +156,20 @@ impl From<Key> for Bytes {
             Key::StreamDeleteOnEmptyDeadline(deadline, stream_id) => {
                 stream_doe_deadline::ser_key(deadline, stream_id)
             }
+            Key::StreamCursor(stream_id, consumer) => stream_cursor::ser_key(stream_id, &consumer),
+            Key::StreamCommandIndex(stream_id, pos) => {
+                stream_command_index::ser_key(stream_id, pos)
+            }
+            Key::StreamCreatedAt(basin, created_at, stream) => {
+                stream_created_at::ser_key(&basin, created_at, &stream)
+            }
+            Key::StreamPurgeDeadline(deadline, stream_id) => {
+                stream_purge_deadline::ser_key(deadline, stream_id)
+            }
+            Key::StreamRecordExpiry(stream_id, pos) => {
+                stream_record_expiry::ser_key(stream_id, pos)
+            }
+            Key::StreamCreatedAtBackfill => stream_created_at_backfill::ser_key(),
         }
     }
 }
@@ -149,6 +207,19 @@ impl TryFrom<Bytes> for Key {
                 .map(|(stream_id, pos)| Key::StreamRecordTimestamp(stream_id, pos)),
             KeyType::StreamDeleteOnEmptyDeadline => stream_doe_deadline::deser_key(bytes)
                 .map(|(deadline, stream_id)| Key::StreamDeleteOnEmptyDeadline(deadline, stream_id)),
+            KeyType::StreamCursor => stream_cursor::deser_key(bytes)
+                .map(|(stream_id, consumer)| Key::StreamCursor(stream_id, consumer)),
+            KeyType::StreamCommandIndex => stream_command_index::deser_key(bytes)
+                .map(|(stream_id, pos)| Key::StreamCommandIndex(stream_id, pos)),
+            KeyType::StreamCreatedAt => stream_created_at::deser_key(bytes)
+                .map(|(basin, created_at, stream)| Key::StreamCreatedAt(basin, created_at, stream)),
+            KeyType::StreamPurgeDeadline => stream_purge_deadline::deser_key(bytes)
+                .map(|(deadline, stream_id)| Key::StreamPurgeDeadline(deadline, stream_id)),
+            KeyType::StreamRecordExpiry => stream_record_expiry::deser_key(bytes)
+                .map(|(stream_id, pos)| Key::StreamRecordExpiry(stream_id, pos)),
+            KeyType::StreamCreatedAtBackfill => {
+                stream_created_at_backfill::deser_key(bytes).map(|()| Key::StreamCreatedAtBackfill)
+            }
         }
     }
 }
@@ -224,15 +295,46 @@ fn check_min_size(bytes: &Bytes, min: usize) -> Result<(), DeserializationError>
     Ok(())
 }
 
-pub fn key_type_range(key_type: KeyType) -> Range<Bytes> {
-    let ordinal = key_type as u8;
-    let start = Bytes::from(vec![ordinal]);
-    let end = Bytes::from(vec![
-        ordinal.checked_add(1).expect("key type ordinal overflow"),
-    ]);
-    start..end
+/// Range of keys from `start`, up to but excluding `end`. An absent `end` is open-ended, which is
+/// the case for prefixes made up of only `0xFF` bytes, as they have no successor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyRange {
+    pub start: Bytes,
+    pub end: Option<Bytes>,
 }
 
+impl KeyRange {
+    /// Range of all keys starting with `prefix`.
+    fn prefix(prefix: BytesMut) -> Self {
+        Self {
+            start: prefix.clone().freeze(),
+            end: increment_bytes(prefix),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end.as_ref().is_some_and(|end| self.start >= *end)
+    }
+}
+
+impl RangeBounds<Bytes> for KeyRange {
+    fn start_bound(&self) -> Bound<&Bytes> {
+        Bound::Included(&self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&Bytes> {
+        match &self.end {
+            Some(end) => Bound::Excluded(end),
+            None => Bound::Unbounded,
+        }
+    }
+}
+
+pub fn key_type_range(key_type: KeyType) -> KeyRange {
+    KeyRange::prefix(BytesMut::from(&[key_type as u8][..]))
+}
+
+/// Smallest key greater than every key prefixed by `buf`, or `None` if `buf` is all `0xFF`.
 fn increment_bytes(mut buf: BytesMut) -> Option<Bytes> {
     for i in (0..buf.len()).rev() {
         if buf[i] < 0xFF {
@@ -251,15 +353,25 @@ fn invalid_value_err<E: std::fmt::Display>(name: &'static str, e: E) -> Deserial
     }
 }
 
+/// Format version prefixed to JSON-encoded values.
+///
+/// Values written before versioning was introduced are bare JSON objects, so a leading `{` is
+/// read as the unversioned legacy format. Versions must stay below `b'{'` to remain unambiguous.
+/// When the format changes, bump the version and decode older versions into the current serde
+/// type; values are rewritten in the current version the next time they are updated.
+const JSON_VALUE_VERSION: u8 = 1;
+const LEGACY_JSON_VALUE_PREFIX: u8 = b'{';
+
 fn ser_json_value<T, S>(value: &T, type_name: &str) -> Bytes
 where
     T: Clone + Into<S>,
     S: serde::Serialize,
 {
     let serde_value: S = value.clone().into();
-    serde_json::to_vec(&serde_value)
-        .unwrap_or_else(|_| panic!("failed to serialize {}", type_name))
-        .into()
+    let mut buf = vec![JSON_VALUE_VERSION];
+    serde_json::to_writer(&mut buf, &serde_value)
+        .unwrap_or_else(|_| panic!("failed to serialize {}", type_name));
+    buf.into()
 }
 
 fn deser_json_value<T, S>(bytes: Bytes, name: &'static str) -> Result<T, DeserializationError>
@@ -268,7 +380,12 @@ where
     T: TryFrom<S>,
     T::Error: std::fmt::Display,
 {
-    let serde_value: S = serde_json::from_slice(&bytes)
+    let json = match bytes.first() {
+        None | Some(&LEGACY_JSON_VALUE_PREFIX) => &bytes[..],
+        Some(&JSON_VALUE_VERSION) => &bytes[1..],
+        Some(&version) => return Err(DeserializationError::UnsupportedVersion(version)),
+    };
+    let serde_value: S = serde_json::from_slice(json)
         .map_err(|e| DeserializationError::JsonDeserialization(e.to_string()))?;
     T::try_from(serde_value).map_err(|e| invalid_value_err(name, e))
 }
@@ -278,7 +395,13 @@ mod proptest_strategies {
     use std::str::FromStr;
 
     use proptest::prelude::*;
-    use s2_common::{basin::BasinName, stream::StreamName};
+    use s2_common::{
+        basin::BasinName, consumer::ConsumerName, record::StreamPosition, stream::StreamName,
+    };
+    use time::OffsetDateTime;
+
+    use super::timestamp::TimestampSecs;
+    use crate::stream_id::StreamId;
 
     pub(super) fn basin_name_strategy() -> impl Strategy<Value = BasinName> {
         "[a-z][a-z0-9-]{6,46}[a-z0-9]".prop_map(|s| BasinName::from_str(&s).unwrap())
@@ -287,13 +410,175 @@ mod proptest_strategies {
     pub(super) fn stream_name_strategy() -> impl Strategy<Value = StreamName> {
         "[a-zA-Z0-9_-]{1,100}".prop_map(|s| StreamName::from_str(&s).unwrap())
     }
+
+    pub(super) fn consumer_name_strategy() -> impl Strategy<Value = ConsumerName> {
+        "[a-zA-Z0-9_-]{1,128}".prop_map(|s| ConsumerName::from_str(&s).unwrap())
+    }
+
+    pub(super) fn stream_id_strategy() -> impl Strategy<Value = StreamId> {
+        any::<[u8; StreamId::LEN]>().prop_map(StreamId::from)
+    }
+
+    pub(super) fn stream_position_strategy() -> impl Strategy<Value = StreamPosition> {
+        (any::<u64>(), any::<u64>())
+            .prop_map(|(seq_num, timestamp)| StreamPosition { seq_num, timestamp })
+    }
+
+    pub(super) fn timestamp_secs_strategy() -> impl Strategy<Value = TimestampSecs> {
+        any::<u32>().prop_map(TimestampSecs::from_secs)
+    }
+
+    /// Creation times are indexed with millisecond precision, up to the end of year 9999.
+    pub(super) fn created_at_strategy() -> impl Strategy<Value = OffsetDateTime> {
+        (0..=253_402_300_799_999u64).prop_map(|millis| {
+            OffsetDateTime::from_unix_timestamp_nanos(i128::from(millis) * 1_000_000).unwrap()
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::ops::RangeBounds as _;
+
     use bytes::{BufMut, Bytes, BytesMut};
+    use proptest::{prelude::*, strategy::Union};
 
-    use super::{DeserializationError, Key, KeyType};
+    use super::{
+        DeserializationError, Key, KeyRange, KeyType,
+        proptest_strategies::{
+            basin_name_strategy, consumer_name_strategy, created_at_strategy, stream_id_strategy,
+            stream_name_strategy, stream_position_strategy, timestamp_secs_strategy,
+        },
+    };
+
+    /// Keys of the given type. The exhaustive match means a new key type cannot be added without
+    /// round-trip coverage.
+    fn key_strategy(key_type: KeyType) -> BoxedStrategy<Key> {
+        match key_type {
+            KeyType::BasinMeta => basin_name_strategy().prop_map(Key::BasinMeta).boxed(),
+            KeyType::BasinDeletionPending => basin_name_strategy()
+                .prop_map(Key::BasinDeletionPending)
+                .boxed(),
+            KeyType::StreamMeta => (basin_name_strategy(), stream_name_strategy())
+                .prop_map(|(basin, stream)| Key::StreamMeta(basin, stream))
+                .boxed(),
+            KeyType::StreamIdMapping => stream_id_strategy().prop_map(Key::StreamIdMapping).boxed(),
+            KeyType::StreamTailPosition => stream_id_strategy()
+                .prop_map(Key::StreamTailPosition)
+                .boxed(),
+            KeyType::StreamFencingToken => stream_id_strategy()
+                .prop_map(Key::StreamFencingToken)
+                .boxed(),
+            KeyType::StreamTrimPoint => stream_id_strategy().prop_map(Key::StreamTrimPoint).boxed(),
+            KeyType::StreamRecordData => (stream_id_strategy(), stream_position_strategy())
+                .prop_map(|(stream_id, pos)| Key::StreamRecordData(stream_id, pos))
+                .boxed(),
+            KeyType::StreamRecordTimestamp => (stream_id_strategy(), stream_position_strategy())
+                .prop_map(|(stream_id, pos)| Key::StreamRecordTimestamp(stream_id, pos))
+                .boxed(),
+            KeyType::StreamDeleteOnEmptyDeadline => {
+                (timestamp_secs_strategy(), stream_id_strategy())
+                    .prop_map(|(deadline, stream_id)| {
+                        Key::StreamDeleteOnEmptyDeadline(deadline, stream_id)
+                    })
+                    .boxed()
+            }
+            KeyType::StreamCursor => (stream_id_strategy(), consumer_name_strategy())
+                .prop_map(|(stream_id, consumer)| Key::StreamCursor(stream_id, consumer))
+                .boxed(),
+            KeyType::StreamCommandIndex => (stream_id_strategy(), stream_position_strategy())
+                .prop_map(|(stream_id, pos)| Key::StreamCommandIndex(stream_id, pos))
+                .boxed(),
+            KeyType::StreamCreatedAt => (
+                basin_name_strategy(),
+                created_at_strategy(),
+                stream_name_strategy(),
+            )
+                .prop_map(|(basin, created_at, stream)| {
+                    Key::StreamCreatedAt(basin, created_at, stream)
+                })
+                .boxed(),
+            KeyType::StreamPurgeDeadline => (timestamp_secs_strategy(), stream_id_strategy())
+                .prop_map(|(deadline, stream_id)| Key::StreamPurgeDeadline(deadline, stream_id))
+                .boxed(),
+            KeyType::StreamRecordExpiry => (stream_id_strategy(), stream_position_strategy())
+                .prop_map(|(stream_id, pos)| Key::StreamRecordExpiry(stream_id, pos))
+                .boxed(),
+            KeyType::StreamCreatedAtBackfill => Just(Key::StreamCreatedAtBackfill).boxed(),
+        }
+    }
+
+    fn any_key_strategy() -> impl Strategy<Value = (KeyType, Key)> {
+        Union::new(
+            (0..=u8::MAX)
+                .filter_map(KeyType::from_repr)
+                .map(|key_type| key_strategy(key_type).prop_map(move |key| (key_type, key))),
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip_every_key_type((key_type, key) in any_key_strategy()) {
+            let bytes = Bytes::from(key.clone());
+            prop_assert_eq!(bytes[0], key_type as u8);
+            prop_assert_eq!(Key::try_from(bytes).unwrap(), key);
+        }
+    }
+
+    #[test]
+    fn increment_bytes_carries_past_trailing_ff() {
+        for (input, expected) in [
+            (&b"\x01\x02"[..], Some(&b"\x01\x03"[..])),
+            (&b"\x01\xFF"[..], Some(&b"\x02"[..])),
+            (&b"\x01\xFE\xFF\xFF"[..], Some(&b"\x01\xFF"[..])),
+            (&b"\xFF"[..], None),
+            (&b"\xFF\xFF\xFF"[..], None),
+            (&b""[..], None),
+        ] {
+            assert_eq!(
+                super::increment_bytes(BytesMut::from(input)).as_deref(),
+                expected,
+                "input {input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn prefix_range_with_trailing_ff_covers_prefixed_keys() {
+        let range = KeyRange::prefix(BytesMut::from(&b"\x01\xFF"[..]));
+        assert_eq!(range.end.as_deref(), Some(&b"\x02"[..]));
+        assert!(!range.is_empty());
+        assert!(range.contains(&Bytes::from_static(b"\x01\xFF")));
+        assert!(range.contains(&Bytes::from_static(b"\x01\xFF\xFF\xFF")));
+        assert!(!range.contains(&Bytes::from_static(b"\x01\xFE\xFF")));
+        assert!(!range.contains(&Bytes::from_static(b"\x02")));
+    }
+
+    #[test]
+    fn all_ff_prefix_range_is_open_ended() {
+        let range = KeyRange::prefix(BytesMut::from(&b"\xFF\xFF"[..]));
+        assert_eq!(range.end, None);
+        assert!(!range.is_empty());
+        assert!(range.contains(&Bytes::from_static(b"\xFF\xFF")));
+        assert!(range.contains(&Bytes::from_static(b"\xFF\xFF\xFF\xFF\x00")));
+        assert!(!range.contains(&Bytes::from_static(b"\xFF\xFE")));
+
+        let range = KeyRange::prefix(BytesMut::from(&[u8::MAX][..]));
+        assert_eq!(range.end, None);
+        assert!(range.contains(&Bytes::from_static(b"\xFF")));
+    }
+
+    #[test]
+    fn key_type_range_covers_only_its_key_type() {
+        let range = super::key_type_range(KeyType::StreamCursor);
+        assert_eq!(range.start.as_ref(), &[KeyType::StreamCursor as u8]);
+        assert_eq!(
+            range.end.as_deref(),
+            Some(&[KeyType::StreamCursor as u8 + 1][..])
+        );
+        assert!(range.contains(&Bytes::from(vec![KeyType::StreamCursor as u8, 0xFF, 0xFF])));
+        assert!(!range.contains(&Bytes::from(vec![KeyType::StreamCursor as u8 + 1])));
+    }
 
     #[test]
     fn error_on_invalid_ordinal() {
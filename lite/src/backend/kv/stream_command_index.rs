@@ -0,0 +1,135 @@
+use std::ops::Range;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use s2_common::record::{SeqNum, StreamPosition, Timestamp};
+
+use super::{DeserializationError, KeyType, check_exact_size};
+use crate::stream_id::StreamId;
+
+const KEY_PREFIX_LEN: usize = 1 + StreamId::LEN;
+const KEY_LEN: usize = 1 + StreamId::LEN + 8 + 8;
+
+pub fn ser_key_prefix(stream_id: StreamId) -> Bytes {
+    let mut buf = BytesMut::with_capacity(KEY_PREFIX_LEN);
+    buf.put_u8(KeyType::StreamCommandIndex as u8);
+    buf.put_slice(stream_id.as_bytes());
+    debug_assert_eq!(buf.len(), KEY_PREFIX_LEN, "serialized length mismatch");
+    buf.freeze()
+}
+
+pub fn ser_key(stream_id: StreamId, pos: StreamPosition) -> Bytes {
+    let mut buf = BytesMut::with_capacity(KEY_LEN);
+    buf.put_u8(KeyType::StreamCommandIndex as u8);
+    buf.put_slice(stream_id.as_bytes());
+    buf.put_u64(pos.seq_num);
+    buf.put_u64(pos.timestamp);
+    debug_assert_eq!(buf.len(), KEY_LEN, "serialized length mismatch");
+    buf.freeze()
+}
+
+/// Key range of the stream's index entries for command records at or after `start_seq_num`.
+pub fn ser_range(stream_id: StreamId, start_seq_num: SeqNum) -> Range<Bytes> {
+    let start = ser_key(
+        stream_id,
+        StreamPosition {
+            seq_num: start_seq_num,
+            timestamp: Timestamp::MIN,
+        },
+    );
+    let end = ser_key(
+        stream_id,
+        StreamPosition {
+            seq_num: SeqNum::MAX,
+            timestamp: Timestamp::MAX,
+        },
+    );
+    start..end
+}
+
+pub fn deser_key(mut bytes: Bytes) -> Result<(StreamId, StreamPosition), DeserializationError> {
+    check_exact_size(&bytes, KEY_LEN)?;
+    let ordinal = bytes.get_u8();
+    if ordinal != (KeyType::StreamCommandIndex as u8) {
+        return Err(DeserializationError::InvalidOrdinal(ordinal));
+    }
+    let mut stream_id_bytes = [0u8; StreamId::LEN];
+    bytes.copy_to_slice(&mut stream_id_bytes);
+    let seq_num = bytes.get_u64();
+    let timestamp = bytes.get_u64();
+    Ok((
+        stream_id_bytes.into(),
+        StreamPosition { seq_num, timestamp },
+    ))
+}
+
+pub fn ser_value() -> Bytes {
+    Bytes::new()
+}
+
+pub fn deser_value(bytes: Bytes) -> Result<(), DeserializationError> {
+    check_exact_size(&bytes, 0)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+    use s2_common::record::{SeqNum, StreamPosition, Timestamp};
+
+    use crate::stream_id::StreamId;
+
+    #[test]
+    fn range_starts_at_seq_num_and_excludes_other_streams() {
+        let stream_id = StreamId::from([7u8; StreamId::LEN]);
+        let mut keys = BTreeSet::new();
+        for neighbor in [[0u8; StreamId::LEN], [u8::MAX; StreamId::LEN]] {
+            keys.insert(super::ser_key(
+                StreamId::from(neighbor),
+                StreamPosition {
+                    seq_num: 5,
+                    timestamp: 0,
+                },
+            ));
+        }
+        for seq_num in [1, 5, 9] {
+            keys.insert(super::ser_key(
+                stream_id,
+                StreamPosition {
+                    seq_num,
+                    timestamp: Timestamp::MAX,
+                },
+            ));
+        }
+
+        let seq_nums: Vec<SeqNum> = keys
+            .range(super::ser_range(stream_id, 5))
+            .map(|key| super::deser_key(key.clone()).unwrap().1.seq_num)
+            .collect();
+        assert_eq!(seq_nums, vec![5, 9]);
+    }
+
+    #[test]
+    fn roundtrip_stream_command_index_value() {
+        let bytes = super::ser_value();
+        super::deser_value(bytes).unwrap();
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip_stream_command_index_key(
+            stream_id_bytes in any::<[u8; StreamId::LEN]>(),
+            seq_num in any::<SeqNum>(),
+            timestamp in any::<Timestamp>(),
+        ) {
+            let stream_id = StreamId::from(stream_id_bytes);
+            let pos = StreamPosition { seq_num, timestamp };
+            let key_bytes = super::ser_key(stream_id, pos);
+            prop_assert!(key_bytes.starts_with(&super::ser_key_prefix(stream_id)));
+            let (decoded_stream_id, decoded_pos) = super::deser_key(key_bytes).unwrap();
+            prop_assert_eq!(stream_id, decoded_stream_id);
+            prop_assert_eq!(pos, decoded_pos);
+        }
+    }
+}
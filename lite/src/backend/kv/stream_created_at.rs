@@ -0,0 +1,146 @@
+use std::str::FromStr;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use s2_common::{
+    basin::BasinName,
+    caps::{MIN_BASIN_NAME_LEN, MIN_STREAM_NAME_LEN},
+    stream::StreamName,
+};
+use time::OffsetDateTime;
+
+use super::{
+    DeserializationError, KeyRange, KeyType, check_min_size, increment_bytes, invalid_value_err,
+};
+
+const FIELD_SEPARATOR: u8 = b'\0';
+
+/// Creation times are indexed with millisecond precision; ties are ordered by stream name.
+pub fn ser_key(basin: &BasinName, created_at: OffsetDateTime, stream: &StreamName) -> Bytes {
+    let mut buf = ser_basin_prefix(basin);
+    buf.put_u64(created_at_millis(created_at));
+    buf.put_slice(stream.as_bytes());
+    buf.freeze()
+}
+
+/// Range of the basin's index entries, optionally starting after the position of an entry,
+/// whether or not that entry still exists.
+pub fn ser_key_range(basin: &BasinName, after: Option<(OffsetDateTime, &str)>) -> KeyRange {
+    let prefix = ser_basin_prefix(basin);
+    let start = match after {
+        Some((created_at, stream)) => {
+            let mut start = prefix.clone();
+            start.put_u64(created_at_millis(created_at));
+            start.put_slice(stream.as_bytes());
+            // Appending the smallest byte yields the least key greater than the entry.
+            start.put_u8(0);
+            start.freeze()
+        }
+        None => prefix.clone().freeze(),
+    };
+    KeyRange {
+        start,
+        end: increment_bytes(prefix),
+    }
+}
+
+fn ser_basin_prefix(basin: &BasinName) -> BytesMut {
+    let basin_bytes = basin.as_bytes();
+    let mut buf = BytesMut::with_capacity(1 + basin_bytes.len() + 1 + 8);
+    buf.put_u8(KeyType::StreamCreatedAt as u8);
+    buf.put_slice(basin_bytes);
+    buf.put_u8(FIELD_SEPARATOR);
+    buf
+}
+
+fn created_at_millis(created_at: OffsetDateTime) -> u64 {
+    (created_at.unix_timestamp_nanos() / 1_000_000).clamp(0, u64::MAX as i128) as u64
+}
+
+pub fn deser_key(
+    mut bytes: Bytes,
+) -> Result<(BasinName, OffsetDateTime, StreamName), DeserializationError> {
+    check_min_size(&bytes, 1 + MIN_BASIN_NAME_LEN + 1 + 8 + MIN_STREAM_NAME_LEN)?;
+    let ordinal = bytes.get_u8();
+    if ordinal != (KeyType::StreamCreatedAt as u8) {
+        return Err(DeserializationError::InvalidOrdinal(ordinal));
+    }
+    let sep_pos = bytes
+        .iter()
+        .position(|&b| b == FIELD_SEPARATOR)
+        .ok_or(DeserializationError::MissingFieldSeparator)?;
+
+    let basin_str =
+        std::str::from_utf8(&bytes[..sep_pos]).map_err(|e| invalid_value_err("basin", e))?;
+    let basin = BasinName::from_str(basin_str).map_err(|e| invalid_value_err("basin", e))?;
+    bytes.advance(sep_pos + 1);
+    check_min_size(&bytes, 8 + MIN_STREAM_NAME_LEN)?;
+    let created_at =
+        OffsetDateTime::from_unix_timestamp_nanos(i128::from(bytes.get_u64()) * 1_000_000)
+            .map_err(|e| invalid_value_err("created_at", e))?;
+    let stream_str = std::str::from_utf8(&bytes).map_err(|e| invalid_value_err("stream", e))?;
+    let stream = StreamName::from_str(stream_str).map_err(|e| invalid_value_err("stream", e))?;
+
+    Ok((basin, created_at, stream))
+}
+
+pub fn ser_value() -> Bytes {
+    Bytes::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeBounds as _;
+
+    use proptest::prelude::*;
+    use time::OffsetDateTime;
+
+    use crate::backend::kv::{
+        proptest_strategies::{basin_name_strategy, stream_name_strategy},
+        stream_created_at,
+    };
+
+    proptest! {
+        #[test]
+        fn roundtrip_stream_created_at_key(
+            basin in basin_name_strategy(),
+            stream in stream_name_strategy(),
+            created_at_millis in 0..=253_402_300_799_999u64,
+        ) {
+            let created_at = OffsetDateTime::from_unix_timestamp_nanos(
+                i128::from(created_at_millis) * 1_000_000,
+            )
+            .unwrap();
+            let bytes = stream_created_at::ser_key(&basin, created_at, &stream);
+            let (decoded_basin, decoded_created_at, decoded_stream) =
+                stream_created_at::deser_key(bytes).unwrap();
+            prop_assert_eq!(basin, decoded_basin);
+            prop_assert_eq!(created_at, decoded_created_at);
+            prop_assert_eq!(stream, decoded_stream);
+        }
+    }
+
+    #[test]
+    fn keys_order_by_creation_time_then_name() {
+        let basin = "test-basin".parse().unwrap();
+        let earlier = OffsetDateTime::from_unix_timestamp(1_000).unwrap();
+        let later = OffsetDateTime::from_unix_timestamp(2_000).unwrap();
+        let a = "a".parse().unwrap();
+        let z = "z".parse().unwrap();
+
+        let z_earlier = stream_created_at::ser_key(&basin, earlier, &z);
+        let a_later = stream_created_at::ser_key(&basin, later, &a);
+        let z_later = stream_created_at::ser_key(&basin, later, &z);
+        assert!(z_earlier < a_later);
+        assert!(a_later < z_later);
+
+        let range = stream_created_at::ser_key_range(&basin, Some((later, "a")));
+        assert!(!range.contains(&z_earlier));
+        assert!(!range.contains(&a_later));
+        assert!(range.contains(&z_later));
+
+        let other_basin = "other-basin".parse().unwrap();
+        let range = stream_created_at::ser_key_range(&basin, None);
+        assert!(range.contains(&z_earlier));
+        assert!(!range.contains(&stream_created_at::ser_key(&other_basin, earlier, &a)));
+    }
+}
@@ -0,0 +1,29 @@
+use bytes::{Buf, Bytes};
+
+use super::{DeserializationError, KeyType, check_exact_size};
+
+pub fn ser_key() -> Bytes {
+    Bytes::from_static(&[KeyType::StreamCreatedAtBackfill as u8])
+}
+
+pub fn deser_key(mut bytes: Bytes) -> Result<(), DeserializationError> {
+    check_exact_size(&bytes, 1)?;
+    let ordinal = bytes.get_u8();
+    if ordinal != (KeyType::StreamCreatedAtBackfill as u8) {
+        return Err(DeserializationError::InvalidOrdinal(ordinal));
+    }
+    Ok(())
+}
+
+pub fn ser_value() -> Bytes {
+    Bytes::new()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn roundtrip_stream_created_at_backfill_key() {
+        super::deser_key(super::ser_key()).unwrap();
+        assert!(super::deser_key(bytes::Bytes::from_static(&[0])).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use std::str::FromStr;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use s2_common::{consumer::ConsumerName, record::SeqNum};
+
+use super::{DeserializationError, KeyType, check_exact_size, check_min_size, invalid_value_err};
+use crate::stream_id::StreamId;
+
+const VALUE_LEN: usize = 8;
+
+pub fn ser_key(stream_id: StreamId, consumer: &ConsumerName) -> Bytes {
+    let consumer_bytes = consumer.as_bytes();
+    let capacity = 1 + StreamId::LEN + consumer_bytes.len();
+    let mut buf = BytesMut::with_capacity(capacity);
+    buf.put_u8(KeyType::StreamCursor as u8);
+    buf.put_slice(stream_id.as_bytes());
+    buf.put_slice(consumer_bytes);
+    debug_assert_eq!(buf.len(), capacity, "serialized length mismatch");
+    buf.freeze()
+}
+
+pub fn ser_key_prefix(stream_id: StreamId) -> Bytes {
+    super::ser_stream_id_key(KeyType::StreamCursor, stream_id)
+}
+
+pub fn deser_key(mut bytes: Bytes) -> Result<(StreamId, ConsumerName), DeserializationError> {
+    check_min_size(&bytes, 1 + StreamId::LEN + 1)?;
+    let ordinal = bytes.get_u8();
+    if ordinal != (KeyType::StreamCursor as u8) {
+        return Err(DeserializationError::InvalidOrdinal(ordinal));
+    }
+    let mut stream_id_bytes = [0u8; StreamId::LEN];
+    bytes.copy_to_slice(&mut stream_id_bytes);
+    let consumer_str = std::str::from_utf8(&bytes).map_err(|e| invalid_value_err("consumer", e))?;
+    let consumer =
+        ConsumerName::from_str(consumer_str).map_err(|e| invalid_value_err("consumer", e))?;
+    Ok((stream_id_bytes.into(), consumer))
+}
+
+pub fn ser_value(seq_num: SeqNum) -> Bytes {
+    let mut buf = BytesMut::with_capacity(VALUE_LEN);
+    buf.put_u64(seq_num);
+    debug_assert_eq!(buf.len(), VALUE_LEN, "serialized length mismatch");
+    buf.freeze()
+}
+
+pub fn deser_value(mut bytes: Bytes) -> Result<SeqNum, DeserializationError> {
+    check_exact_size(&bytes, VALUE_LEN)?;
+    Ok(bytes.get_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use proptest::prelude::*;
+    use s2_common::{consumer::ConsumerName, record::SeqNum};
+
+    use crate::stream_id::StreamId;
+
+    proptest! {
+        #[test]
+        fn roundtrip_stream_cursor_key(
+            stream_id_bytes in any::<[u8; StreamId::LEN]>(),
+            consumer in "[a-zA-Z0-9_-]{1,128}",
+        ) {
+            let stream_id = StreamId::from(stream_id_bytes);
+            let consumer = ConsumerName::from_str(&consumer).unwrap();
+            let bytes = super::ser_key(stream_id, &consumer);
+            prop_assert!(bytes.starts_with(&super::ser_key_prefix(stream_id)));
+            let (decoded_stream_id, decoded_consumer) = super::deser_key(bytes).unwrap();
+            prop_assert_eq!(stream_id, decoded_stream_id);
+            prop_assert_eq!(consumer, decoded_consumer);
+        }
+
+        #[test]
+        fn roundtrip_stream_cursor_value(seq_num in any::<SeqNum>()) {
+            let bytes = super::ser_value(seq_num);
+            let decoded = super::deser_value(bytes).unwrap();
+            prop_assert_eq!(seq_num, decoded);
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use std::{ops::Range, time::Duration};
+use std::time::Duration;
 
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 
-use super::{DeserializationError, KeyType, check_exact_size, timestamp::TimestampSecs};
+use super::{DeserializationError, KeyRange, KeyType, check_exact_size, timestamp::TimestampSecs};
 use crate::stream_id::StreamId;
 
 const KEY_LEN: usize = 1 + 4 + StreamId::LEN;
@@ -29,16 +29,16 @@ pub fn ser_key(deadline: TimestampSecs, stream_id: StreamId) -> Bytes {
     buf.freeze()
 }
 
-pub fn expired_key_range(deadline: TimestampSecs) -> Range<Bytes> {
+pub fn expired_key_range(deadline: TimestampSecs) -> KeyRange {
     let start = Bytes::from(vec![KeyType::StreamDeleteOnEmptyDeadline as u8]);
     let end = ser_key_range_end(deadline);
-    start..end
+    KeyRange { start, end }
 }
 
-fn ser_key_range_end(deadline: TimestampSecs) -> Bytes {
+fn ser_key_range_end(deadline: TimestampSecs) -> Option<Bytes> {
     let max_stream_id = StreamId::from([u8::MAX; StreamId::LEN]);
     let end_key = ser_key(deadline, max_stream_id);
-    super::increment_bytes(BytesMut::from(end_key.as_ref())).expect("non-empty")
+    super::increment_bytes(BytesMut::from(end_key.as_ref()))
 }
 
 pub fn deser_key(mut bytes: Bytes) -> Result<(TimestampSecs, StreamId), DeserializationError> {
@@ -70,12 +70,12 @@ pub fn deser_value(mut bytes: Bytes) -> Result<Duration, DeserializationError> {
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::{ops::RangeBounds as _, time::Duration};
 
     use proptest::prelude::*;
 
     use crate::{
-        backend::kv::{stream_doe_deadline, timestamp::TimestampSecs},
+        backend::kv::{KeyType, stream_doe_deadline, timestamp::TimestampSecs},
         stream_id::StreamId,
     };
 
@@ -94,6 +94,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn expired_key_range_includes_max_stream_id_at_max_deadline() {
+        let range = stream_doe_deadline::expired_key_range(TimestampSecs::MAX);
+        let max_key = stream_doe_deadline::ser_key(
+            TimestampSecs::MAX,
+            StreamId::from([u8::MAX; StreamId::LEN]),
+        );
+        assert!(range.contains(&max_key));
+        assert_eq!(
+            range.end.as_deref(),
+            Some(&[KeyType::StreamDeleteOnEmptyDeadline as u8 + 1][..])
+        );
+    }
+
     #[test]
     fn roundtrip_stream_doe_deadline_value() {
         let min_age = Duration::from_secs(123);
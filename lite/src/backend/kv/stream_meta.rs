@@ -1,4 +1,4 @@
-use std::{ops::Range, str::FromStr};
+use std::str::FromStr;
 
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use s2_common::{
@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use super::{
-    DeserializationError, KeyType, check_min_size, deser_json_value, increment_bytes,
+    DeserializationError, KeyRange, KeyType, check_min_size, deser_json_value, increment_bytes,
     invalid_value_err, ser_json_value,
 };
 
@@ -74,8 +74,8 @@ pub fn ser_key_prefix(basin: &BasinName, prefix: &StreamNamePrefix) -> Bytes {
     ser_key_internal(basin.as_bytes(), prefix.as_bytes()).freeze()
 }
 
-pub fn ser_key_prefix_end(basin: &BasinName, prefix: &StreamNamePrefix) -> Bytes {
-    increment_bytes(ser_key_internal(basin.as_bytes(), prefix.as_bytes())).expect("non-empty")
+pub fn ser_key_prefix_end(basin: &BasinName, prefix: &StreamNamePrefix) -> Option<Bytes> {
+    increment_bytes(ser_key_internal(basin.as_bytes(), prefix.as_bytes()))
 }
 
 pub fn ser_key_start_after(basin: &BasinName, start_after: &StreamNameStartAfter) -> Bytes {
@@ -90,7 +90,7 @@ pub fn ser_key_range(
     basin: &BasinName,
     prefix: &StreamNamePrefix,
     start_after: &StreamNameStartAfter,
-) -> Range<Bytes> {
+) -> KeyRange {
     let prefix_start = ser_key_prefix(basin, prefix);
     let start = if !start_after.is_empty() {
         let start_after_key = ser_key_start_after(basin, start_after);
@@ -99,7 +99,7 @@ pub fn ser_key_range(
         prefix_start
     };
     let end = ser_key_prefix_end(basin, prefix);
-    start..end
+    KeyRange { start, end }
 }
 
 pub fn ser_key(basin: &BasinName, stream: &StreamName) -> Bytes {
@@ -149,7 +149,7 @@ pub fn deser_value(bytes: Bytes) -> Result<StreamMeta, DeserializationError> {
 
 #[cfg(test)]
 mod tests {
-    use std::{str::FromStr, time::Duration};
+    use std::{ops::RangeBounds as _, str::FromStr, time::Duration};
 
     use bytes::Bytes;
     use proptest::prelude::*;
@@ -162,7 +162,10 @@ mod tests {
     use s2_storage::bash::Bash;
     use time::OffsetDateTime;
 
-    use crate::backend::kv::proptest_strategies::{basin_name_strategy, stream_name_strategy};
+    use crate::backend::kv::{
+        DeserializationError,
+        proptest_strategies::{basin_name_strategy, stream_name_strategy},
+    };
 
     #[test]
     fn value_roundtrip_stream_meta() {
@@ -250,6 +253,51 @@ mod tests {
         assert_eq!(decoded.cipher, serde_value.cipher);
     }
 
+    #[test]
+    fn stream_meta_deser_legacy_unversioned_value() {
+        let bytes = Bytes::from_static(
+            br#"{"config":null,"cipher":"aegis-256","created_at":"2009-02-13T23:31:30Z","deleted_at":null,"creation_idempotency_key":null}"#,
+        );
+        let decoded = super::deser_value(bytes).unwrap();
+
+        assert_eq!(
+            decoded.created_at,
+            OffsetDateTime::from_unix_timestamp(1234567890).unwrap()
+        );
+        assert_eq!(decoded.deleted_at, None);
+        assert_eq!(decoded.cipher, Some(EncryptionAlgorithm::Aegis256));
+    }
+
+    fn test_stream_meta() -> super::StreamMeta {
+        super::StreamMeta {
+            config: StreamConfig::default(),
+            cipher: None,
+            created_at: OffsetDateTime::from_unix_timestamp(1234567890).unwrap(),
+            deleted_at: None,
+            creation_idempotency_key: None,
+        }
+    }
+
+    #[test]
+    fn stream_meta_ser_value_is_versioned() {
+        let stream_meta = test_stream_meta();
+        let bytes = super::ser_value(&stream_meta);
+        assert_eq!(bytes[0], super::super::JSON_VALUE_VERSION);
+        assert_eq!(bytes[1], b'{');
+
+        let decoded = super::deser_value(bytes).unwrap();
+        assert_eq!(decoded.created_at, stream_meta.created_at);
+    }
+
+    #[test]
+    fn stream_meta_deser_unsupported_version() {
+        let mut bytes = super::ser_value(&test_stream_meta()).to_vec();
+        bytes[0] = super::super::JSON_VALUE_VERSION + 1;
+
+        let err = super::deser_value(Bytes::from(bytes)).unwrap_err();
+        assert!(matches!(err, DeserializationError::UnsupportedVersion(2)));
+    }
+
     fn stream_name_prefix_strategy() -> impl Strategy<Value = StreamNamePrefix> {
         prop_oneof![
             Just(StreamNamePrefix::default()),
@@ -266,14 +314,14 @@ mod tests {
         let range = super::ser_key_range(&basin, &prefix, &start_after);
 
         assert!(
-            range.start < range.end,
+            !range.is_empty(),
             "range should be valid when start_after is before prefix range"
         );
 
         let staging_stream = StreamName::from_str("staging-api").unwrap();
         let staging_key = super::ser_key(&basin, &staging_stream);
         assert!(
-            staging_key >= range.start && staging_key < range.end,
+            range.contains(&staging_key),
             "streams matching prefix should be in range"
         );
 
@@ -311,10 +359,9 @@ mod tests {
             let key = super::ser_key(&basin, &stream);
 
             if matches_prefix {
-                prop_assert!(key >= range.start, "key {:?} should be >= range.start {:?}", key, range.start);
-                prop_assert!(key < range.end, "key {:?} should be < range.end {:?}", key, range.end);
+                prop_assert!(range.contains(&key), "key {:?} should be in range {:?}", key, range);
             } else {
-                prop_assert!(key < range.start || key >= range.end);
+                prop_assert!(!range.contains(&key));
             }
         }
 
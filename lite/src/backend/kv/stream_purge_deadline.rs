@@ -0,0 +1,109 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use time::OffsetDateTime;
+
+use super::{
+    DeserializationError, KeyRange, KeyType, check_exact_size, invalid_value_err,
+    timestamp::TimestampSecs,
+};
+use crate::stream_id::StreamId;
+
+const KEY_LEN: usize = 1 + 4 + StreamId::LEN;
+const VALUE_LEN: usize = 16;
+
+pub fn ser_key(deadline: TimestampSecs, stream_id: StreamId) -> Bytes {
+    let mut buf = BytesMut::with_capacity(KEY_LEN);
+    buf.put_u8(KeyType::StreamPurgeDeadline as u8);
+    buf.put_u32(deadline.as_u32());
+    buf.put_slice(stream_id.as_bytes());
+    debug_assert_eq!(buf.len(), KEY_LEN, "serialized length mismatch");
+    buf.freeze()
+}
+
+pub fn expired_key_range(deadline: TimestampSecs) -> KeyRange {
+    let start = Bytes::from(vec![KeyType::StreamPurgeDeadline as u8]);
+    let max_stream_id = StreamId::from([u8::MAX; StreamId::LEN]);
+    let end_key = ser_key(deadline, max_stream_id);
+    let end = super::increment_bytes(BytesMut::from(end_key.as_ref()));
+    KeyRange { start, end }
+}
+
+pub fn deser_key(mut bytes: Bytes) -> Result<(TimestampSecs, StreamId), DeserializationError> {
+    check_exact_size(&bytes, KEY_LEN)?;
+    let ordinal = bytes.get_u8();
+    if ordinal != (KeyType::StreamPurgeDeadline as u8) {
+        return Err(DeserializationError::InvalidOrdinal(ordinal));
+    }
+    let deadline_secs = bytes.get_u32();
+    let mut stream_id_bytes = [0u8; StreamId::LEN];
+    bytes.copy_to_slice(&mut stream_id_bytes);
+    Ok((
+        TimestampSecs::from_secs(deadline_secs),
+        stream_id_bytes.into(),
+    ))
+}
+
+/// The value is the deletion time the deadline was armed for, so that a deadline left behind by
+/// an undeleted stream does not purge it after a later deletion.
+pub fn ser_value(deleted_at: OffsetDateTime) -> Bytes {
+    let mut buf = BytesMut::with_capacity(VALUE_LEN);
+    buf.put_i128(deleted_at.unix_timestamp_nanos());
+    debug_assert_eq!(buf.len(), VALUE_LEN, "serialized length mismatch");
+    buf.freeze()
+}
+
+pub fn deser_value(mut bytes: Bytes) -> Result<OffsetDateTime, DeserializationError> {
+    check_exact_size(&bytes, VALUE_LEN)?;
+    OffsetDateTime::from_unix_timestamp_nanos(bytes.get_i128())
+        .map_err(|e| invalid_value_err("deleted_at", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeBounds as _;
+
+    use proptest::prelude::*;
+    use time::OffsetDateTime;
+
+    use crate::{
+        backend::kv::{stream_purge_deadline, timestamp::TimestampSecs},
+        stream_id::StreamId,
+    };
+
+    proptest! {
+        #[test]
+        fn roundtrip_stream_purge_deadline_key(
+            deadline_secs in any::<u32>(),
+            stream_id_bytes in any::<[u8; StreamId::LEN]>(),
+        ) {
+            let deadline = TimestampSecs::from_secs(deadline_secs);
+            let stream_id = StreamId::from(stream_id_bytes);
+            let bytes = stream_purge_deadline::ser_key(deadline, stream_id);
+            let (decoded_deadline, decoded_stream_id) =
+                stream_purge_deadline::deser_key(bytes).unwrap();
+            prop_assert_eq!(deadline, decoded_deadline);
+            prop_assert_eq!(stream_id, decoded_stream_id);
+        }
+    }
+
+    #[test]
+    fn expired_key_range_excludes_later_deadlines() {
+        let stream_id = StreamId::from([7; StreamId::LEN]);
+        let range = stream_purge_deadline::expired_key_range(TimestampSecs::from_secs(100));
+        assert!(range.contains(&stream_purge_deadline::ser_key(
+            TimestampSecs::from_secs(100),
+            stream_id
+        )));
+        assert!(!range.contains(&stream_purge_deadline::ser_key(
+            TimestampSecs::from_secs(101),
+            stream_id
+        )));
+    }
+
+    #[test]
+    fn roundtrip_stream_purge_deadline_value() {
+        let deleted_at = OffsetDateTime::now_utc();
+        let bytes = stream_purge_deadline::ser_value(deleted_at);
+        let decoded = stream_purge_deadline::deser_value(bytes).unwrap();
+        assert_eq!(deleted_at, decoded);
+    }
+}
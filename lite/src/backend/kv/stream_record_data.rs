@@ -1,12 +1,39 @@
+use std::io::{Read, Write};
+
 use bytes::{Buf, BufMut, Bytes, BytesMut};
-use s2_common::record::{Metered, StreamPosition};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use s2_common::{
+    caps,
+    config::CompressionHint,
+    record::{Metered, StreamPosition},
+};
 use s2_storage::record::{StoredRecord, decode_stored_record, encode_stored_record};
 
 use super::{DeserializationError, KeyType, check_exact_size, invalid_value_err};
 use crate::stream_id::StreamId;
 
+const KEY_PREFIX_LEN: usize = 1 + StreamId::LEN;
 const KEY_LEN: usize = 1 + StreamId::LEN + 8 + 8;
 
+/// Leading byte of a compressed value, followed by the codec ordinal and the compressed encoding.
+///
+/// An encoded stored record starts with a magic byte whose record type bits are never all zero, so
+/// compressed and uncompressed values can be told apart and streams may switch codecs at any time.
+const COMPRESSED_VALUE_PREFIX: u8 = 0;
+const COMPRESSED_VALUE_HEADER_LEN: usize = 2;
+/// Values smaller than this are not worth compressing.
+const COMPRESSION_THRESHOLD_BYTES: usize = 256;
+/// Upper bound on a decompressed value: a record is at most a full batch, plus encoding overhead.
+const MAX_DECOMPRESSED_VALUE_BYTES: usize = 2 * caps::RECORD_BATCH_MAX.bytes;
+
+pub fn ser_key_prefix(stream_id: StreamId) -> Bytes {
+    let mut buf = BytesMut::with_capacity(KEY_PREFIX_LEN);
+    buf.put_u8(KeyType::StreamRecordData as u8);
+    buf.put_slice(stream_id.as_bytes());
+    debug_assert_eq!(buf.len(), KEY_PREFIX_LEN, "serialized length mismatch");
+    buf.freeze()
+}
+
 pub fn ser_key(stream_id: StreamId, pos: StreamPosition) -> Bytes {
     let mut buf = BytesMut::with_capacity(KEY_LEN);
     buf.put_u8(KeyType::StreamRecordData as u8);
@@ -37,15 +64,78 @@ pub fn ser_value(record: Metered<&StoredRecord>) -> Bytes {
     encode_stored_record(record)
 }
 
+/// Serialize `record`, compressed with the stream's `compression` hint if that makes it smaller.
+pub fn ser_value_with_compression(
+    record: Metered<&StoredRecord>,
+    compression: CompressionHint,
+) -> Bytes {
+    let encoded = encode_stored_record(record);
+    if compression == CompressionHint::None || encoded.len() < COMPRESSION_THRESHOLD_BYTES {
+        return encoded;
+    }
+    let mut buf = Vec::with_capacity(encoded.len());
+    buf.push(COMPRESSED_VALUE_PREFIX);
+    buf.push(compression as u8);
+    let compressed = match compression {
+        CompressionHint::Zstd => zstd::stream::copy_encode(&encoded[..], &mut buf, 0).map(|_| buf),
+        CompressionHint::Gzip => {
+            let mut encoder = GzEncoder::new(buf, Compression::default());
+            encoder.write_all(&encoded).and_then(|_| encoder.finish())
+        }
+        CompressionHint::None => unreachable!("handled above"),
+    };
+    match compressed {
+        Ok(compressed) if compressed.len() < encoded.len() => compressed.into(),
+        _ => encoded,
+    }
+}
+
 pub fn deser_value(bytes: Bytes) -> Result<Metered<StoredRecord>, DeserializationError> {
+    let bytes = match bytes.first() {
+        Some(&COMPRESSED_VALUE_PREFIX) => decompress_value(&bytes)?,
+        _ => bytes,
+    };
     decode_stored_record(bytes).map_err(|e| invalid_value_err("record", e))
 }
 
+fn decompress_value(bytes: &[u8]) -> Result<Bytes, DeserializationError> {
+    let compression = bytes
+        .get(1)
+        .and_then(|&codec| CompressionHint::from_repr(codec))
+        .filter(|&codec| codec != CompressionHint::None)
+        .ok_or_else(|| invalid_value_err("record", "invalid compression codec"))?;
+    let payload = &bytes[COMPRESSED_VALUE_HEADER_LEN..];
+    let mut buf = Vec::with_capacity(payload.len().saturating_mul(2));
+    let read = match compression {
+        CompressionHint::Zstd => zstd::stream::Decoder::new(payload).and_then(|decoder| {
+            decoder
+                .take(MAX_DECOMPRESSED_VALUE_BYTES as u64 + 1)
+                .read_to_end(&mut buf)
+        }),
+        CompressionHint::Gzip => GzDecoder::new(payload)
+            .take(MAX_DECOMPRESSED_VALUE_BYTES as u64 + 1)
+            .read_to_end(&mut buf),
+        CompressionHint::None => unreachable!("filtered above"),
+    };
+    read.map_err(|e| invalid_value_err("record", e))?;
+    if buf.len() > MAX_DECOMPRESSED_VALUE_BYTES {
+        return Err(invalid_value_err(
+            "record",
+            "decompressed value exceeds limit",
+        ));
+    }
+    Ok(buf.into())
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
     use proptest::prelude::*;
-    use s2_common::record::{Metered, SeqNum, StreamPosition, Timestamp};
+    use s2_common::{
+        config::CompressionHint,
+        record::{Metered, MeteredExt as _, Record, SeqNum, StreamPosition, Timestamp},
+    };
+    use s2_storage::record::StoredRecord;
 
     use crate::{backend::kv::DeserializationError, stream_id::StreamId};
 
@@ -58,6 +148,32 @@ mod tests {
         ));
     }
 
+    #[rstest::rstest]
+    #[case::zstd(CompressionHint::Zstd)]
+    #[case::gzip(CompressionHint::Gzip)]
+    fn compressed_value_roundtrips(#[case] compression: CompressionHint) {
+        let record = Record::try_from_parts(vec![], Bytes::from(vec![b'a'; 4096])).unwrap();
+        let record = StoredRecord::from(record).metered();
+        let plain = super::ser_value(record.as_ref());
+
+        let compressed = super::ser_value_with_compression(record.as_ref(), compression);
+        assert_eq!(compressed[0], super::COMPRESSED_VALUE_PREFIX);
+        assert!(compressed.len() < plain.len());
+
+        let decoded = super::deser_value(compressed).unwrap();
+        assert_eq!(super::ser_value(decoded.as_ref()), plain);
+    }
+
+    #[test]
+    fn small_value_is_stored_uncompressed() {
+        let record = Record::try_from_parts(vec![], Bytes::from_static(b"small")).unwrap();
+        let record = StoredRecord::from(record).metered();
+        assert_eq!(
+            super::ser_value_with_compression(record.as_ref(), CompressionHint::Zstd),
+            super::ser_value(record.as_ref())
+        );
+    }
+
     proptest! {
         #[test]
         fn roundtrip_stream_record_data_key(
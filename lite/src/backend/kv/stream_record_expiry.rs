@@ -0,0 +1,125 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use s2_common::record::StreamPosition;
+
+use super::{DeserializationError, KeyRange, KeyType, check_exact_size, timestamp::TimestampSecs};
+use crate::stream_id::StreamId;
+
+const KEY_PREFIX_LEN: usize = 1 + StreamId::LEN;
+const KEY_LEN: usize = 1 + StreamId::LEN + 8 + 8;
+const VALUE_LEN: usize = 4;
+
+pub fn ser_key_prefix(stream_id: StreamId) -> Bytes {
+    let mut buf = BytesMut::with_capacity(KEY_PREFIX_LEN);
+    buf.put_u8(KeyType::StreamRecordExpiry as u8);
+    buf.put_slice(stream_id.as_bytes());
+    debug_assert_eq!(buf.len(), KEY_PREFIX_LEN, "serialized length mismatch");
+    buf.freeze()
+}
+
+pub fn ser_key(stream_id: StreamId, pos: StreamPosition) -> Bytes {
+    let mut buf = BytesMut::with_capacity(KEY_LEN);
+    buf.put_u8(KeyType::StreamRecordExpiry as u8);
+    buf.put_slice(stream_id.as_bytes());
+    buf.put_u64(pos.seq_num);
+    buf.put_u64(pos.timestamp);
+    debug_assert_eq!(buf.len(), KEY_LEN, "serialized length mismatch");
+    buf.freeze()
+}
+
+/// Key range of the index entries of all streams whose ID sorts after `after`, or of all streams
+/// if `None`. The first entry in the range belongs to the next stream with expiring records.
+pub fn ser_streams_after_range(after: Option<StreamId>) -> KeyRange {
+    let KeyRange { start, end } = super::key_type_range(KeyType::StreamRecordExpiry);
+    let start = match after {
+        Some(stream_id) => {
+            super::increment_bytes(BytesMut::from(ser_key_prefix(stream_id).as_ref()))
+                .expect("prefix starts with a key type ordinal")
+        }
+        None => start,
+    };
+    KeyRange { start, end }
+}
+
+pub fn deser_key(mut bytes: Bytes) -> Result<(StreamId, StreamPosition), DeserializationError> {
+    check_exact_size(&bytes, KEY_LEN)?;
+    let ordinal = bytes.get_u8();
+    if ordinal != (KeyType::StreamRecordExpiry as u8) {
+        return Err(DeserializationError::InvalidOrdinal(ordinal));
+    }
+    let mut stream_id_bytes = [0u8; StreamId::LEN];
+    bytes.copy_to_slice(&mut stream_id_bytes);
+    let seq_num = bytes.get_u64();
+    let timestamp = bytes.get_u64();
+    Ok((
+        stream_id_bytes.into(),
+        StreamPosition { seq_num, timestamp },
+    ))
+}
+
+pub fn ser_value(expires_at: TimestampSecs) -> Bytes {
+    let mut buf = BytesMut::with_capacity(VALUE_LEN);
+    buf.put_u32(expires_at.as_u32());
+    debug_assert_eq!(buf.len(), VALUE_LEN, "serialized length mismatch");
+    buf.freeze()
+}
+
+pub fn deser_value(mut bytes: Bytes) -> Result<TimestampSecs, DeserializationError> {
+    check_exact_size(&bytes, VALUE_LEN)?;
+    Ok(TimestampSecs::from_secs(bytes.get_u32()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeBounds as _;
+
+    use proptest::prelude::*;
+    use s2_common::record::StreamPosition;
+
+    use crate::{
+        backend::kv::{stream_record_expiry, timestamp::TimestampSecs},
+        stream_id::StreamId,
+    };
+
+    proptest! {
+        #[test]
+        fn roundtrip_stream_record_expiry_key(
+            stream_id_bytes in any::<[u8; StreamId::LEN]>(),
+            seq_num in any::<u64>(),
+            timestamp in any::<u64>(),
+        ) {
+            let stream_id = StreamId::from(stream_id_bytes);
+            let pos = StreamPosition { seq_num, timestamp };
+            let bytes = stream_record_expiry::ser_key(stream_id, pos);
+            let (decoded_stream_id, decoded_pos) = stream_record_expiry::deser_key(bytes).unwrap();
+            prop_assert_eq!(stream_id, decoded_stream_id);
+            prop_assert_eq!(pos, decoded_pos);
+        }
+
+        #[test]
+        fn roundtrip_stream_record_expiry_value(expires_at_secs in any::<u32>()) {
+            let expires_at = TimestampSecs::from_secs(expires_at_secs);
+            let bytes = stream_record_expiry::ser_value(expires_at);
+            prop_assert_eq!(stream_record_expiry::deser_value(bytes).unwrap(), expires_at);
+        }
+    }
+
+    #[test]
+    fn streams_after_range_skips_earlier_streams() {
+        let pos = StreamPosition {
+            seq_num: u64::MAX,
+            timestamp: u64::MAX,
+        };
+        let first = StreamId::from([1; StreamId::LEN]);
+        let last = StreamId::from([u8::MAX; StreamId::LEN]);
+
+        let all = stream_record_expiry::ser_streams_after_range(None);
+        assert!(all.contains(&stream_record_expiry::ser_key(first, pos)));
+        assert!(all.contains(&stream_record_expiry::ser_key(last, pos)));
+
+        let after_first = stream_record_expiry::ser_streams_after_range(Some(first));
+        assert!(!after_first.contains(&stream_record_expiry::ser_key(first, pos)));
+        assert!(after_first.contains(&stream_record_expiry::ser_key(last, pos)));
+
+        assert!(stream_record_expiry::ser_streams_after_range(Some(last)).is_empty());
+    }
+}
@@ -1,5 +1,7 @@
+use std::ops::{Bound, Range};
+
 use bytes::{Buf, BufMut, Bytes, BytesMut};
-use s2_common::record::StreamPosition;
+use s2_common::record::{SeqNum, StreamPosition, Timestamp};
 
 use super::{DeserializationError, KeyType, check_exact_size};
 use crate::stream_id::StreamId;
@@ -25,6 +27,35 @@ pub fn ser_key(stream_id: StreamId, pos: StreamPosition) -> Bytes {
     buf.freeze()
 }
 
+/// Key range of the stream's index entries with a timestamp after `start`, or `None` if no
+/// timestamp can be. `Included(t)` seeks to the first record at or after `t` (lower bound), and
+/// `Excluded(t)` to the first record strictly after `t` (upper bound).
+///
+/// Keys order by timestamp, then sequence number, and record timestamps are non-decreasing in
+/// sequence number order, so the first entry in the range is the position to start reading from.
+pub fn ser_seek_range(stream_id: StreamId, start: Bound<Timestamp>) -> Option<Range<Bytes>> {
+    let timestamp = match start {
+        Bound::Included(timestamp) => timestamp,
+        Bound::Excluded(timestamp) => timestamp.checked_add(1)?,
+        Bound::Unbounded => Timestamp::MIN,
+    };
+    let start = ser_key(
+        stream_id,
+        StreamPosition {
+            seq_num: SeqNum::MIN,
+            timestamp,
+        },
+    );
+    let end = ser_key(
+        stream_id,
+        StreamPosition {
+            seq_num: SeqNum::MAX,
+            timestamp: Timestamp::MAX,
+        },
+    );
+    Some(start..end)
+}
+
 pub fn deser_key(mut bytes: Bytes) -> Result<(StreamId, StreamPosition), DeserializationError> {
     check_exact_size(&bytes, KEY_LEN)?;
     let ordinal = bytes.get_u8();
@@ -52,11 +83,132 @@ pub fn deser_value(bytes: Bytes) -> Result<(), DeserializationError> {
 
 #[cfg(test)]
 mod tests {
+    use std::{collections::BTreeSet, ops::Bound};
+
+    use bytes::Bytes;
     use proptest::prelude::*;
     use s2_common::record::{SeqNum, StreamPosition, Timestamp};
 
     use crate::stream_id::StreamId;
 
+    /// Index keys for `timestamps` as consecutive records of `stream_id`, surrounded by entries of
+    /// adjacent streams that a seek must not land on.
+    fn index_keys(
+        stream_id_bytes: [u8; StreamId::LEN],
+        timestamps: &[Timestamp],
+    ) -> BTreeSet<Bytes> {
+        let mut keys = BTreeSet::new();
+        for neighbor in [[0u8; StreamId::LEN], [u8::MAX; StreamId::LEN]] {
+            for (seq_num, timestamp) in [(0, 0), (SeqNum::MAX - 1, Timestamp::MAX)] {
+                keys.insert(super::ser_key(
+                    StreamId::from(neighbor),
+                    StreamPosition { seq_num, timestamp },
+                ));
+            }
+        }
+        for (seq_num, &timestamp) in timestamps.iter().enumerate() {
+            keys.insert(super::ser_key(
+                StreamId::from(stream_id_bytes),
+                StreamPosition {
+                    seq_num: seq_num as SeqNum,
+                    timestamp,
+                },
+            ));
+        }
+        keys
+    }
+
+    fn seek(
+        keys: &BTreeSet<Bytes>,
+        stream_id: StreamId,
+        start: Bound<Timestamp>,
+    ) -> Option<SeqNum> {
+        let range = super::ser_seek_range(stream_id, start)?;
+        keys.range(range).next().map(|key| {
+            let (deser_stream_id, pos) = super::deser_key(key.clone()).unwrap();
+            assert_eq!(deser_stream_id, stream_id);
+            pos.seq_num
+        })
+    }
+
+    fn gen_timestamps() -> impl Strategy<Value = Vec<Timestamp>> {
+        prop_oneof![
+            proptest::collection::vec(0..50u64, 0..40),
+            proptest::collection::vec(any::<Timestamp>(), 0..40),
+        ]
+        .prop_map(|mut timestamps| {
+            timestamps.sort_unstable();
+            timestamps
+        })
+    }
+
+    fn gen_target() -> impl Strategy<Value = Timestamp> {
+        prop_oneof![0..60u64, Just(0), Just(Timestamp::MAX), any::<Timestamp>()]
+    }
+
+    #[test]
+    fn seek_bounds_on_duplicate_timestamps() {
+        let stream_id_bytes = [7u8; StreamId::LEN];
+        let stream_id = StreamId::from(stream_id_bytes);
+        let keys = index_keys(stream_id_bytes, &[10, 20, 20, 20, 30]);
+
+        assert_eq!(seek(&keys, stream_id, Bound::Unbounded), Some(0));
+        assert_eq!(seek(&keys, stream_id, Bound::Included(0)), Some(0));
+        assert_eq!(seek(&keys, stream_id, Bound::Included(10)), Some(0));
+        assert_eq!(seek(&keys, stream_id, Bound::Excluded(10)), Some(1));
+        assert_eq!(seek(&keys, stream_id, Bound::Included(20)), Some(1));
+        assert_eq!(seek(&keys, stream_id, Bound::Excluded(20)), Some(4));
+        assert_eq!(seek(&keys, stream_id, Bound::Included(30)), Some(4));
+        assert_eq!(seek(&keys, stream_id, Bound::Excluded(30)), None);
+        assert_eq!(seek(&keys, stream_id, Bound::Included(31)), None);
+        assert!(super::ser_seek_range(stream_id, Bound::Excluded(Timestamp::MAX)).is_none());
+    }
+
+    fn gen_boundary_u64() -> impl Strategy<Value = u64> {
+        prop_oneof![
+            0..4u64,
+            (0..8u32).prop_flat_map(|byte| {
+                let boundary = 1u64 << (8 * byte);
+                (boundary - 1)..=(boundary + 1)
+            }),
+            (u64::MAX - 2)..=u64::MAX,
+            any::<u64>(),
+        ]
+    }
+
+    fn assert_keys_sort_like_positions(stream_id: StreamId, positions: &[StreamPosition]) {
+        let mut by_key: Vec<_> = positions
+            .iter()
+            .map(|&pos| (super::ser_key(stream_id, pos), pos))
+            .collect();
+        by_key.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut by_tuple = positions.to_vec();
+        by_tuple.sort_by_key(|pos| (pos.timestamp, pos.seq_num));
+        let by_key: Vec<_> = by_key.into_iter().map(|(_, pos)| pos).collect();
+        assert_eq!(by_key, by_tuple);
+    }
+
+    #[test]
+    fn keys_sort_by_timestamp_then_seq_num_across_byte_boundaries() {
+        let stream_id = StreamId::from([7u8; StreamId::LEN]);
+        let positions: Vec<_> = [
+            (0, 255),
+            (SeqNum::MAX, 255),
+            (1, 256),
+            (0, 256),
+            (1 << 32, u32::MAX as Timestamp),
+            ((1 << 32) - 1, u32::MAX as Timestamp + 1),
+            (SeqNum::MAX, Timestamp::MAX - 1),
+            (0, Timestamp::MAX),
+            (SeqNum::MAX, Timestamp::MAX),
+            (SeqNum::MAX, 0),
+        ]
+        .into_iter()
+        .map(|(seq_num, timestamp)| StreamPosition { seq_num, timestamp })
+        .collect();
+        assert_keys_sort_like_positions(stream_id, &positions);
+    }
+
     #[test]
     fn roundtrip_stream_record_timestamp_value() {
         let bytes = super::ser_value();
@@ -91,5 +243,42 @@ mod tests {
             prop_assert_eq!(prefix.len(), super::KEY_PREFIX_LEN);
             prop_assert!(key_bytes.as_ref().starts_with(prefix.as_ref()));
         }
+
+        #[test]
+        fn keys_sort_like_timestamp_seq_num_tuples(
+            stream_id_bytes in any::<[u8; StreamId::LEN]>(),
+            positions in proptest::collection::vec(
+                (gen_boundary_u64(), gen_boundary_u64())
+                    .prop_map(|(seq_num, timestamp)| StreamPosition { seq_num, timestamp }),
+                0..40,
+            ),
+        ) {
+            assert_keys_sort_like_positions(StreamId::from(stream_id_bytes), &positions);
+        }
+
+        #[test]
+        fn seek_matches_linear_scan(
+            stream_id_bytes in any::<[u8; StreamId::LEN]>()
+                .prop_filter("distinct from neighbors", |b| {
+                    *b != [0u8; StreamId::LEN] && *b != [u8::MAX; StreamId::LEN]
+                }),
+            timestamps in gen_timestamps(),
+            target in gen_target(),
+        ) {
+            let stream_id = StreamId::from(stream_id_bytes);
+            let keys = index_keys(stream_id_bytes, &timestamps);
+
+            let expected_lower = timestamps.iter().position(|&ts| ts >= target);
+            prop_assert_eq!(
+                seek(&keys, stream_id, Bound::Included(target)),
+                expected_lower.map(|i| i as SeqNum)
+            );
+
+            let expected_upper = timestamps.iter().position(|&ts| ts > target);
+            prop_assert_eq!(
+                seek(&keys, stream_id, Bound::Excluded(target)),
+                expected_upper.map(|i| i as SeqNum)
+            );
+        }
     }
 }
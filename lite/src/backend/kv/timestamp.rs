@@ -1,5 +1,7 @@
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::backend::clock::Clock;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct TimestampSecs(u32);
 
@@ -7,12 +9,12 @@ impl TimestampSecs {
     pub const ZERO: Self = Self(0);
     pub const MAX: Self = Self(u32::MAX);
 
-    pub fn now() -> Self {
-        Self::from_system_time(SystemTime::now())
+    pub fn now(clock: &Clock) -> Self {
+        Self::from_system_time(clock.now())
     }
 
-    pub fn after(dur: Duration) -> Self {
-        match SystemTime::now().checked_add(dur) {
+    pub fn after(clock: &Clock, dur: Duration) -> Self {
+        match clock.now().checked_add(dur) {
             Some(deadline) => Self::from_system_time(deadline),
             None => Self(u32::MAX),
         }
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use bytesize::ByteSize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::metrics::{self, MemoryComponent};
+
+/// Global byte budget shared by buffers of one kind that grow with load, i.e. in-flight appends or
+/// read-ahead, so that approaching the limit applies backpressure instead of risking an OOM kill.
+///
+/// Appends and read-ahead get separate budgets, so that batches buffered for slow readers cannot
+/// block appends.
+///
+/// Sizes are approximate heap sizes per [`s2_common::deep_size::DeepSize`].
+#[derive(Debug, Clone, Default)]
+pub(super) struct MemoryBudget {
+    inner: Option<Arc<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    component: MemoryComponent,
+    capacity: usize,
+    sema: Arc<Semaphore>,
+}
+
+impl MemoryBudget {
+    /// A zero capacity disables the budget.
+    pub fn new(capacity: ByteSize, component: MemoryComponent) -> Self {
+        let capacity = (capacity.as_u64() as usize).min(Semaphore::MAX_PERMITS);
+        Self {
+            inner: (capacity > 0).then(|| {
+                Arc::new(Inner {
+                    component,
+                    capacity,
+                    sema: Arc::new(Semaphore::new(capacity)),
+                })
+            }),
+        }
+    }
+
+    /// Wait until `bytes` fit within the budget. A request for more than the whole budget is
+    /// admitted once nothing else holds any of it, so that it cannot wait forever.
+    pub async fn acquire(&self, bytes: usize) -> MemoryPermit {
+        let Some(inner) = self.inner.as_ref() else {
+            return MemoryPermit { held: None };
+        };
+        let num_permits = bytes.min(inner.capacity).min(u32::MAX as usize) as u32;
+        if num_permits == 0 {
+            return MemoryPermit { held: None };
+        }
+        let permit = inner
+            .sema
+            .clone()
+            .acquire_many_owned(num_permits)
+            .await
+            .expect("semaphore is never closed");
+        inner.observe_in_use();
+        MemoryPermit {
+            held: Some((permit, inner.clone())),
+        }
+    }
+
+    /// Bytes currently held against the budget.
+    pub fn in_use(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.in_use())
+    }
+}
+
+impl Inner {
+    fn in_use(&self) -> usize {
+        self.capacity - self.sema.available_permits()
+    }
+
+    fn observe_in_use(&self) {
+        metrics::observe_memory_usage(self.component, self.in_use());
+    }
+}
+
+/// Bytes held against a [`MemoryBudget`] until dropped.
+#[derive(Debug)]
+pub(super) struct MemoryPermit {
+    held: Option<(OwnedSemaphorePermit, Arc<Inner>)>,
+}
+
+impl Drop for MemoryPermit {
+    fn drop(&mut self) {
+        if let Some((permit, inner)) = self.held.take() {
+            drop(permit);
+            inner.observe_in_use();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bytesize::ByteSize;
+
+    use super::MemoryBudget;
+    use crate::metrics::MemoryComponent;
+
+    #[tokio::test]
+    async fn acquire_waits_for_released_bytes() {
+        let budget = MemoryBudget::new(ByteSize::b(100), MemoryComponent::AppendBudget);
+        let first = budget.acquire(60).await;
+        assert_eq!(budget.in_use(), 60);
+
+        let blocked = tokio::time::timeout(Duration::from_millis(10), budget.acquire(60)).await;
+        assert!(blocked.is_err());
+
+        drop(first);
+        assert_eq!(budget.in_use(), 0);
+        let _second = budget.acquire(60).await;
+        assert_eq!(budget.in_use(), 60);
+    }
+
+    #[tokio::test]
+    async fn oversized_request_is_admitted_alone() {
+        let budget = MemoryBudget::new(ByteSize::b(100), MemoryComponent::AppendBudget);
+        let permit = budget.acquire(1_000).await;
+        assert_eq!(budget.in_use(), 100);
+        drop(permit);
+        assert_eq!(budget.in_use(), 0);
+    }
+
+    #[tokio::test]
+    async fn zero_capacity_disables_budget() {
+        let budget = MemoryBudget::new(ByteSize::b(0), MemoryComponent::AppendBudget);
+        let _permits = [budget.acquire(1_000).await, budget.acquire(1_000).await];
+        assert_eq!(budget.in_use(), 0);
+    }
+}
@@ -4,9 +4,16 @@ pub mod error;
 
 mod basins;
 pub mod bgtasks;
+pub mod clock;
+mod commands;
 mod core;
+mod cursors;
 mod durability_notifier;
+pub mod fsck;
+mod memory_budget;
+mod rate_limit;
 mod read;
+mod read_cache;
 mod store;
 mod streamer;
 mod streams;
@@ -14,7 +21,7 @@ mod streams;
 mod append;
 mod kv;
 
-pub use core::Backend;
+pub use core::{Backend, StreamConfigChange};
 
 pub use crate::stream_id::StreamId;
 
@@ -22,6 +29,15 @@ pub struct StreamHandle {
     db: slatedb::Db,
     client: streamer::GuardedStreamerClient,
     encryption: EncryptionSpec,
+    read_cache: read_cache::ReadCache,
+    read_ahead_memory_budget: memory_budget::MemoryBudget,
+    read_ahead_batches: usize,
+    skip_undecodable_records: bool,
 }
 
+/// Default number of durable record batches a follower may fall behind before it has to catch up
+/// from storage.
 pub const FOLLOWER_MAX_LAG: usize = 25;
+
+/// Default cap on how long a unary read may wait for new records.
+pub const MAX_UNARY_READ_WAIT: std::time::Duration = std::time::Duration::from_secs(60);
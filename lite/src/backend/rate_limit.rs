@@ -0,0 +1,229 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+
+use super::error::AppendRateLimitedError;
+use crate::stream_id::StreamId;
+
+/// Token buckets enforcing per-stream append rate limits, keyed by stream.
+///
+/// Buckets hold up to one second's worth of their rate. A batch is admitted if its cost is
+/// available, where cost is capped at the bucket capacity so that a batch larger than the byte
+/// rate can still go through once the bucket is full, leaving it in debt.
+#[derive(Clone, Default)]
+pub(super) struct AppendRateLimiters {
+    buckets: Arc<DashMap<StreamId, Buckets>>,
+}
+
+struct Buckets {
+    bytes: TokenBucket,
+    ops: TokenBucket,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(now: Instant) -> Self {
+        Self {
+            tokens: f64::INFINITY,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, rate: u64, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate as f64).min(rate as f64);
+        self.refilled_at = now;
+    }
+
+    fn has(&self, rate: u64, cost: u64) -> bool {
+        rate == 0 || self.tokens >= cost.min(rate) as f64
+    }
+
+    /// Time until [`Self::has`] would hold, assuming no other takers.
+    fn wait(&self, rate: u64, cost: u64) -> Duration {
+        Duration::from_secs_f64(((cost.min(rate) as f64 - self.tokens) / rate as f64).max(0.0))
+    }
+
+    fn take(&mut self, rate: u64, cost: u64) {
+        if rate > 0 {
+            self.tokens -= cost as f64;
+        }
+    }
+}
+
+impl AppendRateLimiters {
+    /// Charge an append of `bytes` metered bytes against the stream's limits. A zero limit is
+    /// unrestricted.
+    pub fn try_acquire(
+        &self,
+        stream_id: StreamId,
+        max_bytes_per_sec: u64,
+        max_ops_per_sec: u64,
+        bytes: usize,
+    ) -> Result<(), AppendRateLimitedError> {
+        self.try_acquire_at(
+            stream_id,
+            max_bytes_per_sec,
+            max_ops_per_sec,
+            bytes,
+            Instant::now(),
+        )
+    }
+
+    fn try_acquire_at(
+        &self,
+        stream_id: StreamId,
+        max_bytes_per_sec: u64,
+        max_ops_per_sec: u64,
+        bytes: usize,
+        now: Instant,
+    ) -> Result<(), AppendRateLimitedError> {
+        if max_bytes_per_sec == 0 && max_ops_per_sec == 0 {
+            self.buckets.remove(&stream_id);
+            return Ok(());
+        }
+        let bytes = bytes as u64;
+        let mut buckets = self.buckets.entry(stream_id).or_insert_with(|| Buckets {
+            bytes: TokenBucket::new(now),
+            ops: TokenBucket::new(now),
+        });
+        buckets.bytes.refill(max_bytes_per_sec, now);
+        buckets.ops.refill(max_ops_per_sec, now);
+        if !buckets.ops.has(max_ops_per_sec, 1) {
+            return Err(AppendRateLimitedError::Ops(
+                max_ops_per_sec,
+                buckets.ops.wait(max_ops_per_sec, 1),
+            ));
+        }
+        if !buckets.bytes.has(max_bytes_per_sec, bytes) {
+            return Err(AppendRateLimitedError::Bytes(
+                max_bytes_per_sec,
+                buckets.bytes.wait(max_bytes_per_sec, bytes),
+            ));
+        }
+        buckets.ops.take(max_ops_per_sec, 1);
+        buckets.bytes.take(max_bytes_per_sec, bytes);
+        Ok(())
+    }
+
+    /// Forget the stream's buckets.
+    pub fn remove(&self, stream_id: StreamId) {
+        self.buckets.remove(&stream_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream_id() -> StreamId {
+        StreamId::from([1u8; StreamId::LEN])
+    }
+
+    #[test]
+    fn ops_limit_refills_over_time() {
+        let limiters = AppendRateLimiters::default();
+        let start = Instant::now();
+
+        for _ in 0..2 {
+            assert!(
+                limiters
+                    .try_acquire_at(stream_id(), 0, 2, 10, start)
+                    .is_ok()
+            );
+        }
+        let err = limiters
+            .try_acquire_at(stream_id(), 0, 2, 10, start)
+            .unwrap_err();
+        assert!(matches!(err, AppendRateLimitedError::Ops(2, _)));
+        assert_eq!(err.retry_after(), Duration::from_millis(500));
+
+        let later = start + Duration::from_millis(500);
+        assert!(
+            limiters
+                .try_acquire_at(stream_id(), 0, 2, 10, later)
+                .is_ok()
+        );
+        assert!(
+            limiters
+                .try_acquire_at(stream_id(), 0, 2, 10, later)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn oversized_batch_admitted_when_full_then_repaid() {
+        let limiters = AppendRateLimiters::default();
+        let start = Instant::now();
+
+        assert!(
+            limiters
+                .try_acquire_at(stream_id(), 100, 0, 250, start)
+                .is_ok()
+        );
+        let err = limiters
+            .try_acquire_at(stream_id(), 100, 0, 1, start)
+            .unwrap_err();
+        assert!(matches!(err, AppendRateLimitedError::Bytes(100, _)));
+        assert!(err.retry_after() > Duration::from_millis(1500));
+        let later = start + Duration::from_millis(1500);
+        assert!(
+            limiters
+                .try_acquire_at(stream_id(), 100, 0, 1, later)
+                .is_err()
+        );
+        let later = start + Duration::from_secs(3);
+        assert!(
+            limiters
+                .try_acquire_at(stream_id(), 100, 0, 100, later)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejection_does_not_consume_tokens() {
+        let limiters = AppendRateLimiters::default();
+        let start = Instant::now();
+
+        assert!(
+            limiters
+                .try_acquire_at(stream_id(), 100, 10, 100, start)
+                .is_ok()
+        );
+        for _ in 0..5 {
+            assert!(
+                limiters
+                    .try_acquire_at(stream_id(), 100, 10, 50, start)
+                    .is_err()
+            );
+        }
+        let later = start + Duration::from_millis(500);
+        assert!(
+            limiters
+                .try_acquire_at(stream_id(), 100, 10, 50, later)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn zero_limits_are_unrestricted() {
+        let limiters = AppendRateLimiters::default();
+        let start = Instant::now();
+
+        for _ in 0..100 {
+            assert!(
+                limiters
+                    .try_acquire_at(stream_id(), 0, 0, 1 << 20, start)
+                    .is_ok()
+            );
+        }
+        assert!(limiters.buckets.is_empty());
+    }
+}
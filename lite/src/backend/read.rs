@@ -1,29 +1,41 @@
-use std::time::Duration;
+use std::{ops::Bound, time::Duration};
 
-use futures::{Stream, StreamExt as _};
+use futures::{Stream, StreamExt as _, stream::Either};
 use s2_common::{
     basin::BasinName,
     caps,
+    deep_size::DeepSize as _,
     encryption::{EncryptionKey, EncryptionSpec},
     read_extent::{EvaluatedReadLimit, ReadLimit, ReadUntil},
     record::{Metered, MeteredSize as _, SeqNum, StreamPosition, Timestamp},
-    stream::{ReadEnd, ReadPosition, ReadSessionOutput, ReadStart, StreamName},
+    stream::{
+        PositionsBatch, ReadBatch, ReadEnd, ReadPosition, ReadSessionOutput, ReadStart, StreamName,
+    },
 };
 use s2_storage::record::{
     StoredReadBatch, StoredReadSessionOutput, StoredSequencedRecord, decrypt_read_session_output,
+    decrypt_stored_record,
+};
+use slatedb::config::{DurabilityLevel, ReadOptions, ScanOptions};
+use tokio::{
+    sync::{broadcast, mpsc},
+    time::Instant,
 };
-use slatedb::config::{DurabilityLevel, ScanOptions};
-use tokio::{sync::broadcast, time::Instant};
+use tracing::warn;
 
 use super::{Backend, StreamHandle};
 use crate::{
     backend::{
         error::{
-            CheckTailError, ReadError, StorageError, StreamerMissingInActionError, UnwrittenError,
+            CheckTailError, ReadError, StorageError, StreamerMissingInActionError, TrimmedError,
+            UnwrittenError,
         },
         kv,
+        memory_budget::MemoryBudget,
+        read_cache::ReadCache,
         streamer::GuardedStreamerClient,
     },
+    metrics,
     stream_id::StreamId,
 };
 
@@ -56,6 +68,22 @@ impl Backend {
         )
         .await
     }
+
+    /// Open `stream` for [`StreamHandle::read_positions`], which needs no encryption key as
+    /// records are not decrypted.
+    pub async fn open_for_read_positions(
+        &self,
+        basin: &BasinName,
+        stream: &StreamName,
+    ) -> Result<StreamHandle, ReadError> {
+        self.stream_handle_with_auto_create::<ReadError>(
+            basin,
+            stream,
+            |config| config.create_stream_on_read,
+            |_| Ok(EncryptionSpec::Plain),
+        )
+        .await
+    }
 }
 
 impl StreamHandle {
@@ -64,17 +92,110 @@ impl StreamHandle {
         Ok(tail)
     }
 
+    /// Skip stored records that fail to decode or decrypt, rather than ending the read with an
+    /// error.
+    pub fn skip_undecodable_records(self, skip: bool) -> Self {
+        Self {
+            skip_undecodable_records: skip,
+            ..self
+        }
+    }
+
+    /// Read the positions of records from data keys alone, without decoding their values.
+    ///
+    /// Only the count of `end.limit` applies, capped at a batch, as there are no bodies to count
+    /// towards a byte limit. The read does not wait for new records, and includes the tail if it
+    /// reaches it before the end of any sequence number range.
+    pub async fn read_positions(
+        self,
+        start: ReadStart,
+        end: ReadEnd,
+    ) -> Result<PositionsBatch, ReadError> {
+        let stream_id = self.client.stream_id();
+        let (start, end, end_seq_num) = start.resolve_seq_num_range(end);
+        let tail = self.client.check_tail().await?;
+        let start_seq_num = read_start_seq_num(&self.db, stream_id, start, end, tail).await?;
+        let max_count = end
+            .limit
+            .count()
+            .unwrap_or(usize::MAX)
+            .min(caps::RECORD_BATCH_MAX.count);
+        let scan_end = end_seq_num.map_or(tail.seq_num, |end| end.min(tail.seq_num));
+        let mut batch = PositionsBatch::default();
+        if start_seq_num >= scan_end {
+            batch.tail = (start_seq_num >= tail.seq_num).then_some(tail);
+            return Ok(batch);
+        }
+        let start_key = kv::stream_record_data::ser_key(
+            stream_id,
+            StreamPosition {
+                seq_num: start_seq_num,
+                timestamp: 0,
+            },
+        );
+        let end_key = kv::stream_record_data::ser_key(
+            stream_id,
+            StreamPosition {
+                seq_num: scan_end,
+                timestamp: 0,
+            },
+        );
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let mut it = self
+            .db
+            .scan_with_options(start_key..end_key, &scan_opts)
+            .await?;
+        loop {
+            let Some(kv) = it.next().await? else {
+                batch.tail = (scan_end == tail.seq_num).then_some(tail);
+                break;
+            };
+            let (deser_stream_id, pos) = kv::stream_record_data::deser_key(kv.key)?;
+            assert_eq!(deser_stream_id, stream_id);
+            if batch.positions.len() == max_count || end.until.deny(pos.timestamp) {
+                break;
+            }
+            batch.positions.push(pos);
+        }
+        Ok(batch)
+    }
+
     pub async fn read(
         self,
         start: ReadStart,
         end: ReadEnd,
     ) -> Result<impl Stream<Item = Result<ReadSessionOutput, ReadError>> + 'static, ReadError> {
         let stream_id = self.client.stream_id();
-        let session = read_session(self.db, self.client, start, end).await?;
-        Ok(async_stream::stream! {
+        let read_ahead_batches = self.read_ahead_batches;
+        let memory_budget = self.read_ahead_memory_budget;
+        let skip_undecodable_records = self.skip_undecodable_records;
+        let session = read_session(
+            self.db,
+            self.client,
+            self.read_cache,
+            start,
+            end,
+            skip_undecodable_records,
+        )
+        .await?;
+        let session = async_stream::stream! {
             tokio::pin!(session);
             while let Some(output) = session.next().await {
                 let output = match output {
+                    Ok(output) if skip_undecodable_records => {
+                        let output = decrypt_read_session_output_skipping(
+                            output,
+                            &self.encryption,
+                            stream_id,
+                        );
+                        match output {
+                            Some(output) => Ok(output),
+                            None => continue,
+                        }
+                    }
                     Ok(output) => {
                         decrypt_read_session_output(output, &self.encryption, stream_id.as_bytes())
                             .map_err(ReadError::from)
@@ -87,17 +208,116 @@ impl StreamHandle {
                     break;
                 }
             }
+        };
+        Ok(read_ahead(
+            session,
+            read_ahead_batches,
+            memory_budget,
+            |output| match output {
+                Ok(ReadSessionOutput::Batch(batch)) => {
+                    batch.records.iter().map(|record| record.deep_size()).sum()
+                }
+                Ok(ReadSessionOutput::Heartbeat(_)) | Err(_) => 0,
+            },
+        ))
+    }
+}
+
+/// Decrypt `output` like [`decrypt_read_session_output`], but skip records that fail to decrypt
+/// rather than failing the batch. `None` if every record of a batch was skipped.
+fn decrypt_read_session_output_skipping(
+    output: StoredReadSessionOutput,
+    encryption: &EncryptionSpec,
+    stream_id: StreamId,
+) -> Option<ReadSessionOutput> {
+    let batch = match output {
+        ReadSessionOutput::Heartbeat(tail) => return Some(ReadSessionOutput::Heartbeat(tail)),
+        ReadSessionOutput::Batch(batch) => batch,
+    };
+    let records: Metered<Vec<_>> = batch
+        .records
+        .into_inner()
+        .into_iter()
+        .filter_map(|record| {
+            let (position, record) = record.into_parts();
+            match decrypt_stored_record(record, encryption, stream_id.as_bytes()) {
+                Ok(record) => Some(record.sequenced(position)),
+                Err(err) => {
+                    warn!(
+                        %stream_id,
+                        seq_num = position.seq_num,
+                        %err,
+                        "skipping undecryptable record"
+                    );
+                    metrics::observe_undecodable_record_skipped();
+                    None
+                }
+            }
+        })
+        .collect();
+    (!records.is_empty()).then(|| {
+        ReadSessionOutput::Batch(ReadBatch {
+            records,
+            tail: batch.tail,
         })
+    })
+}
+
+/// Drive `session` in a separate task that buffers up to `batches` outputs, so that storage
+/// reads for subsequent batches overlap with delivery of the current one.
+///
+/// Buffered outputs are also held against `memory_budget` per `size_of`, so prefetching stalls
+/// while the budget is exhausted.
+fn read_ahead<S>(
+    session: S,
+    batches: usize,
+    memory_budget: MemoryBudget,
+    size_of: impl Fn(&S::Item) -> usize + Send + 'static,
+) -> impl Stream<Item = S::Item> + 'static
+where
+    S: Stream + Send + 'static,
+    S::Item: Send + 'static,
+{
+    if batches == 0 {
+        return Either::Left(session);
     }
+    let (tx, mut rx) = mpsc::channel(batches);
+    tokio::spawn(async move {
+        tokio::pin!(session);
+        loop {
+            let output = tokio::select! {
+                _ = tx.closed() => break,
+                output = session.next() => output,
+            };
+            let Some(output) = output else {
+                break;
+            };
+            let memory_permit = tokio::select! {
+                _ = tx.closed() => break,
+                permit = memory_budget.acquire(size_of(&output)) => permit,
+            };
+            if tx.send((output, memory_permit)).await.is_err() {
+                break;
+            }
+        }
+    });
+    Either::Right(async_stream::stream! {
+        while let Some((output, _memory_permit)) = rx.recv().await {
+            yield output;
+        }
+    })
 }
 
 async fn read_session(
     db: slatedb::Db,
     client: GuardedStreamerClient,
+    read_cache: ReadCache,
     start: ReadStart,
     end: ReadEnd,
+    skip_undecodable_records: bool,
 ) -> Result<impl Stream<Item = Result<StoredReadSessionOutput, ReadError>> + 'static, ReadError> {
     let stream_id = client.stream_id();
+    let (start, end, end_seq_num) = start.resolve_seq_num_range(end);
     let tail = client.check_tail().await?;
     let mut state = ReadSessionState {
         start_seq_num: read_start_seq_num(&db, stream_id, start, end, tail).await?,
@@ -109,7 +329,16 @@ async fn read_session(
     };
     let session = async_stream::try_stream! {
         'session: while let EvaluatedReadLimit::Remaining(limit) = state.limit {
+            if end_seq_num.is_some_and(|end| state.start_seq_num >= end) {
+                break;
+            }
             if state.start_seq_num < state.tail.seq_num {
+                let scan_end = end_seq_num
+                    .map_or(state.tail.seq_num, |end| end.min(state.tail.seq_num));
+                let cache_generation = read_cache.generation();
+                let mut cached = read_cache
+                    .get(stream_id, state.start_seq_num..scan_end)
+                    .map(Vec::into_iter);
                 let start_key = kv::stream_record_data::ser_key(
                     stream_id,
                     StreamPosition {
@@ -120,7 +349,7 @@ async fn read_session(
                 let end_key = kv::stream_record_data::ser_key(
                     stream_id,
                     StreamPosition {
-                        seq_num: state.tail.seq_num,
+                        seq_num: scan_end,
                         timestamp: 0,
                     },
                 );
@@ -131,7 +360,14 @@ async fn read_session(
                     max_fetch_tasks: 8,
                     ..Default::default()
                 };
-                let mut it = db.scan_with_options(start_key..end_key, &scan_opts).await?;
+                let mut it = match cached {
+                    Some(_) => None,
+                    None => Some(db.scan_with_options(start_key..end_key, &scan_opts).await?),
+                };
+                let mut block = Vec::new();
+                let mut block_expires_at = None;
+                // A block with a skipped record is not contiguous, so it must not be cached.
+                let mut skipped = false;
 
                 let mut records = Metered::with_capacity(
                     limit.count()
@@ -140,15 +376,43 @@ async fn read_session(
                 );
 
                 while let EvaluatedReadLimit::Remaining(limit) = state.limit {
-                    let Some(kv) = it.next().await? else {
+                    let record = match (cached.as_mut(), it.as_mut()) {
+                        (Some(cached), _) => cached.next(),
+                        (None, Some(it)) => match it.next().await? {
+                            Some(kv) => {
+                                let expire_ts = kv.expire_ts;
+                                let (deser_stream_id, pos) =
+                                    kv::stream_record_data::deser_key(kv.key)?;
+                                assert_eq!(deser_stream_id, stream_id);
+                                let record = match kv::stream_record_data::deser_value(kv.value) {
+                                    Ok(record) => record.sequenced(pos),
+                                    Err(err) if skip_undecodable_records => {
+                                        warn!(
+                                            %stream_id,
+                                            seq_num = pos.seq_num,
+                                            %err,
+                                            "skipping undecodable record"
+                                        );
+                                        metrics::observe_undecodable_record_skipped();
+                                        skipped = true;
+                                        continue;
+                                    }
+                                    Err(err) => Err(err)?,
+                                };
+                                block.push(record.clone());
+                                block_expires_at =
+                                    block_expires_at.into_iter().chain(expire_ts).min();
+                                Some(record)
+                            }
+                            None => None,
+                        },
+                        (None, None) => unreachable!("either cached records or a DB iterator"),
+                    };
+                    let Some(record) = record else {
                         break;
                     };
-                    let (deser_stream_id, pos) = kv::stream_record_data::deser_key(kv.key)?;
-                    assert_eq!(deser_stream_id, stream_id);
-
-                    let record = kv::stream_record_data::deser_value(kv.value)?.sequenced(pos);
 
-                    if end.until.deny(pos.timestamp)
+                    if end.until.deny(record.position().timestamp)
                         || limit.deny(records.len() + 1, records.metered_size() + record.metered_size())
                     {
                         if records.is_empty() {
@@ -175,13 +439,17 @@ async fn read_session(
                     records.push(record);
                 }
 
+                if !skipped {
+                    read_cache.insert(stream_id, cache_generation, block, block_expires_at);
+                }
+
                 if !records.is_empty() {
                     yield state.on_batch(StoredReadBatch {
                         records,
                         tail: None,
                     });
                 } else {
-                    state.start_seq_num = state.tail.seq_num;
+                    state.start_seq_num = scan_end;
                 }
             } else {
                 assert_eq!(state.start_seq_num, state.tail.seq_num);
@@ -259,6 +527,9 @@ async fn read_start_seq_num(
         s2_common::stream::ReadFrom::TailOffset(tail_offset) => {
             ReadPosition::SeqNum(tail.seq_num.saturating_sub(tail_offset))
         }
+        s2_common::stream::ReadFrom::SeqNumRange(_) => {
+            unreachable!("resolved by `ReadStart::resolve_seq_num_range`")
+        }
     };
     if match read_pos {
         ReadPosition::SeqNum(start_seq_num) => start_seq_num > tail.seq_num,
@@ -276,6 +547,15 @@ async fn read_start_seq_num(
     {
         return Err(UnwrittenError(tail).into());
     }
+    if start.error_if_trimmed
+        && matches!(start.from, s2_common::stream::ReadFrom::SeqNum(_))
+        && let ReadPosition::SeqNum(start_seq_num) = read_pos
+        && start_seq_num < tail.seq_num
+        && let Some(earliest_available) =
+            earliest_available_seq_num(db, stream_id, start_seq_num, tail).await?
+    {
+        return Err(TrimmedError { earliest_available }.into());
+    }
     Ok(match read_pos {
         ReadPosition::SeqNum(start_seq_num) => start_seq_num,
         ReadPosition::Timestamp(start_timestamp) => {
@@ -287,23 +567,39 @@ async fn read_start_seq_num(
     })
 }
 
-async fn resolve_timestamp(
+/// If `start_seq_num` has been trimmed, whether by an explicit trim or by retention, the earliest
+/// sequence number that remains readable.
+async fn earliest_available_seq_num(
     db: &slatedb::Db,
     stream_id: StreamId,
-    timestamp: Timestamp,
-) -> Result<Option<StreamPosition>, StorageError> {
-    let start_key = kv::stream_record_timestamp::ser_key(
+    start_seq_num: SeqNum,
+    tail: StreamPosition,
+) -> Result<Option<SeqNum>, StorageError> {
+    let read_opts = ReadOptions {
+        durability_filter: DurabilityLevel::Remote,
+        ..Default::default()
+    };
+    if let Some(trim_point) = db
+        .get_with_options(kv::stream_trim_point::ser_key(stream_id), &read_opts)
+        .await?
+        .map(kv::stream_trim_point::deser_value)
+        .transpose()?
+        && start_seq_num < trim_point.end.get()
+    {
+        return Ok(Some(trim_point.end.get().min(tail.seq_num)));
+    }
+    let start_key = kv::stream_record_data::ser_key(
         stream_id,
         StreamPosition {
-            seq_num: SeqNum::MIN,
-            timestamp,
+            seq_num: start_seq_num,
+            timestamp: 0,
         },
     );
-    let end_key = kv::stream_record_timestamp::ser_key(
+    let end_key = kv::stream_record_data::ser_key(
         stream_id,
         StreamPosition {
-            seq_num: SeqNum::MAX,
-            timestamp: Timestamp::MAX,
+            seq_num: tail.seq_num,
+            timestamp: 0,
         },
     );
     let scan_opts = ScanOptions {
@@ -311,6 +607,31 @@ async fn resolve_timestamp(
         ..Default::default()
     };
     let mut it = db.scan_with_options(start_key..end_key, &scan_opts).await?;
+    Ok(match it.next().await? {
+        Some(kv) => {
+            let (deser_stream_id, pos) = kv::stream_record_data::deser_key(kv.key)?;
+            assert_eq!(deser_stream_id, stream_id);
+            (pos.seq_num > start_seq_num).then_some(pos.seq_num)
+        }
+        None => Some(tail.seq_num),
+    })
+}
+
+async fn resolve_timestamp(
+    db: &slatedb::Db,
+    stream_id: StreamId,
+    timestamp: Timestamp,
+) -> Result<Option<StreamPosition>, StorageError> {
+    let Some(range) =
+        kv::stream_record_timestamp::ser_seek_range(stream_id, Bound::Included(timestamp))
+    else {
+        return Ok(None);
+    };
+    let scan_opts = ScanOptions {
+        durability_filter: DurabilityLevel::Remote,
+        ..Default::default()
+    };
+    let mut it = db.scan_with_options(range, &scan_opts).await?;
     Ok(match it.next().await? {
         Some(kv) => {
             let (deser_stream_id, pos) = kv::stream_record_timestamp::deser_key(kv.key)?;
@@ -411,19 +732,26 @@ async fn wait_sleep_until(deadline: Option<Instant>) {
 
 #[cfg(test)]
 mod tests {
-    use std::{sync::Arc, task::Poll};
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        task::Poll,
+    };
 
     use bytesize::ByteSize;
     use futures::StreamExt;
     use s2_common::{
         basin::BasinName,
-        config::{BasinConfig, OptionalStreamConfig},
+        config::{BasinConfig, OptionalStreamConfig, RetentionPolicy},
+        encryption::{EncryptionAlgorithm, EncryptionKey},
         read_extent::{ReadLimit, ReadUntil},
-        record::{Metered, Record},
+        record::{Metered, NonZeroSeqNum, Record},
         resources::ProvisionMode,
         stream::{
             AppendInput, AppendRecord, AppendRecordBatch, AppendRecordParts, ReadEnd, ReadFrom,
-            ReadSessionOutput, ReadStart, StreamName,
+            ReadSessionOutput, ReadStart, SeqNumRange, StreamName,
         },
     };
     use slatedb::{Db, WriteBatch, object_store::memory::InMemory};
@@ -489,7 +817,7 @@ mod tests {
     async fn resolve_timestamp_bounded_to_stream() {
         let object_store = Arc::new(InMemory::new());
         let db = Db::builder("/test", object_store).build().await.unwrap();
-        let backend = Backend::new(db, ByteSize::mib(10));
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
 
         let stream_a: StreamId = [0u8; 32].into();
         let stream_b: StreamId = [1u8; 32].into();
@@ -544,7 +872,7 @@ mod tests {
     async fn read_completes_when_all_records_deleted() {
         let object_store = Arc::new(InMemory::new());
         let db = Db::builder("/test", object_store).build().await.unwrap();
-        let backend = Backend::new(db, ByteSize::mib(10));
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
 
         let basin: BasinName = "test-basin".parse().unwrap();
         backend
@@ -588,6 +916,7 @@ mod tests {
         let start = ReadStart {
             from: ReadFrom::SeqNum(0),
             clamp: false,
+            error_if_trimmed: false,
         };
         let end = ReadEnd {
             limit: ReadLimit::Count(10),
@@ -610,11 +939,669 @@ mod tests {
         assert!(records.into_iter().all(|r| r.is_ok()));
     }
 
+    #[tokio::test]
+    async fn read_below_trim_point_is_trimmed() {
+        let object_store = Arc::new(InMemory::new());
+        let db = Db::builder("/test", object_store).build().await.unwrap();
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
+
+        let basin: BasinName = "test-basin".parse().unwrap();
+        backend
+            .provision_basin(
+                basin.clone(),
+                BasinConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+        let stream: StreamName = "test-stream".parse().unwrap();
+        backend
+            .provision_stream(
+                basin.clone(),
+                stream.clone(),
+                OptionalStreamConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut positions = Vec::new();
+        for _ in 0..3 {
+            let input =
+                append_input(Record::try_from_parts(vec![], bytes::Bytes::from("x")).unwrap());
+            let ack = backend
+                .open_for_append(&basin, &stream, None)
+                .await
+                .unwrap()
+                .append(input)
+                .await
+                .unwrap();
+            positions.push(ack.start);
+        }
+
+        let read_from = |seq_num, error_if_trimmed| {
+            let backend = backend.clone();
+            let (basin, stream) = (basin.clone(), stream.clone());
+            async move {
+                let start = ReadStart {
+                    from: ReadFrom::SeqNum(seq_num),
+                    clamp: false,
+                    error_if_trimmed,
+                };
+                let end = ReadEnd {
+                    limit: ReadLimit::Count(10),
+                    until: ReadUntil::Unbounded,
+                    wait: None,
+                };
+                backend
+                    .open_for_read(&basin, &stream, None)
+                    .await
+                    .unwrap()
+                    .read(start, end)
+                    .await
+                    .map(|_| ())
+            }
+        };
+
+        // Pending trim point, records not yet deleted.
+        let stream_id = StreamId::new(&basin, &stream);
+        backend
+            .db
+            .put(
+                kv::stream_trim_point::ser_key(stream_id),
+                kv::stream_trim_point::ser_value(..NonZeroSeqNum::new(2).unwrap()),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            read_from(0, true).await,
+            Err(ReadError::Trimmed(TrimmedError {
+                earliest_available: 2
+            }))
+        ));
+        assert!(read_from(0, false).await.is_ok());
+        assert!(read_from(2, true).await.is_ok());
+
+        // Trim applied, records deleted.
+        let mut batch = WriteBatch::new();
+        batch.delete(kv::stream_trim_point::ser_key(stream_id));
+        for pos in &positions[..2] {
+            batch.delete(kv::stream_record_data::ser_key(stream_id, *pos));
+        }
+        backend.db.write(batch).await.unwrap();
+        assert!(matches!(
+            read_from(1, true).await,
+            Err(ReadError::Trimmed(TrimmedError {
+                earliest_available: 2
+            }))
+        ));
+        assert!(read_from(1, false).await.is_ok());
+        assert!(read_from(2, true).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn cached_records_expire_with_age_retention() {
+        let object_store = Arc::new(InMemory::new());
+        let db = Db::builder("/test", object_store).build().await.unwrap();
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
+
+        let basin: BasinName = "test-basin".parse().unwrap();
+        backend
+            .provision_basin(
+                basin.clone(),
+                BasinConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+        let stream: StreamName = "test-stream".parse().unwrap();
+        backend
+            .provision_stream(
+                basin.clone(),
+                stream.clone(),
+                OptionalStreamConfig {
+                    retention_policy: Some(RetentionPolicy::Age(Duration::from_millis(500))),
+                    ..Default::default()
+                },
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            let input =
+                append_input(Record::try_from_parts(vec![], bytes::Bytes::from("x")).unwrap());
+            backend
+                .open_for_append(&basin, &stream, None)
+                .await
+                .unwrap()
+                .append(input)
+                .await
+                .unwrap();
+        }
+
+        // The first read populates the read cache, which serves the second.
+        for _ in 0..2 {
+            let outputs = read_all(&backend, &basin, &stream, None, false).await;
+            assert_eq!(read_seq_nums(outputs), vec![0, 1, 2]);
+        }
+
+        // Record expiry is enforced against the DB's system clock, so this must be real time.
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        let outputs = read_all(&backend, &basin, &stream, None, false).await;
+        assert!(read_seq_nums(outputs).is_empty());
+    }
+
+    #[tokio::test]
+    async fn seq_num_range_read_after_trim_stops_at_range_end() {
+        let object_store = Arc::new(InMemory::new());
+        let db = Db::builder("/test", object_store).build().await.unwrap();
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
+
+        let basin: BasinName = "test-basin".parse().unwrap();
+        backend
+            .provision_basin(
+                basin.clone(),
+                BasinConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+        let stream: StreamName = "test-stream".parse().unwrap();
+        backend
+            .provision_stream(
+                basin.clone(),
+                stream.clone(),
+                OptionalStreamConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut positions = Vec::new();
+        for _ in 0..12 {
+            let input =
+                append_input(Record::try_from_parts(vec![], bytes::Bytes::from("x")).unwrap());
+            let ack = backend
+                .open_for_append(&basin, &stream, None)
+                .await
+                .unwrap()
+                .append(input)
+                .await
+                .unwrap();
+            positions.push(ack.start);
+        }
+
+        let stream_id = StreamId::new(&basin, &stream);
+        let mut batch = WriteBatch::new();
+        for pos in &positions[..8] {
+            batch.delete(kv::stream_record_data::ser_key(stream_id, *pos));
+        }
+        backend.db.write(batch).await.unwrap();
+
+        let start = ReadStart {
+            from: ReadFrom::SeqNumRange(SeqNumRange { start: 5, end: 10 }),
+            clamp: false,
+            error_if_trimmed: false,
+        };
+        // The second read is served from the read cache.
+        for _ in 0..2 {
+            let outputs = backend
+                .open_for_read(&basin, &stream, None)
+                .await
+                .unwrap()
+                .read(start, ReadEnd::default())
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+                .await;
+            assert_eq!(read_seq_nums(outputs), vec![8, 9]);
+        }
+
+        let batch = backend
+            .open_for_read_positions(&basin, &stream)
+            .await
+            .unwrap()
+            .read_positions(start, ReadEnd::default())
+            .await
+            .unwrap();
+        assert_eq!(batch.positions, positions[8..10].to_vec());
+        assert_eq!(batch.tail, None);
+    }
+
+    /// A backend with a stream of three records, where the stored value of the second one has been
+    /// replaced by `corrupt`.
+    async fn stream_with_corrupt_record(
+        read_cache_bytes: ByteSize,
+        encryption_key: Option<EncryptionKey>,
+        corrupt: impl FnOnce(bytes::Bytes) -> bytes::Bytes,
+    ) -> (Backend, BasinName, StreamName) {
+        let object_store = Arc::new(InMemory::new());
+        let db = Db::builder("/test", object_store).build().await.unwrap();
+        let backend = Backend::new(db, ByteSize::mib(10), read_cache_bytes);
+
+        let basin: BasinName = "test-basin".parse().unwrap();
+        backend
+            .provision_basin(
+                basin.clone(),
+                BasinConfig {
+                    stream_cipher: encryption_key
+                        .as_ref()
+                        .map(|_| EncryptionAlgorithm::Aegis256),
+                    ..Default::default()
+                },
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+        let stream: StreamName = "test-stream".parse().unwrap();
+        backend
+            .provision_stream(
+                basin.clone(),
+                stream.clone(),
+                OptionalStreamConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut positions = Vec::new();
+        for _ in 0..3 {
+            let input =
+                append_input(Record::try_from_parts(vec![], bytes::Bytes::from("x")).unwrap());
+            let ack = backend
+                .open_for_append(&basin, &stream, encryption_key.clone())
+                .await
+                .unwrap()
+                .append(input)
+                .await
+                .unwrap();
+            positions.push(ack.start);
+        }
+
+        let key = kv::stream_record_data::ser_key(StreamId::new(&basin, &stream), positions[1]);
+        let value = backend.db.get(&key).await.unwrap().expect("stored record");
+        backend.db.put(&key, corrupt(value)).await.unwrap();
+        (backend, basin, stream)
+    }
+
+    async fn read_all(
+        backend: &Backend,
+        basin: &BasinName,
+        stream: &StreamName,
+        encryption_key: Option<EncryptionKey>,
+        skip: bool,
+    ) -> Vec<Result<ReadSessionOutput, ReadError>> {
+        let start = ReadStart {
+            from: ReadFrom::SeqNum(0),
+            clamp: false,
+            error_if_trimmed: false,
+        };
+        let end = ReadEnd {
+            limit: ReadLimit::Count(10),
+            until: ReadUntil::Unbounded,
+            wait: None,
+        };
+        backend
+            .open_for_read(basin, stream, encryption_key)
+            .await
+            .unwrap()
+            .skip_undecodable_records(skip)
+            .read(start, end)
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await
+    }
+
+    fn read_seq_nums(outputs: Vec<Result<ReadSessionOutput, ReadError>>) -> Vec<SeqNum> {
+        outputs
+            .into_iter()
+            .flat_map(|output| match output {
+                Ok(ReadSessionOutput::Batch(batch)) => batch
+                    .records
+                    .iter()
+                    .map(|record| record.position().seq_num)
+                    .collect(),
+                Ok(ReadSessionOutput::Heartbeat(_)) => vec![],
+                Err(e) => panic!("Read error: {e:?}"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn undecodable_record_fails_or_is_skipped() {
+        let (backend, basin, stream) =
+            stream_with_corrupt_record(ByteSize::b(0), None, |_| bytes::Bytes::new()).await;
+
+        let outputs = read_all(&backend, &basin, &stream, None, false).await;
+        assert!(matches!(outputs.last(), Some(Err(_))));
+
+        let skipped_before = metrics::undecodable_records_skipped();
+        let outputs = read_all(&backend, &basin, &stream, None, true).await;
+        assert_eq!(read_seq_nums(outputs), vec![0, 2]);
+        assert!(metrics::undecodable_records_skipped() > skipped_before);
+    }
+
+    #[tokio::test]
+    async fn undecryptable_record_fails_or_is_skipped() {
+        let key = EncryptionKey::new([0x42; 32]);
+        let (backend, basin, stream) =
+            stream_with_corrupt_record(ByteSize::b(0), Some(key.clone()), |value| {
+                // Flip a bit of the authentication tag, so the record still decodes but fails to
+                // decrypt.
+                let mut value = value.to_vec();
+                *value.last_mut().expect("non-empty record") ^= 1;
+                value.into()
+            })
+            .await;
+
+        let outputs = read_all(&backend, &basin, &stream, Some(key.clone()), false).await;
+        assert!(matches!(
+            outputs.last(),
+            Some(Err(ReadError::RecordDecryption(_)))
+        ));
+
+        let skipped_before = metrics::undecodable_records_skipped();
+        let outputs = read_all(&backend, &basin, &stream, Some(key), true).await;
+        assert_eq!(read_seq_nums(outputs), vec![0, 2]);
+        assert!(metrics::undecodable_records_skipped() > skipped_before);
+    }
+
+    #[tokio::test]
+    async fn read_with_skipped_record_is_not_cached() {
+        let (backend, basin, stream) =
+            stream_with_corrupt_record(ByteSize::mib(1), None, |_| bytes::Bytes::new()).await;
+
+        let outputs = read_all(&backend, &basin, &stream, None, true).await;
+        assert_eq!(read_seq_nums(outputs), vec![0, 2]);
+
+        // Without skipping, the read must still reach the undecodable record rather than being
+        // served the cached records around it.
+        let outputs = read_all(&backend, &basin, &stream, None, false).await;
+        assert!(matches!(outputs.last(), Some(Err(_))));
+    }
+
+    /// Simulates a sequential scan where each batch takes `fetch` to read from storage and
+    /// `deliver` to write to the client, returning the total time taken.
+    async fn timed_sequential_scan(
+        batches: usize,
+        fetch: Duration,
+        deliver: Duration,
+        read_ahead_batches: usize,
+    ) -> Duration {
+        let session = async_stream::stream! {
+            for i in 0..batches {
+                tokio::time::sleep(fetch).await;
+                yield i;
+            }
+        };
+        let started = Instant::now();
+        let session = read_ahead(session, read_ahead_batches, MemoryBudget::default(), |_| 0);
+        tokio::pin!(session);
+        let mut expected = 0;
+        while let Some(i) = session.next().await {
+            assert_eq!(i, expected);
+            expected += 1;
+            tokio::time::sleep(deliver).await;
+        }
+        assert_eq!(expected, batches);
+        started.elapsed()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn read_ahead_overlaps_fetch_with_delivery() {
+        let fetch = Duration::from_millis(10);
+        let deliver = Duration::from_millis(10);
+
+        let sequential = timed_sequential_scan(20, fetch, deliver, 0).await;
+        let overlapped = timed_sequential_scan(20, fetch, deliver, 4).await;
+
+        assert!(sequential >= Duration::from_millis(400), "{sequential:?}");
+        assert!(overlapped <= Duration::from_millis(220), "{overlapped:?}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn read_ahead_stalls_when_memory_budget_is_exhausted() {
+        let fetched = Arc::new(AtomicUsize::new(0));
+        let session = {
+            let fetched = fetched.clone();
+            async_stream::stream! {
+                for i in 0..32 {
+                    fetched.fetch_add(1, Ordering::Relaxed);
+                    yield i;
+                }
+            }
+        };
+        let budget = MemoryBudget::new(ByteSize::b(2), metrics::MemoryComponent::ReadAheadBudget);
+        let session = read_ahead(session, 16, budget.clone(), |_| 1);
+        tokio::pin!(session);
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        // Two outputs fit within the budget, and the third waits for one of them to be delivered.
+        assert_eq!(fetched.load(Ordering::Relaxed), 3);
+        assert_eq!(budget.in_use(), 2);
+
+        assert_eq!(session.next().await, Some(0));
+        assert_eq!(session.next().await, Some(1));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(fetched.load(Ordering::Relaxed), 4);
+    }
+
+    #[tokio::test]
+    async fn repeated_read_is_served_from_cache() {
+        let object_store = Arc::new(InMemory::new());
+        let db = Db::builder("/test", object_store).build().await.unwrap();
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
+
+        let basin: BasinName = "test-basin".parse().unwrap();
+        backend
+            .provision_basin(
+                basin.clone(),
+                BasinConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+        let stream: StreamName = "test-stream".parse().unwrap();
+        backend
+            .provision_stream(
+                basin.clone(),
+                stream.clone(),
+                OptionalStreamConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        for body in ["a", "b", "c"] {
+            let input =
+                append_input(Record::try_from_parts(vec![], bytes::Bytes::from(body)).unwrap());
+            backend
+                .open_for_append(&basin, &stream, None)
+                .await
+                .unwrap()
+                .append(input)
+                .await
+                .unwrap();
+        }
+
+        let read_seq_nums = async || {
+            let start = ReadStart {
+                from: ReadFrom::SeqNum(0),
+                clamp: false,
+                error_if_trimmed: false,
+            };
+            let end = ReadEnd {
+                limit: ReadLimit::Count(10),
+                until: ReadUntil::Unbounded,
+                wait: None,
+            };
+            let session = backend
+                .open_for_read(&basin, &stream, None)
+                .await
+                .unwrap()
+                .read(start, end)
+                .await
+                .unwrap();
+            let outputs: Vec<_> = session.collect().await;
+            outputs
+                .into_iter()
+                .filter_map(|output| match output.unwrap() {
+                    ReadSessionOutput::Batch(batch) => Some(batch.records),
+                    ReadSessionOutput::Heartbeat(_) => None,
+                })
+                .flat_map(|records| {
+                    records
+                        .into_iter()
+                        .map(|record| record.position().seq_num)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let first = read_seq_nums().await;
+        let hits_before = crate::metrics::read_cache_lookups(crate::metrics::ReadCacheLookup::Hit);
+        let second = read_seq_nums().await;
+
+        assert_eq!(first, vec![0, 1, 2]);
+        assert_eq!(second, first);
+        assert!(
+            crate::metrics::read_cache_lookups(crate::metrics::ReadCacheLookup::Hit) > hits_before
+        );
+    }
+
+    #[tokio::test]
+    async fn read_until_stops_before_timestamp_from_db_and_cache() {
+        let object_store = Arc::new(InMemory::new());
+        let db = Db::builder("/test", object_store).build().await.unwrap();
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
+
+        let basin: BasinName = "test-basin".parse().unwrap();
+        backend
+            .provision_basin(
+                basin.clone(),
+                BasinConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+        let stream: StreamName = "test-stream".parse().unwrap();
+        backend
+            .provision_stream(
+                basin.clone(),
+                stream.clone(),
+                OptionalStreamConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        for timestamp in [10, 20, 30] {
+            let record: AppendRecord = AppendRecordParts {
+                timestamp: Some(timestamp),
+                record: Metered::from(
+                    Record::try_from_parts(vec![], bytes::Bytes::from("x")).unwrap(),
+                ),
+            }
+            .try_into()
+            .unwrap();
+            let input = AppendInput {
+                records: vec![record].try_into().unwrap(),
+                match_seq_num: None,
+                fencing_token: None,
+            };
+            backend
+                .open_for_append(&basin, &stream, None)
+                .await
+                .unwrap()
+                .append(input)
+                .await
+                .unwrap();
+        }
+
+        let read_until = async |until| {
+            let start = ReadStart {
+                from: ReadFrom::SeqNum(0),
+                clamp: false,
+                error_if_trimmed: false,
+            };
+            let end = ReadEnd {
+                limit: ReadLimit::Count(10),
+                until: ReadUntil::Timestamp(until),
+                wait: None,
+            };
+            let session = backend
+                .open_for_read(&basin, &stream, None)
+                .await
+                .unwrap()
+                .read(start, end)
+                .await
+                .unwrap();
+            let outputs: Vec<_> = session.collect().await;
+            outputs
+                .into_iter()
+                .filter_map(|output| match output.unwrap() {
+                    ReadSessionOutput::Batch(batch) => Some(batch.records),
+                    ReadSessionOutput::Heartbeat(_) => None,
+                })
+                .flat_map(|records| {
+                    records
+                        .into_iter()
+                        .map(|record| record.position().seq_num)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // The first read scans the DB, and caches the block it read.
+        let misses_before =
+            crate::metrics::read_cache_lookups(crate::metrics::ReadCacheLookup::Miss);
+        assert_eq!(read_until(25).await, vec![0, 1]);
+        assert!(
+            crate::metrics::read_cache_lookups(crate::metrics::ReadCacheLookup::Miss)
+                > misses_before
+        );
+
+        let hits_before = crate::metrics::read_cache_lookups(crate::metrics::ReadCacheLookup::Hit);
+        assert_eq!(read_until(25).await, vec![0, 1]);
+        assert_eq!(read_until(30).await, vec![0, 1]);
+        assert_eq!(read_until(31).await, vec![0, 1, 2]);
+        assert_eq!(read_until(10).await, Vec::<SeqNum>::new());
+        assert!(
+            crate::metrics::read_cache_lookups(crate::metrics::ReadCacheLookup::Hit) > hits_before
+        );
+    }
+
     #[tokio::test(flavor = "current_thread", start_paused = true)]
     async fn read_wait_is_not_extended_by_heartbeats() {
         let object_store = Arc::new(InMemory::new());
         let db = Db::builder("/test", object_store).build().await.unwrap();
-        let backend = Backend::new(db, ByteSize::mib(10));
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
 
         let basin: BasinName = "test-basin".parse().unwrap();
         backend
@@ -644,6 +1631,7 @@ mod tests {
         let start = ReadStart {
             from: ReadFrom::SeqNum(0),
             clamp: false,
+            error_if_trimmed: false,
         };
         let end = ReadEnd {
             limit: ReadLimit::Unbounded,
@@ -697,7 +1685,7 @@ mod tests {
     async fn read_wait_is_reset_by_delivered_follow_batch() {
         let object_store = Arc::new(InMemory::new());
         let db = Db::builder("/test", object_store).build().await.unwrap();
-        let backend = Backend::new(db, ByteSize::mib(10));
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
 
         let basin: BasinName = "test-basin".parse().unwrap();
         backend
@@ -738,6 +1726,7 @@ mod tests {
         let start = ReadStart {
             from: ReadFrom::SeqNum(0),
             clamp: false,
+            error_if_trimmed: false,
         };
         let end = ReadEnd {
             limit: ReadLimit::Unbounded,
@@ -854,7 +1843,7 @@ mod tests {
     async fn read_wait_is_not_reset_after_follow_lag_without_catchup_records() {
         let object_store = Arc::new(InMemory::new());
         let db = Db::builder("/test", object_store).build().await.unwrap();
-        let backend = Backend::new(db, ByteSize::mib(10));
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
 
         let basin: BasinName = "test-basin".parse().unwrap();
         backend
@@ -884,6 +1873,7 @@ mod tests {
         let start = ReadStart {
             from: ReadFrom::SeqNum(0),
             clamp: false,
+            error_if_trimmed: false,
         };
         let end = ReadEnd {
             limit: ReadLimit::Unbounded,
@@ -941,7 +1931,7 @@ mod tests {
     async fn unbounded_follow_survives_streamer_dormancy() {
         let object_store = Arc::new(InMemory::new());
         let db = Db::builder("/test", object_store).build().await.unwrap();
-        let backend = Backend::new(db, ByteSize::mib(10));
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
 
         let basin: BasinName = "test-basin".parse().unwrap();
         backend
@@ -980,6 +1970,7 @@ mod tests {
         let start = ReadStart {
             from: ReadFrom::SeqNum(0),
             clamp: false,
+            error_if_trimmed: false,
         };
         let end = ReadEnd {
             limit: ReadLimit::Unbounded,
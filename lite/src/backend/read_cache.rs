@@ -0,0 +1,381 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bytesize::ByteSize;
+use parking_lot::Mutex;
+use s2_common::{
+    deep_size::DeepSize as _,
+    record::{Metered, MeteredSize as _, SeqNum},
+};
+use s2_storage::record::StoredSequencedRecord;
+
+use crate::{
+    metrics::{self, MemoryComponent, ReadCacheLookup},
+    stream_id::StreamId,
+};
+
+/// LRU cache of record blocks recently read from the DB, bounded by metered size.
+///
+/// A block is a run of durable records read by a single scan, keyed by stream and the block's
+/// first sequence number. Blocks are invalidated when the stream's trim point advances past them,
+/// and expire along with the earliest expiring of their records, so age retention applies to
+/// cached reads as it does to the DB.
+///
+/// Capacity is accounted in metered bytes, while the approximate heap size of cached records is
+/// tracked separately and reported as a memory usage metric.
+#[derive(Clone)]
+pub(super) struct ReadCache {
+    inner: Option<Arc<Mutex<Inner>>>,
+}
+
+struct Block {
+    end: SeqNum,
+    /// Earliest expiry of the block's records, in milliseconds since the Unix epoch.
+    expires_at: Option<i64>,
+    records: Arc<[Metered<StoredSequencedRecord>]>,
+    size: usize,
+    deep_size: usize,
+    tick: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    capacity: usize,
+    size: usize,
+    deep_size: usize,
+    tick: u64,
+    generation: u64,
+    streams: HashMap<StreamId, BTreeMap<SeqNum, Block>>,
+    recency: BTreeMap<u64, (StreamId, SeqNum)>,
+}
+
+impl ReadCache {
+    /// A zero capacity disables caching.
+    pub fn new(capacity: ByteSize) -> Self {
+        let capacity = capacity.as_u64() as usize;
+        Self {
+            inner: (capacity > 0).then(|| {
+                Arc::new(Mutex::new(Inner {
+                    capacity,
+                    ..Default::default()
+                }))
+            }),
+        }
+    }
+
+    /// Current invalidation generation, to be passed to [`Self::insert`] for records read after
+    /// this call.
+    pub fn generation(&self) -> u64 {
+        self.inner
+            .as_ref()
+            .map_or(0, |inner| inner.lock().generation)
+    }
+
+    /// Cached records from the block containing `seq_nums.start`, up to `seq_nums.end`.
+    pub fn get(
+        &self,
+        stream_id: StreamId,
+        seq_nums: Range<SeqNum>,
+    ) -> Option<Vec<Metered<StoredSequencedRecord>>> {
+        let inner = self.inner.as_ref()?;
+        let now_millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| i64::try_from(duration.as_millis()).unwrap_or(i64::MAX))
+            .unwrap_or(0);
+        let records = inner.lock().get(stream_id, seq_nums, now_millis);
+        metrics::observe_read_cache_lookup(if records.is_some() {
+            ReadCacheLookup::Hit
+        } else {
+            ReadCacheLookup::Miss
+        });
+        records
+    }
+
+    /// Cache a run of records read from the DB, unless an invalidation happened since
+    /// `generation` was obtained.
+    ///
+    /// `expires_at` is the earliest expiry of the records as stored in the DB, after which the
+    /// block is no longer served.
+    pub fn insert(
+        &self,
+        stream_id: StreamId,
+        generation: u64,
+        records: Vec<Metered<StoredSequencedRecord>>,
+        expires_at: Option<i64>,
+    ) {
+        if let Some(inner) = self.inner.as_ref() {
+            let mut inner = inner.lock();
+            inner.insert(stream_id, generation, records, expires_at);
+            metrics::observe_memory_usage(MemoryComponent::ReadCache, inner.deep_size);
+        }
+    }
+
+    /// Drop all blocks of the stream that start before `trim_point`.
+    pub fn invalidate(&self, stream_id: StreamId, trim_point: SeqNum) {
+        if let Some(inner) = self.inner.as_ref() {
+            let mut inner = inner.lock();
+            inner.invalidate(stream_id, trim_point);
+            metrics::observe_memory_usage(MemoryComponent::ReadCache, inner.deep_size);
+        }
+    }
+
+    /// Approximate heap size of the cached records.
+    pub fn deep_size(&self) -> usize {
+        self.inner
+            .as_ref()
+            .map_or(0, |inner| inner.lock().deep_size)
+    }
+}
+
+impl Inner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(
+        &mut self,
+        stream_id: StreamId,
+        seq_nums: Range<SeqNum>,
+        now_millis: i64,
+    ) -> Option<Vec<Metered<StoredSequencedRecord>>> {
+        let tick = self.next_tick();
+        let blocks = self.streams.get_mut(&stream_id)?;
+        let (&start, block) = blocks.range_mut(..=seq_nums.start).next_back()?;
+        if block.end <= seq_nums.start {
+            return None;
+        }
+        if block
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now_millis)
+        {
+            self.remove(stream_id, start);
+            return None;
+        }
+        let first = block
+            .records
+            .partition_point(|r| r.position().seq_num < seq_nums.start);
+        let last = block
+            .records
+            .partition_point(|r| r.position().seq_num < seq_nums.end);
+        if first == last {
+            return None;
+        }
+        self.recency.remove(&block.tick);
+        block.tick = tick;
+        self.recency.insert(tick, (stream_id, start));
+        Some(block.records[first..last].to_vec())
+    }
+
+    fn insert(
+        &mut self,
+        stream_id: StreamId,
+        generation: u64,
+        records: Vec<Metered<StoredSequencedRecord>>,
+        expires_at: Option<i64>,
+    ) {
+        if generation != self.generation {
+            return;
+        }
+        let (Some(first), Some(last)) = (records.first(), records.last()) else {
+            return;
+        };
+        let start = first.position().seq_num;
+        let end = last.position().seq_num + 1;
+        let size = records.iter().map(|r| r.metered_size()).sum();
+        if size > self.capacity {
+            return;
+        }
+        let deep_size = records.iter().map(|r| r.deep_size()).sum();
+        self.remove(stream_id, start);
+        let tick = self.next_tick();
+        self.streams.entry(stream_id).or_default().insert(
+            start,
+            Block {
+                end,
+                expires_at,
+                records: records.into(),
+                size,
+                deep_size,
+                tick,
+            },
+        );
+        self.recency.insert(tick, (stream_id, start));
+        self.size += size;
+        self.deep_size += deep_size;
+        while self.size > self.capacity {
+            let (_, (stream_id, start)) = self.recency.pop_first().expect("non-empty");
+            self.remove(stream_id, start);
+        }
+    }
+
+    fn invalidate(&mut self, stream_id: StreamId, trim_point: SeqNum) {
+        self.generation += 1;
+        let Some(blocks) = self.streams.get(&stream_id) else {
+            return;
+        };
+        let starts: Vec<_> = blocks
+            .range(..trim_point)
+            .map(|(&start, _)| start)
+            .collect();
+        for start in starts {
+            self.remove(stream_id, start);
+        }
+    }
+
+    fn remove(&mut self, stream_id: StreamId, start: SeqNum) {
+        let Some(blocks) = self.streams.get_mut(&stream_id) else {
+            return;
+        };
+        if let Some(block) = blocks.remove(&start) {
+            self.recency.remove(&block.tick);
+            self.size -= block.size;
+            self.deep_size -= block.deep_size;
+        }
+        if blocks.is_empty() {
+            self.streams.remove(&stream_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use s2_common::{
+        deep_size::DeepSize as _,
+        record::{EnvelopeRecord, MeteredExt as _, Record, StreamPosition},
+    };
+    use s2_storage::record::StoredRecord;
+
+    use super::*;
+
+    fn records(seq_nums: Range<SeqNum>) -> Vec<Metered<StoredSequencedRecord>> {
+        seq_nums
+            .map(|seq_num| {
+                let envelope =
+                    EnvelopeRecord::try_from_parts(vec![], Bytes::from_static(b"hello")).unwrap();
+                StoredRecord::from(Record::Envelope(envelope))
+                    .metered()
+                    .sequenced(StreamPosition {
+                        seq_num,
+                        timestamp: seq_num,
+                    })
+            })
+            .collect()
+    }
+
+    fn seq_nums(records: &[Metered<StoredSequencedRecord>]) -> Vec<SeqNum> {
+        records.iter().map(|r| r.position().seq_num).collect()
+    }
+
+    #[test]
+    fn get_returns_records_within_block() {
+        let cache = ReadCache::new(ByteSize::mib(1));
+        let stream_id = StreamId::from([1u8; StreamId::LEN]);
+        cache.insert(stream_id, cache.generation(), records(10..20), None);
+
+        assert!(cache.get(stream_id, 5..30).is_none());
+        assert!(cache.get(stream_id, 20..30).is_none());
+        assert_eq!(
+            seq_nums(&cache.get(stream_id, 15..30).unwrap()),
+            (15..20).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            seq_nums(&cache.get(stream_id, 12..14).unwrap()),
+            vec![12, 13]
+        );
+        assert!(
+            cache
+                .get(StreamId::from([2u8; StreamId::LEN]), 10..20)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let block_size: usize = records(0..10).iter().map(|r| r.metered_size()).sum();
+        let cache = ReadCache::new(ByteSize::b(2 * block_size as u64));
+        let stream_id = StreamId::from([1u8; StreamId::LEN]);
+
+        cache.insert(stream_id, cache.generation(), records(0..10), None);
+        cache.insert(stream_id, cache.generation(), records(10..20), None);
+        assert!(cache.get(stream_id, 0..30).is_some());
+        cache.insert(stream_id, cache.generation(), records(20..30), None);
+
+        assert!(cache.get(stream_id, 0..30).is_some());
+        assert!(cache.get(stream_id, 10..30).is_none());
+        assert!(cache.get(stream_id, 20..30).is_some());
+    }
+
+    #[test]
+    fn invalidate_drops_trimmed_blocks_and_stale_inserts() {
+        let cache = ReadCache::new(ByteSize::mib(1));
+        let stream_id = StreamId::from([1u8; StreamId::LEN]);
+        cache.insert(stream_id, cache.generation(), records(0..10), None);
+        cache.insert(stream_id, cache.generation(), records(10..20), None);
+
+        let generation = cache.generation();
+        cache.invalidate(stream_id, 10);
+
+        assert!(cache.get(stream_id, 0..20).is_none());
+        assert!(cache.get(stream_id, 10..20).is_some());
+
+        cache.insert(stream_id, generation, records(0..10), None);
+        assert!(cache.get(stream_id, 0..20).is_none());
+
+        cache.invalidate(stream_id, SeqNum::MAX);
+        assert!(cache.get(stream_id, 10..20).is_none());
+    }
+
+    #[test]
+    fn expired_blocks_are_not_served() {
+        let cache = ReadCache::new(ByteSize::mib(1));
+        let stream_id = StreamId::from([1u8; StreamId::LEN]);
+        cache.insert(stream_id, cache.generation(), records(0..10), Some(1));
+        cache.insert(
+            stream_id,
+            cache.generation(),
+            records(10..20),
+            Some(i64::MAX),
+        );
+
+        assert!(cache.get(stream_id, 0..20).is_none());
+        assert_eq!(
+            seq_nums(&cache.get(stream_id, 10..20).unwrap()),
+            (10..20).collect::<Vec<_>>()
+        );
+
+        let block_deep_size: usize = records(10..20).iter().map(|r| r.deep_size()).sum();
+        assert_eq!(cache.deep_size(), block_deep_size);
+    }
+
+    #[test]
+    fn deep_size_tracks_cached_records() {
+        let block_deep_size: usize = records(0..10).iter().map(|r| r.deep_size()).sum();
+        let cache = ReadCache::new(ByteSize::mib(1));
+        let stream_id = StreamId::from([1u8; StreamId::LEN]);
+        assert_eq!(cache.deep_size(), 0);
+
+        cache.insert(stream_id, cache.generation(), records(0..10), None);
+        cache.insert(stream_id, cache.generation(), records(10..20), None);
+        assert_eq!(cache.deep_size(), 2 * block_deep_size);
+
+        cache.invalidate(stream_id, 10);
+        assert_eq!(cache.deep_size(), block_deep_size);
+
+        cache.invalidate(stream_id, SeqNum::MAX);
+        assert_eq!(cache.deep_size(), 0);
+    }
+
+    #[test]
+    fn zero_capacity_disables_cache() {
+        let cache = ReadCache::new(ByteSize::b(0));
+        let stream_id = StreamId::from([1u8; StreamId::LEN]);
+        cache.insert(stream_id, cache.generation(), records(0..10), None);
+        assert!(cache.get(stream_id, 0..10).is_none());
+    }
+}
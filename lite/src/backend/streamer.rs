@@ -14,13 +14,18 @@ use futures::{
 };
 use parking_lot::Mutex;
 use s2_common::{
-    config::{RetentionPolicy, StreamConfig, TimestampingConfig, TimestampingMode},
+    basin::BasinName,
+    config::{
+        CompressionHint, RetentionPolicy, SERVER_PREFER_TIMESTAMP_TOLERANCE, StreamConfig,
+        TimestampingConfig, TimestampingMode,
+    },
+    deep_size::DeepSize as _,
     encryption::EncryptionAlgorithm,
     record::{
         CommandRecord, FencingToken, Metered, MeteredExt as _, MeteredSize, NonZeroSeqNum, Record,
         SeqNum, StreamPosition, Timestamp,
     },
-    stream::AppendAck,
+    stream::{AppendAck, StreamName},
 };
 use s2_storage::record::{
     StoredAppendInput, StoredAppendRecord, StoredAppendRecordBatch, StoredAppendRecordParts,
@@ -31,28 +36,43 @@ use slatedb::{
     config::{PutOptions, ScanOptions, Ttl, WriteOptions},
 };
 use tokio::{
-    sync::{Semaphore, SemaphorePermit, broadcast, mpsc, oneshot},
+    sync::{AcquireError, Semaphore, SemaphorePermit, broadcast, mpsc, oneshot},
     time::Instant,
 };
+use tokio_util::sync::CancellationToken;
 use tracing::debug;
 
 use crate::{
     backend::{
         append,
         bgtasks::BgtaskTrigger,
+        clock::Clock,
         durability_notifier::DurabilityNotifier,
         error::{
             AppendConditionFailedError, AppendErrorInternal, AppendTimestampRequiredError,
-            DeleteStreamError, MaxSeqNumError, RequestDroppedError, StorageError,
+            AppendTimestampTooFarInFutureError, DeleteStreamError, MaxSeqNumError,
+            MinRetainedRecordsError, RequestDroppedError, ShuttingDownError, StorageError,
             StreamDeletionPendingError, StreamerMissingInActionError,
         },
         kv,
+        memory_budget::{MemoryBudget, MemoryPermit},
+        rate_limit::AppendRateLimiters,
+        read_cache::ReadCache,
     },
-    metrics,
+    metrics::{self, AppendRejection, TimestampAdjustment},
     stream_id::StreamId,
 };
 
 pub(super) const DORMANT_TIMEOUT: Duration = Duration::from_secs(60);
+/// Record header setting a time-to-live for the record, in whole seconds from when it is
+/// appended.
+///
+/// Expired records are removed in the background, but only ever from the start of the stream:
+/// trimming cannot leave a gap mid-stream, so a record is removed once it and every record before
+/// it have expired. A record with no TTL, or a later expiry, holds back the records after it until
+/// it is itself trimmed or dropped by the stream's retention policy. The header has no effect on
+/// encrypted records, and values that are not a non-negative integer are ignored.
+pub(super) const RECORD_TTL_HEADER: &[u8] = b"s2-ttl-seconds";
 // Rate-limit delete-on-empty scheduling and pad deadlines to cover the period.
 const DOE_DEADLINE_REFRESH_PERIOD: Duration = Duration::from_secs(600);
 
@@ -80,9 +100,11 @@ struct InFlightAppend {
 
 struct DbSubmitAppendOptions {
     retention: RetentionPolicy,
+    compression: CompressionHint,
     doe_deadline: Option<kv::stream_doe_deadline::Entry>,
     fencing_token: Option<FencingToken>,
     trim_point: Option<RangeTo<SeqNum>>,
+    clock: Clock,
 }
 
 #[derive(Debug, Default)]
@@ -194,7 +216,7 @@ impl GuardedStreamerClient {
     pub(super) async fn append_permit(
         &self,
         input: StoredAppendInput,
-    ) -> Result<AppendPermit<'_>, StreamerMissingInActionError> {
+    ) -> Result<AppendPermit<'_>, AppendErrorInternal> {
         self.client.append_permit(input).await
     }
 
@@ -209,6 +231,8 @@ impl GuardedStreamerClient {
 pub(super) struct Spawner {
     pub generation_id: StreamerGenerationId,
     pub db: slatedb::Db,
+    pub basin: BasinName,
+    pub stream: StreamName,
     pub stream_id: StreamId,
     pub config: StreamConfig,
     pub cipher: Option<EncryptionAlgorithm>,
@@ -216,9 +240,16 @@ pub(super) struct Spawner {
     pub last_tail_write_timestamp: kv::timestamp::TimestampSecs,
     pub fencing_token: FencingToken,
     pub trim_point: RangeTo<SeqNum>,
+    pub deleted: bool,
     pub append_inflight_bytes_sema: Arc<Semaphore>,
+    pub memory_budget: MemoryBudget,
+    pub append_rate_limiters: AppendRateLimiters,
     pub durability_notifier: DurabilityNotifier,
     pub bgtask_trigger_tx: broadcast::Sender<BgtaskTrigger>,
+    pub read_cache: ReadCache,
+    pub follower_max_lag: usize,
+    pub clock: Clock,
+    pub shutdown: CancellationToken,
 }
 
 impl Spawner {
@@ -229,6 +260,8 @@ impl Spawner {
         let Self {
             generation_id,
             db,
+            basin,
+            stream,
             stream_id,
             config,
             cipher,
@@ -236,15 +269,24 @@ impl Spawner {
             last_tail_write_timestamp,
             fencing_token,
             trim_point,
+            deleted,
             append_inflight_bytes_sema,
+            memory_budget,
+            append_rate_limiters,
             durability_notifier,
             bgtask_trigger_tx,
+            read_cache,
+            follower_max_lag,
+            clock,
+            shutdown,
         } = self;
 
         let (msg_tx, msg_rx) = mpsc::unbounded_channel();
         let (streamer_lease_state, client_lease_state) = StreamerLeaseState::new();
         let streamer = Streamer {
             db,
+            basin,
+            stream,
             stream_id,
             msg_tx: msg_tx.clone(),
             config,
@@ -257,16 +299,20 @@ impl Spawner {
                 state: trim_point,
                 applied_point: ..tail_pos.seq_num,
             },
+            deleted,
             last_doe_deadline_at: None,
             db_writes_pending: VecDeque::new(),
             db_durability_subscription: 0,
             inflight_appends: VecDeque::new(),
             pending_appends: append::PendingAppends::new(),
             stable_pos: tail_pos,
-            follow_tx: broadcast::Sender::new(super::FOLLOWER_MAX_LAG),
+            follow_tx: broadcast::Sender::new(follower_max_lag),
             lease_state: streamer_lease_state,
+            append_rate_limiters,
             durability_notifier,
             bgtask_trigger_tx,
+            read_cache,
+            clock,
         };
 
         tokio::spawn(async move {
@@ -280,7 +326,9 @@ impl Spawner {
             cipher,
             msg_tx,
             append_inflight_bytes: append_inflight_bytes_sema,
+            memory_budget,
             lease_state: client_lease_state,
+            shutdown,
         }
     }
 }
@@ -305,12 +353,17 @@ impl<T> CommandState<T> {
 
 struct Streamer {
     db: slatedb::Db,
+    basin: BasinName,
+    stream: StreamName,
     stream_id: StreamId,
     msg_tx: mpsc::UnboundedSender<Message>,
     config: StreamConfig,
     last_tail_write_timestamp: kv::timestamp::TimestampSecs,
     fencing_token: CommandState<FencingToken>,
     trim_point: CommandState<RangeTo<SeqNum>>,
+    /// Marked deleted within the deletion grace period, so only a terminal trim is accepted until
+    /// the stream is undeleted.
+    deleted: bool,
     last_doe_deadline_at: Option<Instant>,
     db_writes_pending: VecDeque<BoxFuture<'static, Result<InFlightAppend, slatedb::Error>>>,
     db_durability_subscription: u64,
@@ -319,8 +372,11 @@ struct Streamer {
     stable_pos: StreamPosition,
     follow_tx: broadcast::Sender<Vec<Metered<StoredSequencedRecord>>>,
     lease_state: StreamerLeaseState,
+    append_rate_limiters: AppendRateLimiters,
     durability_notifier: DurabilityNotifier,
     bgtask_trigger_tx: broadcast::Sender<BgtaskTrigger>,
+    read_cache: ReadCache,
+    clock: Clock,
 }
 
 impl Streamer {
@@ -337,10 +393,12 @@ impl Streamer {
             match_seq_num,
             fencing_token,
         }: StoredAppendInput,
+        append_type: AppendType,
     ) -> Result<Vec<Metered<StoredSequencedRecord>>, AppendErrorInternal> {
         if let Some(provided_token) = fencing_token
             && provided_token != self.fencing_token.state
         {
+            metrics::observe_append_rejected(AppendRejection::FencingTokenMismatch);
             Err(AppendConditionFailedError::FencingTokenMismatch {
                 expected: provided_token,
                 actual: self.fencing_token.state.clone(),
@@ -352,6 +410,7 @@ impl Streamer {
         if let Some(match_seq_num) = match_seq_num
             && match_seq_num != first_seq_num
         {
+            metrics::observe_append_rejected(AppendRejection::SeqNumMismatch);
             Err(AppendConditionFailedError::SeqNumMismatch {
                 assigned_seq_num: first_seq_num,
                 match_seq_num,
@@ -361,7 +420,12 @@ impl Streamer {
             records,
             first_seq_num,
             next_assignable_pos.timestamp,
+            self.clock.timestamp_millis(),
             &self.config.timestamping,
+            match append_type {
+                AppendType::Regular => self.config.max_future_skew_secs,
+                AppendType::Terminal => 0,
+            },
         )
     }
 
@@ -384,6 +448,7 @@ impl Streamer {
                         applied_point: new_applied_point,
                         state: trim_point,
                     };
+                    self.read_cache.invalidate(self.stream_id, trim_point.end);
                 }
             }
         }
@@ -399,10 +464,32 @@ impl Streamer {
         let Some(ticket) = append::admit(reply_tx, session) else {
             return;
         };
-        let sequenced_records = if self.trim_point.state.end == SeqNum::MAX {
+        let sequenced_records = if self.trim_point.state.end == SeqNum::MAX
+            || (self.deleted && append_type == AppendType::Regular)
+        {
             Err(StreamDeletionPendingError.into())
         } else {
-            self.sequence_records(input)
+            self.sequence_records(input, append_type)
+                .and_then(|sequenced_records| {
+                    if append_type == AppendType::Regular {
+                        check_min_retained_records(
+                            &sequenced_records,
+                            self.trim_point.state.end,
+                            self.config.min_retained_records,
+                        )?;
+                        self.append_rate_limiters
+                            .try_acquire(
+                                self.stream_id,
+                                self.config.max_append_bytes_per_sec,
+                                self.config.max_append_ops_per_sec,
+                                sequenced_records.metered_size(),
+                            )
+                            .inspect_err(|_| {
+                                metrics::observe_append_rejected(AppendRejection::RateLimited)
+                            })?;
+                    }
+                    Ok(sequenced_records)
+                })
         };
         match sequenced_records {
             Ok(sequenced_records) => {
@@ -422,6 +509,7 @@ impl Streamer {
                 let seq_num_range = first_pos.seq_num..next_pos.seq_num;
                 let opts = DbSubmitAppendOptions {
                     retention: self.config.retention_policy,
+                    compression: self.config.compression_hint,
                     doe_deadline: self.doe_deadline_maybe(),
                     fencing_token: self
                         .fencing_token
@@ -431,13 +519,14 @@ impl Streamer {
                         .trim_point
                         .is_applied_in(&seq_num_range)
                         .then_some(self.trim_point.state),
+                    clock: self.clock.clone(),
                 };
                 self.db_writes_pending.push_back(
                     db_submit_append(self.db.clone(), self.stream_id, sequenced_records, opts)
                         .boxed(),
                 );
                 self.pending_appends.accept(ticket, first_pos..next_pos);
-                self.last_tail_write_timestamp = kv::timestamp::TimestampSecs::now();
+                self.last_tail_write_timestamp = kv::timestamp::TimestampSecs::now(&self.clock);
             }
             Err(e) => {
                 self.pending_appends.reject(ticket, e, self.stable_pos);
@@ -540,9 +629,21 @@ impl Streamer {
                 Ok(Err(AppendErrorInternal::TimestampMissing(_))) => {
                     unreachable!("Timestamp::MAX used")
                 }
+                Ok(Err(AppendErrorInternal::TimestampTooFarInFuture(_))) => {
+                    unreachable!("terminal append is not subject to future skew limits")
+                }
                 Ok(Err(AppendErrorInternal::MaxSeqNum(_))) => {
                     unreachable!("terminal append is plaintext command record")
                 }
+                Ok(Err(AppendErrorInternal::MinRetainedRecords(_))) => {
+                    unreachable!("terminal append is not subject to min retained records")
+                }
+                Ok(Err(AppendErrorInternal::ShuttingDown(_))) => {
+                    unreachable!("terminal append bypasses append permits")
+                }
+                Ok(Err(AppendErrorInternal::RateLimited(_))) => {
+                    unreachable!("terminal append is not subject to rate limits")
+                }
                 Err(_) => Err(DeleteStreamError::StreamerMissingInActionError(
                     StreamerMissingInActionError,
                 )),
@@ -560,8 +661,10 @@ impl Streamer {
             .is_none_or(|t| now.duration_since(t) >= DOE_DEADLINE_REFRESH_PERIOD)
         {
             self.last_doe_deadline_at = Some(now);
-            let deadline =
-                kv::timestamp::TimestampSecs::after(doe_arm_delay(retention_age, min_age));
+            let deadline = kv::timestamp::TimestampSecs::after(
+                &self.clock,
+                doe_arm_delay(retention_age, min_age),
+            );
             Some(kv::stream_doe_deadline::Entry { deadline, min_age })
         } else {
             None
@@ -607,6 +710,10 @@ impl Streamer {
             if self.follow_tx.send(records).is_err() {
                 debug!(stream_id = %self.stream_id, "no active followers for durable records broadcast");
             }
+            // A terminally trimmed stream is being deleted, and its label set must not reappear.
+            if self.trim_point.state.end != SeqNum::MAX {
+                metrics::observe_follower_lag(&self.basin, &self.stream, self.follow_tx.len());
+            }
         }
     }
 
@@ -687,6 +794,9 @@ impl Streamer {
                         Message::Reconfigure { config } => {
                             self.config = config;
                         }
+                        Message::SetDeleted { deleted } => {
+                            self.deleted = deleted;
+                        }
                         Message::DurabilityStatus(status) => {
                             match status {
                                 Ok(durable_seq) => {
@@ -712,6 +822,7 @@ impl Streamer {
                 }
             }
         }
+        metrics::remove_follower_lag(&self.basin, &self.stream);
     }
 }
 
@@ -744,6 +855,9 @@ enum Message {
     Reconfigure {
         config: StreamConfig,
     },
+    SetDeleted {
+        deleted: bool,
+    },
     DurabilityStatus(Result<u64, slatedb::CloseReason>),
 }
 
@@ -767,7 +881,9 @@ pub(super) struct StreamerClient {
     cipher: Option<EncryptionAlgorithm>,
     msg_tx: mpsc::UnboundedSender<Message>,
     append_inflight_bytes: Arc<Semaphore>,
+    memory_budget: MemoryBudget,
     lease_state: StreamerClientLeaseState,
+    shutdown: CancellationToken,
 }
 
 impl StreamerClient {
@@ -815,23 +931,40 @@ impl StreamerClient {
     async fn append_permit(
         &self,
         input: StoredAppendInput,
-    ) -> Result<AppendPermit<'_>, StreamerMissingInActionError> {
+    ) -> Result<AppendPermit<'_>, AppendErrorInternal> {
         let metered_size = input.records.metered_size();
         metrics::observe_append_batch_size(input.records.len(), metered_size);
         let start = Instant::now();
         let num_permits =
             u32::try_from(metered_size.max(1)).expect("append batch size fits in u32");
-        let sema_permit = tokio::select! {
-            res = self.append_inflight_bytes.acquire_many(num_permits) => {
-                res.map_err(|_| StreamerMissingInActionError)
+        let deep_size = input
+            .records
+            .iter()
+            .map(|record| record.parts().record.deep_size())
+            .sum();
+        let (sema_permit, memory_permit) = tokio::select! {
+            biased;
+            _ = self.shutdown.cancelled() => {
+                Err(AppendErrorInternal::ShuttingDown(ShuttingDownError))
+            }
+            res = async {
+                let sema_permit = self.append_inflight_bytes.acquire_many(num_permits).await?;
+                Ok::<_, AcquireError>((sema_permit, self.memory_budget.acquire(deep_size).await))
+            } => {
+                res.map_err(|_| AppendErrorInternal::StreamerMissingInActionError(
+                    StreamerMissingInActionError,
+                ))
             }
             _ = self.msg_tx.closed() => {
-                Err(StreamerMissingInActionError)
+                Err(AppendErrorInternal::StreamerMissingInActionError(
+                    StreamerMissingInActionError,
+                ))
             }
         }?;
         metrics::observe_append_permit_latency(start.elapsed());
         Ok(AppendPermit {
             sema_permit,
+            memory_permit,
             msg_tx: &self.msg_tx,
             input,
         })
@@ -841,6 +974,10 @@ impl StreamerClient {
         self.msg_tx.send(Message::Reconfigure { config }).is_ok()
     }
 
+    pub(super) fn advise_deleted(&self, deleted: bool) -> bool {
+        self.msg_tx.send(Message::SetDeleted { deleted }).is_ok()
+    }
+
     async fn terminal_trim(
         &self,
         condition: TerminalTrimCondition,
@@ -860,13 +997,11 @@ impl StreamerClient {
     }
 }
 
-fn timestamp_now() -> Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::SystemTime::UNIX_EPOCH)
-        .expect("21st century")
+fn server_prefer_tolerance_ms() -> u64 {
+    SERVER_PREFER_TIMESTAMP_TOLERANCE
         .as_millis()
         .try_into()
-        .expect("Milliseconds since Unix epoch fits into a u64")
+        .expect("tolerance fits into a u64")
 }
 
 fn terminal_trim_input() -> StoredAppendInput {
@@ -886,6 +1021,7 @@ fn terminal_trim_input() -> StoredAppendInput {
 #[derive(Debug)]
 pub struct AppendPermit<'a> {
     sema_permit: SemaphorePermit<'a>,
+    memory_permit: MemoryPermit,
     msg_tx: &'a mpsc::UnboundedSender<Message>,
     input: StoredAppendInput,
 }
@@ -911,6 +1047,7 @@ impl AppendPermit<'_> {
         let start = Instant::now();
         let AppendPermit {
             sema_permit,
+            memory_permit,
             msg_tx,
             input,
         } = self;
@@ -925,6 +1062,7 @@ impl AppendPermit<'_> {
             .map_err(|_| StreamerMissingInActionError)?;
         let ack = reply_rx.await.map_err(|_| RequestDroppedError)??;
         drop(sema_permit);
+        drop(memory_permit);
         metrics::observe_append_ack_latency(start.elapsed());
         Ok(ack)
     }
@@ -965,11 +1103,14 @@ fn sequenced_records(
     batch: StoredAppendRecordBatch,
     first_seq_num: SeqNum,
     prev_max_timestamp: Timestamp,
+    now: Timestamp,
     config: &TimestampingConfig,
+    max_future_skew_secs: u64,
 ) -> Result<Vec<Metered<StoredSequencedRecord>>, AppendErrorInternal> {
     let mut sequenced_records = Vec::with_capacity(batch.len());
     let mut max_timestamp = prev_max_timestamp;
-    let now = timestamp_now();
+    let mut capped_count = 0;
+    let mut monotonic_count = 0;
     for (i, StoredAppendRecordParts { timestamp, record }) in batch
         .into_iter()
         .map(|record| record.into_parts())
@@ -985,16 +1126,24 @@ fn sequenced_records(
                 max_assignable_seq_num,
             })?;
         }
-        let mut timestamp = match config.mode {
-            TimestampingMode::ClientPrefer => timestamp.unwrap_or(now),
-            TimestampingMode::ClientRequire => timestamp.ok_or(AppendTimestampRequiredError)?,
-            TimestampingMode::Arrival => now,
-        };
+        let client_timestamp = resolve_client_timestamp::<AppendErrorInternal>(
+            timestamp,
+            now,
+            config,
+            max_future_skew_secs,
+        )?;
+        let mut timestamp = client_timestamp.unwrap_or(now);
         if !config.uncapped && timestamp > now {
             timestamp = now;
+            if client_timestamp.is_some() {
+                capped_count += 1;
+            }
         }
         if timestamp < max_timestamp {
             timestamp = max_timestamp;
+            if client_timestamp.is_some() {
+                monotonic_count += 1;
+            }
         } else {
             max_timestamp = timestamp;
         }
@@ -1004,18 +1153,99 @@ fn sequenced_records(
             timestamp,
         }));
     }
+    metrics::observe_timestamp_adjustments(TimestampAdjustment::Capped, capped_count);
+    metrics::observe_timestamp_adjustments(TimestampAdjustment::Monotonic, monotonic_count);
     Ok(sequenced_records)
 }
 
+/// The client-specified timestamp to honour under `config`, if any.
+///
+/// Fails if the timestamping mode requires a timestamp that is missing, or if the timestamp is more
+/// than `max_future_skew_secs` ahead of `now`.
+pub(super) fn resolve_client_timestamp<E>(
+    timestamp: Option<Timestamp>,
+    now: Timestamp,
+    config: &TimestampingConfig,
+    max_future_skew_secs: u64,
+) -> Result<Option<Timestamp>, E>
+where
+    E: From<AppendTimestampRequiredError> + From<AppendTimestampTooFarInFutureError>,
+{
+    let client_timestamp = match config.mode {
+        TimestampingMode::ClientPrefer => timestamp,
+        TimestampingMode::ClientRequire => Some(timestamp.ok_or(AppendTimestampRequiredError)?),
+        TimestampingMode::Arrival => None,
+        TimestampingMode::ServerPrefer => {
+            timestamp.filter(|ts| ts.abs_diff(now) <= server_prefer_tolerance_ms())
+        }
+    };
+    if let Some(timestamp) = client_timestamp
+        && max_future_skew_secs > 0
+        && timestamp > now.saturating_add(max_future_skew_secs.saturating_mul(1000))
+    {
+        Err(AppendTimestampTooFarInFutureError {
+            timestamp,
+            arrival_timestamp: now,
+            max_future_skew_secs,
+        })?;
+    }
+    Ok(client_timestamp)
+}
+
+fn check_min_retained_records(
+    records: &[Metered<StoredSequencedRecord>],
+    current_trim_point: SeqNum,
+    min_retained_records: u64,
+) -> Result<(), MinRetainedRecordsError> {
+    if min_retained_records == 0 {
+        return Ok(());
+    }
+    let (first_pos, next_pos) = pos_span(records);
+    for sr in records {
+        if let StoredRecord::Plaintext(Record::Command(CommandRecord::Trim(trim_point))) =
+            sr.inner()
+        {
+            let trim_point = (*trim_point).min(sr.position().seq_num + 1);
+            let retained_records = next_pos.seq_num - trim_point;
+            if trim_point > current_trim_point && retained_records < min_retained_records {
+                return Err(MinRetainedRecordsError {
+                    first_seq_num: first_pos.seq_num,
+                    trim_point,
+                    retained_records,
+                    min_retained_records,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn record_expires_at(record: &StoredRecord, clock: &Clock) -> Option<kv::timestamp::TimestampSecs> {
+    let StoredRecord::Plaintext(Record::Envelope(envelope)) = record else {
+        return None;
+    };
+    let header = envelope
+        .headers()
+        .iter()
+        .find(|header| header.name.as_ref() == RECORD_TTL_HEADER)?;
+    let ttl_secs: u64 = std::str::from_utf8(&header.value).ok()?.parse().ok()?;
+    Some(kv::timestamp::TimestampSecs::after(
+        clock,
+        Duration::from_secs(ttl_secs),
+    ))
+}
+
 async fn db_submit_append(
     db: slatedb::Db,
     stream_id: StreamId,
     records: Vec<Metered<StoredSequencedRecord>>,
     DbSubmitAppendOptions {
         retention,
+        compression,
         doe_deadline,
         fencing_token,
         trim_point,
+        clock,
     }: DbSubmitAppendOptions,
 ) -> Result<InFlightAppend, slatedb::Error> {
     let ttl = match retention {
@@ -1023,11 +1253,31 @@ async fn db_submit_append(
         RetentionPolicy::Infinite() => Ttl::NoExpiry,
     };
     let ttl_put_opts = PutOptions { ttl };
+    let ser_values = move |records: Vec<Metered<StoredSequencedRecord>>| {
+        let values: Vec<_> = records
+            .iter()
+            .map(|msr| {
+                kv::stream_record_data::ser_value_with_compression(msr.parts().1, compression)
+            })
+            .collect();
+        (records, values)
+    };
+    // The streamer polls this future, so CPU-bound compression runs on a blocking thread rather
+    // than stalling it.
+    let (records, values) = match compression {
+        CompressionHint::None => ser_values(records),
+        CompressionHint::Zstd | CompressionHint::Gzip => {
+            tokio::task::spawn_blocking(move || ser_values(records))
+                .await
+                .expect("record compression should not panic")
+        }
+    };
     let mut wb = WriteBatch::new();
-    for (position, record) in records.iter().map(|msr| msr.parts()) {
+    for (msr, value) in records.iter().zip(values) {
+        let (position, _) = msr.parts();
         wb.put_with_options(
             kv::stream_record_data::ser_key(stream_id, position),
-            kv::stream_record_data::ser_value(record),
+            value,
             &ttl_put_opts,
         );
         wb.put_with_options(
@@ -1035,6 +1285,20 @@ async fn db_submit_append(
             kv::stream_record_timestamp::ser_value(),
             &ttl_put_opts,
         );
+        if let StoredRecord::Plaintext(Record::Command(_)) = msr.inner() {
+            wb.put_with_options(
+                kv::stream_command_index::ser_key(stream_id, position),
+                kv::stream_command_index::ser_value(),
+                &ttl_put_opts,
+            );
+        }
+        if let Some(expires_at) = record_expires_at(msr.inner(), &clock) {
+            wb.put_with_options(
+                kv::stream_record_expiry::ser_key(stream_id, position),
+                kv::stream_record_expiry::ser_value(expires_at),
+                &ttl_put_opts,
+            );
+        }
     }
     if let Some(fencing_token) = fencing_token {
         wb.put(
@@ -1071,7 +1335,7 @@ async fn db_submit_append(
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::VecDeque, sync::Arc};
+    use std::{collections::VecDeque, str::FromStr as _, sync::Arc};
 
     use bytes::Bytes;
     use s2_common::{
@@ -1087,6 +1351,10 @@ mod tests {
 
     use super::*;
 
+    fn timestamp_now() -> Timestamp {
+        Clock::system().timestamp_millis()
+    }
+
     fn test_record(body: Bytes, timestamp: Option<Timestamp>) -> StoredAppendRecord {
         let envelope = EnvelopeRecord::try_from_parts(vec![], body).unwrap();
         let record = StoredRecord::from(Record::Envelope(envelope)).metered();
@@ -1132,7 +1400,7 @@ mod tests {
         .try_into()
         .unwrap();
 
-        let result = sequenced_records(records, 100, 0, &config).unwrap();
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].position().seq_num, 100);
@@ -1156,7 +1424,7 @@ mod tests {
         .try_into()
         .unwrap();
 
-        let result = sequenced_records(records, 100, 0, &config).unwrap();
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].position().seq_num, 100);
@@ -1176,7 +1444,7 @@ mod tests {
             .try_into()
             .unwrap();
 
-        let result = sequenced_records(records, 100, 0, &config);
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0);
 
         assert!(matches!(
             result,
@@ -1184,6 +1452,35 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn sequenced_records_rejects_timestamps_beyond_future_skew() {
+        let config = TimestampingConfig {
+            mode: TimestampingMode::ClientRequire,
+            uncapped: true,
+        };
+        let now = timestamp_now();
+
+        let records: StoredAppendRecordBatch = vec![
+            test_record(vec![1, 2, 3].into(), Some(now + 5_000)),
+            test_record(vec![4, 5, 6].into(), Some(now + 60_000)),
+        ]
+        .try_into()
+        .unwrap();
+        let result = sequenced_records(records.clone(), 100, 0, timestamp_now(), &config, 30);
+        assert!(matches!(
+            result,
+            Err(AppendErrorInternal::TimestampTooFarInFuture(
+                AppendTimestampTooFarInFutureError {
+                    max_future_skew_secs: 30,
+                    ..
+                }
+            ))
+        ));
+
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
+        assert_eq!(result[1].position().timestamp, now + 60_000);
+    }
+
     #[test]
     fn sequenced_records_client_require_with_timestamps() {
         let config = TimestampingConfig {
@@ -1198,7 +1495,7 @@ mod tests {
         .try_into()
         .unwrap();
 
-        let result = sequenced_records(records, 100, 0, &config).unwrap();
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].position().timestamp, 900);
@@ -1220,11 +1517,56 @@ mod tests {
         .try_into()
         .unwrap();
 
-        let result = sequenced_records(records, 100, 0, &config).unwrap();
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert!(result[0].position().timestamp >= now);
+        assert!(result[1].position().timestamp >= now);
+    }
+
+    #[test]
+    fn sequenced_records_server_prefer_within_tolerance() {
+        let now = timestamp_now();
+        let config = TimestampingConfig {
+            mode: TimestampingMode::ServerPrefer,
+            uncapped: false,
+        };
+
+        let records: StoredAppendRecordBatch = vec![
+            test_record(vec![1, 2, 3].into(), Some(now - 1_000)),
+            test_record(vec![4, 5, 6].into(), None),
+        ]
+        .try_into()
+        .unwrap();
+
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].position().timestamp, now - 1_000);
+        assert!(result[1].position().timestamp >= now);
+    }
+
+    #[test]
+    fn sequenced_records_server_prefer_outside_tolerance() {
+        let now = timestamp_now();
+        let config = TimestampingConfig {
+            mode: TimestampingMode::ServerPrefer,
+            uncapped: true,
+        };
+
+        let records: StoredAppendRecordBatch = vec![
+            test_record(vec![1, 2, 3].into(), Some(900)),
+            test_record(vec![4, 5, 6].into(), Some(now + 60_000)),
+        ]
+        .try_into()
+        .unwrap();
+
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
 
         assert_eq!(result.len(), 2);
         assert!(result[0].position().timestamp >= now);
         assert!(result[1].position().timestamp >= now);
+        assert!(result[1].position().timestamp < now + 60_000);
     }
 
     #[test]
@@ -1242,7 +1584,7 @@ mod tests {
         .try_into()
         .unwrap();
 
-        let result = sequenced_records(records, 100, 0, &config).unwrap();
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
 
         assert_eq!(result.len(), 3);
         assert_eq!(result[0].position().timestamp, 1000);
@@ -1250,6 +1592,35 @@ mod tests {
         assert_eq!(result[2].position().timestamp, 1100);
     }
 
+    #[test]
+    fn sequenced_records_counts_timestamp_adjustments() {
+        let now = timestamp_now();
+        let config = TimestampingConfig {
+            mode: TimestampingMode::ClientPrefer,
+            uncapped: false,
+        };
+
+        let records: StoredAppendRecordBatch = vec![
+            test_record(vec![1, 2, 3].into(), Some(1000)),
+            test_record(vec![4, 5, 6].into(), Some(900)),
+            test_record(vec![7, 8, 9].into(), Some(800)),
+            test_record(vec![10, 11, 12].into(), Some(now + 60_000)),
+        ]
+        .try_into()
+        .unwrap();
+
+        let capped_before = metrics::timestamp_adjustments(TimestampAdjustment::Capped);
+        let monotonic_before = metrics::timestamp_adjustments(TimestampAdjustment::Monotonic);
+
+        sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
+
+        // Other tests may concurrently bump the process-wide counters.
+        assert!(metrics::timestamp_adjustments(TimestampAdjustment::Capped) > capped_before);
+        assert!(
+            metrics::timestamp_adjustments(TimestampAdjustment::Monotonic) >= monotonic_before + 2
+        );
+    }
+
     #[test]
     fn sequenced_records_prev_max_timestamp_enforced() {
         let config = TimestampingConfig {
@@ -1264,7 +1635,7 @@ mod tests {
         .try_into()
         .unwrap();
 
-        let result = sequenced_records(records, 100, 1000, &config).unwrap();
+        let result = sequenced_records(records, 100, 1000, timestamp_now(), &config, 0).unwrap();
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].position().timestamp, 1000);
@@ -1285,7 +1656,7 @@ mod tests {
                 .try_into()
                 .unwrap();
 
-        let result = sequenced_records(records, 100, 0, &config).unwrap();
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
 
         assert_eq!(result.len(), 1);
         assert!(result[0].position().timestamp <= now + 100);
@@ -1305,7 +1676,7 @@ mod tests {
                 .try_into()
                 .unwrap();
 
-        let result = sequenced_records(records, 100, 0, &config).unwrap();
+        let result = sequenced_records(records, 100, 0, timestamp_now(), &config, 0).unwrap();
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].position().timestamp, future);
@@ -1323,7 +1694,7 @@ mod tests {
         .try_into()
         .unwrap();
 
-        let result = sequenced_records(records, 42, 0, &config).unwrap();
+        let result = sequenced_records(records, 42, 0, timestamp_now(), &config, 0).unwrap();
 
         assert_eq!(result.len(), 3);
         assert_eq!(result[0].position().seq_num, 42);
@@ -1352,7 +1723,14 @@ mod tests {
         .try_into()
         .unwrap();
 
-        let result = sequenced_records(records, max_assignable_seq_num, 0, &config);
+        let result = sequenced_records(
+            records,
+            max_assignable_seq_num,
+            0,
+            timestamp_now(),
+            &config,
+            0,
+        );
 
         assert!(matches!(
             result,
@@ -1381,12 +1759,51 @@ mod tests {
                 .unwrap();
 
         let first_command_seq_num = max_assignable_seq_num + 1;
-        let result = sequenced_records(records, first_command_seq_num, 0, &config).unwrap();
+        let result = sequenced_records(
+            records,
+            first_command_seq_num,
+            0,
+            timestamp_now(),
+            &config,
+            0,
+        )
+        .unwrap();
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].position().seq_num, first_command_seq_num);
     }
 
+    #[test]
+    fn check_min_retained_records_rejects_trims_below_floor() {
+        let config = TimestampingConfig::default();
+        let records: StoredAppendRecordBatch = vec![
+            test_record(vec![1].into(), None),
+            test_record(vec![2].into(), None),
+            test_command_record(CommandRecord::Trim(SeqNum::MAX), None),
+        ]
+        .try_into()
+        .unwrap();
+        let records = sequenced_records(records, 10, 0, timestamp_now(), &config, 0).unwrap();
+
+        assert!(check_min_retained_records(&records, 0, 0).is_ok());
+        assert!(check_min_retained_records(&records, 13, 5).is_ok());
+
+        let error = check_min_retained_records(&records, 0, 5).unwrap_err();
+        assert_eq!(error.first_seq_num, 10);
+        assert_eq!(error.trim_point, 13);
+        assert_eq!(error.retained_records, 0);
+        assert_eq!(error.min_retained_records, 5);
+
+        let records: StoredAppendRecordBatch =
+            vec![test_command_record(CommandRecord::Trim(8), None)]
+                .try_into()
+                .unwrap();
+        let records = sequenced_records(records, 12, 0, timestamp_now(), &config, 0).unwrap();
+
+        assert!(check_min_retained_records(&records, 0, 5).is_ok());
+        assert!(check_min_retained_records(&records, 0, 6).is_err());
+    }
+
     #[test]
     fn command_state_is_applied_in_excludes_range_start() {
         let state = CommandState {
@@ -1420,6 +1837,8 @@ mod tests {
         let (lease_state, _) = StreamerLeaseState::new();
         Streamer {
             db: db.clone(),
+            basin: BasinName::from_str("test-basin").unwrap(),
+            stream: StreamName::from_str("test-stream").unwrap(),
             stream_id: [3u8; StreamId::LEN].into(),
             msg_tx,
             config: StreamConfig::default(),
@@ -1432,6 +1851,7 @@ mod tests {
                 state: ..SeqNum::MIN,
                 applied_point: ..SeqNum::MIN,
             },
+            deleted: false,
             last_doe_deadline_at: None,
             db_writes_pending: VecDeque::new(),
             db_durability_subscription: 0,
@@ -1440,8 +1860,11 @@ mod tests {
             stable_pos: StreamPosition::MIN,
             follow_tx: broadcast::Sender::new(super::super::FOLLOWER_MAX_LAG),
             lease_state,
+            append_rate_limiters: AppendRateLimiters::default(),
             durability_notifier: DurabilityNotifier::spawn(&db),
             bgtask_trigger_tx,
+            read_cache: ReadCache::new(bytesize::ByteSize::b(0)),
+            clock: Clock::system(),
         }
     }
 
@@ -1519,6 +1942,68 @@ mod tests {
         run_handle.abort();
     }
 
+    #[tokio::test]
+    async fn append_while_deleted_returns_stream_deletion_pending() {
+        let mut streamer = test_streamer().await;
+        streamer.deleted = true;
+        let (msg_tx, msg_rx) = mpsc::unbounded_channel();
+        let run_handle = tokio::spawn(streamer.run(msg_rx));
+
+        let (reply_tx, reply_rx) = oneshot::channel();
+        msg_tx
+            .send(Message::Append {
+                input: append_input(b"late"),
+                session: None,
+                reply_tx,
+                append_type: AppendType::Regular,
+            })
+            .expect("streamer should accept append message");
+
+        let err = reply_rx
+            .await
+            .expect("streamer should reply")
+            .expect_err("append should be rejected");
+        let AppendErrorInternal::StreamDeletionPending(_) = err else {
+            panic!("expected stream deletion pending");
+        };
+
+        run_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn sequence_records_counts_append_rejections() {
+        let streamer = test_streamer().await;
+
+        let fencing_before = metrics::append_rejected(AppendRejection::FencingTokenMismatch);
+        let seq_num_before = metrics::append_rejected(AppendRejection::SeqNumMismatch);
+
+        let mut input = append_input(b"fenced");
+        input.fencing_token = Some("other-token".parse().unwrap());
+        let err = streamer
+            .sequence_records(input, AppendType::Regular)
+            .expect_err("fencing token mismatch");
+        assert!(matches!(
+            err,
+            AppendErrorInternal::ConditionFailed(
+                AppendConditionFailedError::FencingTokenMismatch { .. }
+            )
+        ));
+
+        let mut input = append_input(b"mismatched");
+        input.match_seq_num = Some(42);
+        let err = streamer
+            .sequence_records(input, AppendType::Regular)
+            .expect_err("seq num mismatch");
+        assert!(matches!(
+            err,
+            AppendErrorInternal::ConditionFailed(AppendConditionFailedError::SeqNumMismatch { .. })
+        ));
+
+        // Other tests may concurrently bump the process-wide counters.
+        assert!(metrics::append_rejected(AppendRejection::FencingTokenMismatch) > fencing_before);
+        assert!(metrics::append_rejected(AppendRejection::SeqNumMismatch) > seq_num_before);
+    }
+
     #[tokio::test]
     async fn delete_on_empty_terminal_trim_skips_pending_append() {
         let mut streamer = test_streamer().await;
@@ -1545,6 +2030,53 @@ mod tests {
         assert_eq!(streamer.db_writes_pending.len(), 1);
     }
 
+    #[tokio::test]
+    async fn append_permit_waits_for_memory_budget() {
+        let (msg_tx, _msg_rx) = mpsc::unbounded_channel();
+        let input = append_input(b"budgeted");
+        let deep_size: usize = input
+            .records
+            .iter()
+            .map(|record| record.parts().record.deep_size())
+            .sum();
+        let memory_budget = MemoryBudget::new(
+            bytesize::ByteSize::b(deep_size as u64),
+            metrics::MemoryComponent::AppendBudget,
+        );
+        let (_lease_state, client_lease_state) = StreamerLeaseState::new();
+        let client = StreamerClient {
+            generation_id: StreamerGenerationId::next(),
+            stream_id: [3u8; StreamId::LEN].into(),
+            cipher: None,
+            msg_tx,
+            append_inflight_bytes: Arc::new(Semaphore::new(1 << 20)),
+            memory_budget: memory_budget.clone(),
+            lease_state: client_lease_state,
+            shutdown: CancellationToken::new(),
+        };
+
+        let first = client
+            .append_permit(input.clone())
+            .await
+            .expect("first append fits within the budget");
+        assert_eq!(memory_budget.in_use(), deep_size);
+
+        let blocked = tokio::time::timeout(
+            Duration::from_millis(10),
+            client.append_permit(input.clone()),
+        )
+        .await;
+        assert!(blocked.is_err(), "second append should wait for the budget");
+
+        drop(first);
+        assert_eq!(memory_budget.in_use(), 0);
+        let _second = client
+            .append_permit(input)
+            .await
+            .expect("second append proceeds once the first releases its bytes");
+        assert_eq!(memory_budget.in_use(), deep_size);
+    }
+
     #[tokio::test]
     async fn append_acks_release_only_after_durable_seq_and_in_order() {
         let mut streamer = test_streamer().await;
@@ -1675,4 +2207,45 @@ mod tests {
         assert_eq!(streamer.stable_pos.seq_num, 4);
         assert!(streamer.inflight_appends.is_empty());
     }
+
+    #[tokio::test]
+    async fn follower_lag_is_reported_per_stream() {
+        let mut streamer = test_streamer().await;
+        streamer.stream = StreamName::from_str("follower-lag-stream").unwrap();
+        streamer.follow_tx = broadcast::Sender::new(2);
+        let mut follow_rx = streamer.follow_tx.subscribe();
+
+        for i in 0..3 {
+            let (tx, _rx) = oneshot::channel();
+            let payload = format!("lag-{i}");
+            streamer.handle_append(
+                append_input(payload.as_bytes()),
+                None,
+                tx,
+                AppendType::Regular,
+            );
+            let submitted = streamer
+                .db_writes_pending
+                .pop_front()
+                .expect("pending write")
+                .await
+                .expect("db submit");
+            let db_seq = submitted.db_seq;
+            streamer.inflight_appends.push_back(submitted);
+            streamer.on_db_durable_seq_advanced(db_seq);
+            assert_eq!(
+                metrics::follower_lag(&streamer.basin, &streamer.stream),
+                (i + 1).min(2)
+            );
+        }
+
+        assert!(matches!(
+            follow_rx.recv().await,
+            Err(broadcast::error::RecvError::Lagged(1))
+        ));
+        follow_rx.recv().await.expect("follow batch");
+
+        metrics::remove_follower_lag(&streamer.basin, &streamer.stream);
+        assert_eq!(metrics::follower_lag(&streamer.basin, &streamer.stream), 0);
+    }
 }
@@ -1,20 +1,19 @@
 use s2_common::{
     basin::BasinName,
     config::{OptionalStreamConfig, StreamConfig, StreamReconfiguration},
-    record::StreamPosition,
+    record::{NonZeroSeqNum, StreamPosition},
     resources::{Page, ProvisionMode, ProvisionResult, RequestToken},
-    stream::{ListStreamsRequest, StreamInfo, StreamName},
+    stream::{ListStreamsRequest, StreamInfo, StreamName, StreamNamePrefix, StreamNameStartAfter},
 };
 use s2_storage::bash::Bash;
 use slatedb::{
-    IsolationLevel,
+    DbTransaction, IsolationLevel, WriteBatch,
     config::{DurabilityLevel, ScanOptions},
 };
-use time::OffsetDateTime;
 use tracing::instrument;
 
 use super::{
-    Backend,
+    Backend, StreamConfigChange,
     store::db_txn_get,
     streamer::{TerminalTrimCondition, TerminalTrimOutcome, doe_arm_delay},
 };
@@ -23,19 +22,27 @@ use crate::{
         error::{
             BasinDeletionPendingError, BasinNotFoundError, DeleteStreamError, GetStreamConfigError,
             ListStreamsError, ProvisionStreamError, ReconfigureStreamError, StorageError,
-            StreamAlreadyExistsError, StreamDeletionPendingError, StreamNotFoundError,
-            StreamerError,
+            StreamAlreadyExistsError, StreamDeletionPendingError, StreamIdCollisionError,
+            StreamNotFoundError, StreamQuotaExceededError, StreamerError, UndeleteStreamError,
         },
         kv,
     },
+    metrics,
     stream_id::StreamId,
 };
 
+const CREATION_INDEX_BACKFILL_BATCH_SIZE: usize = 1024;
+
 impl Backend {
+    /// List streams in name order.
+    ///
+    /// With `deleting`, only streams whose deletion is (`true`) or is not (`false`) pending are
+    /// listed, and the limit applies after filtering.
     pub async fn list_streams(
         &self,
         basin: BasinName,
         request: ListStreamsRequest,
+        deleting: Option<bool>,
     ) -> Result<Page<StreamInfo>, ListStreamsError> {
         let ListStreamsRequest {
             prefix,
@@ -61,11 +68,78 @@ impl Backend {
             assert_eq!(deser_basin.as_ref(), basin.as_ref());
             assert!(stream.as_ref() > start_after.as_ref());
             assert!(stream.as_ref() >= prefix.as_ref());
+            let meta = kv::stream_meta::deser_value(kv.value)?;
+            if deleting.is_some_and(|deleting| deleting != meta.deleted_at.is_some()) {
+                continue;
+            }
+            if streams.len() == limit.as_usize() {
+                has_more = true;
+                break;
+            }
+            streams.push(StreamInfo {
+                name: stream,
+                created_at: meta.created_at,
+                deleted_at: meta.deleted_at,
+                cipher: meta.cipher,
+            });
+        }
+        Ok(Page::new(streams, has_more))
+    }
+
+    /// List streams in the order they were created, oldest first.
+    ///
+    /// With `start_after_created_at`, the listing resumes after the position of `start_after`
+    /// created at that time, which need not exist anymore. `deleting` filters as for
+    /// [`Backend::list_streams`].
+    pub async fn list_streams_by_creation(
+        &self,
+        basin: BasinName,
+        request: ListStreamsRequest,
+        start_after_created_at: Option<OffsetDateTime>,
+        deleting: Option<bool>,
+    ) -> Result<Page<StreamInfo>, ListStreamsError> {
+        let ListStreamsRequest {
+            prefix,
+            start_after,
+            limit,
+        } = request;
+
+        let key_range = kv::stream_created_at::ser_key_range(
+            &basin,
+            start_after_created_at.map(|created_at| (created_at, start_after.as_ref())),
+        );
+
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let mut it = self.db.scan_with_options(key_range, &scan_opts).await?;
+
+        let mut streams = Vec::with_capacity(limit.as_usize());
+        let mut has_more = false;
+        while let Some(kv) = it.next().await? {
+            let (deser_basin, _, stream) = kv::stream_created_at::deser_key(kv.key)?;
+            assert_eq!(deser_basin.as_ref(), basin.as_ref());
+            if !stream.as_ref().starts_with(prefix.as_ref()) {
+                continue;
+            }
+            // Index entries of streams whose deletion was finalized concurrently are skipped.
+            let Some(meta) = self
+                .db_get(
+                    kv::stream_meta::ser_key(&basin, &stream),
+                    kv::stream_meta::deser_value,
+                )
+                .await?
+            else {
+                continue;
+            };
+            if deleting.is_some_and(|deleting| deleting != meta.deleted_at.is_some()) {
+                continue;
+            }
             if streams.len() == limit.as_usize() {
                 has_more = true;
                 break;
             }
-            let meta = kv::stream_meta::deser_value(kv.value)?;
             streams.push(StreamInfo {
                 name: stream,
                 created_at: meta.created_at,
@@ -76,6 +150,57 @@ impl Backend {
         Ok(Page::new(streams, has_more))
     }
 
+    /// Add creation-time index entries for streams created before the index existed.
+    ///
+    /// Entries are written in batches, and completion is recorded so that later calls return
+    /// immediately. An interrupted backfill is picked up again by the next call.
+    ///
+    /// Returns the number of entries added.
+    pub async fn backfill_stream_creation_index(&self) -> Result<usize, StorageError> {
+        let done_key = kv::stream_created_at_backfill::ser_key();
+        if self.db_get(&done_key, |_| Ok(())).await?.is_some() {
+            return Ok(0);
+        }
+
+        let scan_opts = ScanOptions {
+            durability_filter: DurabilityLevel::Remote,
+            ..Default::default()
+        };
+        let mut it = self
+            .db
+            .scan_with_options(kv::key_type_range(kv::KeyType::StreamMeta), &scan_opts)
+            .await?;
+
+        let mut batch = WriteBatch::new();
+        let mut pending = 0;
+        let mut added = 0;
+        while let Some(kv) = it.next().await? {
+            let (basin, stream) = kv::stream_meta::deser_key(kv.key)?;
+            let meta = kv::stream_meta::deser_value(kv.value)?;
+            let index_key = kv::stream_created_at::ser_key(&basin, meta.created_at, &stream);
+            if self.db_get(&index_key, |_| Ok(())).await?.is_none() {
+                batch.put(index_key, kv::stream_created_at::ser_value());
+                pending += 1;
+            }
+            if pending == CREATION_INDEX_BACKFILL_BATCH_SIZE {
+                self.db
+                    .write(std::mem::replace(&mut batch, WriteBatch::new()))
+                    .await?;
+                added += pending;
+                pending = 0;
+            }
+        }
+        batch.put(done_key, kv::stream_created_at_backfill::ser_value());
+        self.db.write(batch).await?;
+        Ok(added + pending)
+    }
+
+    /// Create a stream, or with [`ProvisionMode::Ensure`] bring an existing one to `config`.
+    ///
+    /// Fields left unset in `config` inherit the basin's default stream config field by field,
+    /// including nested timestamping and delete-on-empty fields, and otherwise the system
+    /// defaults. The resolved config is what gets stored, so later changes to basin defaults do not
+    /// reach existing streams, except for fields that a reconfiguration clears.
     pub async fn provision_stream(
         &self,
         basin: BasinName,
@@ -157,7 +282,7 @@ impl Backend {
                     ProvisionResult::Created(kv::stream_meta::StreamMeta {
                         config: config.merge(basin_defaults),
                         cipher: basin_meta.config.stream_cipher,
-                        created_at: OffsetDateTime::now_utc(),
+                        created_at: self.clock.now_utc(),
                         deleted_at: None,
                         creation_idempotency_key: new_creation_idempotency_key,
                     }),
@@ -168,7 +293,7 @@ impl Backend {
                 ProvisionResult::Created(kv::stream_meta::StreamMeta {
                     config: config.merge(basin_defaults),
                     cipher: basin_meta.config.stream_cipher,
-                    created_at: OffsetDateTime::now_utc(),
+                    created_at: self.clock.now_utc(),
                     deleted_at: None,
                     creation_idempotency_key: None,
                 }),
@@ -176,6 +301,13 @@ impl Backend {
             ),
         };
 
+        if let Some(limit) = self.max_streams_per_basin
+            && matches!(&outcome, ProvisionResult::Created(_))
+            && count_streams(&txn, &basin, limit).await? >= limit
+        {
+            return Err(StreamQuotaExceededError { basin, limit }.into());
+        }
+
         if !matches!(&outcome, ProvisionResult::Noop(_)) {
             let meta = outcome.inner();
 
@@ -184,14 +316,31 @@ impl Backend {
             let stream_id = StreamId::new(&basin, &stream);
 
             if matches!(&outcome, ProvisionResult::Created(_)) {
+                let id_mapping_key = kv::stream_id_mapping::ser_key(stream_id);
+                if let Some((mapped_basin, mapped_stream)) =
+                    db_txn_get(&txn, &id_mapping_key, kv::stream_id_mapping::deser_value).await?
+                    && (mapped_basin != basin || mapped_stream != stream)
+                {
+                    return Err(StreamIdCollisionError {
+                        basin,
+                        stream,
+                        mapped_basin,
+                        mapped_stream,
+                    }
+                    .into());
+                }
                 txn.put(
-                    kv::stream_id_mapping::ser_key(stream_id),
+                    &id_mapping_key,
                     kv::stream_id_mapping::ser_value(&basin, &stream),
                 )?;
                 txn.put(
                     kv::stream_tail_position::ser_key(stream_id),
                     kv::stream_tail_position::ser_value(StreamPosition::MIN),
                 )?;
+                txn.put(
+                    kv::stream_created_at::ser_key(&basin, meta.created_at, &stream),
+                    kv::stream_created_at::ser_value(),
+                )?;
             }
 
             if let Some(min_age) = meta.config.delete_on_empty.min_age()
@@ -199,10 +348,13 @@ impl Backend {
             {
                 txn.put(
                     kv::stream_doe_deadline::ser_key(
-                        kv::timestamp::TimestampSecs::after(doe_arm_delay(
-                            meta.config.retention_policy.age().unwrap_or_default(),
-                            min_age,
-                        )),
+                        kv::timestamp::TimestampSecs::after(
+                            &self.clock,
+                            doe_arm_delay(
+                                meta.config.retention_policy.age().unwrap_or_default(),
+                                min_age,
+                            ),
+                        ),
                         stream_id,
                     ),
                     kv::stream_doe_deadline::ser_value(min_age),
@@ -212,10 +364,15 @@ impl Backend {
             txn.commit().await?;
         }
 
-        if let ProvisionResult::Updated(meta) = &outcome
-            && let Some(client) = self.streamer_client_if_active(&basin, &stream)
-        {
-            client.advise_reconfig(meta.config.clone());
+        if let ProvisionResult::Updated(meta) = &outcome {
+            if let Some(client) = self.streamer_client_if_active(&basin, &stream) {
+                client.advise_reconfig(meta.config.clone());
+            }
+            self.publish_stream_config_change(StreamConfigChange::Updated {
+                basin: basin.clone(),
+                stream: stream.clone(),
+                config: meta.config.clone(),
+            });
         }
 
         Ok(outcome.map(|meta| StreamInfo {
@@ -306,10 +463,13 @@ impl Backend {
         {
             txn.put(
                 kv::stream_doe_deadline::ser_key(
-                    kv::timestamp::TimestampSecs::after(doe_arm_delay(
-                        meta.config.retention_policy.age().unwrap_or_default(),
-                        min_age,
-                    )),
+                    kv::timestamp::TimestampSecs::after(
+                        &self.clock,
+                        doe_arm_delay(
+                            meta.config.retention_policy.age().unwrap_or_default(),
+                            min_age,
+                        ),
+                    ),
                     stream_id,
                 ),
                 kv::stream_doe_deadline::ser_value(min_age),
@@ -321,20 +481,159 @@ impl Backend {
         if let Some(client) = self.streamer_client_if_active(&basin, &stream) {
             client.advise_reconfig(meta.config.clone());
         }
+        self.publish_stream_config_change(StreamConfigChange::Updated {
+            basin,
+            stream,
+            config: meta.config.clone(),
+        });
 
         Ok(meta.config)
     }
 
+    /// Delete a stream.
+    ///
+    /// With a deletion grace period configured, the stream is only marked deleted and can be
+    /// undeleted until the grace period elapses, after which it is purged in the background.
     #[instrument(ret, err, skip(self))]
     pub async fn delete_stream(
         &self,
         basin: BasinName,
         stream: StreamName,
     ) -> Result<(), DeleteStreamError> {
+        if !self.stream_deletion_grace_period.is_zero() {
+            return self.soft_delete_stream(basin, stream).await;
+        }
         self.delete_stream_with_condition(basin, stream, TerminalTrimCondition::Always)
             .await
     }
 
+    /// Delete a stream and remove all of its records before returning, bypassing any deletion
+    /// grace period.
+    #[instrument(ret, err, skip(self))]
+    pub async fn purge_stream(
+        &self,
+        basin: BasinName,
+        stream: StreamName,
+    ) -> Result<(), DeleteStreamError> {
+        self.delete_stream_with_condition(
+            basin.clone(),
+            stream.clone(),
+            TerminalTrimCondition::Always,
+        )
+        .await?;
+        let stream_id = StreamId::new(&basin, &stream);
+        // The trim point is only missing if the trim bgtask already finalized the deletion.
+        if let Some(trim_point) = self
+            .db_get(
+                kv::stream_trim_point::ser_key(stream_id),
+                kv::stream_trim_point::deser_value,
+            )
+            .await?
+        {
+            self.process_trim(stream_id, trim_point).await?;
+        }
+        Ok(())
+    }
+
+    async fn soft_delete_stream(
+        &self,
+        basin: BasinName,
+        stream: StreamName,
+    ) -> Result<(), DeleteStreamError> {
+        let txn = self.db.begin(IsolationLevel::SerializableSnapshot).await?;
+        let meta_key = kv::stream_meta::ser_key(&basin, &stream);
+        let mut meta = db_txn_get(&txn, &meta_key, kv::stream_meta::deser_value)
+            .await?
+            .ok_or_else(|| StreamNotFoundError {
+                basin: basin.clone(),
+                stream: stream.clone(),
+            })?;
+        if meta.deleted_at.is_some() {
+            return Ok(());
+        }
+        let deleted_at = self.clock.now_utc();
+        meta.deleted_at = Some(deleted_at);
+        txn.put(&meta_key, kv::stream_meta::ser_value(&meta))?;
+        let stream_id = StreamId::new(&basin, &stream);
+        txn.put(
+            kv::stream_purge_deadline::ser_key(
+                kv::timestamp::TimestampSecs::after(&self.clock, self.stream_deletion_grace_period),
+                stream_id,
+            ),
+            kv::stream_purge_deadline::ser_value(deleted_at),
+        )?;
+        txn.commit().await?;
+        if let Some(client) = self.streamer_client_if_active(&basin, &stream) {
+            client.advise_deleted(true);
+        }
+        self.append_rate_limiters.remove(stream_id);
+        metrics::remove_follower_lag(&basin, &stream);
+        self.publish_stream_config_change(StreamConfigChange::Deleted { basin, stream });
+        Ok(())
+    }
+
+    /// Restore a stream that was deleted within the deletion grace period.
+    ///
+    /// Undeleting a stream that is not deleted is a no-op. Once the stream has started being
+    /// purged, it can no longer be undeleted.
+    #[instrument(ret, err, skip(self))]
+    pub async fn undelete_stream(
+        &self,
+        basin: BasinName,
+        stream: StreamName,
+    ) -> Result<StreamInfo, UndeleteStreamError> {
+        let txn = self.db.begin(IsolationLevel::SerializableSnapshot).await?;
+        let meta_key = kv::stream_meta::ser_key(&basin, &stream);
+        let stream_id = StreamId::new(&basin, &stream);
+
+        let (basin_meta, meta, trim_point) = tokio::try_join!(
+            db_txn_get(
+                &txn,
+                kv::basin_meta::ser_key(&basin),
+                kv::basin_meta::deser_value,
+            ),
+            db_txn_get(&txn, &meta_key, kv::stream_meta::deser_value),
+            db_txn_get(
+                &txn,
+                kv::stream_trim_point::ser_key(stream_id),
+                kv::stream_trim_point::deser_value,
+            ),
+        )?;
+
+        let basin_meta = basin_meta.ok_or_else(|| BasinNotFoundError {
+            basin: basin.clone(),
+        })?;
+        if basin_meta.deleted_at.is_some() {
+            return Err(BasinDeletionPendingError { basin }.into());
+        }
+
+        let mut meta = meta.ok_or_else(|| StreamNotFoundError {
+            basin: basin.clone(),
+            stream: stream.clone(),
+        })?;
+
+        if meta.deleted_at.is_some() {
+            if trim_point == Some(..NonZeroSeqNum::MAX) {
+                return Err(StreamDeletionPendingError.into());
+            }
+            // The purge deadline is left behind, and is ignored as it no longer matches the
+            // stream's deletion time.
+            meta.deleted_at = None;
+            txn.put(&meta_key, kv::stream_meta::ser_value(&meta))?;
+            txn.commit().await?;
+            if let Some(client) = self.streamer_client_if_active(&basin, &stream) {
+                client.advise_deleted(false);
+            }
+        }
+
+        Ok(StreamInfo {
+            name: stream,
+            created_at: meta.created_at,
+            deleted_at: None,
+            cipher: meta.cipher,
+        })
+    }
+
     pub(super) async fn delete_stream_with_condition(
         &self,
         basin: BasinName,
@@ -352,7 +651,15 @@ impl Backend {
             Err(StreamerError::StreamDeletionPending(_)) => TerminalTrimOutcome::DeletionPending,
         };
         match outcome {
-            TerminalTrimOutcome::DeletionPending => self.mark_stream_deleted(basin, stream).await,
+            TerminalTrimOutcome::DeletionPending => {
+                self.append_rate_limiters
+                    .remove(StreamId::new(&basin, &stream));
+                metrics::remove_follower_lag(&basin, &stream);
+                self.mark_stream_deleted(basin.clone(), stream.clone())
+                    .await?;
+                self.publish_stream_config_change(StreamConfigChange::Deleted { basin, stream });
+                Ok(())
+            }
             TerminalTrimOutcome::Ineligible => Ok(()),
         }
     }
@@ -371,7 +678,7 @@ impl Backend {
                 stream: stream.clone(),
             })?;
         if meta.deleted_at.is_none() {
-            meta.deleted_at = Some(OffsetDateTime::now_utc());
+            meta.deleted_at = Some(self.clock.now_utc());
             txn.put(&meta_key, kv::stream_meta::ser_value(&meta))?;
             txn.commit().await?;
         }
@@ -379,6 +686,29 @@ impl Backend {
     }
 }
 
+/// Count the streams in `basin`, including those whose deletion is pending, stopping once `limit`
+/// is reached.
+///
+/// Scanning through `txn` makes the counted range part of its read set, so concurrent creations
+/// that would together exceed the limit conflict instead of both committing.
+async fn count_streams(
+    txn: &DbTransaction,
+    basin: &BasinName,
+    limit: usize,
+) -> Result<usize, slatedb::Error> {
+    let key_range = kv::stream_meta::ser_key_range(
+        basin,
+        &StreamNamePrefix::default(),
+        &StreamNameStartAfter::default(),
+    );
+    let mut it = txn.scan(key_range).await?;
+    let mut count = 0;
+    while count < limit && it.next().await?.is_some() {
+        count += 1;
+    }
+    Ok(count)
+}
+
 fn creation_idempotency_key(req_token: &RequestToken, config: &OptionalStreamConfig) -> Bash {
     Bash::length_prefixed(&[
         req_token.as_bytes(),
@@ -388,3 +718,78 @@ fn creation_idempotency_key(req_token: &RequestToken, config: &OptionalStreamCon
             .unwrap_or_default(),
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bytesize::ByteSize;
+    use s2_common::{
+        basin::BasinName,
+        config::{BasinConfig, OptionalStreamConfig},
+        resources::ProvisionMode,
+        stream::StreamName,
+    };
+    use slatedb::object_store::memory::InMemory;
+
+    use crate::backend::{Backend, kv};
+
+    async fn test_backend() -> Backend {
+        let object_store = std::sync::Arc::new(InMemory::new());
+        let db = slatedb::Db::builder("/test", object_store)
+            .build()
+            .await
+            .unwrap();
+        Backend::new(db, ByteSize::mib(10), ByteSize::mib(10))
+    }
+
+    #[tokio::test]
+    async fn creation_index_backfill_runs_once() {
+        let backend = test_backend().await;
+        let basin = BasinName::from_str("backfill-basin").unwrap();
+        backend
+            .provision_basin(
+                basin.clone(),
+                BasinConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .unwrap();
+        let mut index_keys = Vec::new();
+        for name in ["a", "b", "c"] {
+            let stream = StreamName::from_str(name).unwrap();
+            let info = backend
+                .provision_stream(
+                    basin.clone(),
+                    stream.clone(),
+                    OptionalStreamConfig::default(),
+                    ProvisionMode::CreateOnly {
+                        request_token: None,
+                    },
+                )
+                .await
+                .unwrap()
+                .into_inner();
+            index_keys.push(kv::stream_created_at::ser_key(
+                &basin,
+                info.created_at,
+                &stream,
+            ));
+        }
+        // Simulate streams created before the index existed.
+        for key in &index_keys[..2] {
+            backend.db.delete(key).await.unwrap();
+        }
+
+        assert_eq!(backend.backfill_stream_creation_index().await.unwrap(), 2);
+        for key in &index_keys {
+            assert!(backend.db.get(key).await.unwrap().is_some());
+        }
+
+        backend.db.delete(&index_keys[0]).await.unwrap();
+        assert_eq!(backend.backfill_stream_creation_index().await.unwrap(), 0);
+        assert!(backend.db.get(&index_keys[0]).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http::header::AUTHORIZATION;
+use s2_api::v1::error::{ErrorCode, ErrorInfo, ErrorResponse, StandardError};
+
+/// Paths that stay reachable without a token, so that health probes keep working.
+const UNAUTHENTICATED_PATHS: [&str; 2] = ["/health", "/ping"];
+
+/// A bearer token that is redacted when debug-formatted.
+#[derive(Clone)]
+pub struct BearerToken(String);
+
+impl std::fmt::Debug for BearerToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BearerToken(<redacted>)")
+    }
+}
+
+impl std::str::FromStr for BearerToken {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("token must not be empty");
+        }
+        Ok(Self(s.to_owned()))
+    }
+}
+
+/// Bearer tokens accepted by [`require_token`].
+#[derive(Debug, Clone)]
+pub struct RequiredTokens(Arc<[BearerToken]>);
+
+impl RequiredTokens {
+    pub fn new(tokens: impl IntoIterator<Item = BearerToken>) -> Self {
+        Self(tokens.into_iter().collect())
+    }
+
+    fn accepts(&self, token: &str) -> bool {
+        self.0
+            .iter()
+            .any(|expected| constant_time_eq(expected.0.as_bytes(), token.as_bytes()))
+    }
+}
+
+/// Reject requests without an `Authorization: Bearer <token>` header matching one
+/// of the required tokens.
+pub async fn require_token(
+    State(tokens): State<RequiredTokens>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if UNAUTHENTICATED_PATHS.contains(&request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match token {
+        Some(token) if tokens.accepts(token) => next.run(request).await,
+        Some(_) => unauthorized("Invalid access token"),
+        None => unauthorized("Missing bearer access token"),
+    }
+}
+
+fn unauthorized(message: &str) -> Response {
+    ErrorResponse::Standard(StandardError {
+        status: ErrorCode::Authn.status(),
+        info: ErrorInfo {
+            code: ErrorCode::Authn.into(),
+            message: message.to_owned(),
+        },
+    })
+    .into_response()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, http::StatusCode, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/health", get(|| async { "OK" }))
+            .route("/v1/basins", get(|| async { "[]" }))
+            .layer(middleware::from_fn_with_state(
+                RequiredTokens::new(["secret".parse().unwrap(), "other".parse().unwrap()]),
+                require_token,
+            ))
+    }
+
+    async fn status(path: &str, authorization: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri(path);
+        if let Some(authorization) = authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+        app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_or_wrong_token() {
+        assert_eq!(status("/v1/basins", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status("/v1/basins", Some("Bearer wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status("/v1/basins", Some("secret")).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn accepts_any_required_token() {
+        assert_eq!(
+            status("/v1/basins", Some("Bearer secret")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status("/v1/basins", Some("Bearer other")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn health_is_unauthenticated() {
+        assert_eq!(status("/health", None).await, StatusCode::OK);
+    }
+}
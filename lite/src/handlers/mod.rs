@@ -1,3 +1,4 @@
+pub mod auth;
 pub mod v1;
 
 use axum::{
@@ -1,5 +1,8 @@
-use axum::extract::{FromRequest, Path, Query, State};
-use http::StatusCode;
+use axum::{
+    extract::{FromRequest, Path, Query, State},
+    response::Response,
+};
+use http::{HeaderMap, StatusCode};
 use s2_api::{
     data::{Json, extract::JsonOpt},
     v1 as v1t,
@@ -8,10 +11,16 @@ use s2_common::{
     basin::{BasinName, ListBasinsRequest},
     config::{BasinConfig, BasinReconfiguration},
     http::extract::HeaderOpt,
-    resources::{PROVISION_RESULT_HEADER, Page, ProvisionMode, ProvisionResult, RequestToken},
+    resources::{
+        LIST_LIMIT_HEADER, PROVISION_RESULT_HEADER, Page, ProvisionMode, ProvisionResult,
+        RequestToken,
+    },
 };
 
-use crate::{backend::Backend, handlers::v1::error::ServiceError};
+use crate::{
+    backend::Backend,
+    handlers::v1::{error::ServiceError, etag::json_with_etag},
+};
 
 pub fn router() -> axum::Router<Backend> {
     use axum::routing::{delete, get, patch, post, put};
@@ -32,6 +41,9 @@ pub struct ListArgs {
 }
 
 /// List basins.
+///
+/// A `limit` above 1000 is clamped to 1000. The page size applied is returned in the
+/// `s2-list-limit` response header.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     get,
     path = super::paths::basins::LIST,
@@ -47,13 +59,23 @@ pub struct ListArgs {
 pub async fn list_basins(
     State(backend): State<Backend>,
     ListArgs { request }: ListArgs,
-) -> Result<Json<v1t::basin::ListBasinsResponse>, ServiceError> {
-    let request: ListBasinsRequest = request.try_into()?;
+) -> Result<
+    (
+        [(http::HeaderName, usize); 1],
+        Json<v1t::basin::ListBasinsResponse>,
+    ),
+    ServiceError,
+> {
+    let request: ListBasinsRequest = request.try_into().map_err(ServiceError::InvalidQuery)?;
+    let limit = request.limit.as_usize();
     let Page { values, has_more } = backend.list_basins(request).await?;
-    Ok(Json(v1t::basin::ListBasinsResponse {
-        basins: values.into_iter().map(Into::into).collect(),
-        has_more,
-    }))
+    Ok((
+        [(LIST_LIMIT_HEADER.clone(), limit)],
+        Json(v1t::basin::ListBasinsResponse {
+            basins: values.into_iter().map(Into::into).collect(),
+            has_more,
+        }),
+    ))
 }
 
 #[derive(FromRequest)]
@@ -127,12 +149,16 @@ pub struct GetConfigArgs {
 }
 
 /// Get basin configuration.
+///
+/// The response carries an `ETag`. Sending it back in `If-None-Match` yields 304 Not Modified
+/// if the configuration is unchanged.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     get,
     path = super::paths::basins::GET_CONFIG,
     tag = super::paths::basins::TAG,
     responses(
         (status = StatusCode::OK, body = v1t::config::BasinConfig),
+        (status = StatusCode::NOT_MODIFIED, description = "Configuration matches the `If-None-Match` ETag"),
         (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
         (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
         (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
@@ -143,10 +169,11 @@ pub struct GetConfigArgs {
 ))]
 pub async fn get_basin_config(
     State(backend): State<Backend>,
+    headers: HeaderMap,
     GetConfigArgs { basin }: GetConfigArgs,
-) -> Result<Json<v1t::config::BasinConfig>, ServiceError> {
-    let config = backend.get_basin_config(basin).await?;
-    Ok(Json(config.into()))
+) -> Result<Response, ServiceError> {
+    let config: v1t::config::BasinConfig = backend.get_basin_config(basin).await?.into();
+    Ok(json_with_etag(&headers, &config))
 }
 
 #[derive(FromRequest)]
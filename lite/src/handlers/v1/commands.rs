@@ -0,0 +1,73 @@
+use axum::extract::{FromRequest, Path, Query, State};
+use http::StatusCode;
+use s2_api::{data::Json, v1 as v1t};
+use s2_common::{basin::BasinName, http::extract::Header, stream::StreamName};
+
+use crate::{backend::Backend, handlers::v1::error::ServiceError};
+
+pub fn router() -> axum::Router<Backend> {
+    use axum::routing::get;
+    axum::Router::new().route(super::paths::streams::commands::LIST, get(list_commands))
+}
+
+#[derive(FromRequest)]
+#[from_request(rejection(ServiceError))]
+pub struct ListArgs {
+    #[from_request(via(Header))]
+    basin: BasinName,
+    #[from_request(via(Path))]
+    stream: StreamName,
+    #[from_request(via(Query))]
+    request: v1t::stream::ListCommandRecordsRequest,
+}
+
+/// List the `fence` and `trim` command records of a stream.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = super::paths::streams::commands::LIST,
+    tag = super::paths::streams::commands::TAG,
+    responses(
+        (status = StatusCode::OK, body = v1t::stream::ListCommandRecordsResponse),
+        (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
+        (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
+        (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
+        (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
+        (status = StatusCode::REQUEST_TIMEOUT, body = v1t::error::ErrorInfo),
+    ),
+    params(v1t::StreamNamePathSegment, v1t::stream::ListCommandRecordsRequest),
+    servers(
+        (url = super::paths::cloud_endpoints::BASIN, variables(
+            ("basin" = (
+                description = "Basin name",
+            ))
+        ), description = "Endpoint for the basin"),
+    )
+))]
+pub async fn list_commands(
+    State(backend): State<Backend>,
+    ListArgs {
+        basin,
+        stream,
+        request,
+    }: ListArgs,
+) -> Result<Json<v1t::stream::ListCommandRecordsResponse>, ServiceError> {
+    let page = backend
+        .list_stream_commands(
+            basin,
+            stream,
+            request.seq_num.unwrap_or_default(),
+            request.limit.map(Into::into).unwrap_or_default(),
+        )
+        .await?;
+    Ok(Json(v1t::stream::ListCommandRecordsResponse {
+        commands: page
+            .values
+            .into_iter()
+            .map(|(position, command)| v1t::stream::CommandRecordInfo {
+                position: position.into(),
+                command: command.into(),
+            })
+            .collect(),
+        has_more: page.has_more,
+    }))
+}
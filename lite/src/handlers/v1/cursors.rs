@@ -0,0 +1,105 @@
+use axum::extract::{FromRequest, Path, State};
+use http::StatusCode;
+use s2_api::{data::Json, v1 as v1t};
+use s2_common::{
+    basin::BasinName, consumer::ConsumerName, http::extract::Header, stream::StreamName,
+};
+
+use crate::{backend::Backend, handlers::v1::error::ServiceError};
+
+pub fn router() -> axum::Router<Backend> {
+    use axum::routing::{get, put};
+    axum::Router::new()
+        .route(super::paths::streams::cursors::GET, get(get_cursor))
+        .route(super::paths::streams::cursors::COMMIT, put(commit_cursor))
+}
+
+#[derive(FromRequest)]
+#[from_request(rejection(ServiceError))]
+pub struct GetArgs {
+    #[from_request(via(Header))]
+    basin: BasinName,
+    #[from_request(via(Path))]
+    stream_and_consumer: (StreamName, ConsumerName),
+}
+
+/// Get the committed cursor of a consumer.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = super::paths::streams::cursors::GET,
+    tag = super::paths::streams::cursors::TAG,
+    responses(
+        (status = StatusCode::OK, body = v1t::stream::StreamCursorResponse),
+        (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
+        (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
+        (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
+        (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
+        (status = StatusCode::REQUEST_TIMEOUT, body = v1t::error::ErrorInfo),
+    ),
+    params(v1t::StreamNamePathSegment, v1t::ConsumerNamePathSegment),
+    servers(
+        (url = super::paths::cloud_endpoints::BASIN, variables(
+            ("basin" = (
+                description = "Basin name",
+            ))
+        ), description = "Endpoint for the basin"),
+    )
+))]
+pub async fn get_cursor(
+    State(backend): State<Backend>,
+    GetArgs {
+        basin,
+        stream_and_consumer: (stream, consumer),
+    }: GetArgs,
+) -> Result<Json<v1t::stream::StreamCursorResponse>, ServiceError> {
+    let seq_num = backend.get_stream_cursor(basin, stream, consumer).await?;
+    Ok(Json(v1t::stream::StreamCursorResponse { seq_num }))
+}
+
+#[derive(FromRequest)]
+#[from_request(rejection(ServiceError))]
+pub struct CommitArgs {
+    #[from_request(via(Header))]
+    basin: BasinName,
+    #[from_request(via(Path))]
+    stream_and_consumer: (StreamName, ConsumerName),
+    #[from_request(via(Json))]
+    request: v1t::stream::CommitStreamCursorRequest,
+}
+
+/// Commit the cursor of a consumer.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    put,
+    path = super::paths::streams::cursors::COMMIT,
+    tag = super::paths::streams::cursors::TAG,
+    request_body = v1t::stream::CommitStreamCursorRequest,
+    responses(
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
+        (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
+        (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
+        (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
+        (status = StatusCode::REQUEST_TIMEOUT, body = v1t::error::ErrorInfo),
+    ),
+    params(v1t::StreamNamePathSegment, v1t::ConsumerNamePathSegment),
+    servers(
+        (url = super::paths::cloud_endpoints::BASIN, variables(
+            ("basin" = (
+                description = "Basin name",
+            ))
+        ), description = "Endpoint for the basin"),
+    )
+))]
+pub async fn commit_cursor(
+    State(backend): State<Backend>,
+    CommitArgs {
+        basin,
+        stream_and_consumer: (stream, consumer),
+        request,
+    }: CommitArgs,
+) -> Result<StatusCode, ServiceError> {
+    backend
+        .commit_stream_cursor(basin, stream, consumer, request.seq_num)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
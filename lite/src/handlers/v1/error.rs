@@ -1,5 +1,11 @@
+use std::time::Duration;
+
 use axum::{
-    extract::rejection::{PathRejection, QueryRejection},
+    extract::{
+        rejection::{BytesRejection, PathRejection, QueryRejection},
+        ws::rejection::WebSocketUpgradeRejection,
+    },
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header::RETRY_AFTER},
     response::{IntoResponse, Response},
 };
 use s2_api::{
@@ -14,10 +20,11 @@ use s2_common::{ValidationError, http::extract::HeaderRejection};
 use s2_storage::record::RecordDecryptionError;
 
 use crate::backend::error::{
-    AppendConditionFailedError, AppendError, CheckTailError, DeleteBasinError, DeleteStreamError,
-    GetBasinConfigError, GetStreamConfigError, ListBasinsError, ListStreamsError,
+    AppendConditionFailedError, AppendError, CheckTailError, CommitStreamCursorError,
+    DeleteBasinError, DeleteStreamError, GetBasinConfigError, GetStreamConfigError,
+    GetStreamCursorError, ListBasinsError, ListStreamCommandsError, ListStreamsError,
     ProvisionBasinError, ProvisionStreamError, ReadError, ReconfigureBasinError,
-    ReconfigureStreamError,
+    ReconfigureStreamError, UndeleteStreamError,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -29,14 +36,20 @@ pub enum ServiceError {
     #[error(transparent)]
     QueryRejection(#[from] QueryRejection),
     #[error(transparent)]
+    WebSocketUpgradeRejection(#[from] WebSocketUpgradeRejection),
+    #[error(transparent)]
     JsonRejection(#[from] JsonExtractionRejection),
     #[error(transparent)]
     ProtoRejection(#[from] ProtoRejection),
     #[error(transparent)]
+    BodyRejection(#[from] BytesRejection),
+    #[error(transparent)]
     AppendInputStream(#[from] AppendInputStreamError),
     #[error(transparent)]
     Validation(#[from] ValidationError),
     #[error(transparent)]
+    InvalidQuery(ValidationError),
+    #[error(transparent)]
     ListBasins(#[from] ListBasinsError),
     #[error(transparent)]
     ProvisionBasin(#[from] ProvisionBasinError),
@@ -55,8 +68,16 @@ pub enum ServiceError {
     #[error(transparent)]
     DeleteStream(#[from] DeleteStreamError),
     #[error(transparent)]
+    UndeleteStream(#[from] UndeleteStreamError),
+    #[error(transparent)]
     ReconfigureStream(#[from] ReconfigureStreamError),
     #[error(transparent)]
+    GetStreamCursor(#[from] GetStreamCursorError),
+    #[error(transparent)]
+    ListStreamCommands(#[from] ListStreamCommandsError),
+    #[error(transparent)]
+    CommitStreamCursor(#[from] CommitStreamCursorError),
+    #[error(transparent)]
     CheckTail(#[from] CheckTailError),
     #[error(transparent)]
     Append(#[from] AppendError),
@@ -72,6 +93,7 @@ impl From<AppendRequestRejection> for ServiceError {
             AppendRequestRejection::HeaderRejection(e) => ServiceError::from(e),
             AppendRequestRejection::JsonRejection(e) => ServiceError::from(e),
             AppendRequestRejection::ProtoRejection(e) => ServiceError::from(e),
+            AppendRequestRejection::BodyRejection(e) => ServiceError::from(e),
             AppendRequestRejection::Validation(e) => ServiceError::Validation(e),
         }
     }
@@ -83,8 +105,21 @@ impl ServiceError {
             ServiceError::HeaderRejection(e) => standard(ErrorCode::BadHeader, e.to_string()),
             ServiceError::PathRejection(e) => standard(ErrorCode::BadPath, e.body_text()),
             ServiceError::QueryRejection(e) => standard(ErrorCode::BadQuery, e.body_text()),
+            ServiceError::WebSocketUpgradeRejection(e) => {
+                standard(ErrorCode::BadHeader, e.body_text())
+            }
+            ServiceError::JsonRejection(e) if e.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+                payload_too_large()
+            }
             ServiceError::JsonRejection(e) => standard(ErrorCode::BadJson, e.body_text()),
+            ServiceError::ProtoRejection(ProtoRejection::BytesRejection(e))
+            | ServiceError::BodyRejection(e)
+                if e.status() == StatusCode::PAYLOAD_TOO_LARGE =>
+            {
+                payload_too_large()
+            }
             ServiceError::ProtoRejection(e) => standard(ErrorCode::BadProto, e.to_string()),
+            ServiceError::BodyRejection(e) => standard(ErrorCode::Invalid, e.body_text()),
             ServiceError::AppendInputStream(e) => match e {
                 AppendInputStreamError::FrameDecode(e) => {
                     standard(ErrorCode::BadFrame, e.to_string())
@@ -94,6 +129,7 @@ impl ServiceError {
                 }
             },
             ServiceError::Validation(e) => standard(ErrorCode::Invalid, e.to_string()),
+            ServiceError::InvalidQuery(e) => standard(ErrorCode::BadQuery, e.to_string()),
             ServiceError::ListBasins(e) => match e {
                 ListBasinsError::Storage(e) => standard(ErrorCode::Storage, e.to_string()),
             },
@@ -156,6 +192,12 @@ impl ServiceError {
                 ProvisionStreamError::StreamDeletionPending(e) => {
                     standard(ErrorCode::StreamDeletionPending, e.to_string())
                 }
+                ProvisionStreamError::StreamIdCollision(e) => {
+                    standard(ErrorCode::Other, e.to_string())
+                }
+                ProvisionStreamError::StreamQuotaExceeded(e) => {
+                    standard(ErrorCode::QuotaExhausted, e.to_string())
+                }
                 ProvisionStreamError::Validation(e) => standard(ErrorCode::Invalid, e.to_string()),
             },
             ServiceError::GetStreamConfig(e) => match e {
@@ -184,6 +226,24 @@ impl ServiceError {
                     standard(ErrorCode::StreamNotFound, e.to_string())
                 }
             },
+            ServiceError::UndeleteStream(e) => match e {
+                UndeleteStreamError::Storage(e) => standard(ErrorCode::Storage, e.to_string()),
+                UndeleteStreamError::TransactionConflict(e) => {
+                    standard(ErrorCode::TransactionConflict, e.to_string())
+                }
+                UndeleteStreamError::BasinNotFound(e) => {
+                    standard(ErrorCode::BasinNotFound, e.to_string())
+                }
+                UndeleteStreamError::BasinDeletionPending(e) => {
+                    standard(ErrorCode::BasinDeletionPending, e.to_string())
+                }
+                UndeleteStreamError::StreamNotFound(e) => {
+                    standard(ErrorCode::StreamNotFound, e.to_string())
+                }
+                UndeleteStreamError::StreamDeletionPending(e) => {
+                    standard(ErrorCode::StreamDeletionPending, e.to_string())
+                }
+            },
             ServiceError::ReconfigureStream(e) => match e {
                 ReconfigureStreamError::Storage(e) => standard(ErrorCode::Storage, e.to_string()),
                 ReconfigureStreamError::TransactionConflict(e) => {
@@ -205,6 +265,36 @@ impl ServiceError {
                     standard(ErrorCode::Invalid, e.to_string())
                 }
             },
+            ServiceError::GetStreamCursor(e) => match e {
+                GetStreamCursorError::Storage(e) => standard(ErrorCode::Storage, e.to_string()),
+                GetStreamCursorError::StreamNotFound(e) => {
+                    standard(ErrorCode::StreamNotFound, e.to_string())
+                }
+                GetStreamCursorError::StreamDeletionPending(e) => {
+                    standard(ErrorCode::StreamDeletionPending, e.to_string())
+                }
+            },
+            ServiceError::ListStreamCommands(e) => match e {
+                ListStreamCommandsError::Storage(e) => standard(ErrorCode::Storage, e.to_string()),
+                ListStreamCommandsError::StreamNotFound(e) => {
+                    standard(ErrorCode::StreamNotFound, e.to_string())
+                }
+                ListStreamCommandsError::StreamDeletionPending(e) => {
+                    standard(ErrorCode::StreamDeletionPending, e.to_string())
+                }
+            },
+            ServiceError::CommitStreamCursor(e) => match e {
+                CommitStreamCursorError::Storage(e) => standard(ErrorCode::Storage, e.to_string()),
+                CommitStreamCursorError::TransactionConflict(e) => {
+                    standard(ErrorCode::TransactionConflict, e.to_string())
+                }
+                CommitStreamCursorError::StreamNotFound(e) => {
+                    standard(ErrorCode::StreamNotFound, e.to_string())
+                }
+                CommitStreamCursorError::StreamDeletionPending(e) => {
+                    standard(ErrorCode::StreamDeletionPending, e.to_string())
+                }
+            },
             ServiceError::CheckTail(e) => match e {
                 CheckTailError::Storage(e) => standard(ErrorCode::Storage, e.to_string()),
                 CheckTailError::TransactionConflict(e) => {
@@ -225,6 +315,9 @@ impl ServiceError {
                 CheckTailError::StreamDeletionPending(e) => {
                     standard(ErrorCode::StreamDeletionPending, e.to_string())
                 }
+                CheckTailError::StreamQuotaExceeded(e) => {
+                    standard(ErrorCode::QuotaExhausted, e.to_string())
+                }
             },
             ServiceError::Append(e) => match e {
                 AppendError::Storage(e) => standard(ErrorCode::Storage, e.to_string()),
@@ -242,6 +335,7 @@ impl ServiceError {
                     // AppendType::Regular may have become durable prior to drop.
                     standard(ErrorCode::Other, e.to_string())
                 }
+                AppendError::ShuttingDown(e) => standard(ErrorCode::Unavailable, e.to_string()),
                 AppendError::BasinNotFound(e) => standard(ErrorCode::BasinNotFound, e.to_string()),
                 AppendError::StreamNotFound(e) => {
                     standard(ErrorCode::StreamNotFound, e.to_string())
@@ -252,6 +346,9 @@ impl ServiceError {
                 AppendError::StreamDeletionPending(e) => {
                     standard(ErrorCode::StreamDeletionPending, e.to_string())
                 }
+                AppendError::StreamQuotaExceeded(e) => {
+                    standard(ErrorCode::QuotaExhausted, e.to_string())
+                }
                 AppendError::ConditionFailed(e) => ErrorResponse::AppendConditionFailed(match e {
                     AppendConditionFailedError::FencingTokenMismatch { actual, .. } => {
                         v1t::stream::AppendConditionFailed::FencingTokenMismatch(actual.clone())
@@ -261,7 +358,12 @@ impl ServiceError {
                     } => v1t::stream::AppendConditionFailed::SeqNumMismatch(*assigned_seq_num),
                 }),
                 AppendError::TimestampMissing(e) => standard(ErrorCode::Invalid, e.to_string()),
+                AppendError::TimestampTooFarInFuture(e) => {
+                    standard(ErrorCode::Invalid, e.to_string())
+                }
                 AppendError::MaxSeqNum(e) => standard(ErrorCode::Invalid, e.to_string()),
+                AppendError::MinRetainedRecords(e) => standard(ErrorCode::Invalid, e.to_string()),
+                AppendError::RateLimited(e) => standard(ErrorCode::RateLimited, e.to_string()),
             },
             ServiceError::Read(e) => match e {
                 ReadError::Storage(e) => standard(ErrorCode::Storage, e.to_string()),
@@ -293,9 +395,13 @@ impl ServiceError {
                 ReadError::StreamDeletionPending(e) => {
                     standard(ErrorCode::StreamDeletionPending, e.to_string())
                 }
+                ReadError::StreamQuotaExceeded(e) => {
+                    standard(ErrorCode::QuotaExhausted, e.to_string())
+                }
                 ReadError::Unwritten(tail) => ErrorResponse::Unwritten(v1t::stream::TailResponse {
                     tail: tail.0.into(),
                 }),
+                ReadError::Trimmed(e) => standard(ErrorCode::Trimmed, e.to_string()),
             },
             ServiceError::NotImplemented => {
                 standard(ErrorCode::NotImplemented, "Not implemented".to_string())
@@ -306,10 +412,38 @@ impl ServiceError {
 
 impl IntoResponse for ServiceError {
     fn into_response(self) -> Response {
-        self.to_response().into_response()
+        let error = self.to_response();
+        let retry_after = match (&self, &error) {
+            (ServiceError::Append(AppendError::RateLimited(e)), _) => Some(e.retry_after()),
+            (_, ErrorResponse::Standard(e)) if e.status == StatusCode::SERVICE_UNAVAILABLE => {
+                Some(UNAVAILABLE_RETRY_AFTER)
+            }
+            _ => None,
+        };
+        let mut response = error.into_response();
+        if let Some(retry_after) = retry_after {
+            set_retry_after(response.headers_mut(), retry_after);
+        }
+        response
     }
 }
 
+/// Suggested backoff for clients when the server cannot currently take requests.
+const UNAVAILABLE_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+static RETRY_AFTER_MS_HEADER: HeaderName = HeaderName::from_static("retry-after-ms");
+
+/// Advertise when to retry, in whole seconds for standard clients, and in milliseconds for the
+/// S2 SDKs.
+fn set_retry_after(headers: &mut HeaderMap, retry_after: Duration) {
+    let millis = retry_after.as_millis().max(1);
+    headers.insert(RETRY_AFTER, HeaderValue::from(millis.div_ceil(1000) as u64));
+    headers.insert(
+        RETRY_AFTER_MS_HEADER.clone(),
+        HeaderValue::from(millis as u64),
+    );
+}
+
 impl From<ServiceError> for s2s::TerminalMessage {
     fn from(value: ServiceError) -> Self {
         let (status, body) = value.to_response().to_parts();
@@ -320,6 +454,13 @@ impl From<ServiceError> for s2s::TerminalMessage {
     }
 }
 
+fn payload_too_large() -> ErrorResponse {
+    standard(
+        ErrorCode::PayloadTooLarge,
+        "Request body exceeds the size limit; append batches are limited to 1000 records and 1 MiB",
+    )
+}
+
 fn standard(code: ErrorCode, message: impl Into<String>) -> ErrorResponse {
     ErrorResponse::Standard(StandardError {
         status: code.status(),
@@ -329,3 +470,27 @@ fn standard(code: ErrorCode, message: impl Into<String>) -> ErrorResponse {
         },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::error::AppendRateLimitedError;
+
+    #[test]
+    fn rate_limited_response_advertises_retry_after() {
+        let response = ServiceError::Append(AppendError::RateLimited(AppendRateLimitedError::Ops(
+            1,
+            Duration::from_millis(1500),
+        )))
+        .into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "2");
+        assert_eq!(response.headers()[&RETRY_AFTER_MS_HEADER], "1500");
+    }
+
+    #[test]
+    fn other_errors_do_not_advertise_retry_after() {
+        let response = ServiceError::NotImplemented.into_response();
+        assert!(!response.headers().contains_key(RETRY_AFTER));
+    }
+}
@@ -0,0 +1,91 @@
+//! Conditional GET support for config endpoints.
+
+use axum::{
+    body::Body,
+    response::{IntoResponse, Response},
+};
+use http::{
+    HeaderMap, HeaderValue, StatusCode,
+    header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+};
+use serde::Serialize;
+
+/// Respond with `value` as JSON, tagged with an `ETag` derived from its serialization.
+///
+/// If the request's `If-None-Match` already names that tag, the body is omitted and the response
+/// is 304 Not Modified.
+pub fn json_with_etag<T: Serialize>(request_headers: &HeaderMap, value: &T) -> Response {
+    let body = serde_json::to_vec(value).expect("config serializes to JSON");
+    let etag = etag(&body);
+    if if_none_match(request_headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response();
+    }
+    (
+        [
+            (CONTENT_TYPE, HeaderValue::from_static("application/json")),
+            (ETAG, etag),
+        ],
+        Body::from(body),
+    )
+        .into_response()
+}
+
+fn etag(body: &[u8]) -> HeaderValue {
+    let hash = blake3::hash(body);
+    let hex = hash.to_hex();
+    HeaderValue::from_str(&format!("\"{}\"", &hex[..32])).expect("hex is a valid header value")
+}
+
+/// Whether any `If-None-Match` header matches `etag`, using weak comparison as specified for
+/// GET requests.
+fn if_none_match(request_headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let etag = etag.as_bytes();
+    request_headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| {
+            candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate).as_bytes() == etag
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_headers(if_none_match: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_str(if_none_match).unwrap());
+        headers
+    }
+
+    #[test]
+    fn matching_etag_is_not_modified() {
+        let value = serde_json::json!({"retention_policy": {"age": 3600}});
+        let response = json_with_etag(&HeaderMap::new(), &value);
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[ETAG].to_str().unwrap().to_owned();
+
+        for header in [
+            etag.clone(),
+            format!("W/{etag}"),
+            format!("\"other\", {etag}"),
+        ] {
+            let response = json_with_etag(&request_headers(&header), &value);
+            assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "{header}");
+            assert_eq!(response.headers()[ETAG], etag.as_str());
+        }
+    }
+
+    #[test]
+    fn changed_value_is_returned_in_full() {
+        let before = json_with_etag(&HeaderMap::new(), &serde_json::json!({"age": 1}));
+        let etag = before.headers()[ETAG].to_str().unwrap();
+
+        let after = json_with_etag(&request_headers(etag), &serde_json::json!({"age": 2}));
+        assert_eq!(after.status(), StatusCode::OK);
+        assert_ne!(after.headers()[ETAG], etag);
+    }
+}
@@ -1,6 +1,11 @@
 use axum::extract::{FromRequest, Path, Query, State};
 use s2_api::{data::Json, v1 as v1t};
-use s2_common::{basin::BasinName, stream::StreamName};
+use s2_common::{
+    basin::{BasinName, BasinNameStartAfter, ListBasinsRequest},
+    metrics::{BasinMetricsRequest, MetricsResponse, sum_metrics},
+    resources::{ListLimit, Page},
+    stream::StreamName,
+};
 
 use crate::{backend::Backend, handlers::v1::error::ServiceError};
 
@@ -8,6 +13,10 @@ pub fn router() -> axum::Router<Backend> {
     use axum::routing::get;
     axum::Router::new()
         .route(super::paths::metrics::ACCOUNT, get(account_metrics))
+        .route(
+            super::paths::metrics::BASINS_AGGREGATE,
+            get(basins_aggregate),
+        )
         .route(super::paths::metrics::BASIN, get(basin_metrics))
         .route(super::paths::metrics::STREAM, get(stream_metrics))
 }
@@ -43,9 +52,9 @@ pub async fn account_metrics(
 #[from_request(rejection(ServiceError))]
 pub struct BasinArgs {
     #[from_request(via(Path))]
-    _basin: BasinName,
+    basin: BasinName,
     #[from_request(via(Query))]
-    _request: v1t::metrics::BasinMetricSetRequest,
+    request: v1t::metrics::BasinMetricSetRequest,
 }
 
 /// Basin-level metrics.
@@ -62,12 +71,75 @@ pub struct BasinArgs {
     params(v1t::metrics::BasinMetricSetRequest, v1t::BasinNamePathSegment),
 ))]
 pub async fn basin_metrics(
-    State(_backend): State<Backend>,
-    BasinArgs { .. }: BasinArgs,
+    State(backend): State<Backend>,
+    BasinArgs { basin, request }: BasinArgs,
 ) -> Result<Json<v1t::metrics::MetricSetResponse>, ServiceError> {
+    let response = gather_basin_metrics(&backend, &basin, request.into()).await?;
+    Ok(Json(response.into()))
+}
+
+async fn gather_basin_metrics(
+    _backend: &Backend,
+    _basin: &BasinName,
+    _request: BasinMetricsRequest,
+) -> Result<MetricsResponse, ServiceError> {
     Err(ServiceError::NotImplemented)
 }
 
+#[derive(FromRequest)]
+#[from_request(rejection(ServiceError))]
+pub struct BasinsAggregateArgs {
+    #[from_request(via(Query))]
+    request: v1t::metrics::BasinsAggregateMetricSetRequest,
+}
+
+/// Basin-level metrics summed across all basins matching a prefix.
+///
+/// Scalar and accumulation metrics are summed; gauge and label metrics are omitted.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = super::paths::metrics::BASINS_AGGREGATE,
+    tag = super::paths::metrics::TAG,
+    responses(
+        (status = StatusCode::OK, body = v1t::metrics::MetricSetResponse),
+        (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
+        (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
+        (status = StatusCode::REQUEST_TIMEOUT, body = v1t::error::ErrorInfo),
+    ),
+    params(v1t::metrics::BasinsAggregateMetricSetRequest),
+))]
+pub async fn basins_aggregate(
+    State(backend): State<Backend>,
+    BasinsAggregateArgs { request }: BasinsAggregateArgs,
+) -> Result<Json<v1t::metrics::MetricSetResponse>, ServiceError> {
+    let (prefix, request): (_, BasinMetricsRequest) = request.into();
+    let mut sets = Vec::new();
+    let mut start_after = BasinNameStartAfter::default();
+    loop {
+        let Page { values, has_more } = backend
+            .list_basins(ListBasinsRequest {
+                prefix: prefix.clone(),
+                start_after,
+                limit: ListLimit::MAX,
+            })
+            .await?;
+        for info in values.iter().filter(|info| info.deleted_at.is_none()) {
+            let response = gather_basin_metrics(&backend, &info.name, request.clone()).await?;
+            sets.push(response.values);
+        }
+        match values.last() {
+            Some(last) if has_more => start_after = last.name.clone().into(),
+            _ => break,
+        }
+    }
+    Ok(Json(
+        MetricsResponse {
+            values: sum_metrics(sets),
+        }
+        .into(),
+    ))
+}
+
 #[derive(FromRequest)]
 #[from_request(rejection(ServiceError))]
 pub struct StreamArgs {
@@ -4,15 +4,18 @@ use crate::backend::Backend;
 
 pub mod access_tokens;
 pub mod basins;
+pub mod commands;
+pub mod cursors;
 mod error;
+mod etag;
 pub mod locations;
 pub mod metrics;
+#[cfg(feature = "utoipa")]
+pub mod openapi;
 pub mod paths;
 pub mod records;
 pub mod streams;
 
-const MAX_UNARY_READ_WAIT: std::time::Duration = std::time::Duration::from_secs(60);
-
 pub fn router() -> axum::Router<Backend> {
     let compress_when = {
         use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
@@ -21,15 +24,19 @@ pub fn router() -> axum::Router<Backend> {
             .and(NotForContentType::const_new("s2s/proto"))
     };
 
-    axum::Router::new()
+    let router = axum::Router::new()
         .merge(basins::router())
         .merge(streams::router())
         .merge(records::router())
+        .merge(cursors::router())
+        .merge(commands::router())
         .merge(locations::router())
         .merge(access_tokens::router())
-        .merge(metrics::router())
-        .route_layer((
-            CompressionLayer::new().compress_when(compress_when),
-            RequestDecompressionLayer::new(),
-        ))
+        .merge(metrics::router());
+    #[cfg(feature = "utoipa")]
+    let router = router.merge(openapi::router());
+    router.route_layer((
+        CompressionLayer::new().compress_when(compress_when),
+        RequestDecompressionLayer::new(),
+    ))
 }
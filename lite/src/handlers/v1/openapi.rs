@@ -0,0 +1,229 @@
+use std::sync::OnceLock;
+
+use s2_api::{
+    data::{Format, Json},
+    v1::metrics::{AccountMetricSet, BasinMetricSet, StreamMetricSet},
+};
+use s2_common::resources::RequestToken;
+use utoipa::{
+    Modify, OpenApi,
+    openapi::{
+        path::Operation,
+        security::{Http, HttpAuthScheme, SecurityScheme},
+    },
+};
+
+use super::{
+    access_tokens::{
+        __path_issue_access_token, __path_list_access_tokens, __path_revoke_access_token,
+    },
+    basins::{
+        __path_create_basin, __path_delete_basin, __path_ensure_basin, __path_get_basin_config,
+        __path_list_basins, __path_reconfigure_basin,
+    },
+    commands::__path_list_commands,
+    cursors::{__path_commit_cursor, __path_get_cursor},
+    locations::{__path_get_default_location, __path_list_locations, __path_set_default_location},
+    metrics::{
+        __path_account_metrics, __path_basin_metrics, __path_basins_aggregate,
+        __path_stream_metrics,
+    },
+    paths::{self, cloud_endpoints},
+    records::{__path_append, __path_append_raw, __path_check_tail, __path_follow, __path_read},
+    streams::{
+        __path_create_stream, __path_delete_stream, __path_ensure_stream, __path_get_stream_config,
+        __path_list_streams, __path_reconfigure_stream, __path_undelete_stream,
+        __path_watch_stream_config,
+    },
+};
+use crate::backend::Backend;
+
+pub const PATH: &str = "/openapi.json";
+
+pub fn router() -> axum::Router<Backend> {
+    axum::Router::new().route(PATH, axum::routing::get(serve))
+}
+
+/// The OpenAPI document for the v1 API, assembled on first use.
+pub fn openapi() -> &'static utoipa::openapi::OpenApi {
+    static OPENAPI: OnceLock<utoipa::openapi::OpenApi> = OnceLock::new();
+    OPENAPI.get_or_init(ApiDoc::openapi)
+}
+
+async fn serve() -> Json<&'static utoipa::openapi::OpenApi> {
+    Json(openapi())
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "S2, the durable streams API",
+        description = "Streams as a cloud storage primitive.",
+        version = "1.0.0",
+        license(name = "MIT"),
+        terms_of_service = "https://s2.dev/terms",
+        contact(email = "support@s2.dev")
+    ),
+    servers(
+        (url = cloud_endpoints::ACCOUNT)
+    ),
+    modifiers(&SecurityAddon, &PathLevelServersAddon),
+    security(("access_token" = [])),
+    tags(
+        (name = paths::metrics::TAG, description = paths::metrics::DESCRIPTION),
+        (name = paths::basins::TAG, description = paths::basins::DESCRIPTION),
+        (name = paths::access_tokens::TAG, description = paths::access_tokens::DESCRIPTION),
+        (name = paths::locations::TAG, description = paths::locations::DESCRIPTION),
+        (name = paths::streams::TAG, description = paths::streams::DESCRIPTION),
+        (name = paths::streams::records::TAG, description = paths::streams::records::DESCRIPTION),
+        (name = paths::streams::cursors::TAG, description = paths::streams::cursors::DESCRIPTION),
+        (name = paths::streams::commands::TAG, description = paths::streams::commands::DESCRIPTION),
+    ),
+    paths(
+        // Record ops
+        append,
+        append_raw,
+        read,
+        follow,
+        check_tail,
+        // Cursor ops
+        get_cursor,
+        commit_cursor,
+        // Command ops
+        list_commands,
+        // Stream ops
+        list_streams,
+        create_stream,
+        get_stream_config,
+        ensure_stream,
+        delete_stream,
+        undelete_stream,
+        reconfigure_stream,
+        watch_stream_config,
+        // Basin ops
+        list_basins,
+        create_basin,
+        get_basin_config,
+        ensure_basin,
+        delete_basin,
+        reconfigure_basin,
+        // Access token ops
+        list_access_tokens,
+        issue_access_token,
+        revoke_access_token,
+        // Location ops
+        list_locations,
+        get_default_location,
+        set_default_location,
+        // Metrics ops
+        account_metrics,
+        basin_metrics,
+        basins_aggregate,
+        stream_metrics,
+    ),
+    components(schemas(Format, RequestToken, AccountMetricSet, BasinMetricSet, StreamMetricSet))
+)]
+struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "access_token",
+                SecurityScheme::Http(
+                    Http::builder()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .description(Some(concat!(
+                            "Bearer authentication header of the form `Bearer <token>`, ",
+                            "where `<token>` is your access token."
+                        )))
+                        .build(),
+                ),
+            )
+        }
+    }
+}
+
+struct PathLevelServersAddon;
+
+impl PathLevelServersAddon {
+    fn get_operations_mut(path_item: &mut utoipa::openapi::PathItem) -> Vec<&mut Operation> {
+        [
+            path_item.get.as_mut(),
+            path_item.put.as_mut(),
+            path_item.post.as_mut(),
+            path_item.delete.as_mut(),
+            path_item.options.as_mut(),
+            path_item.head.as_mut(),
+            path_item.patch.as_mut(),
+            path_item.trace.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl Modify for PathLevelServersAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for path_item in openapi.paths.paths.values_mut() {
+            let operations = Self::get_operations_mut(path_item);
+
+            if operations.is_empty() {
+                continue;
+            }
+
+            let all_servers: Vec<_> = operations.iter().map(|op| op.servers.as_ref()).collect();
+
+            let first_servers = all_servers.first().copied().flatten();
+            let all_same = all_servers
+                .iter()
+                .all(|s| s.as_ref() == first_servers.as_ref());
+
+            if all_same && let Some(servers) = first_servers.cloned() {
+                path_item.servers = Some(servers);
+
+                for op in Self::get_operations_mut(path_item) {
+                    op.servers = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn openapi_includes_all_paths() {
+        let doc = openapi();
+        for path in [
+            paths::basins::LIST,
+            paths::basins::ENSURE,
+            paths::metrics::STREAM,
+            paths::metrics::BASINS_AGGREGATE,
+            paths::access_tokens::REVOKE,
+            paths::locations::DEFAULT,
+            paths::streams::LIST,
+            paths::streams::ENSURE,
+            paths::streams::WATCH_CONFIG,
+            paths::streams::UNDELETE,
+            paths::streams::records::READ,
+            paths::streams::records::APPEND_RAW,
+            paths::streams::records::FOLLOW,
+            paths::streams::records::CHECK_TAIL,
+            paths::streams::cursors::GET,
+            paths::streams::commands::LIST,
+        ] {
+            assert!(doc.paths.paths.contains_key(path), "missing {path}");
+        }
+        assert!(
+            doc.components
+                .as_ref()
+                .is_some_and(|c| c.security_schemes.contains_key("access_token"))
+        );
+    }
+}
@@ -17,6 +17,7 @@ pub mod metrics {
     pub const ACCOUNT: &str = "/metrics";
     pub const BASIN: &str = "/metrics/{basin}";
     pub const STREAM: &str = "/metrics/{basin}/{stream}";
+    pub const BASINS_AGGREGATE: &str = "/metrics/basins";
 }
 
 pub mod access_tokens {
@@ -44,8 +45,10 @@ pub mod streams {
     pub const CREATE: &str = "/streams";
     pub const ENSURE: &str = "/streams/{stream}";
     pub const DELETE: &str = "/streams/{stream}";
+    pub const UNDELETE: &str = "/streams/{stream}/undelete";
     pub const GET_CONFIG: &str = "/streams/{stream}";
     pub const RECONFIGURE: &str = "/streams/{stream}";
+    pub const WATCH_CONFIG: &str = "/streams/{stream}/config/watch";
 
     pub mod records {
         pub const TAG: &str = "records";
@@ -53,7 +56,24 @@ pub mod streams {
 
         pub const CHECK_TAIL: &str = "/streams/{stream}/records/tail";
         pub const READ: &str = "/streams/{stream}/records";
+        pub const FOLLOW: &str = "/streams/{stream}/records/follow";
         pub const APPEND: &str = "/streams/{stream}/records";
+        pub const APPEND_RAW: &str = "/streams/{stream}/records/raw";
+    }
+
+    pub mod cursors {
+        pub const TAG: &str = "cursors";
+        pub const DESCRIPTION: &str = "Manage consumer cursors";
+
+        pub const GET: &str = "/streams/{stream}/cursors/{consumer}";
+        pub const COMMIT: &str = "/streams/{stream}/cursors/{consumer}";
+    }
+
+    pub mod commands {
+        pub const TAG: &str = "commands";
+        pub const DESCRIPTION: &str = "Audit command records";
+
+        pub const LIST: &str = "/streams/{stream}/commands";
     }
 }
 
@@ -1,14 +1,17 @@
 use std::time::Duration;
 
 use axum::{
-    body::Body,
-    extract::{FromRequest, Path, Query, State},
+    body::{Body, Bytes},
+    extract::{
+        FromRequest, Path, Query, State,
+        ws::{CloseFrame, Message, Utf8Bytes, WebSocket, WebSocketUpgrade, close_code},
+    },
     response::{IntoResponse, Response},
 };
 use futures::{Stream, StreamExt, TryStreamExt};
 use http::{HeaderValue, StatusCode, header};
 use s2_api::{
-    data::{Json, Proto},
+    data::{Format, Json, Proto},
     mime::JsonOrProto,
     v1::{self as v1t, stream::s2s},
 };
@@ -25,6 +28,7 @@ use s2_common::{
 use crate::{
     backend::{Backend, error::ReadError},
     handlers::v1::error::ServiceError,
+    metrics::{ActiveReaderGuard, ReaderType},
 };
 
 pub fn router() -> axum::Router<Backend> {
@@ -32,7 +36,9 @@ pub fn router() -> axum::Router<Backend> {
     axum::Router::new()
         .route(super::paths::streams::records::CHECK_TAIL, get(check_tail))
         .route(super::paths::streams::records::READ, get(read))
+        .route(super::paths::streams::records::FOLLOW, get(follow))
         .route(super::paths::streams::records::APPEND, post(append))
+        .route(super::paths::streams::records::APPEND_RAW, post(append_raw))
 }
 
 fn validate_read_until(start: ReadStart, end: ReadEnd) -> Result<(), ServiceError> {
@@ -65,7 +71,7 @@ fn apply_last_event_id(
 }
 
 enum ReadMode {
-    Unary,
+    Unary { max_wait: Duration },
     Streaming,
 }
 
@@ -75,9 +81,9 @@ fn prepare_read(
     mode: ReadMode,
 ) -> Result<(ReadStart, ReadEnd), ServiceError> {
     let mut end: ReadEnd = end.into();
-    if matches!(mode, ReadMode::Unary) {
+    if let ReadMode::Unary { max_wait } = mode {
         end.limit = ReadLimit::CountOrBytes(end.limit.into_allowance(RECORD_BATCH_MAX));
-        end.wait = end.wait.map(|d| d.min(super::MAX_UNARY_READ_WAIT));
+        end.wait = end.wait.map(|d| d.min(max_wait));
     }
     validate_read_until(start, end)?;
     Ok((start, end))
@@ -137,6 +143,8 @@ pub struct ReadArgs {
     start: v1t::stream::ReadStart,
     #[from_request(via(Query))]
     end: v1t::stream::ReadEnd,
+    #[from_request(via(Query))]
+    options: v1t::stream::ReadOptions,
     request: v1t::stream::ReadRequest,
 }
 
@@ -147,10 +155,11 @@ pub struct ReadArgs {
     tag = super::paths::streams::records::TAG,
     responses(
         (status = StatusCode::OK, content(
-            (v1t::stream::ReadBatch = "application/json"),
+            (v1t::stream::UnaryReadResponse = "application/json"),
             (v1t::stream::sse::ReadEvent = "text/event-stream"),
-        )),
+        ), description = "Records, or only their positions as a `PositionsBatch` with `positions_only`"),
         (status = StatusCode::RANGE_NOT_SATISFIABLE, body = v1t::stream::TailResponse),
+        (status = StatusCode::GONE, body = v1t::error::ErrorInfo),
         (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
         (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
         (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
@@ -163,6 +172,7 @@ pub struct ReadArgs {
         s2_api::data::S2EncryptionKeyHeader,
         v1t::stream::ReadStart,
         v1t::stream::ReadEnd,
+        v1t::stream::ReadOptions,
     ),
     servers(
         (url = super::paths::cloud_endpoints::BASIN, variables(
@@ -179,26 +189,61 @@ pub async fn read(
         stream,
         start,
         end,
+        options,
         request,
     }: ReadArgs,
 ) -> Result<Response, ServiceError> {
     let start: ReadStart = start.try_into()?;
+    let inline_json = options.inline_json.unwrap_or(false);
+    let skip_undecodable_records =
+        options.on_decode_error == Some(v1t::stream::OnDecodeError::Skip);
+    let positions_only = options.positions_only.unwrap_or(false);
+    if positions_only
+        && !matches!(
+            request,
+            v1t::stream::ReadRequest::Unary {
+                response_mime: JsonOrProto::Json,
+                ..
+            }
+        )
+    {
+        return Err(ServiceError::Validation(ValidationError(
+            "`positions_only` is only supported for unary reads with a JSON response".to_owned(),
+        )));
+    }
     match request {
         v1t::stream::ReadRequest::Unary {
             encryption_key,
             format,
             response_mime,
         } => {
-            let (start, end) = prepare_read(start, end, ReadMode::Unary)?;
+            let (start, end) = prepare_read(
+                start,
+                end,
+                ReadMode::Unary {
+                    max_wait: backend.max_unary_read_wait(),
+                },
+            )?;
+            if positions_only {
+                let batch = backend
+                    .open_for_read_positions(&basin, &stream)
+                    .await?
+                    .read_positions(start, end)
+                    .await?;
+                return Ok(Json(v1t::stream::PositionsBatch::from(batch)).into_response());
+            }
             let session = backend
                 .open_for_read(&basin, &stream, encryption_key)
                 .await?
+                .skip_undecodable_records(skip_undecodable_records)
                 .read(start, end)
                 .await?;
             let batch = merge_read_session(session, end.wait).await?;
             match response_mime {
                 JsonOrProto::Json => Ok(Json(v1t::stream::json::serialize_read_batch(
-                    format, &batch,
+                    format,
+                    inline_json,
+                    &batch,
                 ))
                 .into_response()),
                 JsonOrProto::Proto => {
@@ -217,9 +262,12 @@ pub async fn read(
             let session = backend
                 .open_for_read(&basin, &stream, encryption_key)
                 .await?
+                .skip_undecodable_records(skip_undecodable_records)
                 .read(start, end)
                 .await?;
+            let active = ActiveReaderGuard::new(ReaderType::Sse);
             let events = async_stream::stream! {
+                let _active = active;
                 let mut processed = CountOrBytes::ZERO;
                 tokio::pin!(session);
                 let mut errored = false;
@@ -239,7 +287,7 @@ pub async fn read(
                                 count: processed.count,
                                 bytes: processed.bytes,
                             };
-                            yield v1t::stream::sse::read_batch_event(format, &batch, id);
+                            yield v1t::stream::sse::read_batch_event(format, inline_json, &batch, id);
                         },
                         Err(err) => {
                             let (_, body) = ServiceError::from(err).to_response().to_parts();
@@ -271,6 +319,7 @@ pub async fn read(
             let s2s_stream = backend
                 .open_for_read(&basin, &stream, encryption_key)
                 .await?
+                .skip_undecodable_records(skip_undecodable_records)
                 .read(start, end)
                 .await?
                 .map_ok(|msg| match msg {
@@ -281,6 +330,7 @@ pub async fn read(
                     ReadSessionOutput::Batch(batch) => v1t::stream::proto::ReadBatch::from(batch),
                 })
                 .map_err(ServiceError::from);
+            let s2s_stream = with_active_reader(ReaderType::S2s, s2s_stream);
             let response_stream =
                 s2s::FramedMessageStream::<_>::new(response_compression, Box::pin(s2s_stream));
             Ok(Response::builder()
@@ -294,6 +344,149 @@ pub async fn read(
     }
 }
 
+#[derive(FromRequest)]
+#[from_request(rejection(ServiceError))]
+pub struct FollowArgs {
+    #[from_request(via(Header))]
+    basin: BasinName,
+    #[from_request(via(Path))]
+    stream: StreamName,
+    #[from_request(via(Query))]
+    start: v1t::stream::ReadStart,
+    #[from_request(via(Query))]
+    end: v1t::stream::ReadEnd,
+    #[from_request(via(Query))]
+    options: v1t::stream::ReadOptions,
+    request: v1t::stream::FollowRequest,
+    upgrade: WebSocketUpgrade,
+}
+
+/// Follow records over a WebSocket.
+///
+/// Each record is sent as a text frame containing a JSON `SequencedRecord`. Pings are sent while
+/// waiting for new records. The socket is closed with a normal close code once the read ends, or
+/// with an error close code after a final text frame carrying the error.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = super::paths::streams::records::FOLLOW,
+    tag = super::paths::streams::records::TAG,
+    responses(
+        (status = StatusCode::SWITCHING_PROTOCOLS, description = "WebSocket of `SequencedRecord` JSON frames"),
+        (status = StatusCode::RANGE_NOT_SATISFIABLE, body = v1t::stream::TailResponse),
+        (status = StatusCode::GONE, body = v1t::error::ErrorInfo),
+        (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
+        (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
+        (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
+        (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
+        (status = StatusCode::REQUEST_TIMEOUT, body = v1t::error::ErrorInfo),
+    ),
+    params(
+        v1t::StreamNamePathSegment,
+        s2_api::data::S2FormatHeader,
+        s2_api::data::S2EncryptionKeyHeader,
+        v1t::stream::ReadStart,
+        v1t::stream::ReadEnd,
+        v1t::stream::ReadOptions,
+    ),
+    servers(
+        (url = super::paths::cloud_endpoints::BASIN, variables(
+            ("basin" = (
+                description = "Basin name",
+            ))
+        ), description = "Endpoint for the basin"),
+    )
+))]
+pub async fn follow(
+    State(backend): State<Backend>,
+    FollowArgs {
+        basin,
+        stream,
+        start,
+        end,
+        options,
+        request:
+            v1t::stream::FollowRequest {
+                encryption_key,
+                format,
+            },
+        upgrade,
+    }: FollowArgs,
+) -> Result<Response, ServiceError> {
+    let start: ReadStart = start.try_into()?;
+    let inline_json = options.inline_json.unwrap_or(false);
+    let skip_undecodable_records =
+        options.on_decode_error == Some(v1t::stream::OnDecodeError::Skip);
+    let (start, end) = prepare_read(start, end, ReadMode::Streaming)?;
+    let session = backend
+        .open_for_read(&basin, &stream, encryption_key)
+        .await?
+        .skip_undecodable_records(skip_undecodable_records)
+        .read(start, end)
+        .await?;
+    Ok(upgrade.on_upgrade(move |socket| follow_session(socket, session, format, inline_json)))
+}
+
+async fn follow_session(
+    mut socket: WebSocket,
+    session: impl Stream<Item = Result<ReadSessionOutput, ReadError>>,
+    format: Format,
+    inline_json: bool,
+) {
+    let _active = ActiveReaderGuard::new(ReaderType::Ws);
+    tokio::pin!(session);
+    let code = loop {
+        tokio::select! {
+            output = session.next() => match output {
+                Some(Ok(ReadSessionOutput::Heartbeat(_tail))) => {
+                    if socket.send(Message::Ping(Bytes::new())).await.is_err() {
+                        return;
+                    }
+                }
+                Some(Ok(ReadSessionOutput::Batch(batch))) => {
+                    for record in batch.records {
+                        let record =
+                            v1t::stream::SequencedRecord::encode(format, inline_json, record);
+                        let json = serde_json::to_string(&record).expect("basic json ser");
+                        if socket.send(Message::Text(json.into())).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                Some(Err(err)) => {
+                    let (_, body) = ServiceError::from(err).to_response().to_parts();
+                    if socket.send(Message::Text(body.into())).await.is_err() {
+                        return;
+                    }
+                    break close_code::ERROR;
+                }
+                None => break close_code::NORMAL,
+            },
+            msg = socket.recv() => match msg {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    };
+    let _ = socket
+        .send(Message::Close(Some(CloseFrame {
+            code,
+            reason: Utf8Bytes::default(),
+        })))
+        .await;
+}
+
+/// Count the stream as an active reader of the given type for as long as it is alive.
+fn with_active_reader<S: Stream>(reader: ReaderType, stream: S) -> impl Stream<Item = S::Item> {
+    let active = ActiveReaderGuard::new(reader);
+    async_stream::stream! {
+        let _active = active;
+        tokio::pin!(stream);
+        while let Some(item) = stream.next().await {
+            yield item;
+        }
+    }
+}
+
 async fn merge_read_session(
     session: impl Stream<Item = Result<ReadSessionOutput, ReadError>>,
     wait: Option<Duration>,
@@ -342,17 +535,28 @@ pub struct AppendArgs {
     basin: BasinName,
     #[from_request(via(Path))]
     stream: StreamName,
+    #[from_request(via(Query))]
+    options: v1t::stream::AppendOptions,
     request: v1t::stream::AppendRequest,
 }
 
 /// Append records.
+///
+/// An `application/x-ndjson` body holds one append input per line, and the inputs are appended in
+/// order. The response is then a JSON array with an acknowledgement per input. If an input fails,
+/// the inputs before it remain appended and the inputs after it are not attempted: the error
+/// response reports how many inputs were appended in the `s2-appended-batches` header.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     post,
     path = super::paths::streams::records::APPEND,
     tag = super::paths::streams::records::TAG,
-    request_body(content = v1t::stream::AppendInput, content_type = "application/json"),
+    request_body(content(
+        (v1t::stream::AppendInput = "application/json"),
+        (v1t::stream::AppendInput = "application/x-ndjson"),
+    )),
     responses(
         (status = StatusCode::OK, body = v1t::stream::AppendAck),
+        (status = StatusCode::NO_CONTENT, description = "Records are valid (`validate_only`)"),
         (status = StatusCode::PRECONDITION_FAILED, body = v1t::stream::AppendConditionFailed),
         (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
         (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
@@ -364,6 +568,7 @@ pub struct AppendArgs {
         v1t::StreamNamePathSegment,
         s2_api::data::S2FormatHeader,
         s2_api::data::S2EncryptionKeyHeader,
+        v1t::stream::AppendOptions,
     ),
     servers(
         (url = super::paths::cloud_endpoints::BASIN, variables(
@@ -378,9 +583,13 @@ pub async fn append(
     AppendArgs {
         basin,
         stream,
+        options,
         request,
     }: AppendArgs,
 ) -> Result<Response, ServiceError> {
+    if options.validate_only.unwrap_or(false) {
+        return validate_append(&backend, &basin, &stream, request).await;
+    }
     match request {
         v1t::stream::AppendRequest::Unary {
             encryption_key,
@@ -402,6 +611,31 @@ pub async fn append(
                 }
             }
         }
+        v1t::stream::AppendRequest::Batches {
+            encryption_key,
+            inputs,
+        } => {
+            let handle = backend
+                .open_for_append(&basin, &stream, encryption_key)
+                .await?;
+            let acks = handle.append_session(futures::stream::iter(inputs));
+            tokio::pin!(acks);
+            let mut appended: Vec<v1t::stream::AppendAck> = Vec::new();
+            while let Some(ack) = acks.next().await {
+                match ack {
+                    Ok(ack) => appended.push(ack.into()),
+                    Err(err) => {
+                        let mut response = ServiceError::from(err).into_response();
+                        response.headers_mut().insert(
+                            v1t::stream::APPENDED_BATCHES_HEADER.clone(),
+                            HeaderValue::from(appended.len()),
+                        );
+                        return Ok(response);
+                    }
+                }
+            }
+            Ok(Json(appended).into_response())
+        }
         v1t::stream::AppendRequest::S2s {
             encryption_key,
             inputs,
@@ -411,13 +645,18 @@ pub async fn append(
                 .open_for_append(&basin, &stream, encryption_key)
                 .await?;
             let (err_tx, err_rx) = tokio::sync::oneshot::channel();
+            // Acks are in input order, so correlation ids are queued up to be echoed back.
+            let (correlation_tx, mut correlation_rx) = tokio::sync::mpsc::unbounded_channel();
 
             let inputs = async_stream::stream! {
                 tokio::pin!(inputs);
                 let mut err_tx = Some(err_tx);
                 while let Some(input) = inputs.next().await {
                     match input {
-                        Ok(input) => yield input,
+                        Ok((input, correlation_id)) => {
+                            let _ = correlation_tx.send(correlation_id);
+                            yield input;
+                        }
                         Err(e) => {
                             if let Some(tx) = err_tx.take() {
                                 let _ = tx.send(e);
@@ -428,9 +667,12 @@ pub async fn append(
                 }
             };
 
-            let ack_stream = handle.append_session(inputs).map(|res| {
-                res.map(v1t::stream::proto::AppendAck::from)
-                    .map_err(ServiceError::from)
+            let ack_stream = handle.append_session(inputs).map(move |res| {
+                res.map(|ack| v1t::stream::proto::AppendAck {
+                    correlation_id: correlation_rx.try_recv().ok().flatten(),
+                    ..ack.into()
+                })
+                .map_err(ServiceError::from)
             });
 
             let input_err_stream = futures::stream::once(err_rx).filter_map(|res| async move {
@@ -456,12 +698,120 @@ pub async fn append(
     }
 }
 
+/// Records were already decoded and checked against limits while extracting `request`, so what is
+/// left are the checks that depend on the stream: it must exist, and its timestamping config and
+/// cipher must accept the records and encryption key.
+async fn validate_append(
+    backend: &Backend,
+    basin: &BasinName,
+    stream: &StreamName,
+    request: v1t::stream::AppendRequest,
+) -> Result<Response, ServiceError> {
+    match request {
+        v1t::stream::AppendRequest::Unary {
+            encryption_key,
+            input,
+            ..
+        } => {
+            backend
+                .validate_append(basin, stream, encryption_key, std::slice::from_ref(&input))
+                .await?;
+        }
+        v1t::stream::AppendRequest::Batches {
+            encryption_key,
+            inputs,
+        } => {
+            backend
+                .validate_append(basin, stream, encryption_key, &inputs)
+                .await?;
+        }
+        v1t::stream::AppendRequest::S2s { .. } => {
+            return Err(ServiceError::Validation(ValidationError(
+                "`validate_only` is not supported for S2S append sessions".to_owned(),
+            )));
+        }
+    }
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[derive(FromRequest)]
+#[from_request(rejection(ServiceError))]
+pub struct RawAppendArgs {
+    #[from_request(via(Header))]
+    basin: BasinName,
+    #[from_request(via(Path))]
+    stream: StreamName,
+    request: v1t::stream::RawAppendRequest,
+}
+
+/// Append a single record from a raw body.
+///
+/// The request body is used verbatim as the record body. Each `x-s2-header-{name}` request header
+/// is mapped to a record header named `{name}`, with the header names being lowercase. Record
+/// headers are ordered by name, and repeated headers keep their request order. The record is
+/// subject to the same limits as any appended record, i.e. a metered size of at most 1 MiB.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = super::paths::streams::records::APPEND_RAW,
+    tag = super::paths::streams::records::TAG,
+    request_body(content = Vec<u8>, content_type = "application/octet-stream"),
+    responses(
+        (status = StatusCode::OK, body = v1t::stream::AppendAck),
+        (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
+        (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
+        (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
+        (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
+        (status = StatusCode::REQUEST_TIMEOUT, body = v1t::error::ErrorInfo),
+    ),
+    params(
+        v1t::StreamNamePathSegment,
+        s2_api::data::S2EncryptionKeyHeader,
+    ),
+    servers(
+        (url = super::paths::cloud_endpoints::BASIN, variables(
+            ("basin" = (
+                description = "Basin name",
+            ))
+        ), description = "Endpoint for the basin"),
+    )
+))]
+pub async fn append_raw(
+    State(backend): State<Backend>,
+    RawAppendArgs {
+        basin,
+        stream,
+        request:
+            v1t::stream::RawAppendRequest {
+                encryption_key,
+                input,
+                response_mime,
+            },
+    }: RawAppendArgs,
+) -> Result<Response, ServiceError> {
+    let ack = backend
+        .open_for_append(&basin, &stream, encryption_key)
+        .await?
+        .append(input)
+        .await?;
+    match response_mime {
+        JsonOrProto::Json => {
+            let ack: v1t::stream::AppendAck = ack.into();
+            Ok(Json(ack).into_response())
+        }
+        JsonOrProto::Proto => {
+            let ack: v1t::stream::proto::AppendAck = ack.into();
+            Ok(Proto(ack).into_response())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{sync::Arc, time::Duration};
 
     use axum::{
         body::{self, Body},
+        extract::DefaultBodyLimit,
         http::{Request, StatusCode, header},
         response::Response,
     };
@@ -471,11 +821,11 @@ mod tests {
     use prost::Message as _;
     use s2_api::v1::stream::{
         proto,
-        s2s::{FrameDecoder, SessionMessage},
+        s2s::{CompressionAlgorithm, FrameDecoder, SessionMessage},
     };
     use s2_common::{
         basin::{BASIN_HEADER, BasinName},
-        config::{BasinConfig, OptionalStreamConfig},
+        config::{BasinConfig, OptionalStreamConfig, OptionalTimestampingConfig, TimestampingMode},
         encryption::{EncryptionAlgorithm, EncryptionKey, S2_ENCRYPTION_KEY_HEADER},
         read_extent::{ReadLimit, ReadUntil},
         record::{EnvelopeRecord, Metered, Record},
@@ -515,7 +865,7 @@ mod tests {
             .build()
             .await
             .expect("create in-memory db");
-        Backend::new(db, ByteSize::mib(10))
+        Backend::new(db, ByteSize::mib(10), ByteSize::mib(10))
     }
 
     async fn setup_app_with_config(
@@ -651,7 +1001,7 @@ mod tests {
 
     async fn assert_no_streams(backend: &Backend, basin: &BasinName) {
         let stream_list = backend
-            .list_streams(basin.clone(), ListStreamsRequest::default())
+            .list_streams(basin.clone(), ListStreamsRequest::default(), None)
             .await
             .expect("list streams");
         assert!(stream_list.values.is_empty());
@@ -675,6 +1025,7 @@ mod tests {
             }],
             match_seq_num: None,
             fencing_token: None,
+            correlation_id: None,
         };
 
         let response = send(
@@ -704,6 +1055,7 @@ mod tests {
                 ReadStart {
                     from: ReadFrom::SeqNum(0),
                     clamp: false,
+                    error_if_trimmed: false,
                 },
                 ReadEnd {
                     limit: ReadLimit::Unbounded,
@@ -731,6 +1083,367 @@ mod tests {
         assert_eq!(record.body().as_ref(), b"secret");
     }
 
+    #[tokio::test]
+    async fn ndjson_append_appends_batches_in_order() {
+        let (app, backend, basin, stream) = setup_app_with_config(
+            "append-ndjson",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+
+        let body = concat!(
+            r#"{"records": [{"body": "a"}, {"body": "b"}]}"#,
+            "\n",
+            r#"{"records": [{"body": "c"}], "match_seq_num": 2}"#,
+            "\n",
+        );
+        let response = send(
+            &app,
+            request_builder("POST", format!("/v1/streams/{stream}/records"), &basin)
+                .header(header::CONTENT_TYPE, "application/x-ndjson")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let acks = response_json(response, "append acks body").await;
+        let acks = acks.as_array().expect("array of acks");
+        assert_eq!(acks.len(), 2);
+        assert_eq!(acks[0]["start"]["seq_num"], 0);
+        assert_eq!(acks[0]["end"]["seq_num"], 2);
+        assert_eq!(acks[1]["start"]["seq_num"], 2);
+        assert_eq!(acks[1]["end"]["seq_num"], 3);
+
+        let tail = backend
+            .open_for_check_tail(&basin, &stream)
+            .await
+            .expect("open check tail handle")
+            .check_tail()
+            .await
+            .expect("check tail");
+        assert_eq!(tail.seq_num, 3);
+    }
+
+    #[tokio::test]
+    async fn ndjson_append_reports_appended_batches_on_failure() {
+        let (app, backend, basin, stream) = setup_app_with_config(
+            "append-ndjson-partial",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+
+        let body = concat!(
+            r#"{"records": [{"body": "a"}, {"body": "b"}]}"#,
+            "\n",
+            r#"{"records": [{"body": "c"}], "match_seq_num": 5}"#,
+            "\n",
+            r#"{"records": [{"body": "d"}]}"#,
+            "\n",
+        );
+        let response = send(
+            &app,
+            request_builder("POST", format!("/v1/streams/{stream}/records"), &basin)
+                .header(header::CONTENT_TYPE, "application/x-ndjson")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(
+            response
+                .headers()
+                .get("s2-appended-batches")
+                .expect("appended batches header"),
+            "1"
+        );
+
+        let tail = backend
+            .open_for_check_tail(&basin, &stream)
+            .await
+            .expect("open check tail handle")
+            .check_tail()
+            .await
+            .expect("check tail");
+        assert_eq!(tail.seq_num, 2);
+    }
+
+    #[tokio::test]
+    async fn ndjson_append_rejects_empty_body() {
+        let (app, _backend, basin, stream) = setup_app_with_config(
+            "append-ndjson-empty",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+
+        let response = send(
+            &app,
+            request_builder("POST", format!("/v1/streams/{stream}/records"), &basin)
+                .header(header::CONTENT_TYPE, "application/x-ndjson")
+                .body(Body::from("\n"))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let info = response_json(response, "append error body").await;
+        assert_eq!(info["code"], "invalid");
+    }
+
+    #[tokio::test]
+    async fn validate_only_append_does_not_persist_records() {
+        let (app, backend, basin, stream) = setup_app_with_config(
+            "append-validate-only",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+
+        let response = send(
+            &app,
+            request_builder(
+                "POST",
+                format!("/v1/streams/{stream}/records?validate_only=true"),
+                &basin,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"records": [{"body": "a"}, {"body": "b"}]}"#))
+            .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let tail = backend
+            .open_for_check_tail(&basin, &stream)
+            .await
+            .expect("open check tail handle")
+            .check_tail()
+            .await
+            .expect("check tail");
+        assert_eq!(tail.seq_num, 0);
+    }
+
+    #[tokio::test]
+    async fn validate_only_append_rejects_missing_timestamp_when_required() {
+        let (app, backend, basin, stream) = setup_app_with_config(
+            "append-validate-only-timestamp",
+            BasinConfig::default(),
+            OptionalStreamConfig {
+                timestamping: OptionalTimestampingConfig {
+                    mode: Some(TimestampingMode::ClientRequire),
+                    uncapped: None,
+                },
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let response = send(
+            &app,
+            request_builder(
+                "POST",
+                format!("/v1/streams/{stream}/records?validate_only=true"),
+                &basin,
+            )
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                r#"{"records": [{"body": "a", "timestamp": 1}, {"body": "b"}]}"#,
+            ))
+            .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let info = response_json(response, "validate-only error body").await;
+        assert_eq!(info["code"], "invalid");
+        let tail = backend
+            .open_for_check_tail(&basin, &stream)
+            .await
+            .expect("open check tail handle")
+            .check_tail()
+            .await
+            .expect("check tail");
+        assert_eq!(tail.seq_num, 0);
+    }
+
+    #[tokio::test]
+    async fn validate_only_append_does_not_auto_create_stream() {
+        let (app, backend, basin, stream) = setup_app_without_stream(
+            "append-validate-only-auto-create",
+            BasinConfig {
+                create_stream_on_append: true,
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let response = send(
+            &app,
+            request_builder(
+                "POST",
+                format!("/v1/streams/{stream}/records?validate_only=true"),
+                &basin,
+            )
+            .header(header::CONTENT_TYPE, "application/x-ndjson")
+            .body(Body::from(concat!(r#"{"records": [{"body": "a"}]}"#, "\n")))
+            .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_no_streams(&backend, &basin).await;
+    }
+
+    #[tokio::test]
+    async fn s2s_append_echoes_correlation_ids() {
+        let (app, _backend, basin, stream) = setup_app_with_config(
+            "append-s2s-correlation",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+
+        let mut body = BytesMut::new();
+        for correlation_id in [Some(7), None, Some(3)] {
+            let input = proto::AppendInput {
+                records: vec![proto::AppendRecord {
+                    timestamp: None,
+                    headers: vec![],
+                    body: Bytes::from_static(b"pipelined"),
+                }],
+                match_seq_num: None,
+                fencing_token: None,
+                correlation_id,
+            };
+            let msg = SessionMessage::regular(CompressionAlgorithm::None, &input).unwrap();
+            body.extend_from_slice(&msg.encode());
+        }
+
+        let response = send(
+            &app,
+            request_builder("POST", format!("/v1/streams/{stream}/records"), &basin)
+                .header(header::CONTENT_TYPE, "s2s/proto")
+                .body(Body::from(body.freeze()))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response_bytes(response, "s2s body").await;
+        let mut buf = BytesMut::from(body.as_ref());
+        let mut acks = Vec::new();
+        while let Some(frame) = FrameDecoder.decode(&mut buf).expect("frame decode") {
+            let SessionMessage::Regular(ack) = frame else {
+                panic!("expected regular frame");
+            };
+            acks.push(
+                ack.try_into_proto::<proto::AppendAck>()
+                    .expect("decode append ack proto"),
+            );
+        }
+        let acks = acks
+            .into_iter()
+            .map(|ack| (ack.end.map(|pos| pos.seq_num), ack.correlation_id))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            acks,
+            vec![(Some(1), Some(7)), (Some(2), None), (Some(3), Some(3))]
+        );
+    }
+
+    #[tokio::test]
+    async fn raw_append_maps_prefixed_headers_to_record_headers() {
+        let (app, _backend, basin, stream) = setup_app_with_config(
+            "append-raw",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+
+        let response = send(
+            &app,
+            request_builder("POST", format!("/v1/streams/{stream}/records/raw"), &basin)
+                .header(header::CONTENT_TYPE, "application/octet-stream")
+                .header("x-s2-header-Trace-Id", "abc")
+                .header("x-request-id", "ignored")
+                .header("x-s2-header-tag", "first")
+                .header("x-s2-header-source", "webhook")
+                .header("x-s2-header-tag", "second")
+                .body(Body::from("raw body"))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let ack = response_json(response, "append ack body").await;
+        assert_eq!(ack["start"]["seq_num"], 0);
+        assert_eq!(ack["end"]["seq_num"], 1);
+
+        let response = send(
+            &app,
+            request_builder("GET", read_uri(&stream), &basin)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let batch = response_json(response, "read body").await;
+        let records = batch["records"].as_array().expect("records");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["body"], "raw body");
+        assert_eq!(
+            records[0]["headers"],
+            serde_json::json!([
+                ["source", "webhook"],
+                ["tag", "first"],
+                ["tag", "second"],
+                ["trace-id", "abc"]
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn oversized_append_bodies_are_rejected_with_413() {
+        let (app, _backend, basin, stream) = setup_app_with_config(
+            "append-too-large",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+        let app = app.layer(DefaultBodyLimit::max(16));
+
+        for (uri, content_type, body) in [
+            (
+                format!("/v1/streams/{stream}/records"),
+                "application/json",
+                r#"{"records":[{"body":"more than sixteen bytes"}]}"#,
+            ),
+            (
+                format!("/v1/streams/{stream}/records/raw"),
+                "application/octet-stream",
+                "more than sixteen bytes",
+            ),
+        ] {
+            let response = send(
+                &app,
+                request_builder("POST", uri, &basin)
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await;
+
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+            let error = response_json(response, "error body").await;
+            assert_eq!(error["code"], "payload_too_large");
+        }
+    }
+
     #[tokio::test]
     async fn invalid_read_bounds_do_not_auto_create_stream() {
         let basin_config = BasinConfig {
@@ -764,6 +1477,278 @@ mod tests {
         assert_no_streams(&backend, &basin).await;
     }
 
+    #[tokio::test]
+    async fn positions_only_read_omits_records() {
+        let (app, _backend, basin, stream) = setup_app_with_config(
+            "read-positions-only",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+
+        let response = send(
+            &app,
+            request_builder("POST", format!("/v1/streams/{stream}/records"), &basin)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"records": [{"body": "a"}, {"body": "b"}]}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(
+            &app,
+            request_builder(
+                "GET",
+                format!("/v1/streams/{stream}/records?seq_num=0&positions_only=true"),
+                &basin,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let batch = response_json(response, "positions batch body").await;
+        assert!(batch.get("records").is_none());
+        let positions = batch["positions"].as_array().expect("array of positions");
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[0]["seq_num"], 0);
+        assert_eq!(positions[1]["seq_num"], 1);
+        assert!(positions[1]["timestamp"].is_u64());
+        assert!(positions[1].get("body").is_none());
+        assert_eq!(batch["tail"]["seq_num"], 2);
+    }
+
+    #[tokio::test]
+    async fn positions_only_read_needs_no_key_and_ignores_byte_limit() {
+        let (app, backend, basin, stream) = setup_app_with_config(
+            "read-positions-only-encrypted",
+            basin_config_with_stream_cipher(EncryptionAlgorithm::Aegis256),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+        for body in [b"first".as_slice(), b"second".as_slice()] {
+            append_encrypted_payload(&backend, &basin, &stream, body, aegis_key(0x42)).await;
+        }
+
+        let response = send(
+            &app,
+            request_builder(
+                "GET",
+                format!("/v1/streams/{stream}/records?seq_num=0&bytes=1&positions_only=true"),
+                &basin,
+            )
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let batch = response_json(response, "positions batch body").await;
+        let positions = batch["positions"].as_array().expect("array of positions");
+        assert_eq!(positions.len(), 2);
+        assert_eq!(batch["tail"]["seq_num"], 2);
+    }
+
+    #[tokio::test]
+    async fn positions_only_sse_read_is_rejected() {
+        let (app, _backend, basin, stream) = setup_app_with_config(
+            "read-positions-only-sse",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+
+        let response = send(
+            &app,
+            request_builder(
+                "GET",
+                format!("/v1/streams/{stream}/records?seq_num=0&positions_only=true"),
+                &basin,
+            )
+            .header(header::ACCEPT, "text/event-stream")
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let info = response_json(response, "read error body").await;
+        assert_eq!(info["code"], "invalid");
+    }
+
+    /// Serve `app` on a local port, since WebSocket upgrades need a real connection.
+    async fn serve(app: axum::Router) -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind listener");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        addr
+    }
+
+    async fn connect_follow(
+        addr: std::net::SocketAddr,
+        basin: &BasinName,
+        stream: &StreamName,
+        query: &str,
+        encryption_key: Option<&EncryptionKey>,
+    ) -> (
+        tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >,
+        StatusCode,
+    ) {
+        use tokio_tungstenite::tungstenite::client::IntoClientRequest as _;
+
+        let mut request = format!("ws://{addr}/v1/streams/{stream}/records/follow?{query}")
+            .into_client_request()
+            .expect("client request");
+        request.headers_mut().insert(
+            BASIN_HEADER.clone(),
+            basin.as_ref().parse().expect("header value"),
+        );
+        if let Some(key) = encryption_key {
+            request
+                .headers_mut()
+                .insert(S2_ENCRYPTION_KEY_HEADER.clone(), key.to_header_value());
+        }
+        let (socket, response) = tokio_tungstenite::connect_async(request)
+            .await
+            .expect("websocket connect");
+        (socket, response.status())
+    }
+
+    /// Next non-ping frame, as the text of a data frame or the code of a close frame.
+    async fn next_follow_frame(
+        socket: &mut tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >,
+    ) -> Result<String, u16> {
+        use futures::StreamExt as _;
+        use tokio_tungstenite::tungstenite::Message;
+
+        loop {
+            let msg = tokio::time::timeout(Duration::from_secs(5), socket.next())
+                .await
+                .expect("frame before timeout")
+                .expect("socket open")
+                .expect("valid frame");
+            match msg {
+                Message::Text(text) => return Ok(text.to_string()),
+                Message::Close(frame) => {
+                    return Err(frame.map(|f| u16::from(f.code)).unwrap_or_default());
+                }
+                Message::Ping(_) | Message::Pong(_) => {}
+                other => panic!("unexpected frame: {other:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn follow_upgrades_and_streams_records_until_limit() {
+        let (app, backend, basin, stream) = setup_app_with_config(
+            "follow-ws",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+        backend
+            .open_for_append(&basin, &stream, None)
+            .await
+            .expect("open append handle")
+            .append(append_input(b"before"))
+            .await
+            .expect("append before connect");
+
+        let addr = serve(app).await;
+        let (mut socket, status) =
+            connect_follow(addr, &basin, &stream, "seq_num=0&count=2", None).await;
+        assert_eq!(status, StatusCode::SWITCHING_PROTOCOLS);
+
+        let first: serde_json::Value =
+            serde_json::from_str(&next_follow_frame(&mut socket).await.expect("record frame"))
+                .expect("json frame");
+        assert_eq!(first["seq_num"], 0);
+        assert_eq!(first["body"], "before");
+
+        backend
+            .open_for_append(&basin, &stream, None)
+            .await
+            .expect("open append handle")
+            .append(append_input(b"after"))
+            .await
+            .expect("append while following");
+
+        let second: serde_json::Value =
+            serde_json::from_str(&next_follow_frame(&mut socket).await.expect("record frame"))
+                .expect("json frame");
+        assert_eq!(second["seq_num"], 1);
+        assert_eq!(second["body"], "after");
+
+        assert_eq!(next_follow_frame(&mut socket).await, Err(1000));
+    }
+
+    #[tokio::test]
+    async fn follow_sends_error_frame_and_closes_with_error_code() {
+        let encryption_key = aegis_key(0x42);
+        let wrong_key = aegis_key(0x24);
+        let (app, backend, basin, stream) = setup_app_with_config(
+            "follow-ws-bad-key",
+            basin_config_with_stream_cipher(EncryptionAlgorithm::Aegis256),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+        append_encrypted_payload(&backend, &basin, &stream, b"secret", encryption_key).await;
+
+        let addr = serve(app).await;
+        let (mut socket, status) =
+            connect_follow(addr, &basin, &stream, "seq_num=0", Some(&wrong_key)).await;
+        assert_eq!(status, StatusCode::SWITCHING_PROTOCOLS);
+
+        let info: serde_json::Value =
+            serde_json::from_str(&next_follow_frame(&mut socket).await.expect("error frame"))
+                .expect("json frame");
+        assert_eq!(info["code"], "decryption_failed");
+
+        assert_eq!(next_follow_frame(&mut socket).await, Err(1011));
+    }
+
+    #[tokio::test]
+    async fn unary_read_wait_is_capped_by_backend_max() {
+        let (_app, backend, basin, stream) = setup_app_with_config(
+            "read-max-wait",
+            BasinConfig::default(),
+            OptionalStreamConfig::default(),
+        )
+        .await;
+        let app = handlers::router()
+            .with_state(backend.with_max_unary_read_wait(Duration::from_millis(200)));
+
+        let started = tokio::time::Instant::now();
+        let response = tokio::time::timeout(
+            Duration::from_secs(10),
+            send(
+                &app,
+                request_builder(
+                    "GET",
+                    format!("/v1/streams/{stream}/records?seq_num=0&wait=30"),
+                    &basin,
+                )
+                .body(Body::empty())
+                .unwrap(),
+            ),
+        )
+        .await
+        .expect("read should return once the capped wait elapses");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        let batch = response_json(response, "read body").await;
+        assert_eq!(batch["records"], serde_json::json!([]));
+    }
+
     #[tokio::test]
     async fn unary_read_with_wrong_key_returns_decryption_failed_error() {
         let encryption_key = aegis_key(0x42);
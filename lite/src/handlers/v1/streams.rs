@@ -1,18 +1,33 @@
-use axum::extract::{FromRequest, Path, Query, State};
-use http::StatusCode;
+use std::time::Duration;
+
+use axum::{
+    extract::{FromRequest, Path, Query, State},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, Sse},
+    },
+};
+use http::{HeaderMap, HeaderValue, StatusCode, header};
 use s2_api::{
     data::{Json, extract::JsonOpt},
     v1 as v1t,
 };
 use s2_common::{
     basin::BasinName,
-    config::{OptionalStreamConfig, StreamReconfiguration},
+    config::{OptionalStreamConfig, StreamConfig, StreamReconfiguration},
     http::extract::{Header, HeaderOpt},
-    resources::{PROVISION_RESULT_HEADER, Page, ProvisionMode, ProvisionResult, RequestToken},
+    resources::{
+        LIST_LIMIT_HEADER, PROVISION_RESULT_HEADER, Page, ProvisionMode, ProvisionResult,
+        RequestToken,
+    },
     stream::{ListStreamsRequest, StreamName},
 };
+use tokio::sync::broadcast::error::RecvError;
 
-use crate::{backend::Backend, handlers::v1::error::ServiceError};
+use crate::{
+    backend::{Backend, StreamConfigChange},
+    handlers::v1::{error::ServiceError, etag::json_with_etag},
+};
 
 pub fn router() -> axum::Router<Backend> {
     use axum::routing::{delete, get, patch, post, put};
@@ -20,8 +35,13 @@ pub fn router() -> axum::Router<Backend> {
         .route(super::paths::streams::LIST, get(list_streams))
         .route(super::paths::streams::CREATE, post(create_stream))
         .route(super::paths::streams::GET_CONFIG, get(get_stream_config))
+        .route(
+            super::paths::streams::WATCH_CONFIG,
+            get(watch_stream_config),
+        )
         .route(super::paths::streams::ENSURE, put(ensure_stream))
         .route(super::paths::streams::DELETE, delete(delete_stream))
+        .route(super::paths::streams::UNDELETE, post(undelete_stream))
         .route(
             super::paths::streams::RECONFIGURE,
             patch(reconfigure_stream),
@@ -38,6 +58,13 @@ pub struct ListArgs {
 }
 
 /// List streams.
+///
+/// A `limit` above 1000 is clamped to 1000. The page size applied is returned in the
+/// `s2-list-limit` response header.
+///
+/// Streams are listed by name unless `order=created` is given, in which case they are
+/// listed oldest first. Pages in that order resume from the `start_after` stream together with
+/// its `start_after_created_at`.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     get,
     path = super::paths::streams::LIST,
@@ -61,13 +88,45 @@ pub struct ListArgs {
 pub async fn list_streams(
     State(backend): State<Backend>,
     ListArgs { basin, request }: ListArgs,
-) -> Result<Json<v1t::stream::ListStreamsResponse>, ServiceError> {
-    let request: ListStreamsRequest = request.try_into()?;
-    let Page { values, has_more } = backend.list_streams(basin, request).await?;
-    Ok(Json(v1t::stream::ListStreamsResponse {
-        streams: values.into_iter().map(Into::into).collect(),
-        has_more,
-    }))
+) -> Result<
+    (
+        [(http::HeaderName, usize); 1],
+        Json<v1t::stream::ListStreamsResponse>,
+    ),
+    ServiceError,
+> {
+    let order = request.order.unwrap_or_default();
+    let deleting = request
+        .state
+        .map(|state| state == v1t::stream::ListStreamsState::Deleting);
+    let start_after_created_at = request.start_after_created_at;
+    let request: ListStreamsRequest = request.try_into().map_err(ServiceError::InvalidQuery)?;
+    let limit = request.limit.as_usize();
+    let Page { values, has_more } = match order {
+        v1t::stream::ListStreamsOrder::Name => {
+            backend.list_streams(basin, request, deleting).await?
+        }
+        v1t::stream::ListStreamsOrder::Created => {
+            if !request.start_after.is_empty() && start_after_created_at.is_none() {
+                return Err(ServiceError::InvalidQuery(
+                    "`start_after_created_at` is required with `start_after` when listing in \
+                     creation order"
+                        .to_owned()
+                        .into(),
+                ));
+            }
+            backend
+                .list_streams_by_creation(basin, request, start_after_created_at, deleting)
+                .await?
+        }
+    };
+    Ok((
+        [(LIST_LIMIT_HEADER.clone(), limit)],
+        Json(v1t::stream::ListStreamsResponse {
+            streams: values.into_iter().map(Into::into).collect(),
+            has_more,
+        }),
+    ))
 }
 
 #[derive(FromRequest)]
@@ -154,12 +213,16 @@ pub struct GetConfigArgs {
 }
 
 /// Get stream configuration.
+///
+/// The response carries an `ETag`. Sending it back in `If-None-Match` yields 304 Not Modified
+/// if the configuration is unchanged.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     get,
     path = super::paths::streams::GET_CONFIG,
     tag = super::paths::streams::TAG,
     responses(
         (status = StatusCode::OK, body = v1t::config::StreamConfig),
+        (status = StatusCode::NOT_MODIFIED, description = "Configuration matches the `If-None-Match` ETag"),
         (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
         (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
         (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
@@ -177,9 +240,117 @@ pub struct GetConfigArgs {
 ))]
 pub async fn get_stream_config(
     State(backend): State<Backend>,
+    headers: HeaderMap,
     GetConfigArgs { basin, stream }: GetConfigArgs,
-) -> Result<Json<v1t::config::StreamConfig>, ServiceError> {
-    Ok(Json(backend.get_stream_config(basin, stream).await?.into()))
+) -> Result<Response, ServiceError> {
+    let config: v1t::config::StreamConfig = backend.get_stream_config(basin, stream).await?.into();
+    Ok(json_with_etag(&headers, &config))
+}
+
+/// Interval between pings on an otherwise idle config watch.
+const WATCH_PING_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Watch stream configuration.
+///
+/// Server-sent events, starting with a `config` event carrying the current configuration and
+/// followed by another each time it changes. `ping` events are sent while idle. Once the stream
+/// is deleted, an `error` event is sent and the response ends.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    get,
+    path = super::paths::streams::WATCH_CONFIG,
+    tag = super::paths::streams::TAG,
+    responses(
+        (status = StatusCode::OK, content(
+            (v1t::config::StreamConfig = "text/event-stream"),
+        )),
+        (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
+        (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
+        (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
+        (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
+        (status = StatusCode::REQUEST_TIMEOUT, body = v1t::error::ErrorInfo),
+    ),
+    params(v1t::StreamNamePathSegment),
+    servers(
+        (url = super::paths::cloud_endpoints::BASIN, variables(
+            ("basin" = (
+                description = "Basin name",
+            ))
+        ), description = "Endpoint for the basin"),
+    )
+))]
+pub async fn watch_stream_config(
+    State(backend): State<Backend>,
+    GetConfigArgs { basin, stream }: GetConfigArgs,
+) -> Result<Response, ServiceError> {
+    // Subscribe before fetching, so that no change can slip in between.
+    let mut changes = backend.subscribe_stream_configs();
+    let config = backend
+        .get_stream_config(basin.clone(), stream.clone())
+        .await?;
+    let events = async_stream::stream! {
+        let mut current = config.clone();
+        yield config_event(config);
+        let mut ping = tokio::time::interval(WATCH_PING_INTERVAL);
+        ping.reset();
+        loop {
+            let change = tokio::select! {
+                change = changes.recv() => Some(change),
+                _ = ping.tick() => None,
+            };
+            let Some(change) = change else {
+                yield v1t::stream::sse::ping_event();
+                continue;
+            };
+            let config = match change {
+                Ok(StreamConfigChange::Updated {
+                    basin: changed_basin,
+                    stream: changed_stream,
+                    config,
+                }) if changed_basin == basin && changed_stream == stream => config,
+                Ok(StreamConfigChange::Updated { .. }) => continue,
+                Ok(StreamConfigChange::Deleted {
+                    basin: changed_basin,
+                    stream: changed_stream,
+                }) if changed_basin != basin || changed_stream != stream => continue,
+                Ok(StreamConfigChange::BasinReconfigured { basin: changed_basin })
+                    if changed_basin != basin => continue,
+                Ok(_) | Err(RecvError::Lagged(_)) => {
+                    // The stream was deleted, its basin's defaults changed, or changes were
+                    // missed, so resynchronize from the source of truth. Fetching the config of
+                    // a deleted stream fails, which ends the watch.
+                    match backend.get_stream_config(basin.clone(), stream.clone()).await {
+                        Ok(config) => config,
+                        Err(err) => {
+                            let (_, body) = ServiceError::from(err).to_response().to_parts();
+                            yield v1t::stream::sse::error_event(body);
+                            break;
+                        }
+                    }
+                }
+                Err(RecvError::Closed) => break,
+            };
+            if config != current {
+                current = config.clone();
+                yield config_event(config);
+            }
+        }
+    };
+
+    let mut response = Sse::new(events).into_response();
+    response.headers_mut().insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("no-cache, no-transform"),
+    );
+    response
+        .headers_mut()
+        .insert("x-accel-buffering", HeaderValue::from_static("no"));
+    Ok(response)
+}
+
+fn config_event(config: StreamConfig) -> Result<Event, axum::Error> {
+    Event::default()
+        .event("config")
+        .json_data(v1t::config::StreamConfig::from(config))
 }
 
 #[derive(FromRequest)]
@@ -258,22 +429,28 @@ pub struct DeleteArgs {
     basin: BasinName,
     #[from_request(via(Path))]
     stream: StreamName,
+    #[from_request(via(Query))]
+    request: v1t::stream::DeleteStreamRequest,
 }
 
 /// Delete a stream.
+///
+/// With `purge=true`, the stream and all of its records are removed before responding, even if
+/// a deletion grace period is configured.
 #[cfg_attr(feature = "utoipa", utoipa::path(
     delete,
     path = super::paths::streams::DELETE,
     tag = super::paths::streams::TAG,
     responses(
         (status = StatusCode::ACCEPTED),
+        (status = StatusCode::NO_CONTENT),
         (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
         (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
         (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
         (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
         (status = StatusCode::REQUEST_TIMEOUT, body = v1t::error::ErrorInfo),
     ),
-    params(v1t::StreamNamePathSegment),
+    params(v1t::StreamNamePathSegment, v1t::stream::DeleteStreamRequest),
     servers(
         (url = super::paths::cloud_endpoints::BASIN, variables(
             ("basin" = (
@@ -284,10 +461,61 @@ pub struct DeleteArgs {
 ))]
 pub async fn delete_stream(
     State(backend): State<Backend>,
-    DeleteArgs { basin, stream }: DeleteArgs,
+    DeleteArgs {
+        basin,
+        stream,
+        request,
+    }: DeleteArgs,
 ) -> Result<StatusCode, ServiceError> {
-    backend.delete_stream(basin, stream).await?;
-    Ok(StatusCode::ACCEPTED)
+    if request.purge.unwrap_or(false) {
+        backend.purge_stream(basin, stream).await?;
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        backend.delete_stream(basin, stream).await?;
+        Ok(StatusCode::ACCEPTED)
+    }
+}
+
+#[derive(FromRequest)]
+#[from_request(rejection(ServiceError))]
+pub struct UndeleteArgs {
+    #[from_request(via(Header))]
+    basin: BasinName,
+    #[from_request(via(Path))]
+    stream: StreamName,
+}
+
+/// Undelete a stream.
+///
+/// Restores a stream deleted within the deletion grace period. Undeleting a stream that is not
+/// deleted has no effect.
+#[cfg_attr(feature = "utoipa", utoipa::path(
+    post,
+    path = super::paths::streams::UNDELETE,
+    tag = super::paths::streams::TAG,
+    responses(
+        (status = StatusCode::OK, body = v1t::stream::StreamInfo),
+        (status = StatusCode::CONFLICT, body = v1t::error::ErrorInfo),
+        (status = StatusCode::NOT_FOUND, body = v1t::error::ErrorInfo),
+        (status = StatusCode::BAD_REQUEST, body = v1t::error::ErrorInfo),
+        (status = StatusCode::FORBIDDEN, body = v1t::error::ErrorInfo),
+        (status = StatusCode::REQUEST_TIMEOUT, body = v1t::error::ErrorInfo),
+    ),
+    params(v1t::StreamNamePathSegment),
+    servers(
+        (url = super::paths::cloud_endpoints::BASIN, variables(
+            ("basin" = (
+                description = "Basin name",
+            ))
+        ), description = "Endpoint for the basin"),
+    )
+))]
+pub async fn undelete_stream(
+    State(backend): State<Backend>,
+    UndeleteArgs { basin, stream }: UndeleteArgs,
+) -> Result<Json<v1t::stream::StreamInfo>, ServiceError> {
+    let info = backend.undelete_stream(basin, stream).await?;
+    Ok(Json(info.into()))
 }
 
 #[derive(FromRequest)]
@@ -338,3 +566,177 @@ pub async fn reconfigure_stream(
         .await?;
     Ok(Json(config.into()))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use axum::{
+        body::{Body, BodyDataStream},
+        http::{Request, StatusCode},
+    };
+    use bytesize::ByteSize;
+    use futures::StreamExt as _;
+    use s2_common::{
+        basin::{BASIN_HEADER, BasinName},
+        config::{BasinConfig, OptionalStreamConfig, StorageClass, StreamReconfiguration},
+        maybe::Maybe,
+        resources::ProvisionMode,
+        stream::StreamName,
+    };
+    use slatedb::{Db, object_store::memory::InMemory};
+    use tower::ServiceExt as _;
+    use uuid::Uuid;
+
+    use crate::{backend::Backend, handlers};
+
+    async fn setup_app(test_suffix: &str) -> (axum::Router, Backend, BasinName, StreamName) {
+        let object_store = Arc::new(InMemory::new());
+        let db_path = format!("/tmp/streams-handler-test-{}", Uuid::new_v4());
+        let db = Db::builder(db_path, object_store)
+            .build()
+            .await
+            .expect("create in-memory db");
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
+        let basin: BasinName = format!("test-basin-{test_suffix}").parse().unwrap();
+        backend
+            .provision_basin(
+                basin.clone(),
+                BasinConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .expect("create basin");
+        let stream: StreamName = format!("test-stream-{test_suffix}").parse().unwrap();
+        backend
+            .provision_stream(
+                basin.clone(),
+                stream.clone(),
+                OptionalStreamConfig::default(),
+                ProvisionMode::CreateOnly {
+                    request_token: None,
+                },
+            )
+            .await
+            .expect("create stream");
+        let app = handlers::router().with_state(backend.clone());
+        (app, backend, basin, stream)
+    }
+
+    /// Read the next server-sent event as its name and data, or `None` once the body ends.
+    async fn next_event(body: &mut BodyDataStream, buf: &mut String) -> Option<(String, String)> {
+        loop {
+            if let Some(end) = buf.find("\n\n") {
+                let frame: String = buf.drain(..end + 2).collect();
+                let mut event = String::new();
+                let mut data = String::new();
+                for line in frame.lines() {
+                    if let Some(value) = line.strip_prefix("event:") {
+                        event = value.trim().to_owned();
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data.push_str(value.trim());
+                    }
+                }
+                return Some((event, data));
+            }
+            let chunk = tokio::time::timeout(Duration::from_secs(10), body.next())
+                .await
+                .expect("event should arrive")?
+                .expect("body chunk");
+            buf.push_str(std::str::from_utf8(&chunk).expect("utf-8 event stream"));
+        }
+    }
+
+    #[tokio::test]
+    async fn watch_config_streams_changes_and_ends_on_deletion() {
+        let (app, backend, basin, stream) = setup_app("watch-config").await;
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/v1/streams/{stream}/config/watch"))
+                    .header(BASIN_HEADER.as_str(), basin.as_ref())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("request should complete");
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut body = response.into_body().into_data_stream();
+        let mut buf = String::new();
+
+        let (event, data) = next_event(&mut body, &mut buf)
+            .await
+            .expect("initial config");
+        assert_eq!(event, "config");
+        let config: serde_json::Value = serde_json::from_str(&data).expect("config json");
+        assert_eq!(config["storage_class"], "standard");
+
+        backend
+            .reconfigure_stream(
+                basin.clone(),
+                stream.clone(),
+                StreamReconfiguration {
+                    storage_class: Maybe::from(Some(StorageClass::Express)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("reconfigure stream");
+        let (event, data) = next_event(&mut body, &mut buf)
+            .await
+            .expect("changed config");
+        assert_eq!(event, "config");
+        let config: serde_json::Value = serde_json::from_str(&data).expect("config json");
+        assert_eq!(config["storage_class"], "express");
+
+        backend
+            .delete_stream(basin.clone(), stream.clone())
+            .await
+            .expect("delete stream");
+        let (event, data) = next_event(&mut body, &mut buf).await.expect("error event");
+        assert_eq!(event, "error");
+        let info: serde_json::Value = serde_json::from_str(&data).expect("error json");
+        assert_eq!(info["code"], "stream_deletion_pending");
+        assert!(next_event(&mut body, &mut buf).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn list_with_start_after_less_than_prefix_returns_bad_query() {
+        let (app, _backend, basin, _stream) = setup_app("list-start-after").await;
+
+        for uri in [
+            "/v1/basins?prefix=test-basin&start_after=a",
+            "/v1/streams?prefix=test-stream&start_after=a",
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(uri)
+                        .header(BASIN_HEADER.as_str(), basin.as_ref())
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .expect("request should complete");
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("error body");
+            let info: serde_json::Value = serde_json::from_slice(&body).expect("error json");
+            assert_eq!(info["code"], "bad_query");
+            assert!(
+                info["message"]
+                    .as_str()
+                    .expect("error message string")
+                    .contains("`start_after` must be greater than or equal to `prefix`")
+            );
+        }
+    }
+}
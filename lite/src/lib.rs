@@ -4,5 +4,6 @@ pub mod backend;
 pub mod handlers;
 pub mod init;
 pub mod metrics;
+pub mod seed;
 pub mod server;
 pub mod stream_id;
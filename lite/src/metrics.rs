@@ -1,7 +1,330 @@
 use std::{sync::LazyLock, time::Duration};
 
 use bytes::{BufMut, Bytes, BytesMut};
-use prometheus::{Encoder, Histogram, TextEncoder, register_histogram};
+use prometheus::{
+    Encoder, Histogram, IntCounter, IntCounterVec, IntGaugeVec, TextEncoder, register_histogram,
+    register_int_counter, register_int_counter_vec, register_int_gauge_vec,
+};
+use s2_common::{basin::BasinName, stream::StreamName};
+
+/// Why a client-specified timestamp was not used as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampAdjustment {
+    /// Capped at the arrival time because it was in the future and the stream is not uncapped.
+    Capped,
+    /// Raised to the previous maximum timestamp to keep the stream monotonic.
+    Monotonic,
+}
+
+impl TimestampAdjustment {
+    fn as_label(self) -> &'static str {
+        match self {
+            Self::Capped => "capped",
+            Self::Monotonic => "monotonic",
+        }
+    }
+}
+
+fn timestamp_adjustments_counter() -> &'static IntCounterVec {
+    static COUNTER: LazyLock<IntCounterVec> = LazyLock::new(|| {
+        register_int_counter_vec!(
+            "s2_timestamp_adjustments_total",
+            "Client-specified record timestamps adjusted during append",
+            &["reason"]
+        )
+        .unwrap()
+    });
+    &COUNTER
+}
+
+pub fn observe_timestamp_adjustments(reason: TimestampAdjustment, count: u64) {
+    if count > 0 {
+        timestamp_adjustments_counter()
+            .with_label_values(&[reason.as_label()])
+            .inc_by(count);
+    }
+}
+
+pub fn timestamp_adjustments(reason: TimestampAdjustment) -> u64 {
+    timestamp_adjustments_counter()
+        .with_label_values(&[reason.as_label()])
+        .get()
+}
+
+/// Why an append was rejected by its preconditions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppendRejection {
+    /// The provided fencing token did not match the stream's current token.
+    FencingTokenMismatch,
+    /// `match_seq_num` did not match the next assignable sequence number.
+    SeqNumMismatch,
+    /// The stream's append rate limit was exceeded.
+    RateLimited,
+}
+
+impl AppendRejection {
+    fn as_label(self) -> &'static str {
+        match self {
+            Self::FencingTokenMismatch => "fencing_token_mismatch",
+            Self::SeqNumMismatch => "seq_num_mismatch",
+            Self::RateLimited => "rate_limited",
+        }
+    }
+}
+
+fn append_rejected_counter() -> &'static IntCounterVec {
+    static COUNTER: LazyLock<IntCounterVec> = LazyLock::new(|| {
+        register_int_counter_vec!(
+            "s2_append_rejected_total",
+            "Appends rejected due to failed preconditions",
+            &["reason"]
+        )
+        .unwrap()
+    });
+    &COUNTER
+}
+
+pub fn observe_append_rejected(reason: AppendRejection) {
+    append_rejected_counter()
+        .with_label_values(&[reason.as_label()])
+        .inc();
+}
+
+pub fn append_rejected(reason: AppendRejection) -> u64 {
+    append_rejected_counter()
+        .with_label_values(&[reason.as_label()])
+        .get()
+}
+
+/// Outcome of a read cache lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadCacheLookup {
+    Hit,
+    Miss,
+}
+
+impl ReadCacheLookup {
+    fn as_label(self) -> &'static str {
+        match self {
+            Self::Hit => "hit",
+            Self::Miss => "miss",
+        }
+    }
+}
+
+fn read_cache_lookups_counter() -> &'static IntCounterVec {
+    static COUNTER: LazyLock<IntCounterVec> = LazyLock::new(|| {
+        register_int_counter_vec!(
+            "s2_read_cache_lookups_total",
+            "Read cache lookups for recently read record blocks",
+            &["outcome"]
+        )
+        .unwrap()
+    });
+    &COUNTER
+}
+
+pub fn observe_read_cache_lookup(outcome: ReadCacheLookup) {
+    read_cache_lookups_counter()
+        .with_label_values(&[outcome.as_label()])
+        .inc();
+}
+
+pub fn read_cache_lookups(outcome: ReadCacheLookup) -> u64 {
+    read_cache_lookups_counter()
+        .with_label_values(&[outcome.as_label()])
+        .get()
+}
+
+/// In-memory structure whose approximate size, per [`s2_common::deep_size::DeepSize`], is
+/// reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryComponent {
+    /// Record blocks held by the read cache.
+    ReadCache,
+    /// In-flight appends held against the append memory budget.
+    AppendBudget,
+    /// Prefetched read batches held against the read-ahead memory budget.
+    ReadAheadBudget,
+}
+
+impl MemoryComponent {
+    fn as_label(self) -> &'static str {
+        match self {
+            Self::ReadCache => "read_cache",
+            Self::AppendBudget => "append_budget",
+            Self::ReadAheadBudget => "read_ahead_budget",
+        }
+    }
+}
+
+fn memory_usage_gauge() -> &'static IntGaugeVec {
+    static GAUGE: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+        register_int_gauge_vec!(
+            "s2_memory_usage_bytes",
+            "Approximate bytes held in memory by caches and buffers",
+            &["component"]
+        )
+        .unwrap()
+    });
+    &GAUGE
+}
+
+pub fn observe_memory_usage(component: MemoryComponent, bytes: usize) {
+    memory_usage_gauge()
+        .with_label_values(&[component.as_label()])
+        .set(bytes as i64);
+}
+
+pub fn memory_usage(component: MemoryComponent) -> i64 {
+    memory_usage_gauge()
+        .with_label_values(&[component.as_label()])
+        .get()
+}
+
+/// Outcome of a basin config cache lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BasinConfigCacheLookup {
+    Hit,
+    Miss,
+}
+
+impl BasinConfigCacheLookup {
+    fn as_label(self) -> &'static str {
+        match self {
+            Self::Hit => "hit",
+            Self::Miss => "miss",
+        }
+    }
+}
+
+fn basin_config_cache_lookups_counter() -> &'static IntCounterVec {
+    static COUNTER: LazyLock<IntCounterVec> = LazyLock::new(|| {
+        register_int_counter_vec!(
+            "s2_basin_config_cache_lookups_total",
+            "Basin config cache lookups",
+            &["outcome"]
+        )
+        .unwrap()
+    });
+    &COUNTER
+}
+
+pub fn observe_basin_config_cache_lookup(outcome: BasinConfigCacheLookup) {
+    basin_config_cache_lookups_counter()
+        .with_label_values(&[outcome.as_label()])
+        .inc();
+}
+
+pub fn basin_config_cache_lookups(outcome: BasinConfigCacheLookup) -> u64 {
+    basin_config_cache_lookups_counter()
+        .with_label_values(&[outcome.as_label()])
+        .get()
+}
+
+fn undecodable_records_skipped_counter() -> &'static IntCounter {
+    static COUNTER: LazyLock<IntCounter> = LazyLock::new(|| {
+        register_int_counter!(
+            "s2_read_undecodable_records_skipped_total",
+            "Stored records skipped by reads because they could not be decoded"
+        )
+        .unwrap()
+    });
+    &COUNTER
+}
+
+pub fn observe_undecodable_record_skipped() {
+    undecodable_records_skipped_counter().inc();
+}
+
+pub fn undecodable_records_skipped() -> u64 {
+    undecodable_records_skipped_counter().get()
+}
+
+fn follower_lag_gauge() -> &'static IntGaugeVec {
+    static GAUGE: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+        register_int_gauge_vec!(
+            "s2_follower_lag_batches",
+            "Durable record batches not yet received by the slowest follower of a stream",
+            &["basin", "stream"]
+        )
+        .unwrap()
+    });
+    &GAUGE
+}
+
+pub fn observe_follower_lag(basin: &BasinName, stream: &StreamName, lag: usize) {
+    follower_lag_gauge()
+        .with_label_values(&[basin.as_ref(), stream.as_ref()])
+        .set(lag as i64);
+}
+
+pub fn follower_lag(basin: &BasinName, stream: &StreamName) -> i64 {
+    follower_lag_gauge()
+        .with_label_values(&[basin.as_ref(), stream.as_ref()])
+        .get()
+}
+
+pub fn remove_follower_lag(basin: &BasinName, stream: &StreamName) {
+    let _ = follower_lag_gauge().remove_label_values(&[basin.as_ref(), stream.as_ref()]);
+}
+
+/// Kind of streaming read connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReaderType {
+    Sse,
+    S2s,
+    Ws,
+}
+
+impl ReaderType {
+    fn as_label(self) -> &'static str {
+        match self {
+            Self::Sse => "sse",
+            Self::S2s => "s2s",
+            Self::Ws => "ws",
+        }
+    }
+}
+
+fn active_readers_gauge() -> &'static IntGaugeVec {
+    static GAUGE: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+        register_int_gauge_vec!(
+            "s2_stream_active_readers",
+            "Streaming read connections currently open",
+            &["type"]
+        )
+        .unwrap()
+    });
+    &GAUGE
+}
+
+/// Counts a streaming read connection as active until dropped.
+#[derive(Debug)]
+pub struct ActiveReaderGuard(ReaderType);
+
+impl ActiveReaderGuard {
+    pub fn new(reader: ReaderType) -> Self {
+        active_readers_gauge()
+            .with_label_values(&[reader.as_label()])
+            .inc();
+        Self(reader)
+    }
+}
+
+impl Drop for ActiveReaderGuard {
+    fn drop(&mut self) {
+        active_readers_gauge()
+            .with_label_values(&[self.0.as_label()])
+            .dec();
+    }
+}
+
+pub fn active_readers(reader: ReaderType) -> i64 {
+    active_readers_gauge()
+        .with_label_values(&[reader.as_label()])
+        .get()
+}
 
 pub fn observe_append_permit_latency(latency: Duration) {
     static HISTOGRAM: LazyLock<Histogram> = LazyLock::new(|| {
@@ -0,0 +1,285 @@
+//! Fixture seeding: basins, streams and records to create at startup.
+//!
+//! Loaded at startup when `--seed` is set. Records already in a stream are assumed to be its
+//! leading fixture records, so seeding resumes from the tail: restarting against persistent
+//! storage neither duplicates records nor leaves an interrupted seed incomplete.
+
+use std::path::Path;
+
+use s2_api::{data::Format, v1::stream::AppendRecord as JsonAppendRecord};
+use s2_common::{
+    basin::BasinName,
+    caps,
+    record::{MeteredSize, SeqNum},
+    stream::{AppendInput, AppendRecord, AppendRecordBatch, StreamName},
+};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::{backend::Backend, init};
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Fixtures {
+    #[serde(default)]
+    pub basins: Vec<BasinFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BasinFixture {
+    pub name: BasinName,
+    #[serde(default)]
+    pub config: Option<s2_resource_spec::BasinConfig>,
+    #[serde(default)]
+    pub streams: Vec<StreamFixture>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StreamFixture {
+    pub name: StreamName,
+    #[serde(default)]
+    pub config: Option<s2_resource_spec::StreamConfig>,
+    /// Encoding of record header and body strings: `raw` (the default) or `base64`.
+    #[serde(default)]
+    pub format: Option<String>,
+    /// Records in the same JSON shape as the `append` API.
+    #[serde(default)]
+    pub records: Vec<JsonAppendRecord>,
+}
+
+pub fn load(path: &Path) -> eyre::Result<Fixtures> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| eyre::eyre!("failed to read seed file {:?}: {}", path, e))?;
+    let fixtures: Fixtures = serde_json::from_str(&contents)
+        .map_err(|e| eyre::eyre!("failed to parse seed file {:?}: {}", path, e))?;
+    Ok(fixtures)
+}
+
+pub async fn apply(backend: &Backend, fixtures: Fixtures) -> eyre::Result<()> {
+    let mut resources = s2_resource_spec::Resources::default();
+    let mut seeds = Vec::new();
+    for basin in fixtures.basins {
+        let mut streams = Vec::with_capacity(basin.streams.len());
+        for stream in basin.streams {
+            if !stream.records.is_empty() {
+                seeds.push((
+                    basin.name.clone(),
+                    stream.name.clone(),
+                    stream.format,
+                    stream.records,
+                ));
+            }
+            streams.push(s2_resource_spec::Stream {
+                name: stream.name,
+                config: stream.config,
+            });
+        }
+        resources.basins.push(s2_resource_spec::Basin {
+            name: basin.name,
+            config: basin.config,
+            streams,
+        });
+    }
+
+    init::apply(backend, resources).await?;
+
+    for (basin, stream, format, records) in seeds {
+        let format = match format {
+            Some(format) => format.parse::<Format>().map_err(|e| {
+                eyre::eyre!(
+                    "invalid record format {:?} for stream {:?}/{:?}",
+                    e.to_string(),
+                    basin.as_ref(),
+                    stream.as_ref()
+                )
+            })?,
+            None => Format::default(),
+        };
+        let records = records
+            .into_iter()
+            .map(|record| record.decode(format))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                eyre::eyre!(
+                    "invalid record for stream {:?}/{:?}: {}",
+                    basin.as_ref(),
+                    stream.as_ref(),
+                    e
+                )
+            })?;
+        seed_stream(backend, &basin, &stream, records).await?;
+    }
+    Ok(())
+}
+
+async fn seed_stream(
+    backend: &Backend,
+    basin: &BasinName,
+    stream: &StreamName,
+    records: Vec<AppendRecord>,
+) -> eyre::Result<()> {
+    let seed_error = |e: &dyn std::fmt::Display| {
+        eyre::eyre!(
+            "failed to seed stream {:?}/{:?}: {}",
+            basin.as_ref(),
+            stream.as_ref(),
+            e
+        )
+    };
+
+    let tail = backend
+        .open_for_check_tail(basin, stream)
+        .await
+        .map_err(|e| seed_error(&e))?
+        .check_tail()
+        .await
+        .map_err(|e| seed_error(&e))?;
+    let seeded = usize::try_from(tail.seq_num).unwrap_or(usize::MAX);
+    if seeded >= records.len() {
+        info!(
+            basin = basin.as_ref(),
+            stream = stream.as_ref(),
+            "stream already seeded"
+        );
+        return Ok(());
+    }
+
+    let mut next_seq_num: SeqNum = tail.seq_num;
+    for records in batches(records.into_iter().skip(seeded).collect()) {
+        // A mismatch means something other than the seed is writing to the stream, so fail
+        // rather than interleave fixture records with its writes.
+        let input = AppendInput {
+            records,
+            match_seq_num: Some(next_seq_num),
+            fencing_token: None,
+        };
+        let ack = backend
+            .open_for_append(basin, stream, None)
+            .await
+            .map_err(|e| seed_error(&e))?
+            .append(input)
+            .await
+            .map_err(|e| seed_error(&e))?;
+        next_seq_num = ack.end.seq_num;
+    }
+
+    info!(
+        basin = basin.as_ref(),
+        stream = stream.as_ref(),
+        resumed_from = tail.seq_num,
+        records = next_seq_num,
+        "stream seeded"
+    );
+    Ok(())
+}
+
+/// Split `records` into as few batches as the append limits allow.
+fn batches(records: Vec<AppendRecord>) -> Vec<AppendRecordBatch> {
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut batch_bytes = 0;
+    for record in records {
+        let record_bytes = record.metered_size();
+        if !batch.is_empty()
+            && (batch.len() == caps::RECORD_BATCH_MAX.count
+                || batch_bytes + record_bytes > caps::RECORD_BATCH_MAX.bytes)
+        {
+            batches.push(
+                AppendRecordBatch::try_from(std::mem::take(&mut batch))
+                    .expect("batch within limits"),
+            );
+            batch_bytes = 0;
+        }
+        batch_bytes += record_bytes;
+        batch.push(record);
+    }
+    if !batch.is_empty() {
+        batches.push(AppendRecordBatch::try_from(batch).expect("batch within limits"));
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use bytesize::ByteSize;
+    use s2_common::{
+        record::{Metered, Record},
+        stream::AppendRecordParts,
+    };
+    use slatedb::{Db, object_store::memory::InMemory};
+
+    use super::*;
+
+    fn record(body: Vec<u8>) -> AppendRecord {
+        AppendRecordParts {
+            timestamp: None,
+            record: Metered::from(Record::try_from_parts(vec![], Bytes::from(body)).unwrap()),
+        }
+        .try_into()
+        .unwrap()
+    }
+
+    #[test]
+    fn batches_respect_count_limit() {
+        let records = (0..2_500).map(|_| record(vec![])).collect();
+        let sizes: Vec<_> = batches(records).iter().map(|batch| batch.len()).collect();
+        assert_eq!(sizes, [1_000, 1_000, 500]);
+    }
+
+    #[test]
+    fn batches_respect_bytes_limit() {
+        let records = (0..3).map(|_| record(vec![0; 400 * 1024])).collect();
+        let sizes: Vec<_> = batches(records).iter().map(|batch| batch.len()).collect();
+        assert_eq!(sizes, [2, 1]);
+    }
+
+    fn fixtures(num_records: usize) -> Fixtures {
+        let records: Vec<_> = (0..num_records)
+            .map(|i| serde_json::json!({ "body": format!("record-{i}") }))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "basins": [{
+                "name": "seed-basin",
+                "streams": [{ "name": "events", "records": records }],
+            }],
+        }))
+        .expect("valid fixtures")
+    }
+
+    async fn tail(backend: &Backend, basin: &BasinName, stream: &StreamName) -> SeqNum {
+        backend
+            .open_for_check_tail(basin, stream)
+            .await
+            .expect("open stream")
+            .check_tail()
+            .await
+            .expect("check tail")
+            .seq_num
+    }
+
+    #[tokio::test]
+    async fn seeding_resumes_from_tail_and_is_idempotent() {
+        let db = Db::builder("/test", Arc::new(InMemory::new()))
+            .build()
+            .await
+            .expect("db");
+        let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
+        let basin: BasinName = "seed-basin".parse().unwrap();
+        let stream: StreamName = "events".parse().unwrap();
+
+        // An interrupted seed that only appended the first two records.
+        apply(&backend, fixtures(2)).await.expect("partial seed");
+        assert_eq!(tail(&backend, &basin, &stream).await, 2);
+
+        apply(&backend, fixtures(5)).await.expect("resumed seed");
+        assert_eq!(tail(&backend, &basin, &stream).await, 5);
+
+        apply(&backend, fixtures(5)).await.expect("repeated seed");
+        assert_eq!(tail(&backend, &basin, &stream).await, 5);
+    }
+}
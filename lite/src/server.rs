@@ -1,10 +1,11 @@
 use std::{
-    net::SocketAddr,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
     sync::Arc,
     time::{Duration, SystemTime},
 };
 
+use axum::extract::DefaultBodyLimit;
 use axum_server::tls_rustls::RustlsConfig;
 use bytesize::ByteSize;
 use http::header::AUTHORIZATION;
@@ -16,9 +17,16 @@ use tower_http::{
     sensitive_headers::SetSensitiveRequestHeadersLayer,
     trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
 };
-use tracing::info;
-
-use crate::{backend::Backend, handlers, init};
+use tracing::{info, warn};
+
+use crate::{
+    backend::{Backend, FOLLOWER_MAX_LAG, MAX_UNARY_READ_WAIT},
+    handlers::{
+        self,
+        auth::{self, BearerToken, RequiredTokens},
+    },
+    init, seed,
+};
 
 #[derive(clap::Args, Debug, Clone)]
 pub struct TlsConfig {
@@ -45,6 +53,13 @@ pub struct LiteArgs {
     #[arg(long)]
     pub bucket: Option<String>,
 
+    /// Back the database with in-memory storage, which is lost when the server exits.
+    ///
+    /// This is already the default without --bucket or --local-root, but being explicit guards
+    /// against a deployment silently running without persistence.
+    #[arg(long, conflicts_with_all = ["bucket", "local_root"])]
+    pub in_memory: bool,
+
     /// Root directory to back the database on the local filesystem.
     ///
     /// Conflicts with --bucket.
@@ -59,10 +74,31 @@ pub struct LiteArgs {
     #[command(flatten)]
     pub tls: TlsConfig,
 
+    /// IP address to listen on.
+    ///
+    /// Use 127.0.0.1 to only accept connections from this machine.
+    #[arg(long, value_name = "ADDR", default_value_t = IpAddr::V4(Ipv4Addr::UNSPECIFIED))]
+    pub bind: IpAddr,
+
     /// Port to listen on [default: 443 if HTTPS configured, otherwise 80 for HTTP]
     #[arg(long)]
     pub port: Option<u16>,
 
+    /// Require clients to authenticate with one of these bearer tokens.
+    ///
+    /// Repeat the flag or separate tokens with commas. Requests without a matching
+    /// `Authorization: Bearer <token>` header are rejected with 401, except for the
+    /// /health and /ping probes. Can also be set via S2LITE_REQUIRE_TOKEN environment
+    /// variable.
+    #[arg(
+        long = "require-token",
+        value_name = "TOKEN",
+        env = "S2LITE_REQUIRE_TOKEN",
+        value_delimiter = ',',
+        hide_env_values = true
+    )]
+    pub require_tokens: Vec<BearerToken>,
+
     /// Disable permissive CORS headers.
     ///
     /// By default, Lite sends CORS headers that allow browser-based clients
@@ -80,9 +116,77 @@ pub struct LiteArgs {
     #[arg(long, env = "S2LITE_INIT_FILE")]
     pub init_file: Option<PathBuf>,
 
+    /// Path to a JSON fixtures file of basins, streams and records to seed at startup.
+    ///
+    /// Streams are created as with --init-file, and their records are appended
+    /// only while the stream is still empty, so restarts do not duplicate them.
+    #[arg(long, value_name = "FILE")]
+    pub seed: Option<PathBuf>,
+
     /// Maximum in-flight append metered bytes across all streams before admission blocks.
     #[arg(long, default_value = "128MiB")]
     pub append_inflight_bytes: ByteSize,
+
+    /// Maximum metered bytes of recently read records to cache in memory. Set to 0 to disable.
+    #[arg(long, default_value = "64MiB")]
+    pub read_cache_bytes: ByteSize,
+
+    /// Approximate in-memory bytes that in-flight appends may hold across all streams before
+    /// appends block. Set to 0 to disable.
+    #[arg(long, default_value = "0")]
+    pub append_memory_budget_bytes: ByteSize,
+
+    /// Approximate in-memory bytes that prefetched read batches may hold across all streams
+    /// before read-ahead stalls. Kept separate from the append budget so that slow readers
+    /// cannot block appends. Set to 0 to disable.
+    #[arg(long, default_value = "0")]
+    pub read_ahead_memory_budget_bytes: ByteSize,
+
+    /// Maximum size of a buffered request body, such as a unary append. Larger requests are
+    /// rejected with 413.
+    ///
+    /// The default comfortably fits a maximal append batch (1000 records, 1 MiB) even after
+    /// JSON and base64 encoding overhead. Streaming append sessions are not subject to this limit.
+    #[arg(long, default_value = "4MiB")]
+    pub max_request_body_bytes: ByteSize,
+
+    /// Number of read batches to prefetch while earlier batches are being delivered to a
+    /// client. Set to 0 to disable.
+    #[arg(long, default_value_t = 4)]
+    pub read_ahead_batches: usize,
+
+    /// Number of durable record batches a following read session may fall behind a stream's
+    /// tail before it has to catch up from storage.
+    #[arg(long, default_value_t = FOLLOWER_MAX_LAG)]
+    pub follower_max_lag: usize,
+
+    /// Maximum number of seconds a non-streaming read may wait for new records, i.e. the cap on
+    /// long polling.
+    #[arg(long, default_value_t = MAX_UNARY_READ_WAIT.as_secs())]
+    pub max_unary_read_wait_secs: u64,
+
+    /// Number of seconds a deleted stream is retained, during which it can be undeleted, before
+    /// its data is purged. Zero purges streams as soon as they are deleted.
+    #[arg(long, default_value_t = 0)]
+    pub stream_deletion_grace_period_secs: u64,
+
+    /// Maximum number of streams a basin may contain, including streams whose deletion is
+    /// pending. Unbounded if not set.
+    #[arg(long)]
+    pub max_streams_per_basin: Option<usize>,
+
+    /// Check every stream's records, timestamp index, tail and trim point for inconsistencies
+    /// before serving requests.
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub fsck: Option<FsckMode>,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsckMode {
+    /// Only report inconsistencies.
+    Report,
+    /// Report inconsistencies and repair timestamp index mismatches.
+    Repair,
 }
 
 #[derive(Debug, Clone)]
@@ -137,17 +241,22 @@ impl ServerProtocol {
     }
 }
 
-fn cli_endpoint(protocol: ServerProtocol, port: u16) -> String {
-    format!("{}://localhost:{port}", protocol.scheme())
+fn cli_endpoint(protocol: ServerProtocol, addr: SocketAddr) -> String {
+    if addr.ip().is_unspecified() {
+        format!("{}://localhost:{}", protocol.scheme(), addr.port())
+    } else {
+        format!("{}://{addr}", protocol.scheme())
+    }
 }
 
-fn cli_env_hint(protocol: ServerProtocol, port: u16) -> String {
-    let endpoint = cli_endpoint(protocol, port);
+fn cli_env_hint(protocol: ServerProtocol, addr: SocketAddr, token_required: bool) -> String {
+    let endpoint = cli_endpoint(protocol, addr);
+    let token = if token_required { "<token>" } else { "ignored" };
     let mut lines = vec![
         "copy/paste into a new terminal to point the S2 CLI at this server:".to_string(),
         format!("export S2_ACCOUNT_ENDPOINT={endpoint}"),
         format!("export S2_BASIN_ENDPOINT={endpoint}"),
-        "export S2_ACCESS_TOKEN=ignored".to_string(),
+        format!("export S2_ACCESS_TOKEN={token}"),
     ];
 
     if protocol.requires_ssl_no_verify() {
@@ -162,10 +271,12 @@ pub async fn run(args: LiteArgs) -> eyre::Result<()> {
 
     let protocol = ServerProtocol::from_args(&args);
     let port = args.port.unwrap_or_else(|| protocol.default_port());
-    let addr = format!("0.0.0.0:{port}");
-    let cli_hint = cli_env_hint(protocol, port);
+    let addr = SocketAddr::new(args.bind, port);
+    let cli_hint = cli_env_hint(protocol, addr, !args.require_tokens.is_empty());
 
-    let store_type = if let Some(bucket) = args.bucket {
+    let store_type = if args.in_memory {
+        StoreType::InMemory
+    } else if let Some(bucket) = args.bucket {
         StoreType::S3Bucket(bucket)
     } else if let Some(local_root) = args.local_root {
         StoreType::LocalFileSystem(local_root)
@@ -197,8 +308,36 @@ pub async fn run(args: LiteArgs) -> eyre::Result<()> {
 
     tokio::time::sleep(manifest_poll_interval).await;
 
-    info!(%args.append_inflight_bytes, "starting backend");
-    let backend = Backend::new(db, args.append_inflight_bytes);
+    info!(
+        %args.append_inflight_bytes,
+        %args.read_cache_bytes,
+        %args.append_memory_budget_bytes,
+        %args.read_ahead_memory_budget_bytes,
+        args.read_ahead_batches,
+        args.follower_max_lag,
+        args.max_unary_read_wait_secs,
+        "starting backend"
+    );
+    let backend = Backend::new(db, args.append_inflight_bytes, args.read_cache_bytes)
+        .with_append_memory_budget(args.append_memory_budget_bytes)
+        .with_read_ahead_memory_budget(args.read_ahead_memory_budget_bytes)
+        .with_read_ahead_batches(args.read_ahead_batches)
+        .with_follower_max_lag(args.follower_max_lag)
+        .with_max_unary_read_wait(Duration::from_secs(args.max_unary_read_wait_secs))
+        .with_stream_deletion_grace_period(Duration::from_secs(
+            args.stream_deletion_grace_period_secs,
+        ))
+        .with_max_streams_per_basin(args.max_streams_per_basin);
+
+    if let Some(mode) = args.fsck {
+        fsck(&backend, mode == FsckMode::Repair).await?;
+    }
+
+    let indexed = backend.backfill_stream_creation_index().await?;
+    if indexed > 0 {
+        info!(indexed, "backfilled stream creation index");
+    }
+
     crate::backend::bgtasks::spawn(&backend);
 
     if let Some(init_file) = &args.init_file {
@@ -206,8 +345,27 @@ pub async fn run(args: LiteArgs) -> eyre::Result<()> {
         init::apply(&backend, spec).await?;
     }
 
+    if let Some(seed_file) = &args.seed {
+        let fixtures = seed::load(seed_file)?;
+        seed::apply(&backend, fixtures).await?;
+    }
+
     let mut app = handlers::router()
-        .with_state(backend)
+        .with_state(backend.clone())
+        .layer(DefaultBodyLimit::max(
+            args.max_request_body_bytes.as_u64() as usize
+        ));
+    if !args.require_tokens.is_empty() {
+        info!(
+            tokens = args.require_tokens.len(),
+            "requiring bearer token authentication"
+        );
+        app = app.layer(axum::middleware::from_fn_with_state(
+            RequiredTokens::new(args.require_tokens.clone()),
+            auth::require_token,
+        ));
+    }
+    let mut app = app
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(DefaultMakeSpan::new().level(tracing::Level::INFO))
@@ -224,7 +382,7 @@ pub async fn run(args: LiteArgs) -> eyre::Result<()> {
     }
 
     let server_handle = axum_server::Handle::new();
-    tokio::spawn(shutdown_signal(server_handle.clone()));
+    tokio::spawn(shutdown_signal(server_handle.clone(), backend.clone()));
     match (
         args.tls.tls_self,
         args.tls.tls_cert.clone(),
@@ -232,20 +390,20 @@ pub async fn run(args: LiteArgs) -> eyre::Result<()> {
     ) {
         (false, Some(cert_path), Some(key_path)) => {
             info!(
-                addr,
+                %addr,
                 ?cert_path,
                 "starting https server with provided certificate"
             );
             let rustls_config = RustlsConfig::from_pem_file(cert_path, key_path).await?;
             info!("{}", cli_hint);
-            axum_server::bind_rustls(addr.parse()?, rustls_config)
+            axum_server::bind_rustls(addr, rustls_config)
                 .handle(server_handle)
                 .serve(app.into_make_service())
                 .await?;
         }
         (true, None, None) => {
             info!(
-                addr,
+                %addr,
                 "starting https server with self-signed certificate, clients will need to use --insecure"
             );
             let rcgen::CertifiedKey { cert, signing_key } = rcgen::generate_simple_self_signed([
@@ -259,15 +417,15 @@ pub async fn run(args: LiteArgs) -> eyre::Result<()> {
             )
             .await?;
             info!("{}", cli_hint);
-            axum_server::bind_rustls(addr.parse()?, rustls_config)
+            axum_server::bind_rustls(addr, rustls_config)
                 .handle(server_handle)
                 .serve(app.into_make_service())
                 .await?;
         }
         (false, None, None) => {
-            info!(addr, "starting plain http server");
+            info!(%addr, "starting plain http server");
             info!("{}", cli_hint);
-            axum_server::bind(addr.parse()?)
+            axum_server::bind(addr)
                 .handle(server_handle)
                 .serve(app.into_make_service())
                 .await?;
@@ -278,6 +436,27 @@ pub async fn run(args: LiteArgs) -> eyre::Result<()> {
         }
     }
 
+    info!("waiting for background tasks and in-flight appends");
+    backend.shutdown().await?;
+    info!("shutdown complete");
+
+    Ok(())
+}
+
+async fn fsck(backend: &Backend, repair: bool) -> eyre::Result<()> {
+    info!(repair, "checking streams for inconsistencies");
+    let reports = backend.fsck(repair).await?;
+    let mut issues = 0;
+    for (basin, stream, report) in &reports {
+        for issue in &report.issues {
+            warn!(%basin, %stream, ?issue, "fsck issue");
+        }
+        issues += report.issues.len();
+        if report.repaired > 0 {
+            info!(%basin, %stream, report.repaired, "fsck repaired issues");
+        }
+    }
+    info!(streams = reports.len(), issues, "fsck complete");
     Ok(())
 }
 
@@ -347,13 +526,13 @@ async fn init_object_store(
             )?)
         }
         StoreType::InMemory => {
-            info!("using in-memory object store");
+            warn!("using in-memory object store, data is not persisted and is lost on exit");
             Arc::new(object_store::memory::InMemory::new())
         }
     })
 }
 
-async fn shutdown_signal(handle: axum_server::Handle<SocketAddr>) {
+async fn shutdown_signal(handle: axum_server::Handle<SocketAddr>, backend: Backend) {
     let ctrl_c = async {
         tokio::signal::ctrl_c().await.expect("ctrl-c");
     };
@@ -378,6 +557,8 @@ async fn shutdown_signal(handle: axum_server::Handle<SocketAddr>) {
         },
     }
 
+    // Reject new appends right away so that append sessions wind down within the grace period.
+    backend.initiate_shutdown();
     handle.graceful_shutdown(Some(Duration::from_secs(10)));
 }
 
@@ -445,24 +626,42 @@ impl object_store::CredentialProvider for S3CredentialProvider {
 
 #[cfg(test)]
 mod tests {
+    use std::net::SocketAddr;
+
     use super::{ServerProtocol, cli_endpoint, cli_env_hint};
 
+    fn any_addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([0, 0, 0, 0], port))
+    }
+
     #[test]
     fn cli_endpoint_uses_localhost_with_explicit_port() {
         assert_eq!(
-            cli_endpoint(ServerProtocol::Http, 80),
+            cli_endpoint(ServerProtocol::Http, any_addr(80)),
             "http://localhost:80"
         );
         assert_eq!(
-            cli_endpoint(ServerProtocol::Https { self_signed: false }, 443),
+            cli_endpoint(ServerProtocol::Https { self_signed: false }, any_addr(443)),
             "https://localhost:443"
         );
     }
 
+    #[test]
+    fn cli_endpoint_uses_specific_bind_address() {
+        assert_eq!(
+            cli_endpoint(ServerProtocol::Http, "127.0.0.1:8080".parse().unwrap()),
+            "http://127.0.0.1:8080"
+        );
+        assert_eq!(
+            cli_endpoint(ServerProtocol::Http, "[::1]:8080".parse().unwrap()),
+            "http://[::1]:8080"
+        );
+    }
+
     #[test]
     fn cli_env_hint_includes_exports_for_http() {
         assert_eq!(
-            cli_env_hint(ServerProtocol::Http, 8080),
+            cli_env_hint(ServerProtocol::Http, any_addr(8080), false),
             concat!(
                 "copy/paste into a new terminal to point the S2 CLI at this server:\n",
                 "export S2_ACCOUNT_ENDPOINT=http://localhost:8080\n",
@@ -475,7 +674,11 @@ mod tests {
     #[test]
     fn cli_env_hint_includes_ssl_no_verify_for_self_signed_tls() {
         assert_eq!(
-            cli_env_hint(ServerProtocol::Https { self_signed: true }, 8443),
+            cli_env_hint(
+                ServerProtocol::Https { self_signed: true },
+                any_addr(8443),
+                false
+            ),
             concat!(
                 "copy/paste into a new terminal to point the S2 CLI at this server:\n",
                 "export S2_ACCOUNT_ENDPOINT=https://localhost:8443\n",
@@ -485,4 +688,12 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn cli_env_hint_asks_for_token_when_required() {
+        assert!(
+            cli_env_hint(ServerProtocol::Http, any_addr(8080), true)
+                .ends_with("export S2_ACCESS_TOKEN=<token>")
+        );
+    }
 }
@@ -2,6 +2,12 @@ use s2_common::{basin::BasinName, stream::StreamName};
 use s2_storage::bash::Bash;
 
 /// Unique identifier for a stream scoped by its basin.
+///
+/// Derived deterministically by hashing the basin and stream names, so a recreated stream gets the
+/// same id. Recreation is only possible once deletion has been finalized, which removes all keys
+/// under the id atomically with the stream's metadata, so records of a prior incarnation can never
+/// be aliased. Distinct names mapping to the same id are detected on creation against the
+/// `StreamIdMapping` entry.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct StreamId(Bash);
 
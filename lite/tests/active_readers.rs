@@ -0,0 +1,80 @@
+//! Kept in its own test binary, as the active reader gauge is process-wide and would otherwise
+//! be moved by streaming reads in concurrently running tests.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use bytesize::ByteSize;
+use s2_common::{
+    basin::{BASIN_HEADER, BasinName},
+    config::{BasinConfig, OptionalStreamConfig},
+    resources::ProvisionMode,
+    stream::StreamName,
+};
+use s2_lite::{
+    backend::Backend,
+    handlers,
+    metrics::{self, ReaderType},
+};
+use slatedb::{Db, object_store::memory::InMemory};
+use tower::ServiceExt as _;
+
+#[tokio::test]
+async fn sse_read_is_counted_while_its_response_is_alive() {
+    let object_store = Arc::new(InMemory::new());
+    let db = Db::builder("/active-readers", object_store)
+        .build()
+        .await
+        .expect("create in-memory db");
+    let backend = Backend::new(db, ByteSize::mib(10), ByteSize::mib(10));
+
+    let basin: BasinName = "active-readers".parse().unwrap();
+    backend
+        .provision_basin(
+            basin.clone(),
+            BasinConfig::default(),
+            ProvisionMode::CreateOnly {
+                request_token: None,
+            },
+        )
+        .await
+        .expect("create basin");
+    let stream: StreamName = "active-readers".parse().unwrap();
+    backend
+        .provision_stream(
+            basin.clone(),
+            stream.clone(),
+            OptionalStreamConfig::default(),
+            ProvisionMode::CreateOnly {
+                request_token: None,
+            },
+        )
+        .await
+        .expect("create stream");
+    let app = handlers::router().with_state(backend);
+
+    assert_eq!(metrics::active_readers(ReaderType::Sse), 0);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri(format!("/v1/streams/{stream}/records?seq_num=0"))
+                .header(BASIN_HEADER.as_str(), basin.as_ref())
+                .header(header::ACCEPT, "text/event-stream")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("request should complete");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(metrics::active_readers(ReaderType::Sse), 1);
+    assert_eq!(metrics::active_readers(ReaderType::S2s), 0);
+    assert_eq!(metrics::active_readers(ReaderType::Ws), 0);
+
+    drop(response);
+    assert_eq!(metrics::active_readers(ReaderType::Sse), 0);
+}
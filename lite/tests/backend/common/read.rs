@@ -17,6 +17,7 @@ pub fn read_all_bounds() -> (ReadStart, ReadEnd) {
         ReadStart {
             from: ReadFrom::SeqNum(0),
             clamp: false,
+            error_if_trimmed: false,
         },
         ReadEnd {
             limit: ReadLimit::Unbounded,
@@ -6,7 +6,7 @@ use s2_common::{
     basin::BasinName,
     config::{BasinConfig, OptionalStreamConfig},
     encryption::{EncryptionAlgorithm, EncryptionKey, EncryptionSpec},
-    record::{CommandRecord, FencingToken, Metered, Record, Timestamp},
+    record::{CommandRecord, FencingToken, Metered, Record, SeqNum, Timestamp},
     resources::ProvisionMode,
     stream::{
         AppendAck, AppendInput, AppendRecord, AppendRecordBatch, AppendRecordParts, StreamName,
@@ -35,7 +35,7 @@ pub async fn create_in_memory_db() -> Db {
 
 pub async fn create_backend() -> Backend {
     let db = create_in_memory_db().await;
-    Backend::new(db, ByteSize::mib(10))
+    Backend::new(db, ByteSize::mib(10), ByteSize::mib(10))
 }
 
 pub fn test_basin_name(suffix: &str) -> BasinName {
@@ -133,6 +133,15 @@ pub fn create_fencing_command_record(token: FencingToken) -> AppendRecord {
     parts.try_into().unwrap()
 }
 
+pub fn create_trim_command_record(trim_point: SeqNum) -> AppendRecord {
+    let record = Metered::from(Record::Command(CommandRecord::Trim(trim_point)));
+    let parts = AppendRecordParts {
+        timestamp: None,
+        record,
+    };
+    parts.try_into().unwrap()
+}
+
 pub fn create_test_record_batch(bodies: Vec<Bytes>) -> AppendRecordBatch {
     let records: Vec<AppendRecord> = bodies.into_iter().map(create_test_record).collect();
     records.try_into().unwrap()
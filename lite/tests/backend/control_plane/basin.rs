@@ -4,14 +4,17 @@ use s2_common::{
     basin::{BasinNamePrefix, BasinNameStartAfter, ListBasinsRequest},
     config::{
         BasinConfig, BasinReconfiguration, OptionalDeleteOnEmptyConfig, OptionalStreamConfig,
-        RetentionPolicy, StorageClass, StreamReconfiguration, TimestampingMode,
-        TimestampingReconfiguration,
+        OptionalTimestampingConfig, RetentionPolicy, StorageClass, StreamReconfiguration,
+        TimestampingMode, TimestampingReconfiguration,
     },
     maybe::Maybe,
     resources::{ProvisionMode, ProvisionResult, RequestToken},
 };
-use s2_lite::backend::error::{
-    DeleteBasinError, GetBasinConfigError, ProvisionBasinError, ReconfigureBasinError,
+use s2_lite::{
+    backend::error::{
+        DeleteBasinError, GetBasinConfigError, ProvisionBasinError, ReconfigureBasinError,
+    },
+    metrics::{self, BasinConfigCacheLookup},
 };
 
 use super::common::*;
@@ -367,6 +370,93 @@ async fn test_reconfigure_basin_updates_nested_defaults() {
     assert!(fetched.create_stream_on_read);
 }
 
+#[tokio::test]
+async fn test_reconfigure_basin_leaves_omitted_fields_unchanged() {
+    let backend = create_backend().await;
+    let initial_config = BasinConfig {
+        default_stream_config: OptionalStreamConfig {
+            storage_class: Some(StorageClass::Standard),
+            retention_policy: Some(RetentionPolicy::Infinite()),
+            timestamping: OptionalTimestampingConfig {
+                mode: Some(TimestampingMode::ClientPrefer),
+                uncapped: Some(true),
+            },
+            ..Default::default()
+        },
+        create_stream_on_append: true,
+        ..Default::default()
+    };
+    let basin_name = create_test_basin(
+        &backend,
+        "basin-partial-reconfigure",
+        initial_config.clone(),
+    )
+    .await;
+
+    let reconfig = BasinReconfiguration {
+        default_stream_config: Maybe::from(Some(StreamReconfiguration {
+            timestamping: Maybe::from(Some(TimestampingReconfiguration {
+                mode: Maybe::from(Some(TimestampingMode::Arrival)),
+                ..Default::default()
+            })),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+    let updated = backend
+        .reconfigure_basin(basin_name.clone(), reconfig)
+        .await
+        .expect("Failed to reconfigure basin");
+
+    let mut expected = initial_config;
+    expected.default_stream_config.timestamping.mode = Some(TimestampingMode::Arrival);
+    assert_eq!(updated, expected);
+
+    let fetched = backend
+        .get_basin_config(basin_name)
+        .await
+        .expect("Failed to fetch basin config after reconfigure");
+    assert_eq!(fetched, expected);
+}
+
+#[tokio::test]
+async fn test_get_basin_config_is_cached_until_reconfigured() {
+    let backend = create_backend().await;
+    let basin_name =
+        create_test_basin(&backend, "basin-config-cache", BasinConfig::default()).await;
+
+    let initial = backend
+        .get_basin_config(basin_name.clone())
+        .await
+        .expect("Failed to fetch basin config");
+    assert!(!initial.create_stream_on_append);
+
+    let hits_before = metrics::basin_config_cache_lookups(BasinConfigCacheLookup::Hit);
+    let cached = backend
+        .get_basin_config(basin_name.clone())
+        .await
+        .expect("Failed to fetch cached basin config");
+    assert_eq!(cached, initial);
+    assert!(metrics::basin_config_cache_lookups(BasinConfigCacheLookup::Hit) > hits_before);
+
+    backend
+        .reconfigure_basin(
+            basin_name.clone(),
+            BasinReconfiguration {
+                create_stream_on_append: Maybe::from(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to reconfigure basin");
+
+    let fetched = backend
+        .get_basin_config(basin_name)
+        .await
+        .expect("Failed to fetch basin config after reconfigure");
+    assert!(fetched.create_stream_on_append);
+}
+
 #[tokio::test]
 async fn test_delete_basin_marks_deleting_and_blocks_create() {
     let backend = create_backend().await;
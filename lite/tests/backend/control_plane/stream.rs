@@ -1,13 +1,15 @@
 use std::time::Duration;
 
 use bytes::Bytes;
+use futures::StreamExt;
 use s2_common::{
     config::{
-        BasinConfig, BasinReconfiguration, DeleteOnEmptyReconfiguration,
+        BasinConfig, BasinReconfiguration, CompressionHint, DeleteOnEmptyReconfiguration,
         OptionalDeleteOnEmptyConfig, OptionalStreamConfig, OptionalTimestampingConfig,
-        RetentionPolicy, StorageClass, StreamReconfiguration, TimestampingMode,
+        RetentionPolicy, StorageClass, StreamConfig, StreamReconfiguration, TimestampingMode,
         TimestampingReconfiguration,
     },
+    consumer::ConsumerName,
     encryption::EncryptionAlgorithm,
     maybe::Maybe,
     resources::{ProvisionMode, ProvisionResult, RequestToken},
@@ -16,9 +18,13 @@ use s2_common::{
         StreamNameStartAfter,
     },
 };
-use s2_lite::backend::error::{
-    AppendError, CheckTailError, DeleteStreamError, GetStreamConfigError, ProvisionStreamError,
-    ReadError, ReconfigureStreamError,
+use s2_lite::backend::{
+    StreamConfigChange, bgtasks,
+    error::{
+        AppendError, CheckTailError, CommitStreamCursorError, DeleteStreamError,
+        GetStreamConfigError, GetStreamCursorError, ProvisionStreamError, ReadError,
+        ReconfigureStreamError,
+    },
 };
 
 use super::common::*;
@@ -75,6 +81,148 @@ async fn test_create_stream_honors_basin_defaults() {
     assert_eq!(config.timestamping.mode, TimestampingMode::ClientRequire);
 }
 
+#[tokio::test]
+async fn test_create_stream_without_basin_defaults_uses_system_defaults() {
+    let backend = create_backend().await;
+    let basin_name =
+        create_test_basin(&backend, "stream-system-defaults", BasinConfig::default()).await;
+    let stream_name = create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-system-defaults",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    let config = backend
+        .get_stream_config(basin_name, stream_name)
+        .await
+        .expect("Failed to fetch stream config");
+    assert_eq!(config, StreamConfig::default());
+}
+
+#[tokio::test]
+async fn test_create_stream_partial_override_inherits_remaining_basin_defaults() {
+    let backend = create_backend().await;
+    let basin_name = create_test_basin(
+        &backend,
+        "stream-partial-override",
+        BasinConfig {
+            default_stream_config: OptionalStreamConfig {
+                storage_class: Some(StorageClass::Standard),
+                retention_policy: Some(RetentionPolicy::Infinite()),
+                timestamping: OptionalTimestampingConfig {
+                    mode: Some(TimestampingMode::ClientRequire),
+                    uncapped: Some(true),
+                },
+                delete_on_empty: OptionalDeleteOnEmptyConfig {
+                    min_age: Some(Duration::from_secs(300)),
+                },
+                min_retained_records: Some(10),
+                max_future_skew_secs: Some(30),
+                compression_hint: Some(CompressionHint::Zstd),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+    let stream_name = create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-partial-override",
+        OptionalStreamConfig {
+            retention_policy: Some(RetentionPolicy::Age(Duration::from_secs(3600))),
+            timestamping: OptionalTimestampingConfig {
+                mode: Some(TimestampingMode::Arrival),
+                uncapped: None,
+            },
+            min_retained_records: Some(0),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let config = backend
+        .get_stream_config(basin_name, stream_name)
+        .await
+        .expect("Failed to fetch stream config");
+    // Overridden by the stream.
+    assert_eq!(
+        config.retention_policy,
+        RetentionPolicy::Age(Duration::from_secs(3600))
+    );
+    assert_eq!(config.timestamping.mode, TimestampingMode::Arrival);
+    assert_eq!(config.min_retained_records, 0);
+    // Inherited from the basin, including nested fields the stream left unset.
+    assert!(config.timestamping.uncapped);
+    assert_eq!(config.storage_class, StorageClass::Standard);
+    assert_eq!(config.delete_on_empty.min_age, Duration::from_secs(300));
+    assert_eq!(config.max_future_skew_secs, 30);
+    assert_eq!(config.compression_hint, CompressionHint::Zstd);
+    // Unset at both levels.
+    assert_eq!(config.max_append_bytes_per_sec, 0);
+    assert_eq!(config.max_append_ops_per_sec, 0);
+}
+
+#[tokio::test]
+async fn test_basin_default_changes_apply_only_to_new_streams() {
+    let backend = create_backend().await;
+    let basin_name = create_test_basin(
+        &backend,
+        "stream-default-changes",
+        BasinConfig {
+            default_stream_config: OptionalStreamConfig {
+                retention_policy: Some(RetentionPolicy::Infinite()),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+    let stream_name = create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-default-changes",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    let new_retention = RetentionPolicy::Age(Duration::from_secs(60));
+    backend
+        .reconfigure_basin(
+            basin_name.clone(),
+            BasinReconfiguration {
+                default_stream_config: Maybe::from(Some(StreamReconfiguration {
+                    retention_policy: Maybe::from(Some(new_retention)),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to reconfigure basin");
+
+    let next_stream = create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-default-changes-next",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    let original = backend
+        .get_stream_config(basin_name.clone(), stream_name)
+        .await
+        .expect("Failed to fetch original stream config");
+    let next = backend
+        .get_stream_config(basin_name, next_stream)
+        .await
+        .expect("Failed to fetch new stream config");
+    assert_eq!(original.retention_policy, RetentionPolicy::Infinite());
+    assert_eq!(next.retention_policy, new_retention);
+}
+
 #[tokio::test]
 async fn test_create_stream_defaults_to_no_encryption_algorithm() {
     let backend = create_backend().await;
@@ -89,7 +237,7 @@ async fn test_create_stream_defaults_to_no_encryption_algorithm() {
     .await;
 
     let page = backend
-        .list_streams(basin_name, ListStreamsRequest::default())
+        .list_streams(basin_name, ListStreamsRequest::default(), None)
         .await
         .expect("Failed to list streams");
     let info = page
@@ -121,7 +269,7 @@ async fn test_create_stream_uses_basin_cipher() {
     .await;
 
     let page = backend
-        .list_streams(basin_name, ListStreamsRequest::default())
+        .list_streams(basin_name, ListStreamsRequest::default(), None)
         .await;
     let page = page.expect("Failed to list streams");
     let info = page
@@ -172,7 +320,7 @@ async fn test_existing_stream_keeps_cipher_after_basin_reconfigure() {
     .await;
 
     let page = backend
-        .list_streams(basin_name, ListStreamsRequest::default())
+        .list_streams(basin_name, ListStreamsRequest::default(), None)
         .await
         .expect("Failed to list streams");
     let original = page
@@ -583,6 +731,93 @@ async fn test_reconfigure_stream_updates_selected_fields() {
     assert!(fetched.timestamping.uncapped);
 }
 
+#[tokio::test]
+async fn test_reconfigure_stream_leaves_omitted_fields_unchanged() {
+    let (backend, basin_name, stream_name) = setup_backend_with_stream(
+        "stream-partial-reconfigure",
+        "stream-partial-reconfigure",
+        OptionalStreamConfig {
+            storage_class: Some(StorageClass::Express),
+            retention_policy: Some(RetentionPolicy::Age(Duration::from_secs(60))),
+            timestamping: OptionalTimestampingConfig {
+                mode: Some(TimestampingMode::ClientRequire),
+                uncapped: Some(true),
+            },
+            delete_on_empty: OptionalDeleteOnEmptyConfig {
+                min_age: Some(Duration::from_secs(120)),
+            },
+            min_retained_records: Some(5),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let before = backend
+        .get_stream_config(basin_name.clone(), stream_name.clone())
+        .await
+        .expect("Failed to fetch stream config");
+
+    let reconfig = StreamReconfiguration {
+        timestamping: Maybe::from(Some(TimestampingReconfiguration {
+            mode: Maybe::from(Some(TimestampingMode::Arrival)),
+            ..Default::default()
+        })),
+        max_append_ops_per_sec: Maybe::from(Some(10)),
+        ..Default::default()
+    };
+    let updated = backend
+        .reconfigure_stream(basin_name.clone(), stream_name.clone(), reconfig)
+        .await
+        .expect("Failed to reconfigure stream");
+
+    let mut expected = before;
+    expected.timestamping.mode = TimestampingMode::Arrival;
+    expected.max_append_ops_per_sec = 10;
+    assert_eq!(updated, expected);
+    assert!(updated.timestamping.uncapped);
+
+    let fetched = backend
+        .get_stream_config(basin_name, stream_name)
+        .await
+        .expect("Failed to fetch stream config after reconfigure");
+    assert_eq!(fetched, expected);
+}
+
+#[tokio::test]
+async fn test_reconfigure_stream_publishes_config_change() {
+    let (backend, basin_name, stream_name) = setup_backend_with_stream(
+        "stream-config-watch",
+        "stream-config-watch",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+    let mut changes = backend.subscribe_stream_configs();
+
+    let reconfig = StreamReconfiguration {
+        storage_class: Maybe::from(Some(StorageClass::Express)),
+        ..Default::default()
+    };
+    backend
+        .reconfigure_stream(basin_name.clone(), stream_name.clone(), reconfig)
+        .await
+        .expect("Failed to reconfigure stream");
+
+    let change = changes
+        .try_recv()
+        .expect("config change should be published");
+    let StreamConfigChange::Updated {
+        basin,
+        stream,
+        config,
+    } = change
+    else {
+        panic!("expected an update, got {change:?}");
+    };
+    assert_eq!(basin, basin_name);
+    assert_eq!(stream, stream_name);
+    assert_eq!(config.storage_class, StorageClass::Express);
+}
+
 #[tokio::test]
 async fn test_reconfigure_stream_clears_fields_to_basin_defaults() {
     let backend = create_backend().await;
@@ -599,6 +834,11 @@ async fn test_reconfigure_stream_clears_fields_to_basin_defaults() {
             delete_on_empty: OptionalDeleteOnEmptyConfig {
                 min_age: Some(Duration::from_secs(300)),
             },
+            min_retained_records: Some(10),
+            max_append_bytes_per_sec: Some(1024 * 1024),
+            max_append_ops_per_sec: Some(100),
+            max_future_skew_secs: Some(30),
+            compression_hint: Some(CompressionHint::Zstd),
         },
         ..Default::default()
     };
@@ -625,6 +865,11 @@ async fn test_reconfigure_stream_clears_fields_to_basin_defaults() {
         delete_on_empty: OptionalDeleteOnEmptyConfig {
             min_age: Some(Duration::ZERO),
         },
+        min_retained_records: Some(1),
+        max_append_bytes_per_sec: Some(1024),
+        max_append_ops_per_sec: Some(1),
+        max_future_skew_secs: Some(1),
+        compression_hint: Some(CompressionHint::Gzip),
     };
 
     backend
@@ -649,6 +894,11 @@ async fn test_reconfigure_stream_clears_fields_to_basin_defaults() {
         delete_on_empty: Maybe::from(Some(DeleteOnEmptyReconfiguration {
             min_age: Maybe::from(None),
         })),
+        min_retained_records: Maybe::from(None),
+        max_append_bytes_per_sec: Maybe::from(None),
+        max_append_ops_per_sec: Maybe::from(None),
+        max_future_skew_secs: Maybe::from(None),
+        compression_hint: Maybe::from(None),
     };
 
     let updated = backend
@@ -661,6 +911,11 @@ async fn test_reconfigure_stream_clears_fields_to_basin_defaults() {
     assert_eq!(updated.timestamping.mode, TimestampingMode::Arrival);
     assert!(updated.timestamping.uncapped);
     assert_eq!(updated.delete_on_empty.min_age, Duration::from_secs(300));
+    assert_eq!(updated.min_retained_records, 10);
+    assert_eq!(updated.max_append_bytes_per_sec, 1024 * 1024);
+    assert_eq!(updated.max_append_ops_per_sec, 100);
+    assert_eq!(updated.max_future_skew_secs, 30);
+    assert_eq!(updated.compression_hint, CompressionHint::Zstd);
 
     let fetched = backend
         .get_stream_config(basin_name, stream_name)
@@ -799,7 +1054,7 @@ async fn test_delete_stream_marks_deleted_and_blocks_recreation() {
         .unwrap();
 
     let page = backend
-        .list_streams(basin_name.clone(), ListStreamsRequest::default())
+        .list_streams(basin_name.clone(), ListStreamsRequest::default(), None)
         .await
         .expect("Failed to list streams");
     let info = page
@@ -842,6 +1097,262 @@ async fn test_delete_stream_marks_deleted_and_blocks_recreation() {
         .expect("Second delete should be idempotent");
 }
 
+#[tokio::test]
+async fn test_recreate_stream_after_deletion_starts_empty() {
+    let backend = create_backend().await;
+    bgtasks::spawn(&backend);
+    let basin_name = create_test_basin(&backend, "stream-recreate", BasinConfig::default()).await;
+
+    for cycle in 0..3u8 {
+        let stream_name = create_test_stream(
+            &backend,
+            &basin_name,
+            "stream-recreate",
+            OptionalStreamConfig::default(),
+        )
+        .await;
+        let tail = check_tail(&backend, basin_name.clone(), stream_name.clone())
+            .await
+            .expect("Failed to check tail");
+        assert_eq!(tail.seq_num, 0);
+
+        let ack = append_payloads(&backend, &basin_name, &stream_name, &[&[cycle], &[cycle]]).await;
+        assert_eq!(ack.start.seq_num, 0);
+        assert_eq!(ack.end.seq_num, 2);
+
+        let (start, end) = read_all_bounds();
+        let records = read_records(&backend, &basin_name, &stream_name, start, end).await;
+        assert_eq!(envelope_bodies(&records), vec![vec![cycle], vec![cycle]]);
+
+        backend
+            .delete_stream(basin_name.clone(), stream_name.clone())
+            .await
+            .expect("Failed to delete stream");
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !matches!(
+                backend
+                    .get_stream_config(basin_name.clone(), stream_name.clone())
+                    .await,
+                Err(GetStreamConfigError::StreamNotFound(_))
+            ) {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("Stream deletion should be finalized");
+    }
+}
+
+#[tokio::test]
+async fn test_purge_stream_bypasses_grace_period() {
+    let backend = create_backend()
+        .await
+        .with_stream_deletion_grace_period(Duration::from_secs(3600));
+    let basin_name = create_test_basin(&backend, "stream-purge", BasinConfig::default()).await;
+    let stream_name = create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-purge",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    append_payloads(&backend, &basin_name, &stream_name, &[b"sensitive"]).await;
+
+    backend
+        .purge_stream(basin_name.clone(), stream_name.clone())
+        .await
+        .expect("Failed to purge stream");
+
+    let page = backend
+        .list_streams(basin_name.clone(), ListStreamsRequest::default(), None)
+        .await
+        .expect("Failed to list streams");
+    assert!(page.values.iter().all(|info| info.name != stream_name));
+
+    let config_result = backend
+        .get_stream_config(basin_name.clone(), stream_name.clone())
+        .await;
+    assert!(matches!(
+        config_result,
+        Err(GetStreamConfigError::StreamNotFound(_))
+    ));
+
+    create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-purge",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+    let tail = check_tail(&backend, basin_name, stream_name)
+        .await
+        .expect("Failed to check tail of recreated stream");
+    assert_eq!(tail.seq_num, 0);
+}
+
+#[tokio::test]
+async fn test_create_stream_rejected_at_basin_stream_quota() {
+    let backend = create_backend().await.with_max_streams_per_basin(Some(2));
+    let basin_name = create_test_basin(
+        &backend,
+        "stream-quota",
+        BasinConfig {
+            create_stream_on_append: true,
+            ..Default::default()
+        },
+    )
+    .await;
+    let existing = create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-quota-1",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+    create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-quota-2",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    let result = backend
+        .provision_stream(
+            basin_name.clone(),
+            test_stream_name("stream-quota-3"),
+            OptionalStreamConfig::default(),
+            ProvisionMode::CreateOnly {
+                request_token: None,
+            },
+        )
+        .await;
+    let Err(ProvisionStreamError::StreamQuotaExceeded(e)) = result else {
+        panic!("expected stream quota error, got {result:?}");
+    };
+    assert_eq!(e.basin, basin_name);
+    assert_eq!(e.limit, 2);
+
+    let result = backend
+        .open_for_append(&basin_name, &test_stream_name("stream-quota-3"), None)
+        .await;
+    assert!(matches!(result, Err(AppendError::StreamQuotaExceeded(_))));
+
+    let ensured = backend
+        .provision_stream(
+            basin_name.clone(),
+            existing,
+            OptionalStreamConfig {
+                storage_class: Some(StorageClass::Express),
+                ..Default::default()
+            },
+            ProvisionMode::Ensure,
+        )
+        .await
+        .expect("Ensuring an existing stream is not subject to the quota");
+    assert!(matches!(ensured, ProvisionResult::Updated(_)));
+
+    let other_basin =
+        create_test_basin(&backend, "stream-quota-other", BasinConfig::default()).await;
+    create_test_stream(
+        &backend,
+        &other_basin,
+        "stream-quota-3",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_concurrent_creates_do_not_exceed_basin_stream_quota() {
+    let backend = create_backend().await.with_max_streams_per_basin(Some(1));
+    let basin_name =
+        create_test_basin(&backend, "stream-quota-concurrent", BasinConfig::default()).await;
+
+    let results = futures::future::join_all((0..8).map(|i| {
+        backend.provision_stream(
+            basin_name.clone(),
+            test_stream_name(&format!("stream-quota-concurrent-{i}")),
+            OptionalStreamConfig::default(),
+            ProvisionMode::CreateOnly {
+                request_token: None,
+            },
+        )
+    }))
+    .await;
+    for result in &results {
+        assert!(
+            matches!(
+                result,
+                Ok(ProvisionResult::Created(_))
+                    | Err(ProvisionStreamError::StreamQuotaExceeded(_))
+                    | Err(ProvisionStreamError::TransactionConflict(_))
+            ),
+            "unexpected result {result:?}"
+        );
+    }
+
+    let page = backend
+        .list_streams(basin_name, ListStreamsRequest::default(), None)
+        .await
+        .expect("Failed to list streams");
+    assert!(
+        page.values.len() <= 1,
+        "{} streams exist despite a quota of 1",
+        page.values.len()
+    );
+}
+
+#[tokio::test]
+async fn test_purged_stream_frees_basin_stream_quota() {
+    let backend = create_backend()
+        .await
+        .with_max_streams_per_basin(Some(1))
+        .with_stream_deletion_grace_period(Duration::from_secs(3600));
+    let basin_name =
+        create_test_basin(&backend, "stream-quota-purge", BasinConfig::default()).await;
+    let stream_name = create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-quota-purge",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    backend
+        .delete_stream(basin_name.clone(), stream_name.clone())
+        .await
+        .expect("Failed to delete stream");
+    let result = backend
+        .provision_stream(
+            basin_name.clone(),
+            test_stream_name("stream-quota-purge-2"),
+            OptionalStreamConfig::default(),
+            ProvisionMode::CreateOnly {
+                request_token: None,
+            },
+        )
+        .await;
+    assert!(
+        matches!(result, Err(ProvisionStreamError::StreamQuotaExceeded(_))),
+        "a stream pending deletion still counts towards the quota"
+    );
+
+    backend
+        .purge_stream(basin_name.clone(), stream_name)
+        .await
+        .expect("Failed to purge stream");
+    create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-quota-purge-2",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+}
+
 #[tokio::test]
 async fn test_delete_stream_allows_plaintext_command_records_on_encrypted_only_stream() {
     let backend = create_backend().await;
@@ -874,7 +1385,7 @@ async fn test_delete_stream_allows_plaintext_command_records_on_encrypted_only_s
         .expect("Failed to delete encrypted-only stream");
 
     let page = backend
-        .list_streams(basin_name, ListStreamsRequest::default())
+        .list_streams(basin_name, ListStreamsRequest::default(), None)
         .await
         .expect("Failed to list streams");
     let info = page
@@ -931,6 +1442,7 @@ async fn test_delete_stream_blocks_data_operations() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd::default();
     let read_result = try_open_read_session(&backend, &basin_name, &stream_name, start, end).await;
@@ -940,6 +1452,139 @@ async fn test_delete_stream_blocks_data_operations() {
     ));
 }
 
+#[tokio::test]
+async fn test_soft_deleted_stream_blocks_data_operations_until_undeleted() {
+    let backend = create_backend()
+        .await
+        .with_stream_deletion_grace_period(Duration::from_secs(3600));
+
+    let basin_name = create_test_basin(
+        &backend,
+        "stream-soft-delete-blocks",
+        BasinConfig::default(),
+    )
+    .await;
+    let stream_name = create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-soft-delete-blocks",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    let input = || AppendInput {
+        records: create_test_record_batch(vec![Bytes::from_static(b"record")]),
+        match_seq_num: None,
+        fencing_token: None,
+    };
+    let (input_tx, input_rx) = futures::channel::mpsc::unbounded();
+    let mut session = append_session(
+        &backend,
+        basin_name.clone(),
+        stream_name.clone(),
+        None,
+        input_rx,
+    )
+    .await
+    .expect("Failed to open append session");
+    input_tx.unbounded_send(input()).unwrap();
+    session
+        .next()
+        .await
+        .expect("Append session ended early")
+        .expect("Append before deletion should succeed");
+
+    backend
+        .delete_stream(basin_name.clone(), stream_name.clone())
+        .await
+        .expect("Failed to delete stream");
+
+    // The session was opened before the deletion, so the streamer itself must refuse the append.
+    input_tx.unbounded_send(input()).unwrap();
+    assert!(matches!(
+        session.next().await,
+        Some(Err(AppendError::StreamDeletionPending(_)))
+    ));
+
+    assert!(matches!(
+        check_tail(&backend, basin_name.clone(), stream_name.clone()).await,
+        Err(CheckTailError::StreamDeletionPending(_))
+    ));
+    let (start, end) = read_all_bounds();
+    assert!(matches!(
+        try_open_read_session(&backend, &basin_name, &stream_name, start, end).await,
+        Err(ReadError::StreamDeletionPending(_))
+    ));
+    assert!(matches!(
+        backend
+            .open_for_read_positions(&basin_name, &stream_name)
+            .await,
+        Err(ReadError::StreamDeletionPending(_))
+    ));
+
+    backend
+        .undelete_stream(basin_name.clone(), stream_name.clone())
+        .await
+        .expect("Failed to undelete stream");
+    append(
+        &backend,
+        basin_name.clone(),
+        stream_name.clone(),
+        input(),
+        None,
+    )
+    .await
+    .expect("Append after undeletion should succeed");
+    let tail = check_tail(&backend, basin_name, stream_name)
+        .await
+        .expect("Failed to check tail");
+    assert_eq!(tail.seq_num, 2);
+}
+
+#[tokio::test]
+async fn test_delete_stream_removes_follower_lag_metric() {
+    let backend = create_backend().await;
+
+    let basin_name = create_test_basin(&backend, "stream-delete-lag", BasinConfig::default()).await;
+    let stream_name = create_test_stream(
+        &backend,
+        &basin_name,
+        "stream-delete-lag",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    let input = AppendInput {
+        records: create_test_record_batch(vec![Bytes::from_static(b"lag")]),
+        match_seq_num: None,
+        fencing_token: None,
+    };
+    append(
+        &backend,
+        basin_name.clone(),
+        stream_name.clone(),
+        input,
+        None,
+    )
+    .await
+    .expect("Failed to append");
+
+    let has_lag_series = || {
+        let text = s2_lite::metrics::gather().expect("gather metrics");
+        let label = format!("stream=\"{stream_name}\"");
+        String::from_utf8_lossy(&text)
+            .lines()
+            .any(|line| line.starts_with("s2_follower_lag_batches{") && line.contains(&label))
+    };
+    assert!(has_lag_series());
+
+    backend
+        .delete_stream(basin_name.clone(), stream_name.clone())
+        .await
+        .expect("Failed to delete stream");
+    assert!(!has_lag_series());
+}
+
 #[tokio::test]
 async fn test_get_stream_config_for_deleting_stream_returns_pending() {
     let backend = create_backend().await;
@@ -965,6 +1610,88 @@ async fn test_get_stream_config_for_deleting_stream_returns_pending() {
     ));
 }
 
+#[tokio::test]
+async fn test_stream_cursor_commit_and_get() {
+    let (backend, basin_name, stream_name) =
+        setup_backend_with_stream("stream-cursor", "stream-cursor", Default::default()).await;
+    let consumer: ConsumerName = "group-a".parse().unwrap();
+    let other_consumer: ConsumerName = "group-b".parse().unwrap();
+
+    let cursor = backend
+        .get_stream_cursor(basin_name.clone(), stream_name.clone(), consumer.clone())
+        .await
+        .expect("Failed to get cursor");
+    assert_eq!(cursor, None);
+
+    for seq_num in [5, 3] {
+        backend
+            .commit_stream_cursor(
+                basin_name.clone(),
+                stream_name.clone(),
+                consumer.clone(),
+                seq_num,
+            )
+            .await
+            .expect("Failed to commit cursor");
+    }
+
+    let cursor = backend
+        .get_stream_cursor(basin_name.clone(), stream_name.clone(), consumer)
+        .await
+        .expect("Failed to get cursor");
+    assert_eq!(cursor, Some(3));
+
+    let cursor = backend
+        .get_stream_cursor(basin_name, stream_name, other_consumer)
+        .await
+        .expect("Failed to get cursor");
+    assert_eq!(cursor, None);
+}
+
+#[tokio::test]
+async fn test_stream_cursor_errors_for_missing_and_deleting_stream() {
+    let (backend, basin_name, stream_name) = setup_backend_with_stream(
+        "stream-cursor-errors",
+        "stream-cursor-errors",
+        Default::default(),
+    )
+    .await;
+    let consumer: ConsumerName = "group-a".parse().unwrap();
+
+    let result = backend
+        .get_stream_cursor(
+            basin_name.clone(),
+            test_stream_name("missing"),
+            consumer.clone(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(GetStreamCursorError::StreamNotFound(_))
+    ));
+
+    backend
+        .delete_stream(basin_name.clone(), stream_name.clone())
+        .await
+        .expect("Failed to delete stream");
+
+    let result = backend
+        .get_stream_cursor(basin_name.clone(), stream_name.clone(), consumer.clone())
+        .await;
+    assert!(matches!(
+        result,
+        Err(GetStreamCursorError::StreamDeletionPending(_))
+    ));
+
+    let result = backend
+        .commit_stream_cursor(basin_name, stream_name, consumer, 1)
+        .await;
+    assert!(matches!(
+        result,
+        Err(CommitStreamCursorError::StreamDeletionPending(_))
+    ));
+}
+
 #[tokio::test]
 async fn test_delete_stream_nonexistent_returns_not_found() {
     let backend = create_backend().await;
@@ -986,7 +1713,7 @@ async fn test_list_streams_empty() {
     let basin_name = create_test_basin(&backend, "empty-streams", BasinConfig::default()).await;
 
     let page = backend
-        .list_streams(basin_name.clone(), ListStreamsRequest::default())
+        .list_streams(basin_name.clone(), ListStreamsRequest::default(), None)
         .await
         .expect("Failed to list streams");
 
@@ -1010,7 +1737,7 @@ async fn test_list_streams_multiple() {
     }
 
     let page = backend
-        .list_streams(basin_name.clone(), ListStreamsRequest::default())
+        .list_streams(basin_name.clone(), ListStreamsRequest::default(), None)
         .await
         .expect("Failed to list streams");
 
@@ -1051,6 +1778,7 @@ async fn test_list_streams_pagination() {
                 start_after: StreamNameStartAfter::default(),
                 limit: 5.into(),
             },
+            None,
         )
         .await
         .expect("Failed to list streams page 1");
@@ -1076,6 +1804,7 @@ async fn test_list_streams_pagination() {
                 start_after: page1.values.last().unwrap().name.clone().into(),
                 limit: 5.into(),
             },
+            None,
         )
         .await
         .expect("Failed to list streams page 2");
@@ -1101,6 +1830,7 @@ async fn test_list_streams_pagination() {
                 start_after: page2.values.last().unwrap().name.clone().into(),
                 limit: 5.into(),
             },
+            None,
         )
         .await
         .expect("Failed to list streams page 3");
@@ -1113,6 +1843,129 @@ async fn test_list_streams_pagination() {
     );
 }
 
+#[tokio::test]
+async fn test_list_streams_by_creation() {
+    let backend = create_backend().await;
+    let basin_name =
+        create_test_basin(&backend, "stream-by-creation", BasinConfig::default()).await;
+
+    for name in ["zeta", "alpha", "other-mid", "beta"] {
+        create_test_stream(&backend, &basin_name, name, OptionalStreamConfig::default()).await;
+        // Creation times are indexed with millisecond precision.
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    let list = |after: Option<&s2_common::stream::StreamInfo>, prefix: &str, limit: usize| {
+        backend.list_streams_by_creation(
+            basin_name.clone(),
+            ListStreamsRequest {
+                prefix: prefix.parse().unwrap(),
+                start_after: after
+                    .map(|info| info.name.clone().into())
+                    .unwrap_or_default(),
+                limit: limit.into(),
+            },
+            after.map(|info| info.created_at),
+            None,
+        )
+    };
+    let names = |page: &s2_common::resources::Page<s2_common::stream::StreamInfo>| {
+        page.values
+            .iter()
+            .map(|info| info.name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let page1 = list(None, "", 2).await.expect("Failed to list page 1");
+    assert!(page1.has_more);
+    assert_eq!(names(&page1), vec!["test-stream-zeta", "test-stream-alpha"]);
+
+    // Listing resumes from the position of the last stream, even once it no longer exists.
+    let (zeta, alpha) = (&page1.values[0], &page1.values[1]);
+    backend
+        .purge_stream(basin_name.clone(), alpha.name.clone())
+        .await
+        .expect("Failed to purge stream");
+    let page2 = list(Some(alpha), "", 2)
+        .await
+        .expect("Failed to list page 2");
+    assert!(!page2.has_more);
+    assert_eq!(
+        names(&page2),
+        vec!["test-stream-other-mid", "test-stream-beta"]
+    );
+
+    let filtered = list(Some(zeta), "test-stream-other-", 10)
+        .await
+        .expect("Failed to list with prefix");
+    assert_eq!(names(&filtered), vec!["test-stream-other-mid"]);
+}
+
+#[tokio::test]
+async fn test_list_streams_filters_by_deletion_state() {
+    let backend = create_backend()
+        .await
+        .with_stream_deletion_grace_period(Duration::from_secs(3600));
+    let basin_name = create_test_basin(&backend, "stream-list-state", BasinConfig::default()).await;
+
+    let mut streams = Vec::new();
+    for name in ["alpha", "beta", "gamma"] {
+        streams.push(
+            create_test_stream(&backend, &basin_name, name, OptionalStreamConfig::default()).await,
+        );
+        // Creation times are indexed with millisecond precision.
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    backend
+        .delete_stream(basin_name.clone(), streams[0].clone())
+        .await
+        .expect("Failed to delete stream");
+
+    let request = |limit: usize| ListStreamsRequest {
+        prefix: StreamNamePrefix::default(),
+        start_after: StreamNameStartAfter::default(),
+        limit: limit.into(),
+    };
+    let names = |page: &s2_common::resources::Page<s2_common::stream::StreamInfo>| {
+        page.values
+            .iter()
+            .map(|info| info.name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let deleting = backend
+        .list_streams(basin_name.clone(), request(10), Some(true))
+        .await
+        .expect("Failed to list deleting streams");
+    assert_eq!(names(&deleting), vec!["test-stream-alpha"]);
+
+    // The limit applies after filtering, so the deleting stream does not take up the page.
+    let active = backend
+        .list_streams(basin_name.clone(), request(2), Some(false))
+        .await
+        .expect("Failed to list active streams");
+    assert!(!active.has_more);
+    assert_eq!(
+        names(&active),
+        vec!["test-stream-beta", "test-stream-gamma"]
+    );
+
+    let active = backend
+        .list_streams_by_creation(basin_name.clone(), request(10), None, Some(false))
+        .await
+        .expect("Failed to list active streams by creation");
+    assert_eq!(
+        names(&active),
+        vec!["test-stream-beta", "test-stream-gamma"]
+    );
+
+    let all = backend
+        .list_streams(basin_name, request(10), None)
+        .await
+        .expect("Failed to list streams");
+    assert_eq!(all.values.len(), 3);
+}
+
 #[tokio::test]
 async fn test_list_streams_prefix_filter() {
     let backend = create_backend().await;
@@ -1155,6 +2008,7 @@ async fn test_list_streams_prefix_filter() {
                 start_after: StreamNameStartAfter::default(),
                 limit: Default::default(),
             },
+            None,
         )
         .await
         .expect("Failed to list streams with prefix");
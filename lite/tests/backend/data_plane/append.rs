@@ -7,12 +7,13 @@ use s2_common::{
     basin::BasinName,
     config::{OptionalStreamConfig, OptionalTimestampingConfig, TimestampingMode},
     encryption::EncryptionSpec,
-    record::FencingToken,
+    record::{CommandRecord, FencingToken},
+    resources::ListLimit,
     stream::{AppendAck, AppendInput, AppendRecordBatch, StreamName},
 };
 use s2_lite::backend::{
     Backend,
-    error::{AppendConditionFailedError, AppendError},
+    error::{AppendConditionFailedError, AppendError, AppendRateLimitedError},
 };
 
 use super::common::*;
@@ -401,6 +402,224 @@ async fn test_append_with_seq_num_mismatch() {
     ));
 }
 
+#[tokio::test]
+async fn test_append_to_deleting_stream_fails_fast() {
+    let (backend, basin_name, stream_name) = setup_backend_with_stream(
+        "append-deleting",
+        "deleting",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    // Warm up the streamer so the rejection does not rely on streamer initialization.
+    append_payloads(&backend, &basin_name, &stream_name, &[b"before delete"]).await;
+
+    backend
+        .delete_stream(basin_name.clone(), stream_name.clone())
+        .await
+        .expect("Failed to delete stream");
+
+    let input = AppendInput {
+        records: create_test_record_batch(vec![Bytes::from_static(b"after delete")]),
+        match_seq_num: None,
+        fencing_token: None,
+    };
+    let result = append(
+        &backend,
+        basin_name.clone(),
+        stream_name.clone(),
+        input,
+        None,
+    )
+    .await;
+    assert!(matches!(result, Err(AppendError::StreamDeletionPending(_))));
+
+    let result = append_session(
+        &backend,
+        basin_name,
+        stream_name,
+        None,
+        futures::stream::empty(),
+    )
+    .await;
+    assert!(matches!(result, Err(AppendError::StreamDeletionPending(_))));
+}
+
+#[tokio::test]
+async fn test_append_rejected_after_shutdown_initiated() {
+    let (backend, basin_name, stream_name) = setup_backend_with_stream(
+        "append-shutdown",
+        "shutdown",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    let ack = append_payloads(&backend, &basin_name, &stream_name, &[b"before shutdown"]).await;
+    assert_eq!(ack.end.seq_num, 1);
+
+    backend.initiate_shutdown();
+
+    let input = AppendInput {
+        records: create_test_record_batch(vec![Bytes::from_static(b"after shutdown")]),
+        match_seq_num: None,
+        fencing_token: None,
+    };
+    let result = append(
+        &backend,
+        basin_name.clone(),
+        stream_name.clone(),
+        input,
+        None,
+    )
+    .await;
+    assert!(matches!(result, Err(AppendError::ShuttingDown(_))));
+
+    let tail = check_tail(&backend, basin_name, stream_name)
+        .await
+        .expect("Failed to check tail");
+    assert_eq!(tail.seq_num, 1);
+
+    backend.shutdown().await.expect("Failed to shut down");
+}
+
+#[tokio::test]
+async fn test_append_rate_limited() {
+    let stream_config = OptionalStreamConfig {
+        max_append_ops_per_sec: Some(1),
+        ..Default::default()
+    };
+    let (backend, basin_name, stream_name) =
+        setup_backend_with_stream("append-rate-limit", "ops", stream_config).await;
+
+    let ack = append_payloads(&backend, &basin_name, &stream_name, &[b"first"]).await;
+    assert_eq!(ack.end.seq_num, 1);
+
+    let input = AppendInput {
+        records: create_test_record_batch(vec![Bytes::from_static(b"second")]),
+        match_seq_num: None,
+        fencing_token: None,
+    };
+    let result = append(
+        &backend,
+        basin_name.clone(),
+        stream_name.clone(),
+        input,
+        None,
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(AppendError::RateLimited(AppendRateLimitedError::Ops(1, _)))
+    ));
+
+    let tail = check_tail(&backend, basin_name, stream_name)
+        .await
+        .expect("Failed to check tail");
+    assert_eq!(tail.seq_num, 1);
+}
+
+#[tokio::test]
+async fn test_list_stream_commands() {
+    let (backend, basin_name, stream_name) =
+        setup_backend_with_stream("append-commands", "audit", OptionalStreamConfig::default())
+            .await;
+
+    let token: FencingToken = "audit-token".parse().unwrap();
+    let records: AppendRecordBatch = vec![
+        create_fencing_command_record(token.clone()),
+        create_test_record(Bytes::from_static(b"data")),
+        create_trim_command_record(0),
+    ]
+    .try_into()
+    .unwrap();
+    let input = AppendInput {
+        records,
+        match_seq_num: None,
+        fencing_token: None,
+    };
+    append(
+        &backend,
+        basin_name.clone(),
+        stream_name.clone(),
+        input,
+        None,
+    )
+    .await
+    .expect("command append should succeed");
+    append_payloads(&backend, &basin_name, &stream_name, &[b"more data"]).await;
+
+    let page = backend
+        .list_stream_commands(basin_name.clone(), stream_name.clone(), 0, 1.into())
+        .await
+        .expect("Failed to list commands");
+    assert!(page.has_more);
+    assert_eq!(page.values.len(), 1);
+    assert_eq!(page.values[0].0.seq_num, 0);
+    assert_eq!(page.values[0].1, CommandRecord::Fence(token));
+
+    let page = backend
+        .list_stream_commands(basin_name, stream_name, 1, ListLimit::default())
+        .await
+        .expect("Failed to list commands");
+    assert!(!page.has_more);
+    assert_eq!(page.values.len(), 1);
+    assert_eq!(page.values[0].0.seq_num, 2);
+    assert_eq!(page.values[0].1, CommandRecord::Trim(0));
+}
+
+#[tokio::test]
+async fn test_append_rejects_timestamp_beyond_future_skew() {
+    let stream_config = OptionalStreamConfig {
+        timestamping: OptionalTimestampingConfig {
+            mode: Some(TimestampingMode::ClientRequire),
+            uncapped: Some(true),
+        },
+        max_future_skew_secs: Some(60),
+        ..Default::default()
+    };
+    let (backend, basin_name, stream_name) =
+        setup_backend_with_stream("append-future-skew", "skew", stream_config).await;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+
+    let far_future = AppendInput {
+        records: create_test_record_batch_with_timestamps(vec![(
+            Bytes::from_static(b"far future"),
+            now + 3_600_000,
+        )]),
+        match_seq_num: None,
+        fencing_token: None,
+    };
+    let result = append(
+        &backend,
+        basin_name.clone(),
+        stream_name.clone(),
+        far_future,
+        None,
+    )
+    .await;
+    assert!(matches!(
+        result,
+        Err(AppendError::TimestampTooFarInFuture(_))
+    ));
+
+    let near_future = AppendInput {
+        records: create_test_record_batch_with_timestamps(vec![(
+            Bytes::from_static(b"near future"),
+            now + 1_000,
+        )]),
+        match_seq_num: None,
+        fencing_token: None,
+    };
+    let ack = append(&backend, basin_name, stream_name, near_future, None)
+        .await
+        .expect("Expected append within skew to succeed");
+    assert_eq!(ack.end.seq_num, 1);
+}
+
 #[rstest]
 #[case::plaintext("append-session-basic", EncryptionSpec::Plain)]
 #[case::encrypted("appsess-enc", aegis256_encryption_spec())]
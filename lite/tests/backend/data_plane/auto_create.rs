@@ -21,7 +21,7 @@ async fn assert_stream_count(
     expected: usize,
 ) {
     let stream_list = backend
-        .list_streams(basin_name.clone(), ListStreamsRequest::default())
+        .list_streams(basin_name.clone(), ListStreamsRequest::default(), None)
         .await
         .expect("Failed to list streams");
     assert_eq!(stream_list.values.len(), expected);
@@ -34,7 +34,7 @@ async fn assert_stream_cipher(
     expected: Option<EncryptionAlgorithm>,
 ) {
     let stream_list = backend
-        .list_streams(basin_name.clone(), ListStreamsRequest::default())
+        .list_streams(basin_name.clone(), ListStreamsRequest::default(), None)
         .await
         .expect("Failed to list streams");
     assert_eq!(stream_list.values.len(), 1);
@@ -159,6 +159,7 @@ async fn test_backend_read_auto_creates_stream() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let _session = open_read_session(
         &backend,
@@ -189,6 +190,7 @@ async fn test_backend_read_without_auto_create_returns_not_found() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let result = try_open_read_session(
         &backend,
@@ -356,6 +358,7 @@ async fn test_backend_read_auto_create_is_race_safe() {
             let start = ReadStart {
                 from: ReadFrom::SeqNum(0),
                 clamp: false,
+                error_if_trimmed: false,
             };
             let end = ReadEnd {
                 limit: ReadLimit::Unbounded,
@@ -20,6 +20,7 @@ async fn test_operations_on_nonexistent_basin() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -96,6 +97,7 @@ async fn test_concurrent_appends_to_same_stream() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -151,6 +153,11 @@ async fn test_concurrent_reconfigure_during_append() {
         retention_policy: s2_common::maybe::Maybe::from(Some(RetentionPolicy::Infinite())),
         timestamping: s2_common::maybe::Maybe::default(),
         delete_on_empty: s2_common::maybe::Maybe::default(),
+        min_retained_records: s2_common::maybe::Maybe::default(),
+        max_append_bytes_per_sec: s2_common::maybe::Maybe::default(),
+        max_append_ops_per_sec: s2_common::maybe::Maybe::default(),
+        max_future_skew_secs: s2_common::maybe::Maybe::default(),
+        compression_hint: s2_common::maybe::Maybe::default(),
     };
 
     let updated_config = backend
@@ -204,6 +211,7 @@ async fn test_concurrent_reads_same_stream() {
             let start = ReadStart {
                 from: ReadFrom::SeqNum(0),
                 clamp: false,
+                error_if_trimmed: false,
             };
             let end = ReadEnd {
                 limit: ReadLimit::Unbounded,
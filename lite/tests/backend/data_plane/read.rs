@@ -5,11 +5,15 @@ use futures::StreamExt;
 use rstest::rstest;
 use s2_common::{
     basin::BasinName,
-    config::{OptionalStreamConfig, OptionalTimestampingConfig, TimestampingMode},
+    config::{
+        CompressionHint, OptionalStreamConfig, OptionalTimestampingConfig, StreamReconfiguration,
+        TimestampingMode,
+    },
     encryption::EncryptionAlgorithm,
+    maybe::Maybe,
     read_extent::{ReadLimit, ReadUntil},
     record::{MeteredSize, StreamPosition},
-    stream::{ReadEnd, ReadFrom, ReadSessionOutput, ReadStart, StreamName},
+    stream::{ReadEnd, ReadFrom, ReadSessionOutput, ReadStart, SeqNumRange, StreamName},
 };
 use s2_lite::backend::{
     Backend,
@@ -172,6 +176,45 @@ async fn test_read_from_beginning() {
     assert_eq!(envelope_bodies(&records), vec![b"test data".to_vec(); 5]);
 }
 
+#[rstest]
+#[case::zstd(CompressionHint::Zstd)]
+#[case::gzip(CompressionHint::Gzip)]
+#[tokio::test]
+async fn test_read_compressed_roundtrip(#[case] compression: CompressionHint) {
+    let (backend, basin_name, stream_name) = setup_backend_with_stream(
+        "read-compressed",
+        "compressed",
+        OptionalStreamConfig {
+            compression_hint: Some(compression),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let large = vec![b'x'; 64 * 1024];
+    append_payloads(&backend, &basin_name, &stream_name, &[&large, b"small"]).await;
+
+    backend
+        .reconfigure_stream(
+            basin_name.clone(),
+            stream_name.clone(),
+            StreamReconfiguration {
+                compression_hint: Maybe::from(Some(CompressionHint::None)),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to reconfigure stream");
+    append_payloads(&backend, &basin_name, &stream_name, &[&large]).await;
+
+    let (start, end) = read_all_bounds();
+    let records = read_records(&backend, &basin_name, &stream_name, start, end).await;
+    assert_eq!(
+        envelope_bodies(&records),
+        vec![large.clone(), b"small".to_vec(), large]
+    );
+}
+
 #[tokio::test]
 async fn test_read_encrypted_roundtrip() {
     let encryption = aegis256_encryption_spec();
@@ -218,6 +261,7 @@ async fn test_read_with_limit() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Count(5),
@@ -233,6 +277,47 @@ async fn test_read_with_limit() {
     );
 }
 
+#[tokio::test]
+async fn test_read_seq_num_range() {
+    let (backend, basin_name, stream_name) = setup_backend_with_stream(
+        "read-seq-num-range",
+        "range",
+        OptionalStreamConfig::default(),
+    )
+    .await;
+
+    let expected_bodies: Vec<_> = (0..10)
+        .map(|i| format!("record-{i}").into_bytes())
+        .collect();
+    for body in &expected_bodies {
+        append_payloads(&backend, &basin_name, &stream_name, &[body.as_slice()]).await;
+    }
+
+    let start = ReadStart {
+        from: ReadFrom::SeqNumRange(SeqNumRange { start: 3, end: 7 }),
+        clamp: false,
+        error_if_trimmed: false,
+    };
+
+    let records = read_records(
+        &backend,
+        &basin_name,
+        &stream_name,
+        start,
+        ReadEnd::default(),
+    )
+    .await;
+
+    assert_eq!(
+        records
+            .iter()
+            .map(|r| r.position().seq_num)
+            .collect::<Vec<_>>(),
+        vec![3, 4, 5, 6]
+    );
+    assert_eq!(envelope_bodies(&records), expected_bodies[3..7].to_vec());
+}
+
 #[tokio::test]
 async fn test_read_unwritten_clamp_behavior() {
     let (backend, basin_name, stream_name) = setup_backend_with_stream(
@@ -248,6 +333,7 @@ async fn test_read_unwritten_clamp_behavior() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(100),
         clamp: false,
+        error_if_trimmed: false,
     };
     let result = try_open_read_session(
         &backend,
@@ -263,6 +349,7 @@ async fn test_read_unwritten_clamp_behavior() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(100),
         clamp: true,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -309,6 +396,7 @@ async fn test_read_at_tail_without_follow_returns_unwritten(
     let start = ReadStart {
         from: tail_read_from(start_case, &ack.end),
         clamp,
+        error_if_trimmed: false,
     };
     let end = tail_read_end(end_case);
     let result = try_open_read_session(&backend, &basin_name, &stream_name, start, end).await;
@@ -342,6 +430,7 @@ async fn test_read_from_tail_offset() {
     let start = ReadStart {
         from: ReadFrom::TailOffset(2),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -373,6 +462,7 @@ async fn test_read_from_timestamp_includes_duplicate_timestamps() {
     let start = ReadStart {
         from: ReadFrom::Timestamp(timestamp),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -398,6 +488,7 @@ async fn test_read_from_tail_times_out_without_new_data() {
     let start = ReadStart {
         from: ReadFrom::TailOffset(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -439,6 +530,7 @@ async fn test_read_from_tail_wait_is_reset_by_new_data() {
     let start = ReadStart {
         from: ReadFrom::TailOffset(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -512,6 +604,7 @@ async fn test_read_with_bytes_limit_exact_fit() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Bytes(exact_limit),
@@ -544,6 +637,7 @@ async fn test_read_with_bytes_limit_smaller_than_first_record_returns_empty() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Bytes(first_size - 1),
@@ -572,6 +666,7 @@ async fn test_read_with_count_or_bytes_limit_count_wins() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::from_count_and_bytes(Some(5), Some(1_000_000)),
@@ -610,6 +705,7 @@ async fn test_read_with_count_or_bytes_limit_bytes_wins() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::from_count_and_bytes(Some(100), Some(per_record_bytes * 3)),
@@ -669,6 +765,7 @@ async fn test_read_until_timestamp_boundaries(
         ReadStart {
             from: ReadFrom::SeqNum(0),
             clamp: false,
+            error_if_trimmed: false,
         },
         ReadEnd {
             limit: ReadLimit::Unbounded,
@@ -741,6 +838,7 @@ async fn test_read_until_with_additional_limits() {
             ReadStart {
                 from: ReadFrom::SeqNum(0),
                 clamp: false,
+                error_if_trimmed: false,
             },
             ReadEnd {
                 limit,
@@ -780,6 +878,7 @@ async fn test_read_timestamp_range_with_from_and_until() {
         ReadStart {
             from: ReadFrom::Timestamp(2000),
             clamp: false,
+            error_if_trimmed: false,
         },
         ReadEnd {
             limit: ReadLimit::Unbounded,
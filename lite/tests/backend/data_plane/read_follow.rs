@@ -32,6 +32,7 @@ async fn run_follow_mode_receives_new_data_case(test_suffix: &str, encryption: &
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let wait_duration = Duration::from_millis(200);
     let first_follow_delay = Duration::from_millis(100);
@@ -142,6 +143,7 @@ async fn test_follow_mode_broadcast_lag_resets_wait_after_db_catchup() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -200,6 +202,7 @@ async fn test_follow_mode_broadcast_lag_respects_count_limit() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Count(count_limit),
@@ -255,6 +258,7 @@ async fn test_follow_mode_broadcast_lag_respects_bytes_limit() {
         ReadStart {
             from: ReadFrom::TailOffset(0),
             clamp: false,
+            error_if_trimmed: false,
         },
         ReadEnd {
             limit: ReadLimit::Bytes(bytes_limit),
@@ -310,6 +314,7 @@ async fn test_follow_mode_broadcast_lag_respects_timestamp_until() {
         ReadStart {
             from: ReadFrom::SeqNum(0),
             clamp: false,
+            error_if_trimmed: false,
         },
         ReadEnd {
             limit: ReadLimit::Unbounded,
@@ -382,6 +387,7 @@ async fn test_follow_mode_broadcast_lag_resumes_live_follow_after_catchup() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -470,6 +476,7 @@ async fn test_transition_from_catchup_to_follow() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -528,6 +535,7 @@ async fn test_follow_mode_survives_streamer_dormancy_after_catchup_batch() {
         ReadStart {
             from: ReadFrom::SeqNum(0),
             clamp: false,
+            error_if_trimmed: false,
         },
         ReadEnd {
             limit: ReadLimit::Unbounded,
@@ -588,6 +596,7 @@ async fn test_follow_mode_with_count_limit() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Count(3),
@@ -645,6 +654,7 @@ async fn test_follow_mode_with_exact_count_limit() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Count(2),
@@ -701,6 +711,7 @@ async fn test_collect_records_until_advanced_stops_at_target_count_with_multi_re
         ReadStart {
             from: ReadFrom::TailOffset(0),
             clamp: false,
+            error_if_trimmed: false,
         },
         ReadEnd {
             limit: ReadLimit::Unbounded,
@@ -760,6 +771,7 @@ async fn test_follow_mode_with_bytes_limit_truncates_live_batch() {
         ReadStart {
             from: ReadFrom::TailOffset(0),
             clamp: false,
+            error_if_trimmed: false,
         },
         ReadEnd {
             limit: ReadLimit::Bytes(per_record_bytes * 2),
@@ -827,6 +839,7 @@ async fn test_follow_mode_with_bytes_limit_smaller_than_first_live_record_closes
         ReadStart {
             from: ReadFrom::TailOffset(0),
             clamp: false,
+            error_if_trimmed: false,
         },
         ReadEnd {
             limit: ReadLimit::Bytes(per_record_bytes - 1),
@@ -885,6 +898,7 @@ async fn test_follow_mode_with_timestamp_until() {
     let start = ReadStart {
         from: ReadFrom::SeqNum(0),
         clamp: false,
+        error_if_trimmed: false,
     };
     let end = ReadEnd {
         limit: ReadLimit::Unbounded,
@@ -63,6 +63,25 @@ pub struct StreamConfig {
     /// Delete-on-empty configuration.
     #[serde(default)]
     pub delete_on_empty: Option<DeleteOnEmpty>,
+    /// Minimum number of records that must remain in the stream after a trim.
+    /// If unspecified or 0, trims are unrestricted.
+    #[serde(default)]
+    pub min_retained_records: Option<u64>,
+    /// Maximum append throughput in metered bytes per second.
+    /// If unspecified or 0, throughput is unrestricted.
+    #[serde(default)]
+    pub max_append_bytes_per_sec: Option<u64>,
+    /// Maximum number of appends per second.
+    /// If unspecified or 0, appends are unrestricted.
+    #[serde(default)]
+    pub max_append_ops_per_sec: Option<u64>,
+    /// Maximum duration in seconds that a client-specified record timestamp may be ahead of the
+    /// arrival time. If unspecified or 0, future timestamps are not rejected.
+    #[serde(default)]
+    pub max_future_skew_secs: Option<u64>,
+    /// Advisory codec for compressing the stream's records at rest.
+    #[serde(default)]
+    pub compression_hint: Option<CompressionHint>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -95,6 +114,38 @@ impl From<StorageClass> for s2_common::config::StorageClass {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompressionHint {
+    None,
+    Zstd,
+    Gzip,
+}
+
+impl schemars::JsonSchema for CompressionHint {
+    fn schema_name() -> Cow<'static, str> {
+        "CompressionHint".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "description": "Advisory codec for compressing the stream's records at rest.",
+            "enum": ["none", "zstd", "gzip"]
+        })
+    }
+}
+
+impl From<CompressionHint> for s2_common::config::CompressionHint {
+    fn from(c: CompressionHint) -> Self {
+        match c {
+            CompressionHint::None => Self::None,
+            CompressionHint::Zstd => Self::Zstd,
+            CompressionHint::Gzip => Self::Gzip,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum EncryptionAlgorithm {
     #[serde(rename = "aegis-256")]
@@ -186,6 +237,7 @@ pub enum TimestampingMode {
     ClientPrefer,
     ClientRequire,
     Arrival,
+    ServerPrefer,
 }
 
 impl schemars::JsonSchema for TimestampingMode {
@@ -197,7 +249,7 @@ impl schemars::JsonSchema for TimestampingMode {
         schemars::json_schema!({
             "type": "string",
             "description": "Timestamping mode for appends that influences how timestamps are handled.",
-            "enum": ["client-prefer", "client-require", "arrival"]
+            "enum": ["client-prefer", "client-require", "arrival", "server-prefer"]
         })
     }
 }
@@ -208,6 +260,7 @@ impl From<TimestampingMode> for s2_common::config::TimestampingMode {
             TimestampingMode::ClientPrefer => Self::ClientPrefer,
             TimestampingMode::ClientRequire => Self::ClientRequire,
             TimestampingMode::Arrival => Self::Arrival,
+            TimestampingMode::ServerPrefer => Self::ServerPrefer,
         }
     }
 }
@@ -291,6 +344,11 @@ impl From<StreamConfig> for s2_common::config::OptionalStreamConfig {
             retention_policy: s.retention_policy.map(|rp| rp.0),
             timestamping: s.timestamping.map(Into::into).unwrap_or_default(),
             delete_on_empty: s.delete_on_empty.map(Into::into).unwrap_or_default(),
+            min_retained_records: s.min_retained_records,
+            max_append_bytes_per_sec: s.max_append_bytes_per_sec,
+            max_append_ops_per_sec: s.max_append_ops_per_sec,
+            max_future_skew_secs: s.max_future_skew_secs,
+            compression_hint: s.compression_hint.map(Into::into),
         }
     }
 }
@@ -610,6 +668,11 @@ mod tests {
             )),
             timestamping: None,
             delete_on_empty: None,
+            min_retained_records: Some(10),
+            max_append_bytes_per_sec: Some(1024),
+            max_append_ops_per_sec: Some(100),
+            max_future_skew_secs: Some(60),
+            compression_hint: Some(CompressionHint::Zstd),
         };
         let config = s2_common::config::OptionalStreamConfig::from(spec);
         assert_eq!(
@@ -628,5 +691,29 @@ mod tests {
             config.delete_on_empty,
             s2_common::config::OptionalDeleteOnEmptyConfig::default()
         );
+        assert_eq!(config.min_retained_records, Some(10));
+        assert_eq!(config.max_append_bytes_per_sec, Some(1024));
+        assert_eq!(config.max_append_ops_per_sec, Some(100));
+        assert_eq!(config.max_future_skew_secs, Some(60));
+        assert_eq!(
+            config.compression_hint,
+            Some(s2_common::config::CompressionHint::Zstd)
+        );
+    }
+
+    #[test]
+    fn stream_config_limits_and_compression() {
+        let spec = parse_spec(
+            r#"{"basins":[{"name":"my-basin","streams":[{"name":"events","config":{"min_retained_records":5,"max_append_bytes_per_sec":2048,"max_append_ops_per_sec":10,"max_future_skew_secs":30,"compression_hint":"gzip"}}]}]}"#,
+        );
+        let config = spec.basins[0].streams[0].config.as_ref().unwrap();
+        assert_eq!(config.min_retained_records, Some(5));
+        assert_eq!(config.max_append_bytes_per_sec, Some(2048));
+        assert_eq!(config.max_append_ops_per_sec, Some(10));
+        assert_eq!(config.max_future_skew_secs, Some(30));
+        assert!(matches!(
+            config.compression_hint,
+            Some(CompressionHint::Gzip)
+        ));
     }
 }
@@ -1,4 +1,9 @@
-use std::{ops::Deref, pin::Pin, sync::Arc, time::Duration};
+use std::{
+    ops::Deref,
+    pin::Pin,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 use async_stream::try_stream;
 use async_trait::async_trait;
@@ -24,8 +29,9 @@ use s2_api::v1::{
         AccountMetricSetRequest, BasinMetricSetRequest, MetricSetResponse, StreamMetricSetRequest,
     },
     stream::{
-        AppendConditionFailed, CreateStreamRequest, ListStreamsRequest, ListStreamsResponse,
-        ReadEnd, ReadStart, StreamInfo, TailResponse,
+        AppendConditionFailed, CreateStreamRequest, DeleteStreamRequest, ListStreamsRequest,
+        ListStreamsResponse, PositionsBatch, ReadEnd, ReadOptions, ReadStart, StreamInfo,
+        TailResponse,
         proto::{AppendAck, AppendInput, ReadBatch},
         s2s::{self, FrameDecoder, SessionMessage, TerminalMessage},
     },
@@ -356,10 +362,11 @@ impl BasinClient {
     pub async fn delete_stream(
         &self,
         name: StreamName,
+        request: DeleteStreamRequest,
         ignore_not_found: bool,
     ) -> Result<(), ApiError> {
         let url = self.uri(format!("v1/streams/{}", urlencoding::encode(&name)));
-        let request = self.delete(url).build()?;
+        let request = self.delete(url).query(&request).build()?;
         self.request(request)
             .send()
             .await
@@ -367,6 +374,16 @@ impl BasinClient {
         Ok(())
     }
 
+    pub async fn undelete_stream(&self, name: StreamName) -> Result<StreamInfo, ApiError> {
+        let url = self.uri(format!(
+            "v1/streams/{}/undelete",
+            urlencoding::encode(&name)
+        ));
+        let request = self.post(url).build()?;
+        let response = self.request(request).send().await?;
+        Ok(response.json::<StreamInfo>()?)
+    }
+
     pub async fn check_tail(&self, name: &StreamName) -> Result<TailResponse, ApiError> {
         let url = self.uri(format!(
             "v1/streams/{}/records/tail",
@@ -439,6 +456,31 @@ impl BasinClient {
         Ok(ReadBatch::decode(response.into_bytes())?)
     }
 
+    pub async fn read_positions(
+        &self,
+        name: &StreamName,
+        start: ReadStart,
+        end: ReadEnd,
+    ) -> Result<PositionsBatch, ApiError> {
+        let url = self.uri(format!("v1/streams/{}/records", urlencoding::encode(name)));
+        let options = ReadOptions {
+            positions_only: Some(true),
+            ..Default::default()
+        };
+        let request = self
+            .get(url)
+            .query(&start)
+            .query(&end)
+            .query(&options)
+            .build()?;
+        let response = self
+            .request(request)
+            .error_handler(read_response_error_handler)
+            .send()
+            .await?;
+        Ok(response.json::<PositionsBatch>()?)
+    }
+
     pub async fn append_session<I>(
         &self,
         name: &StreamName,
@@ -451,7 +493,7 @@ impl BasinClient {
     {
         let url = self.uri(format!("v1/streams/{}/records", urlencoding::encode(name)));
 
-        let compression = self.config.compression.into();
+        let compression = self.client.request_compression().into();
 
         let encoded_stream = inputs.map(move |input| {
             s2s::SessionMessage::regular(compression, &input).map(|msg| msg.encode())
@@ -829,6 +871,8 @@ pub struct BaseClient {
     request_timeout: Duration,
     retry_builder: RetryBackoffBuilder,
     compression: Compression,
+    /// Request compression learned from responses, when `compression` is [`Compression::Auto`].
+    negotiated_compression: Arc<OnceLock<client::Compression>>,
 }
 
 impl std::fmt::Debug for BaseClient {
@@ -872,10 +916,22 @@ impl BaseClient {
                     HeaderValue::from_static("zstd"),
                 );
             }
+            Compression::Auto => {
+                default_headers.insert(
+                    http::header::ACCEPT_ENCODING,
+                    HeaderValue::from_static("zstd, gzip"),
+                );
+            }
             Compression::None => {}
         }
 
-        let client = client::Pool::new(connector);
+        let client = client::Pool::new(
+            connector,
+            client::PoolConfig {
+                max_idle_per_host: config.pool_max_idle_per_host,
+                idle_timeout: config.pool_idle_timeout,
+            },
+        );
 
         Ok(Self {
             client: Arc::new(client),
@@ -883,9 +939,22 @@ impl BaseClient {
             request_timeout: config.request_timeout,
             retry_builder: retry_builder(&config.retry),
             compression: config.compression,
+            negotiated_compression: Default::default(),
         })
     }
 
+    /// Compression to apply to request bodies.
+    pub fn request_compression(&self) -> client::Compression {
+        match self.compression {
+            Compression::Auto => self
+                .negotiated_compression
+                .get()
+                .copied()
+                .unwrap_or_default(),
+            compression => compression.into(),
+        }
+    }
+
     pub fn get(&self, uri: Uri) -> client::RequestBuilder {
         client::RequestBuilder::get(uri)
             .timeout(self.request_timeout)
@@ -896,21 +965,21 @@ impl BaseClient {
         client::RequestBuilder::post(uri)
             .timeout(self.request_timeout)
             .headers(&self.default_headers)
-            .compression(self.compression)
+            .compression(self.request_compression())
     }
 
     pub fn patch(&self, uri: Uri) -> client::RequestBuilder {
         client::RequestBuilder::patch(uri)
             .timeout(self.request_timeout)
             .headers(&self.default_headers)
-            .compression(self.compression)
+            .compression(self.request_compression())
     }
 
     pub fn put(&self, uri: Uri) -> client::RequestBuilder {
         client::RequestBuilder::put(uri)
             .timeout(self.request_timeout)
             .headers(&self.default_headers)
-            .compression(self.compression)
+            .compression(self.request_compression())
     }
 
     pub fn delete(&self, uri: Uri) -> client::RequestBuilder {
@@ -930,7 +999,19 @@ impl BaseClient {
         &self,
         request: client::Request,
     ) -> Result<UnaryResponse, client::Error> {
-        self.client.execute_unary(request).await
+        let response = self.client.execute_unary(request).await?;
+        self.negotiate_compression(response.headers());
+        Ok(response)
+    }
+
+    /// Adopt the algorithm of the first compressed response for requests, when in
+    /// [`Compression::Auto`] mode.
+    fn negotiate_compression(&self, headers: &HeaderMap) {
+        if let Compression::Auto = self.compression
+            && let Some(compression) = client::Compression::from_response_headers(headers)
+        {
+            let _ = self.negotiated_compression.set(compression);
+        }
     }
 
     fn request(&self, request: client::Request) -> RequestBuilder<'_> {
@@ -1351,4 +1432,55 @@ mod tests {
             "expected 'dns resolution' in error, got: {msg}"
         );
     }
+
+    fn client_with(compression: Compression) -> BaseClient {
+        BaseClient::init(&S2Config::new("token").with_compression(compression))
+            .expect("client init")
+    }
+
+    fn content_encoding(encoding: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_ENCODING,
+            HeaderValue::from_static(encoding),
+        );
+        headers
+    }
+
+    #[test]
+    fn auto_compression_adopts_first_compressed_response() {
+        let client = client_with(Compression::Auto);
+        assert_eq!(
+            client.default_headers[http::header::ACCEPT_ENCODING],
+            "zstd, gzip"
+        );
+        assert_eq!(client.request_compression(), client::Compression::None);
+
+        // Uncompressed or unsupported encodings leave requests uncompressed.
+        client.negotiate_compression(&HeaderMap::new());
+        client.negotiate_compression(&content_encoding("br"));
+        assert_eq!(client.request_compression(), client::Compression::None);
+
+        client.negotiate_compression(&content_encoding("zstd"));
+        assert_eq!(client.request_compression(), client::Compression::Zstd);
+        client.negotiate_compression(&content_encoding("gzip"));
+        assert_eq!(client.request_compression(), client::Compression::Zstd);
+
+        // Clones share what was negotiated.
+        assert_eq!(
+            client.clone().request_compression(),
+            client::Compression::Zstd
+        );
+    }
+
+    #[test]
+    fn explicit_compression_is_not_negotiated() {
+        let client = client_with(Compression::Gzip);
+        client.negotiate_compression(&content_encoding("zstd"));
+        assert_eq!(client.request_compression(), client::Compression::Gzip);
+
+        let client = client_with(Compression::None);
+        client.negotiate_compression(&content_encoding("zstd"));
+        assert_eq!(client.request_compression(), client::Compression::None);
+    }
 }
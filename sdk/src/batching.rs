@@ -149,6 +149,7 @@ impl Stream for AppendInputs {
                     records: batch,
                     match_seq_num,
                     fencing_token: self.fencing_token.clone(),
+                    correlation_id: None,
                 })))
             }
             Poll::Ready(Some(Err(err))) => Poll::Ready(Some(Err(err))),
@@ -43,13 +43,13 @@ use crate::frame_signal::{FrameSignal, RequestFrameMonitorBody};
 
 const APPLICATION_JSON: HeaderValue = HeaderValue::from_static("application/json");
 const MAX_CONCURRENT_REQUESTS_PER_CLIENT: usize = 90;
-const IDLE_TIMEOUT: Duration = Duration::from_secs(90);
 const REAPER_INTERVAL: Duration = Duration::from_secs(30);
+const MIN_REAPER_INTERVAL: Duration = Duration::from_secs(1);
 
 type BoxError = Box<dyn std::error::Error + Send + Sync>;
 type BoxBody = UnsyncBoxBody<Bytes, BoxError>;
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum Compression {
     #[default]
     None,
@@ -60,13 +60,35 @@ pub enum Compression {
 impl From<crate::types::Compression> for Compression {
     fn from(c: crate::types::Compression) -> Self {
         match c {
-            crate::types::Compression::None => Compression::None,
+            // Resolved per request by the client once negotiated.
+            crate::types::Compression::None | crate::types::Compression::Auto => Compression::None,
             crate::types::Compression::Gzip => Compression::Gzip,
             crate::types::Compression::Zstd => Compression::Zstd,
         }
     }
 }
 
+impl From<Compression> for s2_api::v1::stream::s2s::CompressionAlgorithm {
+    fn from(c: Compression) -> Self {
+        match c {
+            Compression::None => Self::None,
+            Compression::Gzip => Self::Gzip,
+            Compression::Zstd => Self::Zstd,
+        }
+    }
+}
+
+impl Compression {
+    /// Algorithm a response was compressed with, per its `content-encoding`.
+    pub fn from_response_headers(headers: &HeaderMap) -> Option<Self> {
+        match headers.get(CONTENT_ENCODING)?.to_str().ok()? {
+            "gzip" => Some(Compression::Gzip),
+            "zstd" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("send error: {0}")]
@@ -744,8 +766,12 @@ impl<C> PooledClient<C> {
         })
     }
 
+    fn is_idle(&self) -> bool {
+        self.active_requests.load(Ordering::Relaxed) == 0
+    }
+
     fn should_reap(&self, idle_timeout: Duration) -> bool {
-        if self.active_requests.load(Ordering::Relaxed) != 0 {
+        if !self.is_idle() {
             return false;
         }
         if let Some(idle_since) = *self.idle_since.lock().unwrap() {
@@ -800,11 +826,17 @@ where
         (client, permit)
     }
 
-    async fn reap_idle_clients(&self) {
-        self.clients
-            .write()
-            .await
-            .retain(|pooled| !pooled.should_reap(IDLE_TIMEOUT));
+    async fn reap_idle_clients(&self, config: &PoolConfig) {
+        let mut clients = self.clients.write().await;
+        clients.retain(|pooled| !pooled.should_reap(config.idle_timeout));
+        let mut idle = 0;
+        clients.retain(|pooled| {
+            if !pooled.is_idle() {
+                return true;
+            }
+            idle += 1;
+            idle <= config.max_idle_per_host
+        });
     }
 
     fn is_empty(&self) -> bool {
@@ -815,9 +847,17 @@ where
     }
 }
 
+/// Limits on idle connections kept by a [`Pool`].
+#[derive(Debug, Clone, Copy)]
+pub struct PoolConfig {
+    pub max_idle_per_host: usize,
+    pub idle_timeout: Duration,
+}
+
 pub struct Pool<C> {
     hosts: Arc<RwLock<HashMap<String, Arc<HostPool<C>>>>>,
     connector: C,
+    config: PoolConfig,
     _reaper: AbortOnDropHandle<()>,
 }
 
@@ -825,16 +865,19 @@ impl<C> Pool<C>
 where
     C: Connect + Clone + Send + Sync + 'static,
 {
-    pub fn new(connector: C) -> Self {
+    pub fn new(connector: C, config: PoolConfig) -> Self {
         let hosts = Arc::new(RwLock::new(HashMap::new()));
 
         let _reaper = AbortOnDropHandle::new(tokio::spawn({
             let hosts = hosts.clone();
             async move {
-                let mut interval = tokio::time::interval(REAPER_INTERVAL);
+                let period = REAPER_INTERVAL
+                    .min(config.idle_timeout)
+                    .max(MIN_REAPER_INTERVAL);
+                let mut interval = tokio::time::interval(period);
                 loop {
                     interval.tick().await;
-                    reap_idle_clients(&hosts).await;
+                    reap_idle_clients(&hosts, &config).await;
                 }
             }
         }));
@@ -842,6 +885,7 @@ where
         Self {
             hosts,
             connector,
+            config,
             _reaper,
         }
     }
@@ -867,6 +911,7 @@ where
 
 async fn reap_idle_clients<C: Connect + Clone + Send + Sync + 'static>(
     hosts: &RwLock<HashMap<String, Arc<HostPool<C>>>>,
+    config: &PoolConfig,
 ) {
     let pools: Vec<Arc<HostPool<C>>> = {
         let hosts = hosts.read().await;
@@ -874,7 +919,7 @@ async fn reap_idle_clients<C: Connect + Clone + Send + Sync + 'static>(
     };
 
     for pool in &pools {
-        pool.reap_idle_clients().await;
+        pool.reap_idle_clients(config).await;
     }
 
     hosts.write().await.retain(|_, pool| !pool.is_empty());
@@ -904,9 +949,20 @@ mod tests {
     use super::*;
 
     const TEST_HOST: &str = "localhost:8080";
+    const IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+    fn test_pool_with(max_idle_per_host: usize) -> Pool<HttpConnector> {
+        Pool::new(
+            HttpConnector::new(),
+            PoolConfig {
+                max_idle_per_host,
+                idle_timeout: IDLE_TIMEOUT,
+            },
+        )
+    }
 
     fn test_pool() -> Pool<HttpConnector> {
-        Pool::new(HttpConnector::new())
+        test_pool_with(usize::MAX)
     }
 
     #[test]
@@ -1045,11 +1101,29 @@ mod tests {
             }
         }
 
-        reap_idle_clients(&pool.hosts).await;
+        reap_idle_clients(&pool.hosts, &pool.config).await;
         assert_eq!(host_client_count(&pool, TEST_HOST).await, 0);
         assert!(pool.hosts.read().await.get(TEST_HOST).is_none());
     }
 
+    #[tokio::test]
+    async fn reaper_enforces_max_idle_per_host() {
+        let pool = test_pool_with(1);
+        let mut permits = Vec::new();
+        for _ in 0..=MAX_CONCURRENT_REQUESTS_PER_CLIENT * 2 {
+            let (_client, permit) = pool.checkout(TEST_HOST).await;
+            permits.push(permit);
+        }
+        assert_eq!(host_client_count(&pool, TEST_HOST).await, 3);
+
+        reap_idle_clients(&pool.hosts, &pool.config).await;
+        assert_eq!(host_client_count(&pool, TEST_HOST).await, 3);
+
+        permits.clear();
+        reap_idle_clients(&pool.hosts, &pool.config).await;
+        assert_eq!(host_client_count(&pool, TEST_HOST).await, 1);
+    }
+
     #[tokio::test]
     async fn different_hosts_get_independent_pools() {
         let pool = test_pool();
@@ -1090,7 +1164,7 @@ mod tests {
             }
         }
 
-        reap_idle_clients(&pool.hosts).await;
+        reap_idle_clients(&pool.hosts, &pool.config).await;
 
         let hosts = pool.hosts.read().await;
         assert!(hosts.get("host-a:443").is_none());
@@ -12,9 +12,9 @@ use crate::{
         EnsureBasinInput, EnsureOutput, EnsureStreamInput, GetAccountMetricsInput,
         GetBasinMetricsInput, GetStreamMetricsInput, IssueAccessTokenInput, ListAccessTokensInput,
         ListAllAccessTokensInput, ListAllBasinsInput, ListAllStreamsInput, ListBasinsInput,
-        ListStreamsInput, LocationInfo, LocationName, Metric, Page, ReadBatch, ReadInput,
-        ReconfigureBasinInput, ReconfigureStreamInput, S2Config, S2Error, StreamConfig, StreamInfo,
-        StreamName, StreamPosition, Streaming,
+        ListStreamsInput, LocationInfo, LocationName, Metric, Page, PositionsBatch, ReadBatch,
+        ReadInput, ReconfigureBasinInput, ReconfigureStreamInput, S2Config, S2Error, StreamConfig,
+        StreamInfo, StreamName, StreamPosition, Streaming,
     },
 };
 
@@ -302,13 +302,25 @@ impl S2Basin {
         let prefix = input.prefix;
         let start_after = input.start_after;
         let include_deleted = input.include_deleted;
+        let state = input.state;
+        let start_after_created_at = input.start_after_created_at;
         let mut input = ListStreamsInput::new()
             .with_prefix(prefix)
-            .with_start_after(start_after);
+            .with_start_after(start_after)
+            .with_order(input.order);
+        if let Some(state) = state {
+            input = input.with_state(state);
+        }
+        if let Some(created_at) = start_after_created_at {
+            input = input.with_start_after_created_at(created_at);
+        }
         Box::pin(async_stream::try_stream! {
             loop {
                 let page = basin.list_streams(input.clone()).await?;
-                let start_after = page.values.last().map(|info| info.name.clone().into());
+                let last = page
+                    .values
+                    .last()
+                    .map(|info| (info.name.clone().into(), info.created_at));
 
                 for info in page.values {
                     if !include_deleted && info.deleted_at.is_some() {
@@ -317,8 +329,10 @@ impl S2Basin {
                     yield info;
                 }
 
-                if page.has_more && let Some(start_after) = start_after {
-                    input = input.with_start_after(start_after);
+                if page.has_more && let Some((start_after, created_at)) = last {
+                    input = input
+                        .with_start_after(start_after)
+                        .with_start_after_created_at(created_at);
                 } else {
                     break;
                 }
@@ -364,12 +378,19 @@ impl S2Basin {
 
     /// Delete a stream.
     pub async fn delete_stream(&self, input: DeleteStreamInput) -> Result<(), S2Error> {
+        let request = (&input).into();
         Ok(self
             .client
-            .delete_stream(input.name, input.ignore_not_found)
+            .delete_stream(input.name, request, input.ignore_not_found)
             .await?)
     }
 
+    /// Undelete a stream that was deleted within the server's deletion grace period.
+    pub async fn undelete_stream(&self, name: StreamName) -> Result<StreamInfo, S2Error> {
+        let info = self.client.undelete_stream(name).await?;
+        Ok(info.try_into()?)
+    }
+
     /// Reconfigure a stream.
     pub async fn reconfigure_stream(
         &self,
@@ -440,6 +461,19 @@ impl S2Stream {
         Ok(batch)
     }
 
+    /// Read the positions of records, without their headers or bodies.
+    ///
+    /// No encryption key is needed, as records are not decrypted. Only the count limit applies,
+    /// and the read does not wait for new records. Command records are always included,
+    /// regardless of [`ignore_command_records`](ReadInput::ignore_command_records).
+    pub async fn read_positions(&self, input: ReadInput) -> Result<PositionsBatch, S2Error> {
+        let batch = self
+            .client
+            .read_positions(&self.name, input.start.into(), input.stop.into())
+            .await?;
+        Ok(PositionsBatch::from_api(batch))
+    }
+
     /// Create an append session for submitting [`AppendInput`]s.
     pub fn append_session(&self, config: AppendSessionConfig) -> AppendSession {
         AppendSession::new(
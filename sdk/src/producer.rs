@@ -5,6 +5,7 @@
 use std::{
     collections::VecDeque,
     future::Future,
+    num::NonZeroU32,
     pin::Pin,
     sync::{Arc, OnceLock},
     task::{Context, Poll},
@@ -62,6 +63,7 @@ pub struct IndexedAppendAck {
 #[derive(Debug, Clone)]
 pub struct ProducerConfig {
     max_unacked_bytes: u32,
+    max_unacked_batches: Option<u32>,
     batching: BatchingConfig,
     fencing_token: Option<FencingToken>,
     match_seq_num: Option<u64>,
@@ -71,6 +73,7 @@ impl Default for ProducerConfig {
     fn default() -> Self {
         Self {
             max_unacked_bytes: 5 * ONE_MIB,
+            max_unacked_batches: None,
             batching: BatchingConfig::default(),
             fencing_token: None,
             match_seq_num: None,
@@ -99,6 +102,18 @@ impl ProducerConfig {
         })
     }
 
+    /// Set the limit on number of unacknowledged [`AppendInput`](crate::types::AppendInput)s in
+    /// flight. Once reached, further records wait to be batched until an earlier batch is
+    /// acknowledged.
+    ///
+    /// Defaults to no limit.
+    pub fn with_max_unacked_batches(self, max_unacked_batches: NonZeroU32) -> Self {
+        Self {
+            max_unacked_batches: Some(max_unacked_batches.get()),
+            ..self
+        }
+    }
+
     /// Set the configuration for batching records into [`AppendInput`](crate::types::AppendInput)s
     /// before appending.
     ///
@@ -235,6 +250,7 @@ impl Producer {
     ) {
         let (record_tx, record_rx) = mpsc::channel::<AppendRecord>(RECORD_BATCH_MAX.count);
         let mut record_tx = Some(record_tx);
+        let max_unacked_batches = config.max_unacked_batches.map(|max| max as usize);
         let mut inputs = AppendInputs {
             batches: AppendRecordBatches::new(ReceiverStream::new(record_rx), config.batching),
             fencing_token: config.fencing_token,
@@ -293,7 +309,9 @@ impl Producer {
                     }
                 }
 
-                input = inputs.next(), if submit_fut.is_none() && !inputs_exhausted => {
+                input = inputs.next(), if submit_fut.is_none()
+                    && !inputs_exhausted
+                    && max_unacked_batches.is_none_or(|max| pending_batch_acks.len() < max) => {
                     match input {
                         Some(Ok(input)) => {
                             submit_batch_len = Some(input.records.len());
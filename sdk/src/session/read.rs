@@ -122,6 +122,7 @@ pub async fn read_session(
                             timestamp: None,
                             tail_offset: None,
                             clamp: start.clamp,
+                            error_if_trimmed: start.error_if_trimmed,
                         };
                     }
                     if let Some(count) = end.count.as_mut() {
@@ -258,7 +258,24 @@ impl S2Endpoints {
         })
     }
 
-    pub(crate) fn for_aws() -> Self {
+    /// Account endpoint URL.
+    pub fn account_url(&self) -> String {
+        format!("{}://{}", self.scheme, self.account_authority)
+    }
+
+    /// Basin endpoint URL for `basin`, or with a `{basin}` placeholder if none is given and the
+    /// endpoint routes by basin name.
+    pub fn basin_url(&self, basin: Option<&BasinName>) -> String {
+        let authority = match (&self.basin_authority, basin) {
+            (BasinAuthority::ParentZone(zone), Some(basin)) => format!("{basin}.{zone}"),
+            (BasinAuthority::ParentZone(zone), None) => format!("{{basin}}.{zone}"),
+            (BasinAuthority::Direct(authority), _) => authority.to_string(),
+        };
+        format!("{}://{authority}", self.scheme)
+    }
+
+    /// Endpoints for S2 on AWS, used unless others are configured.
+    pub fn for_aws() -> Self {
         Self {
             scheme: Scheme::HTTPS,
             account_authority: "aws.s2.dev".try_into().expect("valid authority"),
@@ -270,6 +287,7 @@ impl S2Endpoints {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 /// Compression algorithm for request and response bodies.
 pub enum Compression {
     /// No compression.
@@ -278,12 +296,18 @@ pub enum Compression {
     Gzip,
     /// Zstd compression.
     Zstd,
+    /// Best algorithm supported by both the client and the server.
+    ///
+    /// Responses may be compressed with any supported algorithm. Requests are sent uncompressed
+    /// until a response shows which algorithm the server uses, and are compressed with it after.
+    Auto,
 }
 
 impl From<Compression> for CompressionAlgorithm {
     fn from(value: Compression) -> Self {
         match value {
-            Compression::None => CompressionAlgorithm::None,
+            // Not negotiated yet.
+            Compression::None | Compression::Auto => CompressionAlgorithm::None,
             Compression::Gzip => CompressionAlgorithm::Gzip,
             Compression::Zstd => CompressionAlgorithm::Zstd,
         }
@@ -407,6 +431,8 @@ pub struct S2Config {
     pub(crate) user_agent: HeaderValue,
     pub(crate) insecure_skip_cert_verification: bool,
     pub(crate) rustls_crypto_provider: Option<Arc<rustls::crypto::CryptoProvider>>,
+    pub(crate) pool_max_idle_per_host: usize,
+    pub(crate) pool_idle_timeout: Duration,
 }
 
 impl S2Config {
@@ -424,6 +450,8 @@ impl S2Config {
                 .expect("valid user agent"),
             insecure_skip_cert_verification: false,
             rustls_crypto_provider: default_rustls_crypto_provider(),
+            pool_max_idle_per_host: usize::MAX,
+            pool_idle_timeout: Duration::from_secs(90),
         }
     }
 
@@ -469,6 +497,31 @@ impl S2Config {
         }
     }
 
+    /// Set the maximum number of idle connections kept open per host.
+    ///
+    /// Connections beyond this limit are closed once idle. Raise it for bursty,
+    /// high-throughput workloads to avoid reconnecting. This does not bound how many
+    /// connections are opened while busy, as a new one is added whenever every existing
+    /// connection is carrying its maximum of concurrent requests.
+    ///
+    /// Defaults to no limit.
+    pub fn with_pool_max_idle_per_host(self, pool_max_idle_per_host: usize) -> Self {
+        Self {
+            pool_max_idle_per_host,
+            ..self
+        }
+    }
+
+    /// Set how long an idle connection is kept alive before being closed.
+    ///
+    /// Defaults to `90s`.
+    pub fn with_pool_idle_timeout(self, pool_idle_timeout: Duration) -> Self {
+        Self {
+            pool_idle_timeout,
+            ..self
+        }
+    }
+
     /// Skip TLS certificate verification (insecure).
     ///
     /// This is useful for connecting to endpoints with self-signed certificates
@@ -594,6 +647,37 @@ impl From<StorageClass> for api::config::StorageClass {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Advisory codec for compressing a stream's records at rest.
+pub enum CompressionHint {
+    /// Store records uncompressed.
+    None,
+    /// Compress records with Zstandard.
+    Zstd,
+    /// Compress records with gzip.
+    Gzip,
+}
+
+impl From<api::config::CompressionHint> for CompressionHint {
+    fn from(value: api::config::CompressionHint) -> Self {
+        match value {
+            api::config::CompressionHint::None => CompressionHint::None,
+            api::config::CompressionHint::Zstd => CompressionHint::Zstd,
+            api::config::CompressionHint::Gzip => CompressionHint::Gzip,
+        }
+    }
+}
+
+impl From<CompressionHint> for api::config::CompressionHint {
+    fn from(value: CompressionHint) -> Self {
+        match value {
+            CompressionHint::None => api::config::CompressionHint::None,
+            CompressionHint::Zstd => api::config::CompressionHint::Zstd,
+            CompressionHint::Gzip => api::config::CompressionHint::Gzip,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Retention policy for records in a stream.
 pub enum RetentionPolicy {
@@ -632,6 +716,9 @@ pub enum TimestampingMode {
     ClientRequire,
     /// Use the arrival time and ignore any client-specified timestamp.
     Arrival,
+    /// Use the arrival time unless a client-specified timestamp is present and within a tolerance
+    /// window of it.
+    ServerPrefer,
 }
 
 impl From<api::config::TimestampingMode> for TimestampingMode {
@@ -640,6 +727,7 @@ impl From<api::config::TimestampingMode> for TimestampingMode {
             api::config::TimestampingMode::ClientPrefer => TimestampingMode::ClientPrefer,
             api::config::TimestampingMode::ClientRequire => TimestampingMode::ClientRequire,
             api::config::TimestampingMode::Arrival => TimestampingMode::Arrival,
+            api::config::TimestampingMode::ServerPrefer => TimestampingMode::ServerPrefer,
         }
     }
 }
@@ -650,6 +738,7 @@ impl From<TimestampingMode> for api::config::TimestampingMode {
             TimestampingMode::ClientPrefer => api::config::TimestampingMode::ClientPrefer,
             TimestampingMode::ClientRequire => api::config::TimestampingMode::ClientRequire,
             TimestampingMode::Arrival => api::config::TimestampingMode::Arrival,
+            TimestampingMode::ServerPrefer => api::config::TimestampingMode::ServerPrefer,
         }
     }
 }
@@ -769,6 +858,28 @@ pub struct StreamConfig {
     ///
     /// See [`DeleteOnEmptyConfig`] for defaults.
     pub delete_on_empty: Option<DeleteOnEmptyConfig>,
+    /// Minimum number of records that must remain in the stream after a trim.
+    ///
+    /// Defaults to `0`, i.e. trims are unrestricted.
+    pub min_retained_records: Option<u64>,
+    /// Maximum append throughput in metered bytes per second.
+    ///
+    /// Defaults to `0`, i.e. throughput is unrestricted.
+    pub max_append_bytes_per_sec: Option<u64>,
+    /// Maximum number of appends per second.
+    ///
+    /// Defaults to `0`, i.e. appends are unrestricted.
+    pub max_append_ops_per_sec: Option<u64>,
+    /// Maximum number of seconds a client-specified record timestamp may be ahead of the arrival
+    /// time. Appends with timestamps further in the future are rejected.
+    ///
+    /// Defaults to `0`, i.e. future timestamps are not rejected.
+    pub max_future_skew_secs: Option<u64>,
+    /// Codec the server should use to compress the stream's records at rest. It is a hint, which
+    /// servers may ignore.
+    ///
+    /// Defaults to [`None`](CompressionHint::None).
+    pub compression_hint: Option<CompressionHint>,
 }
 
 impl StreamConfig {
@@ -808,6 +919,47 @@ impl StreamConfig {
             ..self
         }
     }
+
+    /// Set the minimum number of records that must remain in the stream after a trim.
+    pub fn with_min_retained_records(self, min_retained_records: u64) -> Self {
+        Self {
+            min_retained_records: Some(min_retained_records),
+            ..self
+        }
+    }
+
+    /// Set the maximum append throughput in metered bytes per second.
+    pub fn with_max_append_bytes_per_sec(self, max_append_bytes_per_sec: u64) -> Self {
+        Self {
+            max_append_bytes_per_sec: Some(max_append_bytes_per_sec),
+            ..self
+        }
+    }
+
+    /// Set the maximum number of appends per second.
+    pub fn with_max_append_ops_per_sec(self, max_append_ops_per_sec: u64) -> Self {
+        Self {
+            max_append_ops_per_sec: Some(max_append_ops_per_sec),
+            ..self
+        }
+    }
+
+    /// Set the maximum number of seconds a client-specified record timestamp may be ahead of the
+    /// arrival time.
+    pub fn with_max_future_skew_secs(self, max_future_skew_secs: u64) -> Self {
+        Self {
+            max_future_skew_secs: Some(max_future_skew_secs),
+            ..self
+        }
+    }
+
+    /// Set the codec the server should use to compress the stream's records at rest.
+    pub fn with_compression_hint(self, compression_hint: CompressionHint) -> Self {
+        Self {
+            compression_hint: Some(compression_hint),
+            ..self
+        }
+    }
 }
 
 impl From<api::config::StreamConfig> for StreamConfig {
@@ -817,6 +969,11 @@ impl From<api::config::StreamConfig> for StreamConfig {
             retention_policy: value.retention_policy.map(Into::into),
             timestamping: value.timestamping.map(Into::into),
             delete_on_empty: value.delete_on_empty.map(Into::into),
+            min_retained_records: value.min_retained_records,
+            max_append_bytes_per_sec: value.max_append_bytes_per_sec,
+            max_append_ops_per_sec: value.max_append_ops_per_sec,
+            max_future_skew_secs: value.max_future_skew_secs,
+            compression_hint: value.compression_hint.map(Into::into),
         }
     }
 }
@@ -828,6 +985,11 @@ impl From<StreamConfig> for api::config::StreamConfig {
             retention_policy: value.retention_policy.map(Into::into),
             timestamping: value.timestamping.map(Into::into),
             delete_on_empty: value.delete_on_empty.map(Into::into),
+            min_retained_records: value.min_retained_records,
+            max_append_bytes_per_sec: value.max_append_bytes_per_sec,
+            max_append_ops_per_sec: value.max_append_ops_per_sec,
+            max_future_skew_secs: value.max_future_skew_secs,
+            compression_hint: value.compression_hint.map(Into::into),
         }
     }
 }
@@ -1309,6 +1471,19 @@ pub struct StreamReconfiguration {
     pub timestamping: Maybe<Option<TimestampingReconfiguration>>,
     /// Override for the existing [`delete_on_empty`](StreamConfig::delete_on_empty).
     pub delete_on_empty: Maybe<Option<DeleteOnEmptyReconfiguration>>,
+    /// Override for the existing [`min_retained_records`](StreamConfig::min_retained_records).
+    pub min_retained_records: Maybe<Option<u64>>,
+    /// Override for the existing
+    /// [`max_append_bytes_per_sec`](StreamConfig::max_append_bytes_per_sec).
+    pub max_append_bytes_per_sec: Maybe<Option<u64>>,
+    /// Override for the existing
+    /// [`max_append_ops_per_sec`](StreamConfig::max_append_ops_per_sec).
+    pub max_append_ops_per_sec: Maybe<Option<u64>>,
+    /// Override for the existing
+    /// [`max_future_skew_secs`](StreamConfig::max_future_skew_secs).
+    pub max_future_skew_secs: Maybe<Option<u64>>,
+    /// Override for the existing [`compression_hint`](StreamConfig::compression_hint).
+    pub compression_hint: Maybe<Option<CompressionHint>>,
 }
 
 impl StreamReconfiguration {
@@ -1317,6 +1492,22 @@ impl StreamReconfiguration {
         Self::default()
     }
 
+    /// Create a [`StreamReconfiguration`] that clears every override, so that the stream inherits
+    /// its basin's [`default_stream_config`](BasinConfig::default_stream_config) again.
+    pub fn reset() -> Self {
+        Self {
+            storage_class: Maybe::Specified(None),
+            retention_policy: Maybe::Specified(None),
+            timestamping: Maybe::Specified(None),
+            delete_on_empty: Maybe::Specified(None),
+            min_retained_records: Maybe::Specified(None),
+            max_append_bytes_per_sec: Maybe::Specified(None),
+            max_append_ops_per_sec: Maybe::Specified(None),
+            max_future_skew_secs: Maybe::Specified(None),
+            compression_hint: Maybe::Specified(None),
+        }
+    }
+
     /// Set the override for the existing [`storage_class`](StreamConfig::storage_class).
     pub fn with_storage_class(self, storage_class: StorageClass) -> Self {
         Self {
@@ -1348,6 +1539,50 @@ impl StreamReconfiguration {
             ..self
         }
     }
+
+    /// Set the override for the existing
+    /// [`min_retained_records`](StreamConfig::min_retained_records).
+    pub fn with_min_retained_records(self, min_retained_records: u64) -> Self {
+        Self {
+            min_retained_records: Maybe::Specified(Some(min_retained_records)),
+            ..self
+        }
+    }
+
+    /// Set the override for the existing
+    /// [`max_append_bytes_per_sec`](StreamConfig::max_append_bytes_per_sec).
+    pub fn with_max_append_bytes_per_sec(self, max_append_bytes_per_sec: u64) -> Self {
+        Self {
+            max_append_bytes_per_sec: Maybe::Specified(Some(max_append_bytes_per_sec)),
+            ..self
+        }
+    }
+
+    /// Set the override for the existing
+    /// [`max_append_ops_per_sec`](StreamConfig::max_append_ops_per_sec).
+    pub fn with_max_append_ops_per_sec(self, max_append_ops_per_sec: u64) -> Self {
+        Self {
+            max_append_ops_per_sec: Maybe::Specified(Some(max_append_ops_per_sec)),
+            ..self
+        }
+    }
+
+    /// Set the override for the existing
+    /// [`max_future_skew_secs`](StreamConfig::max_future_skew_secs).
+    pub fn with_max_future_skew_secs(self, max_future_skew_secs: u64) -> Self {
+        Self {
+            max_future_skew_secs: Maybe::Specified(Some(max_future_skew_secs)),
+            ..self
+        }
+    }
+
+    /// Set the override for the existing [`compression_hint`](StreamConfig::compression_hint).
+    pub fn with_compression_hint(self, compression_hint: CompressionHint) -> Self {
+        Self {
+            compression_hint: Maybe::Specified(Some(compression_hint)),
+            ..self
+        }
+    }
 }
 
 impl From<StreamReconfiguration> for api::config::StreamReconfiguration {
@@ -1357,6 +1592,11 @@ impl From<StreamReconfiguration> for api::config::StreamReconfiguration {
             retention_policy: value.retention_policy.map(|m| m.map(Into::into)),
             timestamping: value.timestamping.map(|m| m.map(Into::into)),
             delete_on_empty: value.delete_on_empty.map(|m| m.map(Into::into)),
+            min_retained_records: value.min_retained_records,
+            max_append_bytes_per_sec: value.max_append_bytes_per_sec,
+            max_append_ops_per_sec: value.max_append_ops_per_sec,
+            max_future_skew_secs: value.max_future_skew_secs,
+            compression_hint: value.compression_hint.map(|m| m.map(Into::into)),
         }
     }
 }
@@ -2453,12 +2693,17 @@ impl From<GetStreamMetricsInput> for (BasinName, StreamName, api::metrics::Strea
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 /// Unit in which metric values are measured.
 pub enum MetricUnit {
     /// Size in bytes.
     Bytes,
     /// Number of operations.
     Operations,
+    /// Number of items, e.g. basins.
+    Count,
+    /// Percentage, from 0 to 100.
+    Percent,
 }
 
 impl From<api::metrics::MetricUnit> for MetricUnit {
@@ -2466,6 +2711,8 @@ impl From<api::metrics::MetricUnit> for MetricUnit {
         match value {
             api::metrics::MetricUnit::Bytes => MetricUnit::Bytes,
             api::metrics::MetricUnit::Operations => MetricUnit::Operations,
+            api::metrics::MetricUnit::Count => MetricUnit::Count,
+            api::metrics::MetricUnit::Percent => MetricUnit::Percent,
         }
     }
 }
@@ -2563,6 +2810,43 @@ impl From<api::metrics::Metric> for Metric {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Order in which streams are listed.
+pub enum ListStreamsOrder {
+    /// Lexicographic order of stream names.
+    #[default]
+    Name,
+    /// Order of stream creation, oldest first.
+    Created,
+}
+
+impl From<ListStreamsOrder> for api::stream::ListStreamsOrder {
+    fn from(value: ListStreamsOrder) -> Self {
+        match value {
+            ListStreamsOrder::Name => api::stream::ListStreamsOrder::Name,
+            ListStreamsOrder::Created => api::stream::ListStreamsOrder::Created,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// State of streams to list.
+pub enum ListStreamsState {
+    /// Streams that are not being deleted.
+    Active,
+    /// Streams whose deletion is pending.
+    Deleting,
+}
+
+impl From<ListStreamsState> for api::stream::ListStreamsState {
+    fn from(value: ListStreamsState) -> Self {
+        match value {
+            ListStreamsState::Active => api::stream::ListStreamsState::Active,
+            ListStreamsState::Deleting => api::stream::ListStreamsState::Deleting,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
 /// Input for [`list_streams`](crate::S2Basin::list_streams) operation.
@@ -2579,6 +2863,21 @@ pub struct ListStreamsInput {
     ///
     /// Defaults to `1000`.
     pub limit: Option<usize>,
+    /// Order in which streams are listed. With [`ListStreamsOrder::Created`], a non-empty
+    /// `start_after` must be accompanied by `start_after_created_at`.
+    ///
+    /// Defaults to [`ListStreamsOrder::Name`].
+    pub order: ListStreamsOrder,
+    /// Creation time of the `start_after` stream, as it was listed. Listing in
+    /// [`ListStreamsOrder::Created`] resumes after that stream's position, even if it has since
+    /// been deleted.
+    ///
+    /// Defaults to `None`.
+    pub start_after_created_at: Option<S2DateTime>,
+    /// Filter streams in this state.
+    ///
+    /// Defaults to `None`, which lists streams in any state.
+    pub state: Option<ListStreamsState>,
 }
 
 impl ListStreamsInput {
@@ -2608,6 +2907,28 @@ impl ListStreamsInput {
             ..self
         }
     }
+
+    /// Set the order in which streams are listed.
+    pub fn with_order(self, order: ListStreamsOrder) -> Self {
+        Self { order, ..self }
+    }
+
+    /// Set the creation time of the `start_after` stream, for listing in
+    /// [`ListStreamsOrder::Created`].
+    pub fn with_start_after_created_at(self, start_after_created_at: S2DateTime) -> Self {
+        Self {
+            start_after_created_at: Some(start_after_created_at),
+            ..self
+        }
+    }
+
+    /// Set the state of streams to list.
+    pub fn with_state(self, state: ListStreamsState) -> Self {
+        Self {
+            state: Some(state),
+            ..self
+        }
+    }
 }
 
 impl From<ListStreamsInput> for api::stream::ListStreamsRequest {
@@ -2616,6 +2937,17 @@ impl From<ListStreamsInput> for api::stream::ListStreamsRequest {
             prefix: Some(value.prefix),
             start_after: Some(value.start_after),
             limit: value.limit,
+            // The default order and its parameters are left implicit, so requests that do not
+            // need them remain valid for servers without support for ordering.
+            order: match value.order {
+                ListStreamsOrder::Name => None,
+                order => Some(order.into()),
+            },
+            start_after_created_at: value
+                .start_after_created_at
+                .filter(|_| value.order == ListStreamsOrder::Created)
+                .map(Into::into),
+            state: value.state.map(Into::into),
         }
     }
 }
@@ -2635,6 +2967,20 @@ pub struct ListAllStreamsInput {
     ///
     /// Defaults to `false`.
     pub include_deleted: bool,
+    /// Order in which streams are listed. With [`ListStreamsOrder::Created`], a non-empty
+    /// `start_after` must be accompanied by `start_after_created_at`.
+    ///
+    /// Defaults to [`ListStreamsOrder::Name`].
+    pub order: ListStreamsOrder,
+    /// Creation time of the `start_after` stream, as it was listed.
+    ///
+    /// Defaults to `None`.
+    pub start_after_created_at: Option<S2DateTime>,
+    /// Filter streams in this state. Streams being deleted are still skipped unless
+    /// `include_deleted` is set.
+    ///
+    /// Defaults to `None`, which lists streams in any state.
+    pub state: Option<ListStreamsState>,
 }
 
 impl ListAllStreamsInput {
@@ -2664,6 +3010,28 @@ impl ListAllStreamsInput {
             ..self
         }
     }
+
+    /// Set the order in which streams are listed.
+    pub fn with_order(self, order: ListStreamsOrder) -> Self {
+        Self { order, ..self }
+    }
+
+    /// Set the creation time of the `start_after` stream, for listing in
+    /// [`ListStreamsOrder::Created`].
+    pub fn with_start_after_created_at(self, start_after_created_at: S2DateTime) -> Self {
+        Self {
+            start_after_created_at: Some(start_after_created_at),
+            ..self
+        }
+    }
+
+    /// Set the state of streams to list.
+    pub fn with_state(self, state: ListStreamsState) -> Self {
+        Self {
+            state: Some(state),
+            ..self
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -2779,6 +3147,11 @@ pub struct DeleteStreamInput {
     pub name: StreamName,
     /// Whether to ignore `Not Found` error if the stream doesn't exist.
     pub ignore_not_found: bool,
+    /// Whether to remove the stream and all of its records before returning, bypassing any
+    /// deletion grace period.
+    ///
+    /// Defaults to `false`.
+    pub purge: bool,
 }
 
 impl DeleteStreamInput {
@@ -2787,6 +3160,7 @@ impl DeleteStreamInput {
         Self {
             name,
             ignore_not_found: false,
+            purge: false,
         }
     }
 
@@ -2797,6 +3171,19 @@ impl DeleteStreamInput {
             ..self
         }
     }
+
+    /// Set whether to remove the stream and all of its records before returning.
+    pub fn with_purge(self, purge: bool) -> Self {
+        Self { purge, ..self }
+    }
+}
+
+impl From<&DeleteStreamInput> for api::stream::DeleteStreamRequest {
+    fn from(value: &DeleteStreamInput) -> Self {
+        Self {
+            purge: value.purge.then_some(true),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -3223,6 +3610,10 @@ pub struct AppendInput {
     /// If unspecified, no matching is performed. If specified and mismatched,
     /// the append fails. A stream defaults to `""` as its fencing token.
     pub fencing_token: Option<FencingToken>,
+    /// Client-assigned identifier for this batch.
+    ///
+    /// In an append session, it is echoed in [`AppendAck::correlation_id`].
+    pub correlation_id: Option<u64>,
 }
 
 impl AppendInput {
@@ -3232,6 +3623,7 @@ impl AppendInput {
             records,
             match_seq_num: None,
             fencing_token: None,
+            correlation_id: None,
         }
     }
 
@@ -3250,6 +3642,14 @@ impl AppendInput {
             ..self
         }
     }
+
+    /// Set the client-assigned identifier for this batch.
+    pub fn with_correlation_id(self, correlation_id: u64) -> Self {
+        Self {
+            correlation_id: Some(correlation_id),
+            ..self
+        }
+    }
 }
 
 impl From<AppendInput> for api::stream::proto::AppendInput {
@@ -3258,6 +3658,7 @@ impl From<AppendInput> for api::stream::proto::AppendInput {
             records: value.records.iter().cloned().map(Into::into).collect(),
             match_seq_num: value.match_seq_num,
             fencing_token: value.fencing_token.map(|t| t.to_string()),
+            correlation_id: value.correlation_id,
         }
     }
 }
@@ -3279,6 +3680,9 @@ pub struct AppendAck {
     ///
     /// This can be greater than the `end` position in case of concurrent appends.
     pub tail: StreamPosition,
+    /// [`AppendInput::correlation_id`] of the acknowledged batch, if it was appended in an append
+    /// session.
+    pub correlation_id: Option<u64>,
 }
 
 impl From<api::stream::proto::AppendAck> for AppendAck {
@@ -3287,6 +3691,7 @@ impl From<api::stream::proto::AppendAck> for AppendAck {
             start: value.start.unwrap_or_default().into(),
             end: value.end.unwrap_or_default().into(),
             tail: value.tail.unwrap_or_default().into(),
+            correlation_id: value.correlation_id,
         }
     }
 }
@@ -3320,6 +3725,10 @@ pub struct ReadStart {
     ///
     /// Defaults to `false` (errors if position is beyond tail).
     pub clamp_to_tail: bool,
+    /// Whether to fail if the requested starting sequence number has been trimmed.
+    ///
+    /// Defaults to `false` (starts from the earliest available record).
+    pub error_if_trimmed: bool,
 }
 
 impl ReadStart {
@@ -3340,6 +3749,14 @@ impl ReadStart {
             ..self
         }
     }
+
+    /// Set whether to fail if the requested starting sequence number has been trimmed.
+    pub fn with_error_if_trimmed(self, error_if_trimmed: bool) -> Self {
+        Self {
+            error_if_trimmed,
+            ..self
+        }
+    }
 }
 
 impl From<ReadStart> for api::stream::ReadStart {
@@ -3358,6 +3775,11 @@ impl From<ReadStart> for api::stream::ReadStart {
             } else {
                 None
             },
+            error_if_trimmed: if value.error_if_trimmed {
+                Some(true)
+            } else {
+                None
+            },
         }
     }
 }
@@ -3541,6 +3963,24 @@ impl SequencedRecord {
     pub fn is_command_record(&self) -> bool {
         self.headers.len() == 1 && *self.headers[0].name == *b""
     }
+
+    /// Decode the command, if this is a well-formed command record.
+    pub fn command(&self) -> Option<Command> {
+        if !self.is_command_record() {
+            return None;
+        }
+        match self.headers[0].value.as_ref() {
+            CommandRecord::FENCE => {
+                let fencing_token = std::str::from_utf8(&self.body).ok()?.parse().ok()?;
+                Some(Command::Fence { fencing_token })
+            }
+            CommandRecord::TRIM => {
+                let trim_point = u64::from_be_bytes(self.body.as_ref().try_into().ok()?);
+                Some(Command::Trim { trim_point })
+            }
+            _ => None,
+        }
+    }
 }
 
 impl From<api::stream::proto::SequencedRecord> for SequencedRecord {
@@ -3584,6 +4024,28 @@ impl ReadBatch {
     }
 }
 
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+/// Positions of records returned by [`read_positions`](crate::S2Stream::read_positions).
+pub struct PositionsBatch {
+    /// Positions of records that are durably sequenced on the stream, in order.
+    pub positions: Vec<StreamPosition>,
+    /// Sequence number that will be assigned to the next record on the stream, and timestamp of
+    /// the last record.
+    ///
+    /// It will only be present when the positions reach the tail.
+    pub tail: Option<StreamPosition>,
+}
+
+impl PositionsBatch {
+    pub(crate) fn from_api(batch: api::stream::PositionsBatch) -> Self {
+        Self {
+            positions: batch.positions.into_iter().map(Into::into).collect(),
+            tail: batch.tail.map(Into::into),
+        }
+    }
+}
+
 /// A [`Stream`](futures_core::Stream) of values of type `Result<T, S2Error>`.
 pub type Streaming<T> = Pin<Box<dyn Send + futures_core::Stream<Item = Result<T, S2Error>>>>;
 
@@ -3824,6 +4286,7 @@ mod tests {
     #[case::none(Compression::None, CompressionAlgorithm::None)]
     #[case::gzip(Compression::Gzip, CompressionAlgorithm::Gzip)]
     #[case::zstd(Compression::Zstd, CompressionAlgorithm::Zstd)]
+    #[case::auto(Compression::Auto, CompressionAlgorithm::None)]
     fn compression_conversion(#[case] sdk: Compression, #[case] api: CompressionAlgorithm) {
         assert_eq!(CompressionAlgorithm::from(sdk), api);
     }
@@ -3866,6 +4329,18 @@ mod tests {
         assert_eq!(back, sdk);
     }
 
+    // -- CompressionHint --
+
+    #[rstest]
+    #[case::none(CompressionHint::None)]
+    #[case::zstd(CompressionHint::Zstd)]
+    #[case::gzip(CompressionHint::Gzip)]
+    fn compression_hint_roundtrip(#[case] sdk: CompressionHint) {
+        let api: api::config::CompressionHint = sdk.into();
+        let back: CompressionHint = api.into();
+        assert_eq!(back, sdk);
+    }
+
     // -- RetentionPolicy --
 
     #[rstest]
@@ -3889,6 +4364,10 @@ mod tests {
         api::config::TimestampingMode::ClientRequire
     )]
     #[case::arrival(TimestampingMode::Arrival, api::config::TimestampingMode::Arrival)]
+    #[case::server_prefer(
+        TimestampingMode::ServerPrefer,
+        api::config::TimestampingMode::ServerPrefer
+    )]
     fn timestamping_mode_roundtrip(
         #[case] sdk: TimestampingMode,
         #[case] expected_api: api::config::TimestampingMode,
@@ -3939,6 +4418,15 @@ mod tests {
         assert_eq!(back, sdk);
     }
 
+    #[test]
+    fn stream_reconfiguration_reset_clears_every_field() {
+        let api: api::config::StreamReconfiguration = StreamReconfiguration::reset().into();
+        let json = serde_json::to_value(&api).unwrap();
+        let fields = json.as_object().unwrap();
+        assert!(!fields.is_empty());
+        assert!(fields.values().all(serde_json::Value::is_null), "{json}");
+    }
+
     // -- BasinConfig --
 
     #[test]
@@ -4134,6 +4622,38 @@ mod tests {
         assert_eq!(record.is_command_record(), expected);
     }
 
+    #[test]
+    fn sequenced_record_command_decoding() {
+        let record = |headers: Vec<Header>, body: Bytes| SequencedRecord {
+            seq_num: 0,
+            body,
+            headers,
+            timestamp: 0,
+        };
+        assert!(matches!(
+            record(vec![Header::new("", "fence")], Bytes::from("tok")).command(),
+            Some(Command::Fence { fencing_token }) if &*fencing_token == "tok"
+        ));
+        assert!(matches!(
+            record(
+                vec![Header::new("", "trim")],
+                Bytes::copy_from_slice(&42u64.to_be_bytes())
+            )
+            .command(),
+            Some(Command::Trim { trim_point: 42 })
+        ));
+        assert!(
+            record(vec![Header::new("", "trim")], Bytes::from("short"))
+                .command()
+                .is_none()
+        );
+        assert!(
+            record(vec![Header::new("key", "fence")], Bytes::from("tok"))
+                .command()
+                .is_none()
+        );
+    }
+
     // -- ReadStart --
 
     proptest! {
@@ -4222,6 +4742,14 @@ mod tests {
             MetricUnit::from(api::metrics::MetricUnit::Operations),
             MetricUnit::Operations
         );
+        assert_eq!(
+            MetricUnit::from(api::metrics::MetricUnit::Count),
+            MetricUnit::Count
+        );
+        assert_eq!(
+            MetricUnit::from(api::metrics::MetricUnit::Percent),
+            MetricUnit::Percent
+        );
     }
 
     // -- AppendAck --
@@ -4232,11 +4760,18 @@ mod tests {
             start in proptest::option::of(proto_stream_position_strategy()),
             end in proptest::option::of(proto_stream_position_strategy()),
             tail in proptest::option::of(proto_stream_position_strategy()),
+            correlation_id in proptest::option::of(any::<u64>()),
         ) {
             let expected_start = start.unwrap_or_default();
             let expected_end = end.unwrap_or_default();
             let expected_tail = tail.unwrap_or_default();
-            let ack: AppendAck = api::stream::proto::AppendAck { start, end, tail }.into();
+            let ack: AppendAck = api::stream::proto::AppendAck {
+                start,
+                end,
+                tail,
+                correlation_id,
+            }
+            .into();
 
             prop_assert_eq!(ack.start.seq_num, expected_start.seq_num);
             prop_assert_eq!(ack.start.timestamp, expected_start.timestamp);
@@ -4244,6 +4779,7 @@ mod tests {
             prop_assert_eq!(ack.end.timestamp, expected_end.timestamp);
             prop_assert_eq!(ack.tail.seq_num, expected_tail.seq_num);
             prop_assert_eq!(ack.tail.timestamp, expected_tail.timestamp);
+            prop_assert_eq!(ack.correlation_id, correlation_id);
         }
     }
 
@@ -4283,6 +4819,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn positions_batch_from_api() {
+        let batch: api::stream::PositionsBatch = serde_json::from_str(
+            r#"{"positions": [{"seq_num": 0, "timestamp": 40}, {"seq_num": 1, "timestamp": 42}]}"#,
+        )
+        .unwrap();
+        let batch = PositionsBatch::from_api(batch);
+        assert_eq!(
+            batch.positions,
+            [
+                StreamPosition {
+                    seq_num: 0,
+                    timestamp: 40,
+                },
+                StreamPosition {
+                    seq_num: 1,
+                    timestamp: 42,
+                },
+            ]
+        );
+        assert_eq!(batch.tail, None);
+    }
+
     // -- CreateBasinInput --
 
     #[test]
@@ -1700,6 +1700,7 @@ async fn create_stream_inherits_basin_default_config() -> Result<(), S2Error> {
 #[rstest]
 #[case::gzip(Compression::Gzip)]
 #[case::zstd(Compression::Zstd)]
+#[case::auto(Compression::Auto)]
 #[tokio::test]
 async fn compression_roundtrip_unary(#[case] compression: Compression) -> Result<(), S2Error> {
     let config = s2_config(compression).expect("valid S2 config");
@@ -47,7 +47,7 @@ pub async fn serve() -> turmoil::Result {
         .build()
         .await?;
 
-    let backend = Backend::new(db, ByteSize::mib(128));
+    let backend = Backend::new(db, ByteSize::mib(128), ByteSize::mib(64));
     s2_lite::backend::bgtasks::spawn(&backend);
 
     let app = handlers::router().with_state(backend);
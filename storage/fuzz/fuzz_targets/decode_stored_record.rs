@@ -0,0 +1,24 @@
+//! Decodes arbitrary bytes as a stored record, which must fail with an error rather than panic,
+//! and checks that anything that does decode survives re-encoding.
+
+#![no_main]
+
+use bytes::Bytes;
+use libfuzzer_sys::fuzz_target;
+use s2_common::record::MeteredSize;
+use s2_storage::record::{
+    decode_if_command_record, decode_stored_record, encode_stored_record, try_metered_size,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = try_metered_size(data);
+    let _ = decode_if_command_record(data);
+
+    let Ok(record) = decode_stored_record(Bytes::copy_from_slice(data)) else {
+        return;
+    };
+    let reencoded = encode_stored_record(record.as_ref());
+    let redecoded = decode_stored_record(reencoded).expect("re-encoded record should decode");
+    assert_eq!(redecoded.metered_size(), record.metered_size());
+    assert_eq!(redecoded.into_inner(), record.into_inner());
+});
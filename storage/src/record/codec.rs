@@ -313,6 +313,13 @@ pub(super) fn decode_envelope_record(
         .map_err(|_| StoredRecordDecodeError::Truncated("NumHeaders"))?;
     let num_headers = usize::try_from(num_headers)
         .map_err(|_| StoredRecordDecodeError::InvalidValue("NumHeaders", "too many"))?;
+    // Every header takes at least its two length fields and a non-empty name, so a count that
+    // cannot fit in the remaining bytes is rejected before it sizes an allocation.
+    let min_header_len =
+        flag.name_length_bytes.get() as usize + 1 + flag.value_length_bytes.get() as usize;
+    if num_headers > buf.remaining() / min_header_len {
+        return Err(StoredRecordDecodeError::Truncated("Headers"));
+    }
 
     let mut headers: Vec<Header> = Vec::with_capacity(num_headers);
     for _ in 0..num_headers {
@@ -647,6 +654,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn header_count_exceeding_remaining_bytes_returns_error() {
+        let mut encoded = BytesMut::new();
+        encoded.put_u8(
+            HeaderFlag {
+                num_headers_length_bytes: 3,
+                name_length_bytes: NonZeroU8::new(4).unwrap(),
+                value_length_bytes: NonZeroU8::new(4).unwrap(),
+            }
+            .into(),
+        );
+        encoded.put_uint(0xff_ffff, 3);
+        encoded.put_slice(b"body");
+
+        assert_eq!(
+            decode_envelope_record(encoded.freeze()),
+            Err(StoredRecordDecodeError::Truncated("Headers"))
+        );
+    }
+
     #[test]
     fn truncated_envelope_returns_error() {
         let record = EnvelopeRecord::try_from_parts(
@@ -38,26 +38,27 @@ struct MagicByte {
     metered_size_varlen: u8,
 }
 
-/// Read bytes to u32 in big-endian order.
-fn read_vint_u32_be(bytes: &[u8]) -> u32 {
+/// Read bytes to u32 in big-endian order, or `None` if there are not 1-4 of them.
+fn read_vint_u32_be(bytes: &[u8]) -> Option<u32> {
     if bytes.len() > size_of::<u32>() || bytes.is_empty() {
-        panic!("invalid variable int bytes = {} len", bytes.len())
+        return None;
     }
     let mut acc: u32 = 0;
     for &byte in bytes {
         acc = (acc << 8) | byte as u32;
     }
-    acc
+    Some(acc)
 }
 
 pub fn try_metered_size(record_bytes: &[u8]) -> Result<u32, &'static str> {
     let magic_byte_u8 = *record_bytes.first().ok_or("byte range is empty")?;
     let magic_byte = MagicByte::try_from(magic_byte_u8)?;
-    Ok(read_vint_u32_be(
+    read_vint_u32_be(
         record_bytes
             .get(1..1 + magic_byte.metered_size_varlen as usize)
             .ok_or("byte range doesn't include bytes for metered size")?,
-    ))
+    )
+    .ok_or("invalid metered_size_varlen")
 }
 
 impl TryFrom<u8> for MagicByte {
@@ -239,6 +240,13 @@ pub fn decode_stored_record(
     let metered_size =
         buf.try_get_uint(magic_byte.metered_size_varlen as usize)
             .map_err(|_| StoredRecordDecodeError::Truncated("MeteredSize"))? as usize;
+    // Every record is metered with an 8-byte overhead, and a smaller size could not be re-encoded.
+    if metered_size < 8 {
+        return Err(StoredRecordDecodeError::InvalidValue(
+            "MeteredSize",
+            "smaller than record overhead",
+        ));
+    }
 
     let record = match magic_byte.record_type {
         RecordType::Command => {
@@ -451,6 +459,18 @@ mod test {
         assert_eq!(MagicByte::try_from(as_u8), Err(expected));
     }
 
+    proptest!(
+        #[test]
+        fn decoding_arbitrary_bytes_does_not_panic(
+            bytes in proptest::collection::vec(any::<u8>(), 0..64),
+        ) {
+            let bytes = Bytes::from(bytes);
+            let _ = try_metered_size(&bytes);
+            let _ = decode_if_command_record(&bytes);
+            let _ = decode_stored_record(bytes);
+        }
+    );
+
     #[test]
     fn metered_record_truncated_after_magic_byte_returns_error() {
         // Magic byte: Envelope (0b0000_0010), metered_size_varlen = 1 -> expects 1 more byte.
@@ -562,13 +582,32 @@ mod test {
         assert_eq!(decoded.into_inner(), record);
     }
 
+    #[test]
+    fn metered_size_below_record_overhead_returns_error() {
+        let record = StoredRecord::from(Record::Envelope(
+            EnvelopeRecord::try_from_parts(vec![], Bytes::from_static(b"hello")).unwrap(),
+        ));
+        let mut encoded = encode_stored_record(record.metered().as_ref()).to_vec();
+        encoded[1] = 0;
+
+        assert_eq!(
+            decode_stored_record(Bytes::from(encoded)),
+            Err(StoredRecordDecodeError::InvalidValue(
+                "MeteredSize",
+                "smaller than record overhead"
+            ))
+        );
+    }
+
     #[test]
     fn test_read_varint() {
         let data = [0u8, 0, 0, 1, 0, 0, 0];
 
-        assert_eq!(read_vint_u32_be(&data[..4]), 1u32);
-        assert_eq!(read_vint_u32_be(&data[2..5]), 2u32.pow(8));
-        assert_eq!(read_vint_u32_be(&data[2..6]), 2u32.pow(16));
-        assert_eq!(read_vint_u32_be(&data[3..]), 2u32.pow(24));
+        assert_eq!(read_vint_u32_be(&data[..4]), Some(1u32));
+        assert_eq!(read_vint_u32_be(&data[2..5]), Some(2u32.pow(8)));
+        assert_eq!(read_vint_u32_be(&data[2..6]), Some(2u32.pow(16)));
+        assert_eq!(read_vint_u32_be(&data[3..]), Some(2u32.pow(24)));
+        assert_eq!(read_vint_u32_be(&[]), None);
+        assert_eq!(read_vint_u32_be(&data[..5]), None);
     }
 }